@@ -4,7 +4,7 @@ use syn::parse::{Parse, ParseStream, Result};
 use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
 use syn::token::{Comma, Eq};
-use syn::{bracketed, Attribute, Lit, LitStr};
+use syn::{bracketed, Attribute, Lit, LitInt, LitStr};
 
 #[allow(dead_code)]
 pub trait MySpanned {
@@ -23,9 +23,10 @@ pub enum AllowedModelAttrs {
     TableName(Ident, LitStr),
     Description(Ident, LitStr),
     DerivedModel(Ident, LitStr),
+    Order(Ident, LitStr),
 }
 
-static VALID_MODEL_STRINGS: &[&str] = &["table_name", "description", "derived_model"];
+static VALID_MODEL_STRINGS: &[&str] = &["table_name", "description", "derived_model", "order"];
 
 impl Parse for AllowedModelAttrs {
     fn parse(input: ParseStream) -> syn::Result<Self> {
@@ -45,6 +46,10 @@ impl Parse for AllowedModelAttrs {
                 name,
                 parse_eq(input, "derived_model = \"base::models::company\"")?,
             )),
+            "order" => Ok(AllowedModelAttrs::Order(
+                name,
+                parse_eq(input, "order = \"name asc, id desc\"")?,
+            )),
             _ => Err(gen_unknown_key_error(
                 name.span(),
                 &name_str,
@@ -60,6 +65,7 @@ impl MySpanned for AllowedModelAttrs {
             AllowedModelAttrs::TableName(ident, _) => ident.span(),
             AllowedModelAttrs::Description(ident, _) => ident.span(),
             AllowedModelAttrs::DerivedModel(ident, _) => ident.span(),
+            AllowedModelAttrs::Order(ident, _) => ident.span(),
         }
     }
 }
@@ -72,9 +78,27 @@ pub enum AllowedFieldAttrs {
     Compute(Ident, LitStr),
     Depends(Ident, Vec<LitStr>),
     Inverse(Ident, LitStr),
+    OnDelete(Ident, LitStr),
+    Digits(Ident, LitInt),
+    CurrencyField(Ident, LitStr),
+    Many2many(Ident),
+    Relation(Ident, LitStr),
+    Json(Ident),
 }
 
-static VALID_FIELD_STRINGS: &[&str] = &["default", "description", "compute", "depends", "inverse"];
+static VALID_FIELD_STRINGS: &[&str] = &[
+    "default",
+    "description",
+    "compute",
+    "depends",
+    "inverse",
+    "ondelete",
+    "digits",
+    "currency_field",
+    "many2many",
+    "relation",
+    "json",
+];
 
 impl Parse for AllowedFieldAttrs {
     fn parse(input: ParseStream) -> Result<Self> {
@@ -111,6 +135,24 @@ impl Parse for AllowedFieldAttrs {
                 name,
                 parse_eq(input, "inverse = \"inverse\"")?,
             )),
+            "ondelete" => Ok(AllowedFieldAttrs::OnDelete(
+                name,
+                parse_eq(input, "ondelete = \"cascade\"")?,
+            )),
+            "digits" => Ok(AllowedFieldAttrs::Digits(
+                name,
+                parse_eq(input, "digits = 2")?,
+            )),
+            "currency_field" => Ok(AllowedFieldAttrs::CurrencyField(
+                name,
+                parse_eq(input, "currency_field = \"currency\"")?,
+            )),
+            "many2many" => Ok(AllowedFieldAttrs::Many2many(name)),
+            "relation" => Ok(AllowedFieldAttrs::Relation(
+                name,
+                parse_eq(input, "relation = \"contact_tag_rel\"")?,
+            )),
+            "json" => Ok(AllowedFieldAttrs::Json(name)),
             _ => Err(gen_unknown_key_error(
                 name.span(),
                 &name_str,
@@ -128,6 +170,12 @@ impl MySpanned for AllowedFieldAttrs {
             AllowedFieldAttrs::Compute(ident, _) => ident.span(),
             AllowedFieldAttrs::Depends(ident, _) => ident.span(),
             AllowedFieldAttrs::Inverse(ident, _) => ident.span(),
+            AllowedFieldAttrs::OnDelete(ident, _) => ident.span(),
+            AllowedFieldAttrs::Digits(ident, _) => ident.span(),
+            AllowedFieldAttrs::CurrencyField(ident, _) => ident.span(),
+            AllowedFieldAttrs::Many2many(ident) => ident.span(),
+            AllowedFieldAttrs::Relation(ident, _) => ident.span(),
+            AllowedFieldAttrs::Json(ident) => ident.span(),
         }
     }
 }
@@ -1,9 +1,14 @@
 use crate::model::attrs::{parse_attributes, AllowedFieldAttrs};
 use crate::model::util::{
-    gen_field_no_field_error, gen_inverse_not_multiple_ids, gen_missing_key_error,
-    gen_option_not_one_generic, gen_reference_not_two_generic, gen_wrong_default_value,
+    gen_currency_field_not_monetary, gen_digits_not_decimal, gen_field_no_field_error,
+    gen_inverse_not_multiple_ids, gen_json_reference, gen_many2many_not_multiple_ids, gen_many2many_with_inverse,
+    gen_many2many_without_relation, gen_missing_key_error, gen_monetary_without_currency_field,
+    gen_ondelete_not_single_id, gen_option_not_one_generic, gen_reference_not_two_generic,
+    gen_relation_not_many2many, gen_wrong_currency_field_value, gen_wrong_default_value,
+    gen_wrong_ondelete_value,
 };
-use erp::types::field::FieldType;
+use chrono::{DateTime, NaiveDate, Utc};
+use erp::types::field::{CurrencyField, Decimal, DefaultKind, FieldType, Json, OnDelete};
 use proc_macro2::{Ident, Span};
 use syn::spanned::Spanned;
 use syn::{
@@ -20,11 +25,22 @@ pub struct FieldGen {
     pub is_reference: bool,
     pub is_reference_multi: bool,
     pub field_type_keyword: Ident,
+    pub field_type: Type,
     pub default: Option<FieldType>,
+    pub default_kind: DefaultKind,
     pub description: Option<String>,
     pub compute: Option<String>,
     pub depends: Option<Vec<String>>,
     pub inverse: Option<String>,
+    pub ondelete: Option<OnDelete>,
+    pub digits: Option<u32>,
+    pub currency_field: Option<CurrencyField>,
+    /// Relation table of a M2M field
+    pub relation: Option<String>,
+    /// Field stored as JSON, either a `serde_json::Value` or a struct with the `json` attribute
+    pub is_json: bool,
+    /// Struct converted from and to JSON with serde (`#[erp(json)]`)
+    pub is_serialized: bool,
 }
 
 impl FieldGen {
@@ -43,14 +59,24 @@ impl FieldGen {
         let mut is_reference = false;
         let mut is_reference_multi = false;
         let mut default = None;
+        let mut default_span = None;
+        let mut default_lit = None;
         let mut description = None;
         let mut compute = None;
         let mut depends = None;
         let mut inverse = None;
+        let mut ondelete = None;
+        let mut digits = None;
+        let mut currency_field = None;
+        let mut many2many = None;
+        let mut relation = None;
+        let mut json = None;
 
         for attr in parse_attributes(attrs)? {
             match attr.item {
                 AllowedFieldAttrs::Default(ident, default_value) => {
+                    default_span = Some(default_value.span());
+                    default_lit = Some(default_value.clone());
                     default = Some(match default_value {
                         Lit::Str(str) => FieldType::String(str.value()),
                         Lit::Int(i) => {
@@ -58,9 +84,11 @@ impl FieldGen {
                             if int.is_ok() {
                                 FieldType::Integer(int?)
                             } else {
-                                let int = i.base10_parse::<u32>();
+                                let int = i.base10_parse::<u64>();
                                 if int.is_ok() {
                                     FieldType::Ref(int?)
+                                } else if let Ok(int) = i.base10_parse::<i64>() {
+                                    FieldType::BigInteger(int)
                                 } else {
                                     return Err(gen_wrong_default_value(
                                         i.span(),
@@ -142,9 +170,38 @@ impl FieldGen {
                 AllowedFieldAttrs::Inverse(ident, inverse_value) => {
                     inverse = Some((ident, inverse_value.value()));
                 }
+                AllowedFieldAttrs::OnDelete(ident, ondelete_value) => {
+                    let value = OnDelete::try_from(ondelete_value.value().as_str()).map_err(
+                        |err| gen_wrong_ondelete_value(ondelete_value.span(), &err.to_string()),
+                    )?;
+                    ondelete = Some((ident, value));
+                }
+                AllowedFieldAttrs::Digits(ident, digits_value) => {
+                    digits = Some((ident, digits_value.base10_parse::<u32>()?));
+                }
+                AllowedFieldAttrs::CurrencyField(ident, currency_field_value) => {
+                    let value = CurrencyField::try_from(currency_field_value.value().as_str())
+                        .map_err(|err| {
+                            gen_wrong_currency_field_value(
+                                currency_field_value.span(),
+                                &err.to_string(),
+                            )
+                        })?;
+                    currency_field = Some((ident, value));
+                }
+                AllowedFieldAttrs::Many2many(ident) => {
+                    many2many = Some(ident);
+                }
+                AllowedFieldAttrs::Relation(ident, relation_value) => {
+                    relation = Some((ident, relation_value.value()));
+                }
+                AllowedFieldAttrs::Json(ident) => {
+                    json = Some(ident);
+                }
             }
         }
 
+        let mut field_type_keyword = None;
         let mut field_type = None;
         // Check field type
         if let Type::Path(TypePath {
@@ -155,10 +212,10 @@ impl FieldGen {
             },
         }) = ty
         {
-            if segments.len() != 1 {
+            // Only the last segment matters, so that "serde_json::Value" is a "Value"
+            let Some(PathSegment { ident, arguments }) = segments.last() else {
                 return Err(gen_field_no_field_error(ident.span()));
-            }
-            let PathSegment { ident, arguments } = &segments[0];
+            };
             // PathSegment = the value after ":" in "email: Option<String>".
             // ident = "Option", arguments = "<String>"
             if ident == "Option" || ident == "Reference" {
@@ -179,22 +236,25 @@ impl FieldGen {
                     if args_len != 2 && is_reference {
                         return Err(gen_reference_not_two_generic(args.span()));
                     }
-                    if let GenericArgument::Type(Type::Path(TypePath {
-                        qself: _,
-                        path:
-                            Path {
-                                leading_colon: _,
-                                segments,
-                            },
-                    })) = &args[0]
+                    if let GenericArgument::Type(
+                        inner_type @ Type::Path(TypePath {
+                            qself: _,
+                            path:
+                                Path {
+                                    leading_colon: _,
+                                    segments,
+                                },
+                        }),
+                    ) = &args[0]
                     {
-                        if segments.len() != 1 {
+                        let Some(segment) = segments.last() else {
                             return Err(gen_field_no_field_error(segments.span()));
-                        }
-                        field_type = Some(segments[0].ident.clone());
+                        };
+                        field_type_keyword = Some(segment.ident.clone());
+                        field_type = Some(inner_type.clone());
                     }
-                    if is_reference {
-                        if let GenericArgument::Type(Type::Path(TypePath {
+                    if is_reference
+                        && let GenericArgument::Type(Type::Path(TypePath {
                             qself: _,
                             path:
                                 Path {
@@ -208,20 +268,149 @@ impl FieldGen {
                             }
                             is_reference_multi = segments[0].ident == "MultipleIds";
                         }
-                    }
                 }
             } else {
                 is_required = true;
 
-                field_type = Some(ident.clone());
+                field_type_keyword = Some(ident.clone());
+                field_type = Some(ty.clone());
             }
         }
 
         // "inverse" should only work on MultipleIds
-        if !is_reference_multi {
-            if let Some((inverse_ident, _)) = inverse {
+        if !is_reference_multi
+            && let Some((inverse_ident, _)) = inverse {
                 return Err(gen_inverse_not_multiple_ids(inverse_ident.span()));
             }
+
+        // "ondelete" should only work on M2O
+        if (!is_reference || is_reference_multi)
+            && let Some((ondelete_ident, _)) = ondelete {
+                return Err(gen_ondelete_not_single_id(ondelete_ident.span()));
+            }
+
+        // "many2many" should only work on MultipleIds, instead of "inverse", and needs a relation table
+        if let Some(many2many_ident) = &many2many {
+            if !is_reference_multi {
+                return Err(gen_many2many_not_multiple_ids(many2many_ident.span()));
+            }
+            if let Some((inverse_ident, _)) = &inverse {
+                return Err(gen_many2many_with_inverse(inverse_ident.span()));
+            }
+            if relation.is_none() {
+                return Err(gen_many2many_without_relation(many2many_ident.span()));
+            }
+        } else if let Some((relation_ident, _)) = &relation {
+            return Err(gen_relation_not_many2many(relation_ident.span()));
+        }
+
+        // Dates are given as strings, and "now" is the current date when the record is created.
+        // Optional dates without any default value are empty, as there is no meaningful default
+        let field_type_keyword = field_type_keyword.unwrap();
+        let is_temporal = field_type_keyword == "NaiveDate" || field_type_keyword == "DateTime";
+        let mut default_kind = DefaultKind::Value;
+        if let (Some(FieldType::String(value)), Some(default_span)) = (&default, default_span) {
+            if value == "now" && is_temporal {
+                default_kind = DefaultKind::Now;
+                default = None;
+            } else if field_type_keyword == "NaiveDate" {
+                let date = value.parse::<NaiveDate>().map_err(|_| {
+                    gen_wrong_default_value(default_span, value, field_name.as_str())
+                })?;
+                default = Some(FieldType::Date(date));
+            } else if field_type_keyword == "DateTime" {
+                let date = value.parse::<DateTime<Utc>>().map_err(|_| {
+                    gen_wrong_default_value(default_span, value, field_name.as_str())
+                })?;
+                default = Some(FieldType::DateTime(date));
+            }
+        } else if default.is_none() && is_temporal && !is_required {
+            default_kind = DefaultKind::Empty;
+        }
+
+        // Decimals are given as numbers or strings, and are parsed without losing any precision
+        let is_monetary = field_type_keyword == "Monetary";
+        let is_decimal = is_monetary || field_type_keyword == "Decimal";
+        if is_decimal && let Some(default_lit) = &default_lit {
+            let value = match default_lit {
+                Lit::Int(i) => i.base10_digits().to_string(),
+                Lit::Float(f) => f.base10_digits().to_string(),
+                Lit::Str(s) => s.value(),
+                _ => String::new(),
+            };
+            let decimal = Decimal::from_str_exact(&value).map_err(|_| {
+                gen_wrong_default_value(default_lit.span(), &value, field_name.as_str())
+            })?;
+            default = Some(FieldType::Decimal(decimal));
+        }
+
+        // 64-bit numbers are parsed with their own width, so that big defaults are not rejected
+        let is_big_integer = field_type_keyword == "i64";
+        let is_double = field_type_keyword == "f64";
+        if (is_big_integer || is_double) && let Some(default_lit) = &default_lit {
+            let value = match default_lit {
+                Lit::Int(i) => i.base10_digits().to_string(),
+                Lit::Float(f) => f.base10_digits().to_string(),
+                _ => String::new(),
+            };
+            let number = if is_big_integer {
+                value.parse().ok().map(FieldType::BigInteger)
+            } else {
+                value.parse().ok().map(FieldType::Double)
+            };
+            default = Some(number.ok_or_else(|| {
+                gen_wrong_default_value(default_lit.span(), &value, field_name.as_str())
+            })?);
+        }
+
+        // JSON values are given as JSON texts. Optional JSON fields without any default value are
+        // empty, as there is no meaningful default
+        let is_serialized = json.is_some();
+        let is_json = is_serialized || field_type_keyword == "Value" || field_type_keyword == "Json";
+        if let Some(json_ident) = &json
+            && is_reference {
+                return Err(gen_json_reference(json_ident.span()));
+            }
+        if is_json {
+            if let Some(default_lit) = &default_lit {
+                let value = match default_lit {
+                    Lit::Int(i) => i.base10_digits().to_string(),
+                    Lit::Float(f) => f.base10_digits().to_string(),
+                    Lit::Bool(b) => b.value.to_string(),
+                    Lit::Str(s) => s.value(),
+                    _ => String::new(),
+                };
+                let json = value.parse::<Json>().map_err(|_| {
+                    gen_wrong_default_value(default_lit.span(), &value, field_name.as_str())
+                })?;
+                default = Some(FieldType::Json(json));
+            } else if !is_required {
+                default_kind = DefaultKind::Empty;
+            }
+        }
+
+        // Binaries are given as strings, whose bytes are the content. Optional binaries without any
+        // default value are empty, as there is no meaningful default
+        if field_type_keyword == "Binary" {
+            if let Some(FieldType::String(value)) = &default {
+                default = Some(FieldType::Binary(value.clone().into_bytes()));
+            } else if default.is_none() && !is_required {
+                default_kind = DefaultKind::Empty;
+            }
+        }
+
+        // "digits" should only work on Decimal & Monetary
+        if !is_decimal && let Some((digits_ident, _)) = digits {
+            return Err(gen_digits_not_decimal(digits_ident.span()));
+        }
+
+        // "currency_field" should only work on Monetary, and is needed by them
+        match (&currency_field, is_monetary) {
+            (Some((currency_field_ident, _)), false) => {
+                return Err(gen_currency_field_not_monetary(currency_field_ident.span()));
+            }
+            (None, true) => return Err(gen_monetary_without_currency_field(ident.span())),
+            _ => {}
         }
 
         Ok(FieldGen {
@@ -231,12 +420,20 @@ impl FieldGen {
             is_required,
             is_reference,
             is_reference_multi,
-            field_type_keyword: field_type.unwrap(),
+            field_type_keyword,
+            field_type: field_type.unwrap(),
             default,
+            default_kind,
             description,
             compute,
             depends,
             inverse: inverse.map(|inv| inv.1),
+            ondelete: ondelete.map(|ondelete| ondelete.1),
+            digits: digits.map(|digits| digits.1),
+            currency_field: currency_field.map(|currency_field| currency_field.1),
+            relation: relation.map(|relation| relation.1),
+            is_json,
+            is_serialized,
         })
     }
 }
@@ -1,5 +1,6 @@
 pub(crate) mod attrs;
 pub(crate) mod field;
+#[allow(clippy::module_inception)]
 pub(crate) mod model;
 pub(crate) mod model_gen;
 pub(crate) mod util;
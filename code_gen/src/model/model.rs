@@ -1,6 +1,7 @@
 use crate::model::attrs::{parse_attributes, AllowedModelAttrs};
 use crate::model::field::FieldGen;
-use crate::model::util::gen_missing_key_error;
+use crate::model::util::{gen_missing_key_error, gen_wrong_order_value};
+use erp_search::OrderBy;
 use syn::punctuated::Punctuated;
 use syn::token::Comma;
 use syn::{Data, DataStruct, DeriveInput, Field, Fields, Result};
@@ -10,6 +11,7 @@ pub struct ModelGen {
     pub table_name: String,
     pub description: Option<String>,
     pub derived_model: Option<String>,
+    pub order: Vec<OrderBy>,
     pub fields: Vec<FieldGen>,
 }
 
@@ -22,12 +24,17 @@ impl ModelGen {
         let mut table_name = String::new();
         let mut description = None;
         let mut derived_model = None;
+        let mut order = Vec::new();
 
         for attr in parse_attributes(attrs)? {
             match attr.item {
                 AllowedModelAttrs::TableName(_, value) => table_name = value.value(),
                 AllowedModelAttrs::Description(_, value) => description = Some(value.value()),
                 AllowedModelAttrs::DerivedModel(_, value) => derived_model = Some(value.value()),
+                AllowedModelAttrs::Order(_, value) => {
+                    order = OrderBy::parse_list(&value.value())
+                        .map_err(|err| gen_wrong_order_value(value.span(), &err.to_string()))?;
+                }
             }
         }
         if table_name.is_empty() {
@@ -55,6 +62,7 @@ impl ModelGen {
             table_name,
             description,
             derived_model,
+            order,
             fields,
         })
     }
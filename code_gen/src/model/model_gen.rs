@@ -1,7 +1,8 @@
 use crate::model::field::FieldGen;
 use crate::model::model::ModelGen;
-use erp::types::field::FieldType;
+use erp::types::field::{CurrencyField, DefaultKind, FieldType, OnDelete};
 use erp::util::string::StringTransform;
+use erp_search::OrderDirection;
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::quote;
 use syn::{parse_str, DeriveInput, Path, Result};
@@ -13,6 +14,7 @@ pub fn derive(item: &DeriveInput) -> Result<TokenStream> {
         table_name,
         description,
         derived_model,
+        order,
         fields,
         ..
     } = ModelGen::from_item(item)?;
@@ -33,6 +35,7 @@ pub fn derive(item: &DeriveInput) -> Result<TokenStream> {
     });
 
     let struct_name_ident = Ident::new(struct_name.as_str(), Span::call_site());
+    let fields_struct_ident = Ident::new(format!("{struct_name}Fields").as_str(), Span::call_site());
     let camel_case_table_name = table_name.replace("_", " ").to_camel_case();
     let base_model_name = format!("Base{camel_case_table_name}");
 
@@ -64,6 +67,10 @@ pub fn derive(item: &DeriveInput) -> Result<TokenStream> {
                 }
             }
 
+            impl erp::types::domain::BaseModelFields for #base_model_name_ident {
+                type Fields = #fields_struct_ident;
+            }
+
             impl erp::model::Model<erp::types::field::SingleId> for #struct_name_ident<erp::types::field::SingleId> {
 
             }
@@ -79,7 +86,8 @@ pub fn derive(item: &DeriveInput) -> Result<TokenStream> {
             is_required,
             is_reference,
             is_reference_multi,
-            field_type_keyword,
+            is_serialized,
+            field_type,
             ..
         } = f;
         if field_name == "id" {
@@ -88,53 +96,77 @@ pub fn derive(item: &DeriveInput) -> Result<TokenStream> {
         let get_field_ident = Ident::new(format!("get_{field_name}").as_str(), Span::call_site());
         // TODO Move the set to another place, as it's not needed to be different between SingleId & MultipleIds
         let set_field_ident = Ident::new(format!("set_{field_name}").as_str(), Span::call_site());
+        let update_field_ident = Ident::new(format!("update_{field_name}").as_str(), Span::call_site());
 
         if *is_reference {
             if *is_reference_multi {
                 Some(quote! {
                     pub fn #get_field_ident<M>(&self, env: &mut erp::environment::Environment) -> Result<M, Box<dyn std::error::Error>>
                     where
-                        M: erp::model::Model<erp::types::field::MultipleIds, BaseModel=#field_type_keyword>,
+                        M: erp::model::Model<erp::types::field::MultipleIds, BaseModel=#field_type>,
                     {
-                        (self as &dyn erp::model::Model<erp::types::field::SingleId, BaseModel=<Self as erp::types::model::CommonModel<erp::types::field::SingleId>>::BaseModel>).get_references::<M, #field_type_keyword>(#field_name, env)
+                        (self as &dyn erp::model::Model<erp::types::field::SingleId, BaseModel=<Self as erp::types::model::CommonModel<erp::types::field::SingleId>>::BaseModel>).get_references::<M, #field_type>(#field_name, env)
                     }
-                    pub fn #set_field_ident(&self, value: erp::types::field::Reference<#field_type_keyword, erp::types::field::MultipleIds>, env: &mut erp::environment::Environment) -> Result<(), Box<dyn std::error::Error>> {
+                    pub fn #set_field_ident(&self, value: erp::types::field::Reference<#field_type, erp::types::field::MultipleIds>, env: &mut erp::environment::Environment) -> Result<(), Box<dyn std::error::Error>> {
                         (self as &dyn erp::model::Model<erp::types::field::SingleId, BaseModel=<Self as erp::types::model::CommonModel<erp::types::field::SingleId>>::BaseModel>).set_references(#field_name, value, env)
                     }
+                    pub fn #update_field_ident(&self, commands: &[erp::types::field::Command], env: &mut erp::environment::Environment) -> Result<(), Box<dyn std::error::Error>> {
+                        (self as &dyn erp::model::Model<erp::types::field::SingleId, BaseModel=<Self as erp::types::model::CommonModel<erp::types::field::SingleId>>::BaseModel>).update_references(#field_name, commands, env)
+                    }
                 })
             } else {
                 Some(quote! {
                     pub fn #get_field_ident<M>(&self, env: &mut erp::environment::Environment) -> Result<Option<M>, Box<dyn std::error::Error>>
                     where
-                        M: erp::model::Model<erp::types::field::SingleId, BaseModel=#field_type_keyword>,
+                        M: erp::model::Model<erp::types::field::SingleId, BaseModel=#field_type>,
                     {
-                        (self as &dyn erp::model::Model<erp::types::field::SingleId, BaseModel=<Self as erp::types::model::CommonModel<erp::types::field::SingleId>>::BaseModel>).get_reference::<M, #field_type_keyword>(#field_name, env)
+                        (self as &dyn erp::model::Model<erp::types::field::SingleId, BaseModel=<Self as erp::types::model::CommonModel<erp::types::field::SingleId>>::BaseModel>).get_reference::<M, #field_type>(#field_name, env)
                     }
-                    pub fn #set_field_ident(&self, value: Option<erp::types::field::Reference<#field_type_keyword, erp::types::field::SingleId>>, env: &mut erp::environment::Environment) -> Result<(), Box<dyn std::error::Error>> {
+                    pub fn #set_field_ident(&self, value: Option<erp::types::field::Reference<#field_type, erp::types::field::SingleId>>, env: &mut erp::environment::Environment) -> Result<(), Box<dyn std::error::Error>> {
                         if let Some(value) = value {
                             (self as &dyn erp::model::Model<erp::types::field::SingleId, BaseModel=<Self as erp::types::model::CommonModel<erp::types::field::SingleId>>::BaseModel>).set_reference(#field_name, value, env)
                         } else {
-                            (self as &dyn erp::model::Model<erp::types::field::SingleId, BaseModel=<Self as erp::types::model::CommonModel<erp::types::field::SingleId>>::BaseModel>).set_option::<u32>(#field_name, None, env)
+                            (self as &dyn erp::model::Model<erp::types::field::SingleId, BaseModel=<Self as erp::types::model::CommonModel<erp::types::field::SingleId>>::BaseModel>).set_option::<u64>(#field_name, None, env)
                         }
                     }
                 })
             }
+        } else if *is_serialized {
+            if *is_required {
+                Some(quote! {
+                    pub fn #get_field_ident(&self, env: &mut erp::environment::Environment) -> Result<#field_type, Box<dyn std::error::Error>> {
+                        (self as &dyn erp::model::Model<erp::types::field::SingleId, BaseModel=<Self as erp::types::model::CommonModel<erp::types::field::SingleId>>::BaseModel>).get_json(#field_name, env)
+                    }
+                    pub fn #set_field_ident(&self, value: #field_type, env: &mut erp::environment::Environment) -> Result<(), Box<dyn std::error::Error>> {
+                        (self as &dyn erp::model::Model<erp::types::field::SingleId, BaseModel=<Self as erp::types::model::CommonModel<erp::types::field::SingleId>>::BaseModel>).set_json(#field_name, Some(value), env)
+                    }
+                })
+            } else {
+                Some(quote! {
+                    pub fn #get_field_ident(&self, env: &mut erp::environment::Environment) -> Result<Option<#field_type>, Box<dyn std::error::Error>> {
+                        (self as &dyn erp::model::Model<erp::types::field::SingleId, BaseModel=<Self as erp::types::model::CommonModel<erp::types::field::SingleId>>::BaseModel>).get_json_option(#field_name, env)
+                    }
+                    pub fn #set_field_ident(&self, value: Option<#field_type>, env: &mut erp::environment::Environment) -> Result<(), Box<dyn std::error::Error>> {
+                        (self as &dyn erp::model::Model<erp::types::field::SingleId, BaseModel=<Self as erp::types::model::CommonModel<erp::types::field::SingleId>>::BaseModel>).set_json(#field_name, value, env)
+                    }
+                })
+            }
         } else if *is_required {
             Some(quote! {
-                pub fn #get_field_ident<'a>(&self, env: &'a mut erp::environment::Environment) -> Result<&'a #field_type_keyword, Box<dyn std::error::Error>>
+                pub fn #get_field_ident<'a>(&self, env: &'a mut erp::environment::Environment) -> Result<&'a #field_type, Box<dyn std::error::Error>>
                 {
                     (self as &dyn erp::model::Model<erp::types::field::SingleId, BaseModel=<Self as erp::types::model::CommonModel<erp::types::field::SingleId>>::BaseModel>).get(#field_name, env)
                 }
-                pub fn #set_field_ident(&self, value: #field_type_keyword, env: &mut erp::environment::Environment) -> Result<(), Box<dyn std::error::Error>> {
+                pub fn #set_field_ident(&self, value: #field_type, env: &mut erp::environment::Environment) -> Result<(), Box<dyn std::error::Error>> {
                     (self as &dyn erp::model::Model<erp::types::field::SingleId, BaseModel=<Self as erp::types::model::CommonModel<erp::types::field::SingleId>>::BaseModel>).set(#field_name, value, env)
                 }
             })
         } else {
             Some(quote! {
-                pub fn #get_field_ident<'a>(&self, env: &'a mut erp::environment::Environment) -> Result<Option<&'a #field_type_keyword>, Box<dyn std::error::Error>> {
+                pub fn #get_field_ident<'a>(&self, env: &'a mut erp::environment::Environment) -> Result<Option<&'a #field_type>, Box<dyn std::error::Error>> {
                     (self as &dyn erp::model::Model<erp::types::field::SingleId, BaseModel=<Self as erp::types::model::CommonModel<erp::types::field::SingleId>>::BaseModel>).get_option(#field_name, env)
                 }
-                pub fn #set_field_ident(&self, value: Option<#field_type_keyword>, env: &mut erp::environment::Environment) -> Result<(), Box<dyn std::error::Error>> {
+                pub fn #set_field_ident(&self, value: Option<#field_type>, env: &mut erp::environment::Environment) -> Result<(), Box<dyn std::error::Error>> {
                     (self as &dyn erp::model::Model<erp::types::field::SingleId, BaseModel=<Self as erp::types::model::CommonModel<erp::types::field::SingleId>>::BaseModel>).set_option(#field_name, value, env)
                 }
             })
@@ -146,7 +178,8 @@ pub fn derive(item: &DeriveInput) -> Result<TokenStream> {
             is_required,
             is_reference,
             is_reference_multi,
-            field_type_keyword,
+            is_serialized,
+            field_type,
             ..
         } = f;
         if field_name == "id" {
@@ -155,80 +188,169 @@ pub fn derive(item: &DeriveInput) -> Result<TokenStream> {
         let get_field_ident = Ident::new(format!("get_{field_name}").as_str(), Span::call_site());
         // TODO Move the set to another place, as it's not needed to be different between SingleId & MultipleIds
         let set_field_ident = Ident::new(format!("set_{field_name}").as_str(), Span::call_site());
+        let update_field_ident = Ident::new(format!("update_{field_name}").as_str(), Span::call_site());
 
         if *is_reference {
             if *is_reference_multi {
                 Some(quote! {
                     pub fn #get_field_ident<M>(&self, env: &mut erp::environment::Environment) -> Result<M, Box<dyn std::error::Error>>
                     where
-                        M: erp::model::Model<erp::types::field::MultipleIds, BaseModel=#field_type_keyword>,
+                        M: erp::model::Model<erp::types::field::MultipleIds, BaseModel=#field_type>,
                     {
-                        (self as &dyn erp::model::Model<erp::types::field::MultipleIds, BaseModel=<Self as erp::types::model::CommonModel<erp::types::field::MultipleIds>>::BaseModel>).get_references::<M, #field_type_keyword>(#field_name, env)
+                        (self as &dyn erp::model::Model<erp::types::field::MultipleIds, BaseModel=<Self as erp::types::model::CommonModel<erp::types::field::MultipleIds>>::BaseModel>).get_references::<M, #field_type>(#field_name, env)
                     }
-                    pub fn #set_field_ident(&self, value: erp::types::field::Reference<#field_type_keyword, erp::types::field::MultipleIds>, env: &mut erp::environment::Environment) -> Result<(), Box<dyn std::error::Error>> {
+                    pub fn #set_field_ident(&self, value: erp::types::field::Reference<#field_type, erp::types::field::MultipleIds>, env: &mut erp::environment::Environment) -> Result<(), Box<dyn std::error::Error>> {
                         (self as &dyn erp::model::Model<erp::types::field::MultipleIds, BaseModel=<Self as erp::types::model::CommonModel<erp::types::field::MultipleIds>>::BaseModel>).set_references(#field_name, value, env)
                     }
+                    pub fn #update_field_ident(&self, commands: &[erp::types::field::Command], env: &mut erp::environment::Environment) -> Result<(), Box<dyn std::error::Error>> {
+                        (self as &dyn erp::model::Model<erp::types::field::MultipleIds, BaseModel=<Self as erp::types::model::CommonModel<erp::types::field::MultipleIds>>::BaseModel>).update_references(#field_name, commands, env)
+                    }
                 })
             } else {
                 Some(quote! {
                     pub fn #get_field_ident<M>(&self, env: &mut erp::environment::Environment) -> Result<M, Box<dyn std::error::Error>>
                     where
-                        M: erp::model::Model<erp::types::field::MultipleIds, BaseModel=#field_type_keyword>,
+                        M: erp::model::Model<erp::types::field::MultipleIds, BaseModel=#field_type>,
                     {
-                        (self as &dyn erp::model::Model<erp::types::field::MultipleIds, BaseModel=<Self as erp::types::model::CommonModel<erp::types::field::MultipleIds>>::BaseModel>).get_references::<M, #field_type_keyword>(#field_name, env)
+                        (self as &dyn erp::model::Model<erp::types::field::MultipleIds, BaseModel=<Self as erp::types::model::CommonModel<erp::types::field::MultipleIds>>::BaseModel>).get_references::<M, #field_type>(#field_name, env)
                     }
-                    pub fn #set_field_ident(&self, value: Option<erp::types::field::Reference<#field_type_keyword, erp::types::field::SingleId>>, env: &mut erp::environment::Environment) -> Result<(), Box<dyn std::error::Error>> {
+                    pub fn #set_field_ident(&self, value: Option<erp::types::field::Reference<#field_type, erp::types::field::SingleId>>, env: &mut erp::environment::Environment) -> Result<(), Box<dyn std::error::Error>> {
                         if let Some(value) = value {
                             (self as &dyn erp::model::Model<erp::types::field::MultipleIds, BaseModel=<Self as erp::types::model::CommonModel<erp::types::field::MultipleIds>>::BaseModel>).set_reference(#field_name, value, env)
                         } else {
-                            (self as &dyn erp::model::Model<erp::types::field::MultipleIds, BaseModel=<Self as erp::types::model::CommonModel<erp::types::field::MultipleIds>>::BaseModel>).set_option::<u32>(#field_name, None, env)
+                            (self as &dyn erp::model::Model<erp::types::field::MultipleIds, BaseModel=<Self as erp::types::model::CommonModel<erp::types::field::MultipleIds>>::BaseModel>).set_option::<u64>(#field_name, None, env)
                         }
                     }
                 })
             }
+        } else if *is_serialized {
+            if *is_required {
+                Some(quote! {
+                    pub fn #get_field_ident(&self, env: &mut erp::environment::Environment) -> Result<Vec<#field_type>, Box<dyn std::error::Error>> {
+                        (self as &dyn erp::model::Model<erp::types::field::MultipleIds, BaseModel=<Self as erp::types::model::CommonModel<erp::types::field::MultipleIds>>::BaseModel>).gets_json(#field_name, env)
+                    }
+                    pub fn #set_field_ident(&self, value: #field_type, env: &mut erp::environment::Environment) -> Result<(), Box<dyn std::error::Error>> {
+                        (self as &dyn erp::model::Model<erp::types::field::MultipleIds, BaseModel=<Self as erp::types::model::CommonModel<erp::types::field::MultipleIds>>::BaseModel>).set_json(#field_name, Some(value), env)
+                    }
+                })
+            } else {
+                Some(quote! {
+                    pub fn #get_field_ident(&self, env: &mut erp::environment::Environment) -> Result<Vec<Option<#field_type>>, Box<dyn std::error::Error>> {
+                        (self as &dyn erp::model::Model<erp::types::field::MultipleIds, BaseModel=<Self as erp::types::model::CommonModel<erp::types::field::MultipleIds>>::BaseModel>).get_json_options(#field_name, env)
+                    }
+                    pub fn #set_field_ident(&self, value: Option<#field_type>, env: &mut erp::environment::Environment) -> Result<(), Box<dyn std::error::Error>> {
+                        (self as &dyn erp::model::Model<erp::types::field::MultipleIds, BaseModel=<Self as erp::types::model::CommonModel<erp::types::field::MultipleIds>>::BaseModel>).set_json(#field_name, value, env)
+                    }
+                })
+            }
         } else if *is_required {
             Some(quote! {
-                pub fn #get_field_ident<'a>(&self, env: &'a mut erp::environment::Environment) -> Result<Vec<&'a #field_type_keyword>, Box<dyn std::error::Error>>
+                pub fn #get_field_ident<'a>(&self, env: &'a mut erp::environment::Environment) -> Result<Vec<&'a #field_type>, Box<dyn std::error::Error>>
                 {
                     (self as &dyn erp::model::Model<erp::types::field::MultipleIds, BaseModel=<Self as erp::types::model::CommonModel<erp::types::field::MultipleIds>>::BaseModel>).gets(#field_name, env)
                 }
-                pub fn #set_field_ident(&self, value: #field_type_keyword, env: &mut erp::environment::Environment) -> Result<(), Box<dyn std::error::Error>> {
+                pub fn #set_field_ident(&self, value: #field_type, env: &mut erp::environment::Environment) -> Result<(), Box<dyn std::error::Error>> {
                     (self as &dyn erp::model::Model<erp::types::field::MultipleIds, BaseModel=<Self as erp::types::model::CommonModel<erp::types::field::MultipleIds>>::BaseModel>).set(#field_name, value, env)
                 }
             })
         } else {
             Some(quote! {
-                pub fn #get_field_ident<'a>(&self, env: &'a mut erp::environment::Environment) -> Result<Vec<Option<&'a #field_type_keyword>>, Box<dyn std::error::Error>> {
+                pub fn #get_field_ident<'a>(&self, env: &'a mut erp::environment::Environment) -> Result<Vec<Option<&'a #field_type>>, Box<dyn std::error::Error>> {
                     (self as &dyn erp::model::Model<erp::types::field::MultipleIds, BaseModel=<Self as erp::types::model::CommonModel<erp::types::field::MultipleIds>>::BaseModel>).get_options(#field_name, env)
                 }
-                pub fn #set_field_ident(&self, value: Option<#field_type_keyword>, env: &mut erp::environment::Environment) -> Result<(), Box<dyn std::error::Error>> {
+                pub fn #set_field_ident(&self, value: Option<#field_type>, env: &mut erp::environment::Environment) -> Result<(), Box<dyn std::error::Error>> {
                     (self as &dyn erp::model::Model<erp::types::field::MultipleIds, BaseModel=<Self as erp::types::model::CommonModel<erp::types::field::MultipleIds>>::BaseModel>).set_option(#field_name, value, env)
                 }
             })
         }
     });
+    let impl_domain_fields = fields.iter().map(|f| {
+        let FieldGen {
+            field_name,
+            is_reference,
+            is_json,
+            field_type,
+            ..
+        } = f;
+        let field_ident = Ident::new(field_name, Span::call_site());
+
+        if *is_json {
+            // Key paths are built from the JSON value, whatever the type of the field is
+            quote! {
+                pub fn #field_ident(&self) -> erp::types::domain::DomainField<erp::types::field::Json> {
+                    erp::types::domain::DomainField::new(&self.path, #field_name)
+                }
+            }
+        } else if *is_reference {
+            quote! {
+                pub fn #field_ident(&self) -> erp::types::domain::DomainReference<#field_type> {
+                    erp::types::domain::DomainReference::new(&self.path, #field_name)
+                }
+            }
+        } else {
+            quote! {
+                pub fn #field_ident(&self) -> erp::types::domain::DomainField<#field_type> {
+                    erp::types::domain::DomainField::new(&self.path, #field_name)
+                }
+            }
+        }
+    });
+    let domain_fields = quote! {
+        /// Fields of this model, used to build domains that are checked at compile time
+        pub struct #fields_struct_ident {
+            path: Vec<String>,
+        }
+
+        impl erp::types::domain::DomainFields for #fields_struct_ident {
+            fn with_path(path: Vec<String>) -> Self {
+                Self { path }
+            }
+        }
+
+        impl #fields_struct_ident {
+            pub fn id(&self) -> erp::types::domain::DomainField<u64> {
+                erp::types::domain::DomainField::new(&self.path, "id")
+            }
+
+            #(#impl_domain_fields)*
+        }
+    };
+
     let impl_model = quote! {
 
         impl #struct_name_ident<erp::types::field::SingleId> {
-            pub fn get_id(&self) -> u32 {
+            pub fn get_id(&self) -> u64 {
                 self.id.get_id()
             }
-            pub fn get_id_ref(&self) -> &u32 {
+            pub fn get_id_ref(&self) -> &u64 {
                 self.id.get_id_ref()
             }
 
+            pub fn unlink(&self, env: &mut erp::environment::Environment) -> Result<(), Box<dyn std::error::Error>> {
+                (self as &dyn erp::model::Model<erp::types::field::SingleId, BaseModel=<Self as erp::types::model::CommonModel<erp::types::field::SingleId>>::BaseModel>).unlink(env)
+            }
+
             #(#impl_model_fields_single)*
         }
 
         impl #struct_name_ident<erp::types::field::MultipleIds> {
-            pub fn get_ids(&self) -> Vec<u32> {
+            pub fn get_ids(&self) -> Vec<u64> {
                 self.id.get_ids_ref().clone()
             }
 
-            pub fn get_ids_ref(&self) -> &Vec<u32> {
+            pub fn get_ids_ref(&self) -> &Vec<u64> {
                 &self.id.get_ids_ref()
             }
 
+            /// Fields of this model, used to build a domain
+            pub fn fields() -> #fields_struct_ident {
+                <#fields_struct_ident as erp::types::domain::DomainFields>::with_path(Vec::new())
+            }
+
+            pub fn unlink(&self, env: &mut erp::environment::Environment) -> Result<(), Box<dyn std::error::Error>> {
+                (self as &dyn erp::model::Model<erp::types::field::MultipleIds, BaseModel=<Self as erp::types::model::CommonModel<erp::types::field::MultipleIds>>::BaseModel>).unlink(env)
+            }
+
             #(#impl_model_fields_multi)*
         }
 
@@ -245,18 +367,32 @@ pub fn derive(item: &DeriveInput) -> Result<TokenStream> {
         quote! { None }
     };
 
+    let order = order.iter().map(|order| {
+        let path = order.path.join(".");
+        match order.direction {
+            OrderDirection::Asc => quote! { erp::search::OrderBy::asc(#path) },
+            OrderDirection::Desc => quote! { erp::search::OrderBy::desc(#path) },
+        }
+    });
+
     let fields_descriptor = fields.iter().map(|f| {
         let FieldGen {
             field_name,
             is_required,
             is_reference,
             is_reference_multi,
-            field_type_keyword,
+            field_type,
             default: default_value,
+            default_kind,
             description,
             compute,
             depends,
             inverse,
+            ondelete,
+            digits,
+            currency_field,
+            relation,
+            is_serialized,
             ..
         } = f;
 
@@ -268,12 +404,48 @@ pub fn derive(item: &DeriveInput) -> Result<TokenStream> {
                 FieldType::Integer(i) => quote! {
                     Some(erp::types::field::FieldType::Integer(#i))
                 },
+                FieldType::BigInteger(i) => quote! {
+                    Some(erp::types::field::FieldType::BigInteger(#i))
+                },
                 FieldType::Float(f) => quote! {
                     Some(erp::types::field::FieldType::Float(#f))
                 },
+                FieldType::Double(d) => quote! {
+                    Some(erp::types::field::FieldType::Double(#d))
+                },
                 FieldType::Bool(b) => quote! {
                     Some(erp::types::field::FieldType::Bool(#b))
                 },
+                FieldType::Decimal(d) => {
+                    let d = d.to_string();
+                    quote! {
+                        Some(erp::types::field::FieldType::Decimal(#d.parse().unwrap()))
+                    }
+                }
+                FieldType::Date(d) => {
+                    let d = d.to_string();
+                    quote! {
+                        Some(erp::types::field::FieldType::Date(#d.parse().unwrap()))
+                    }
+                }
+                FieldType::DateTime(d) => {
+                    let d = d.to_rfc3339();
+                    quote! {
+                        Some(erp::types::field::FieldType::DateTime(#d.parse().unwrap()))
+                    }
+                }
+                FieldType::Json(j) => {
+                    let j = j.to_string();
+                    quote! {
+                        Some(erp::types::field::FieldType::Json(#j.parse().unwrap()))
+                    }
+                }
+                FieldType::Binary(b) => {
+                    let bytes = b.iter();
+                    quote! {
+                        Some(erp::types::field::FieldType::Binary(vec![#(#bytes),*]))
+                    }
+                }
                 FieldType::Ref(r) => quote! {
                     Some(erp::types::field::FieldType::Ref(#r))
                 },
@@ -297,18 +469,56 @@ pub fn derive(item: &DeriveInput) -> Result<TokenStream> {
                     Some(erp::types::field::FieldType::Ref(0))
                 }
             }
+        } else if *is_serialized {
+            // Optional fields are empty by default, the value only gives the type of the field
+            if *is_required {
+                quote! {
+                    Some(erp::types::field::FieldType::Json(erp::types::field::to_json(&<#field_type as Default>::default()).unwrap()))
+                }
+            } else {
+                quote! {
+                    Some(erp::types::field::FieldType::Json(erp::types::field::Json::Null))
+                }
+            }
         } else {
             quote! {
-                Some((#field_type_keyword::default()).into())
+                Some((<#field_type as Default>::default()).into())
             }
         };
 
+        let default_kind = match default_kind {
+            DefaultKind::Value => quote! { erp::types::field::DefaultKind::Value },
+            DefaultKind::Now => quote! { erp::types::field::DefaultKind::Now },
+            DefaultKind::Empty => quote! { erp::types::field::DefaultKind::Empty },
+        };
+
         let description = if let Some(description) = description {
             quote! { Some(#description.to_string()) }
         } else {
             quote! { None }
         };
 
+        let digits = if let Some(digits) = digits {
+            quote! { Some(#digits) }
+        } else {
+            quote! { None }
+        };
+
+        let currency_field = if let Some(CurrencyField {
+            field_name,
+            decimal_places_field,
+        }) = currency_field
+        {
+            quote! {
+                Some(erp::types::field::CurrencyField {
+                    field_name: #field_name.to_string(),
+                    decimal_places_field: #decimal_places_field.to_string(),
+                })
+            }
+        } else {
+            quote! { None }
+        };
+
         let compute = if compute.is_some() {
             let depends = if let Some(depends) = depends {
                 let tokens = depends.iter().map(|dep| quote! { #dep.to_string() });
@@ -335,13 +545,21 @@ pub fn derive(item: &DeriveInput) -> Result<TokenStream> {
         let field_reference = if *is_reference {
             let inverse_field = if let Some(inverse) = inverse {
                 quote! { erp::types::field::FieldReferenceType::O2M { inverse_field: #inverse.to_string() } }
+            } else if let Some(relation) = relation {
+                quote! { erp::types::field::FieldReferenceType::M2M { relation: #relation.to_string(), inverse_fields: Vec::new() } }
             } else {
-                quote! { erp::types::field::FieldReferenceType::M2O { inverse_fields: Vec::new() } }
+                let ondelete = match ondelete {
+                    Some(OnDelete::Cascade) => quote! { Some(erp::types::field::OnDelete::Cascade) },
+                    Some(OnDelete::SetNull) => quote! { Some(erp::types::field::OnDelete::SetNull) },
+                    Some(OnDelete::Restrict) => quote! { Some(erp::types::field::OnDelete::Restrict) },
+                    None => quote! { None },
+                };
+                quote! { erp::types::field::FieldReferenceType::M2O { inverse_fields: Vec::new(), ondelete: #ondelete } }
             };
 
             quote! {
                 Some(erp::types::field::FieldReference {
-                    target_model: #field_type_keyword::_get_model_name().to_string(),
+                    target_model: #field_type::_get_model_name().to_string(),
                     inverse_field: #inverse_field,
                 })
             }
@@ -356,8 +574,11 @@ pub fn derive(item: &DeriveInput) -> Result<TokenStream> {
                 erp::types::field::FieldDescriptor {
                     name: #field_name.to_string(),
                     default_value: #default_value,
+                    default_kind: #default_kind,
                     description: #description,
                     required: #is_required,
+                    digits: #digits,
+                    currency_field: #currency_field,
                     compute: #compute,
                     field_ref: #field_reference,
                 }
@@ -385,12 +606,14 @@ pub fn derive(item: &DeriveInput) -> Result<TokenStream> {
             fn get_model_descriptor() -> erp::types::model::ModelDescriptor {
                 let name = Self::_get_model_name().to_string();
                 let description = #description;
+                let order = vec![#(#order,)*];
                 let fields = vec![
                     #(#fields_descriptor,)*
                 ];
                 erp::types::model::ModelDescriptor {
                     name,
                     description,
+                    order,
                     fields,
                 }
             }
@@ -446,6 +669,8 @@ pub fn derive(item: &DeriveInput) -> Result<TokenStream> {
 
         #impl_model
 
+        #domain_fields
+
         #common_model_impl
 
         #iterator
@@ -46,3 +46,51 @@ pub fn gen_wrong_default_value(span: Span, default_value: &str, name: &str) -> E
 pub fn gen_inverse_not_multiple_ids(span: Span) -> Error {
     Error::new(span, "Inverse attribute should only work on Reference<..., MultipleIds>")
 }
+
+pub fn gen_wrong_ondelete_value(span: Span, error: &str) -> Error {
+    Error::new(span, error)
+}
+
+pub fn gen_ondelete_not_single_id(span: Span) -> Error {
+    Error::new(span, "Ondelete attribute should only work on Reference<..., SingleId>")
+}
+
+pub fn gen_wrong_order_value(span: Span, error: &str) -> Error {
+    Error::new(span, error)
+}
+
+pub fn gen_digits_not_decimal(span: Span) -> Error {
+    Error::new(span, "Digits attribute should only work on Decimal or Monetary fields")
+}
+
+pub fn gen_currency_field_not_monetary(span: Span) -> Error {
+    Error::new(span, "Currency_field attribute should only work on Monetary fields")
+}
+
+pub fn gen_wrong_currency_field_value(span: Span, error: &str) -> Error {
+    Error::new(span, error)
+}
+
+pub fn gen_monetary_without_currency_field(span: Span) -> Error {
+    Error::new(span, "Monetary fields should have a `currency_field` attribute")
+}
+
+pub fn gen_many2many_not_multiple_ids(span: Span) -> Error {
+    Error::new(span, "Many2many attribute should only work on Reference<..., MultipleIds>")
+}
+
+pub fn gen_many2many_with_inverse(span: Span) -> Error {
+    Error::new(span, "Many2many attribute cannot be used along with the inverse attribute")
+}
+
+pub fn gen_many2many_without_relation(span: Span) -> Error {
+    Error::new(span, "Many2many fields should have a `relation` attribute")
+}
+
+pub fn gen_relation_not_many2many(span: Span) -> Error {
+    Error::new(span, "Relation attribute should only work on many2many fields")
+}
+
+pub fn gen_json_reference(span: Span) -> Error {
+    Error::new(span, "Json attribute should not work on Reference fields")
+}
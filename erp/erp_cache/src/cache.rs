@@ -12,7 +12,7 @@ pub struct Cache {
 impl Cache {
 
     /// Check if a given record is present in cache. If CacheModels not found, panic
-    pub fn is_record_present(&self, model_name: &str, id: &u32) -> bool {
+    pub fn is_record_present(&self, model_name: &str, id: &u64) -> bool {
         self.get_cache_models(model_name).is_record_present(id)
     }
 
@@ -30,13 +30,18 @@ impl Cache {
             .unwrap_or_else(|| panic!("Model {} not found", model_name))
     }
 
+    /// Remove given records from the cache. If CacheModels not found, panic
+    pub fn remove_records_from_cache(&mut self, model_name: &str, ids: &[u64]) {
+        self.get_cache_models_mut(model_name).remove_models(ids);
+    }
+
     /// Get value of given field for given record
-    /// TODO Do not pass a ref to u32
+    /// TODO Do not pass a ref to u64
     pub fn get_field_from_cache(
         &self,
         model_name: &str,
         field_name: &str,
-        id: &u32,
+        id: &u64,
     ) -> Option<&FieldType> {
         self.cache
             .get(model_name)?
@@ -50,8 +55,8 @@ impl Cache {
         &self,
         model_name: &str,
         field_name: &str,
-        ids: &[u32],
-    ) -> Vec<u32> {
+        ids: &[u64],
+    ) -> Vec<u64> {
         let cache_models = self.cache.get(model_name);
         if cache_models.is_none() {
             return vec![];
@@ -74,7 +79,7 @@ impl Cache {
     }
 
     /// Check if given record field is present in cache
-    pub fn is_field_in_cache(&self, model_name: &str, field_name: &str, id: &u32) -> bool {
+    pub fn is_field_in_cache(&self, model_name: &str, field_name: &str, id: &u64) -> bool {
         self.cache
             .get(model_name)
             .and_then(|cache_models| cache_models.get_model(id))
@@ -91,11 +96,11 @@ impl Cache {
         &mut self,
         model_name: &str,
         field_name: &str,
-        ids: &[u32],
+        ids: &[u64],
         field_value: Option<FieldType>,
         update_dirty: &Dirty,
         update_if_exists: &Update,
-    ) -> Vec<u32> {
+    ) -> Vec<u64> {
         let cache_models = self.get_cache_models_mut(model_name);
         let mut updated_ids = Vec::with_capacity(ids.len());
         for id in ids {
@@ -121,7 +126,7 @@ impl Cache {
     pub fn insert_fields_in_cache(
         &mut self,
         model_name: &str,
-        id: u32,
+        id: u64,
         field_values: MapOfFields,
         update_dirty: &Dirty,
         update_if_exists: &Update,
@@ -138,7 +143,7 @@ impl Cache {
         &self,
         model_name: &str,
         field_filter: F,
-    ) -> HashMap<u32, MapOfFields>
+    ) -> HashMap<u64, MapOfFields>
     where
         F: Fn(&str) -> bool,
     {
@@ -147,7 +152,7 @@ impl Cache {
     }
 
     /// Get dirty fields from given list of fields
-    pub fn get_dirty_fields(&self, model_name: &str, fields: &[&str]) -> HashMap<u32, MapOfFields> {
+    pub fn get_dirty_fields(&self, model_name: &str, fields: &[&str]) -> HashMap<u64, MapOfFields> {
         let cache_models = self.get_cache_models(model_name);
         cache_models.get_dirty_fields_for_fields(fields)
     }
@@ -156,9 +161,9 @@ impl Cache {
     pub fn get_dirty_records<F>(
         &self,
         model_name: &str,
-        ids: &[u32],
+        ids: &[u64],
         field_filter: F,
-    ) -> HashMap<u32, MapOfFields>
+    ) -> HashMap<u64, MapOfFields>
     where
         F: Fn(&str) -> bool,
     {
@@ -191,8 +196,8 @@ impl Cache {
 
     // Compute
 
-    pub fn is_field_to_recompute(&self, model_name: &str, field_name: &str, id: &u32) -> bool {
-        self.cache.get(model_name).map_or(false, |cache_models| {
+    pub fn is_field_to_recompute(&self, model_name: &str, field_name: &str, id: &u64) -> bool {
+        self.cache.get(model_name).is_some_and(|cache_models| {
             cache_models.is_to_recompute(field_name, id)
         })
     }
@@ -202,8 +207,8 @@ impl Cache {
         &self,
         model_name: &str,
         field_name: &str,
-        ids: &[u32],
-    ) -> Vec<u32> {
+        ids: &[u64],
+    ) -> Vec<u64> {
         let cache_models = self.cache.get(model_name);
         if cache_models.is_none() {
             return vec![];
@@ -221,7 +226,7 @@ impl Cache {
             .collect()
     }
 
-    pub fn add_ids_to_recompute(&mut self, model_name: &str, fields_name: &[&str], ids: &[u32]) {
+    pub fn add_ids_to_recompute(&mut self, model_name: &str, fields_name: &[&str], ids: &[u64]) {
         let cache_models = self.get_cache_models_mut(model_name);
         cache_models.add_to_recompute(fields_name, ids);
     }
@@ -230,7 +235,7 @@ impl Cache {
         &mut self,
         model_name: &str,
         fields_name: &[&str],
-        ids: &[u32],
+        ids: &[u64],
     ) {
         let cache_models = self.get_cache_models_mut(model_name);
         cache_models.remove_to_recompute(fields_name, ids);
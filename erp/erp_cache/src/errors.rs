@@ -3,7 +3,7 @@ use std::{error, fmt};
 #[derive(Debug, Clone)]
 pub struct RecordsNotFoundError {
     pub(crate) model_name: String,
-    pub(crate) ids: Vec<u32>,
+    pub(crate) ids: Vec<u64>,
 }
 
 impl fmt::Display for RecordsNotFoundError {
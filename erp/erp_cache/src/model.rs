@@ -6,20 +6,20 @@ use crate::CacheField;
 
 #[derive(Clone)]
 pub struct CacheModel {
-    id: u32,
+    id: u64,
     fields: HashMap<String, CacheField>,
 }
 
 impl CacheModel {
-    pub fn new(id: u32) -> CacheModel {
+    pub fn new(id: u64) -> CacheModel {
         Self::new_with_fields(id, HashMap::new())
     }
 
-    pub fn new_with_fields(id: u32, fields: HashMap<String, CacheField>) -> CacheModel {
+    pub fn new_with_fields(id: u64, fields: HashMap<String, CacheField>) -> CacheModel {
         CacheModel { id, fields }
     }
 
-    pub fn id(&self) -> u32 {
+    pub fn id(&self) -> u64 {
         self.id
     }
 
@@ -90,11 +90,10 @@ impl CacheModel {
         let mut dirty_fields = Vec::new();
         for (name, value) in fields.fields {
             let result = self.insert_field(name.as_str(), value, update_if_exists);
-            if let Some(result) = result {
-                if result.1 {
+            if let Some(result) = result
+                && result.1 {
                     dirty_fields.push(name);
                 }
-            }
         }
         dirty_fields
     }
@@ -12,10 +12,11 @@ use crate::CacheModel;
 /// - "To recompute" fields
 #[derive(Clone)]
 pub struct CacheModels {
+    #[allow(dead_code)]
     name: String,
-    pub models: HashMap<u32, CacheModel>,
-    pub dirty: HashMap<u32, HashSet<String>>,
-    pub to_recompute: HashMap<String, HashSet<u32>>,
+    pub models: HashMap<u64, CacheModel>,
+    pub dirty: HashMap<u64, HashSet<String>>,
+    pub to_recompute: HashMap<String, HashSet<u64>>,
 }
 
 impl CacheModels {
@@ -31,26 +32,38 @@ impl CacheModels {
 
     // Cache methods
 
-    pub fn is_record_present(&self, id: &u32) -> bool {
+    pub fn is_record_present(&self, id: &u64) -> bool {
         self.models.contains_key(id)
     }
 
-    pub fn get_model(&self, id: &u32) -> Option<&CacheModel> {
+    pub fn get_model(&self, id: &u64) -> Option<&CacheModel> {
         self.models.get(id)
     }
 
-    pub fn get_model_mut(&mut self, id: &u32) -> Option<&mut CacheModel> {
+    pub fn get_model_mut(&mut self, id: &u64) -> Option<&mut CacheModel> {
         self.models.get_mut(id)
     }
 
-    pub fn get_model_or_create(&mut self, id: u32) -> &mut CacheModel {
+    pub fn get_model_or_create(&mut self, id: u64) -> &mut CacheModel {
         self.models.entry(id).or_insert_with(|| CacheModel::new(id))
     }
 
+    /// Remove given records from the cache, along with their dirty and "to recompute" fields
+    pub fn remove_models(&mut self, ids: &[u64]) {
+        for id in ids {
+            self.models.remove(id);
+            self.dirty.remove(id);
+        }
+        self.to_recompute.retain(|_field_name, set| {
+            set.retain(|id| !ids.contains(id));
+            !set.is_empty()
+        });
+    }
+
     pub fn insert_field(
         &mut self,
         field_name: &str,
-        id: u32,
+        id: u64,
         field_value: Option<FieldType>,
         update_dirty: &Dirty,
         update_if_exists: &Update,
@@ -58,19 +71,17 @@ impl CacheModels {
         let cache_model = self.get_model_or_create(id);
         let result = cache_model.insert_field(field_name, field_value.clone(), update_if_exists);
         let is_some = result.is_some();
-        if matches!(update_dirty, Dirty::UpdateDirty) {
-            if let Some((_cache_field, dirty)) = &result {
-                if *dirty {
+        if matches!(update_dirty, Dirty::UpdateDirty)
+            && let Some((_cache_field, dirty)) = &result
+                && *dirty {
                     self.add_dirty(id, vec![field_name.to_string()]);
                 }
-            }
-        }
         is_some
     }
 
     pub fn insert_fields(
         &mut self,
-        id: u32,
+        id: u64,
         field_values: MapOfFields,
         update_dirty: &Dirty,
         update_if_exists: &Update,
@@ -87,7 +98,7 @@ impl CacheModels {
     /// Get dirty data linked to given model.
     ///
     /// Do not insert non-stored fields
-    pub fn get_dirty_fields<F>(&self, field_filter: F) -> HashMap<u32, MapOfFields>
+    pub fn get_dirty_fields<F>(&self, field_filter: F) -> HashMap<u64, MapOfFields>
     where
         F: Fn(&str) -> bool,
     {
@@ -97,19 +108,19 @@ impl CacheModels {
     /// Get dirty data linked to given model and given fields.
     ///
     /// Do not insert non-stored fields
-    pub fn get_dirty_fields_for_fields(&self, fields: &[&str]) -> HashMap<u32, MapOfFields> {
+    pub fn get_dirty_fields_for_fields(&self, fields: &[&str]) -> HashMap<u64, MapOfFields> {
         self._get_dirty_map_of_fields_from_filter(|field_name| fields.contains(&field_name))
     }
 
     /// Get all dirty filtered fields for given records
-    pub fn get_dirty_records<F>(&self, ids: &[u32], field_filter: F) -> HashMap<u32, MapOfFields>
+    pub fn get_dirty_records<F>(&self, ids: &[u64], field_filter: F) -> HashMap<u64, MapOfFields>
     where
         F: Fn(&str) -> bool,
     {
-        let mut result: HashMap<u32, MapOfFields> = HashMap::new();
+        let mut result: HashMap<u64, MapOfFields> = HashMap::new();
         for id in ids {
-            if let Some(cache_model) = self.get_model(id) {
-                if let Some(dirty_fields) = self.dirty.get(id) {
+            if let Some(cache_model) = self.get_model(id)
+                && let Some(dirty_fields) = self.dirty.get(id) {
                     let map: HashMap<String, Option<FieldType>> = dirty_fields
                         .iter()
                         .filter_map(|dirty_field| {
@@ -126,18 +137,17 @@ impl CacheModels {
                         result.insert(*id, MapOfFields::new(map));
                     }
                 }
-            }
         }
 
         result
     }
 
     /// Get dirty data linked to given filter
-    fn _get_dirty_map_of_fields_from_filter<F>(&self, field_filter: F) -> HashMap<u32, MapOfFields>
+    fn _get_dirty_map_of_fields_from_filter<F>(&self, field_filter: F) -> HashMap<u64, MapOfFields>
     where
         F: Fn(&str) -> bool,
     {
-        let mut result: HashMap<u32, MapOfFields> = HashMap::new();
+        let mut result: HashMap<u64, MapOfFields> = HashMap::new();
         for (id, dirty_fields) in &self.dirty {
             if let Some(cache_model) = self.get_model(id) {
                 let map: HashMap<String, Option<FieldType>> = dirty_fields
@@ -161,21 +171,21 @@ impl CacheModels {
         result
     }
 
-    pub fn add_dirty(&mut self, id: u32, fields: Vec<String>) {
+    pub fn add_dirty(&mut self, id: u64, fields: Vec<String>) {
         self.dirty.entry(id).or_default().extend(fields);
     }
 
-    pub fn is_dirty(&self, id: &u32) -> bool {
+    pub fn is_dirty(&self, id: &u64) -> bool {
         self.dirty.contains_key(id)
     }
 
-    pub fn is_field_dirty(&self, field_name: &str, id: &u32) -> bool {
+    pub fn is_field_dirty(&self, field_name: &str, id: &u64) -> bool {
         self.dirty
             .get(id)
-            .map_or(false, |d| d.iter().any(|f| f == field_name))
+            .is_some_and(|d| d.iter().any(|f| f == field_name))
     }
 
-    pub fn get_dirty(&self, id: &u32) -> Option<&HashSet<String>> {
+    pub fn get_dirty(&self, id: &u64) -> Option<&HashSet<String>> {
         self.dirty.get(id)
     }
 
@@ -183,11 +193,11 @@ impl CacheModels {
         self.dirty.clear();
     }
 
-    pub fn clear_dirty(&mut self, ids: &[u32]) {
+    pub fn clear_dirty(&mut self, ids: &[u64]) {
         self.dirty.retain(|key, _| !ids.contains(key));
     }
 
-    pub fn clear_dirty_records(&mut self, fields: &[&str], ids: &[u32]) {
+    pub fn clear_dirty_records(&mut self, fields: &[&str], ids: &[u64]) {
         for id in ids {
             if let Some(vec) = self.dirty.get_mut(id) {
                 vec.retain(|f| !fields.contains(&f.as_str()));
@@ -200,7 +210,7 @@ impl CacheModels {
 
     // Computed methods
 
-    pub fn add_to_recompute(&mut self, fields_name: &[&str], ids: &[u32]) {
+    pub fn add_to_recompute(&mut self, fields_name: &[&str], ids: &[u64]) {
         for &field_name in fields_name {
             let mut set = self.to_recompute.get_mut(field_name);
             if set.is_none() {
@@ -212,7 +222,7 @@ impl CacheModels {
         }
     }
 
-    pub fn remove_to_recompute(&mut self, fields_name: &[&str], ids: &[u32]) {
+    pub fn remove_to_recompute(&mut self, fields_name: &[&str], ids: &[u64]) {
         for &field_name in fields_name {
             if let Some(set) = self.to_recompute.get_mut(field_name) {
                 set.retain(|f| !ids.contains(f));
@@ -223,12 +233,12 @@ impl CacheModels {
         }
     }
 
-    pub fn is_to_recompute(&self, field_name: &str, id: &u32) -> bool {
+    pub fn is_to_recompute(&self, field_name: &str, id: &u64) -> bool {
         self.get_to_recompute(field_name)
-            .map_or(false, |set| set.contains(id))
+            .is_some_and(|set| set.contains(id))
     }
 
-    pub fn get_to_recompute(&self, field_name: &str) -> Option<&HashSet<u32>> {
+    pub fn get_to_recompute(&self, field_name: &str) -> Option<&HashSet<u64>> {
         self.to_recompute.get(field_name)
     }
 }
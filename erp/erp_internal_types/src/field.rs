@@ -1,4 +1,7 @@
-use erp_types::field::{FieldCompute, FieldDepend, FieldReference, FieldType};
+use erp_types::field::{
+    CurrencyField, DefaultKind, FieldCompute, FieldDepend, FieldReference, FieldReferenceType,
+    FieldType, OnDelete,
+};
 use std::any::TypeId;
 use std::collections::HashSet;
 
@@ -7,8 +10,11 @@ pub struct InternalField {
     pub name: String,
     // TODO Fix this, by separating default value and type of field
     pub default_value: Option<FieldType>,
+    pub default_kind: DefaultKind,
     pub description: Option<String>,
     pub required: bool,
+    pub digits: Option<u32>,
+    pub currency_field: Option<CurrencyField>,
     pub compute: Option<FieldCompute>,
     pub field_ref: Option<FieldReference>,
 }
@@ -21,6 +27,12 @@ pub struct FinalInternalField {
     pub description: String,
     pub required: bool,
     pub default_value: FieldType,
+    /// If it's not `DefaultKind::Value`, default_value is only used to know the type of the field
+    pub default_kind: DefaultKind,
+    /// Number of decimal places of a Decimal field
+    pub digits: Option<u32>,
+    /// Currency of a Monetary field
+    pub currency_field: Option<CurrencyField>,
     pub compute: Option<FieldCompute>,
     // If the type is M2O, O2M or M2M, there is an inverse here (but the field could be empty)
     pub inverse: Option<FieldReference>,
@@ -35,6 +47,9 @@ impl FinalInternalField {
             description: field_name.to_string(),
             required: false,
             default_value: FieldType::String("".to_string()),
+            default_kind: DefaultKind::Value,
+            digits: None,
+            currency_field: None,
             compute: None,
             inverse: None,
             depends: Vec::new(),
@@ -47,6 +62,48 @@ impl FinalInternalField {
         !matches!(self.default_value, FieldType::Refs(_))
     }
 
+    /// Return true if this field is a M2M. Its values are not stored in the table of its model,
+    /// but in its relation table
+    pub fn is_many2many(&self) -> bool {
+        matches!(
+            self.inverse,
+            Some(FieldReference {
+                inverse_field: FieldReferenceType::M2M { .. },
+                ..
+            })
+        )
+    }
+
+    /// Return true if this field stores a JSON value
+    pub fn is_json(&self) -> bool {
+        matches!(self.default_value, FieldType::Json(_))
+    }
+
+    /// Return true if this field stores the content of a file. Only its key is stored in the table
+    /// of its model, the content is saved in the filestore
+    pub fn is_binary(&self) -> bool {
+        matches!(self.default_value, FieldType::Binary(_))
+    }
+
+    /// Return true if values written on this field need to be rounded
+    pub fn is_rounded(&self) -> bool {
+        matches!(self.default_value, FieldType::Decimal(_))
+            && (self.digits.is_some() || self.currency_field.is_some())
+    }
+
+    /// Policy applied when the record targeted by this field is deleted.
+    ///
+    /// Return None if this field is not a M2O
+    pub fn get_ondelete(&self) -> Option<OnDelete> {
+        match &self.inverse {
+            Some(FieldReference {
+                inverse_field: FieldReferenceType::M2O { ondelete, .. },
+                ..
+            }) => Some(ondelete.unwrap_or_default()),
+            _ => None,
+        }
+    }
+
     pub fn register_internal_field(&mut self, field_descriptor: &InternalField, type_id: &TypeId) {
         if let Some(default_value) = &field_descriptor.default_value {
             if self.is_init {
@@ -61,6 +118,7 @@ impl FinalInternalField {
                 }
             }
             self.default_value = default_value.clone();
+            self.default_kind = field_descriptor.default_kind;
         } else if !self.is_init {
             panic!(
                 "First register should have a default value. This is needed to identify the type of the field (name: {}).",
@@ -71,6 +129,12 @@ impl FinalInternalField {
             self.description = description.clone();
         }
         self.required = field_descriptor.required;
+        if let Some(digits) = field_descriptor.digits {
+            self.digits = Some(digits);
+        }
+        if let Some(currency_field) = &field_descriptor.currency_field {
+            self.currency_field = Some(currency_field.clone());
+        }
         if let Some(new_compute) = &field_descriptor.compute {
             if let Some(existing_compute) = &mut self.compute {
                 existing_compute.type_id = *type_id;
@@ -90,7 +154,24 @@ impl FinalInternalField {
             }
         }
         if let Some(inverse) = &field_descriptor.field_ref {
-            self.inverse = Some(inverse.clone());
+            let mut inverse = inverse.clone();
+            // Keep the previous ondelete policy if this one doesn't declare any
+            if let (
+                Some(FieldReference {
+                    inverse_field:
+                        FieldReferenceType::M2O {
+                            ondelete: Some(old_ondelete),
+                            ..
+                        },
+                    ..
+                }),
+                FieldReferenceType::M2O { ondelete, .. },
+            ) = (&self.inverse, &mut inverse.inverse_field)
+                && ondelete.is_none()
+            {
+                *ondelete = Some(*old_ondelete);
+            }
+            self.inverse = Some(inverse);
         }
         self.is_init = true;
     }
@@ -1,8 +1,9 @@
 use std::any::TypeId;
 use std::collections::HashMap;
 use std::error::Error;
+use erp_search::{OrderBy, SearchOptions};
 use erp_types::environment::ErasedEnvironment;
-use erp_types::field::{FieldCompute, FieldType, MultipleIds};
+use erp_types::field::{DefaultKind, FieldCompute, FieldType, MultipleIds};
 use erp_types::model::{CommonModel, ModelDescriptor};
 use crate::field::InternalField;
 use crate::FinalInternalField;
@@ -35,6 +36,8 @@ impl InternalModel {
 pub struct FinalInternalModel {
     pub name: String,
     pub description: String,
+    /// Default order of records
+    pub order: Vec<OrderBy>,
     pub models: HashMap<TypeId, InternalModel>,
     pub fields: HashMap<String, FinalInternalField>,
 }
@@ -55,6 +58,7 @@ impl FinalInternalModel {
         FinalInternalModel {
             name: model_name.to_string(),
             description: "".to_string(),
+            order: Vec::new(),
             models: HashMap::new(),
             fields: HashMap::new(),
         }
@@ -71,6 +75,7 @@ impl FinalInternalModel {
         let ModelDescriptor {
             name: _name,
             description,
+            order,
             fields,
         } = model_descriptor;
 
@@ -84,8 +89,11 @@ impl FinalInternalModel {
             let internal_field = InternalField {
                 name: field_name.clone(),
                 default_value: field.default_value,
+                default_kind: field.default_kind,
                 description: field.description,
                 required: field.required,
+                digits: field.digits,
+                currency_field: field.currency_field,
                 compute: field.compute,
                 field_ref: field.field_ref,
             };
@@ -104,6 +112,9 @@ impl FinalInternalModel {
         if let Some(description) = &internal_model.description {
             self.description = description.clone();
         }
+        if !order.is_empty() {
+            self.order = order;
+        }
         self.models.insert(type_id, internal_model);
     }
 
@@ -144,6 +155,26 @@ impl FinalInternalModel {
             .expect("Internal model not registered")
     }
 
+    /// Get the order to use when searching records of this model with given options.
+    ///
+    /// If the order of given options is empty, the default order of this model is used. Records
+    /// are always sorted by id at the end, so that the order is stable, except for unordered
+    /// searches that don't have any order
+    pub fn get_order(&self, options: &SearchOptions) -> Vec<OrderBy> {
+        if options.unordered {
+            return vec![];
+        }
+        let mut result = if options.order.is_empty() {
+            self.order.clone()
+        } else {
+            options.order.clone()
+        };
+        if !result.iter().any(|order| order.is_id()) {
+            result.push(OrderBy::asc("id"));
+        }
+        result
+    }
+
     /// Get a vector of all registered fields for this model
     pub fn get_fields_name(&self) -> Vec<&str> {
         self.fields.keys().map(|s| s.as_str()).collect()
@@ -189,6 +220,29 @@ impl FinalInternalModel {
         self.fields.get(field_name).is_some_and(|f| f.is_stored())
     }
 
+    /// Return true if given field is a M2M.
+    ///
+    /// If field is not present, return false
+    pub fn is_many2many(&self, field_name: &str) -> bool {
+        self.fields.get(field_name).is_some_and(|f| f.is_many2many())
+    }
+
+    /// Return true if given field is a Binary.
+    ///
+    /// If field is not present, return false
+    pub fn is_binary(&self, field_name: &str) -> bool {
+        self.fields.get(field_name).is_some_and(|f| f.is_binary())
+    }
+
+    /// Return the name of all Binary fields
+    pub fn get_binary_fields(&self) -> Vec<&str> {
+        self.fields
+            .values()
+            .filter(|f| f.is_binary())
+            .map(|f| f.name.as_str())
+            .collect()
+    }
+
     /// TODO Do not panic, but instead return an Option
     pub fn get_internal_field(&self, field_name: &str) -> &FinalInternalField {
         self.fields
@@ -203,12 +257,16 @@ impl FinalInternalModel {
             .unwrap_or_else(|| panic!("Field {} is not present in model {}", field_name, self.name))
     }
 
-    /// Return default value for given field.
+    /// Return default value for given field, or None if the field should be empty.
     ///
     /// If the first is not present, panic
-    pub fn get_default_value(&self, field_name: &str) -> FieldType {
+    pub fn get_default_value(&self, field_name: &str) -> Option<FieldType> {
         let field = self.get_internal_field(field_name);
-        field.default_value.clone()
+        match field.default_kind {
+            DefaultKind::Value => Some(field.default_value.clone()),
+            DefaultKind::Now => field.default_value.now(),
+            DefaultKind::Empty => None,
+        }
     }
 
     /// Return true if given field is a computed field.
@@ -217,7 +275,7 @@ impl FinalInternalModel {
     pub fn is_computed_field(&self, field_name: &str) -> bool {
         self.fields
             .get(field_name)
-            .map_or(false, |field| field.compute.is_some())
+            .is_some_and(|field| field.compute.is_some())
     }
 
     /// Return the internal model linked to the computed given field.
@@ -0,0 +1,329 @@
+use crate::field::{FieldType, Json};
+use crate::model::BaseModel;
+use erp_search::{LeftTuple, RightTuple, SearchOperator, SearchTuple, SearchType};
+use std::marker::PhantomData;
+use std::ops::Deref;
+
+/// Fields of a model, generated by `#[derive(Model)]` to build domains that are checked at
+/// compile time.
+///
+/// Each method of the generated struct returns a `DomainField` or a `DomainReference` whose
+/// path starts with the path of this struct
+pub trait DomainFields {
+    /// Create the fields of a model reached through given path of references
+    fn with_path(path: Vec<String>) -> Self;
+}
+
+/// Fields to use when a domain goes through a reference to this BaseModel
+///
+/// Only implemented by the model that defines the BaseModel, so fields added by derived models
+/// can't be reached through a reference
+pub trait BaseModelFields: BaseModel {
+    type Fields: DomainFields;
+}
+
+/// Value that can be compared with a field of type `T`
+///
+/// Only implemented for `T` itself and for `&str` on String fields, so that literals like `42`
+/// or `4.2` take the type of the field
+pub trait DomainValue<T> {
+    fn into_value(self) -> T;
+}
+
+impl<T> DomainValue<T> for T {
+    fn into_value(self) -> T {
+        self
+    }
+}
+
+impl DomainValue<String> for &str {
+    fn into_value(self) -> String {
+        self.to_string()
+    }
+}
+
+fn join_path(path: &[String], field_name: &str) -> Vec<String> {
+    let mut path = path.to_vec();
+    path.push(field_name.to_string());
+    path
+}
+
+fn make_tuple(path: &[String], operator: SearchOperator, right: RightTuple) -> SearchType {
+    SearchType::Tuple(SearchTuple {
+        left: LeftTuple {
+            path: path.to_vec(),
+        },
+        operator,
+        right,
+    })
+}
+
+fn make_values<T, V>(values: impl IntoIterator<Item = V>) -> RightTuple
+where
+    T: Into<FieldType>,
+    V: DomainValue<T>,
+{
+    RightTuple::Array(
+        values
+            .into_iter()
+            .map(|value| value.into_value().into().into())
+            .collect(),
+    )
+}
+
+/// Non-relational field of type `T`, used to build a domain
+pub struct DomainField<T> {
+    path: Vec<String>,
+    _phantom_data: PhantomData<T>,
+}
+
+impl<T> DomainField<T> {
+    pub fn new(path: &[String], field_name: &str) -> Self {
+        DomainField {
+            path: join_path(path, field_name),
+            _phantom_data: PhantomData,
+        }
+    }
+
+    /// Records where this field has a value
+    pub fn is_set(&self) -> SearchType {
+        make_tuple(&self.path, SearchOperator::NotEqual, RightTuple::None)
+    }
+
+    /// Records where this field doesn't have any value
+    pub fn is_not_set(&self) -> SearchType {
+        make_tuple(&self.path, SearchOperator::Equal, RightTuple::None)
+    }
+}
+
+impl<T: Into<FieldType>> DomainField<T> {
+    fn compare(&self, operator: SearchOperator, value: T) -> SearchType {
+        make_tuple(&self.path, operator, value.into().into())
+    }
+
+    pub fn eq(&self, value: impl DomainValue<T>) -> SearchType {
+        self.compare(SearchOperator::Equal, value.into_value())
+    }
+
+    pub fn ne(&self, value: impl DomainValue<T>) -> SearchType {
+        self.compare(SearchOperator::NotEqual, value.into_value())
+    }
+
+    pub fn gt(&self, value: impl DomainValue<T>) -> SearchType {
+        self.compare(SearchOperator::Greater, value.into_value())
+    }
+
+    pub fn ge(&self, value: impl DomainValue<T>) -> SearchType {
+        self.compare(SearchOperator::GreaterEqual, value.into_value())
+    }
+
+    pub fn lt(&self, value: impl DomainValue<T>) -> SearchType {
+        self.compare(SearchOperator::Lower, value.into_value())
+    }
+
+    pub fn le(&self, value: impl DomainValue<T>) -> SearchType {
+        self.compare(SearchOperator::LowerEqual, value.into_value())
+    }
+
+    pub fn is_in<V: DomainValue<T>>(&self, values: impl IntoIterator<Item = V>) -> SearchType {
+        make_tuple(&self.path, SearchOperator::In, make_values::<T, V>(values))
+    }
+
+    pub fn not_in<V: DomainValue<T>>(&self, values: impl IntoIterator<Item = V>) -> SearchType {
+        make_tuple(
+            &self.path,
+            SearchOperator::NotIn,
+            make_values::<T, V>(values),
+        )
+    }
+}
+
+impl DomainField<String> {
+    /// Case-sensitive search of given value inside this field
+    pub fn like(&self, value: impl Into<String>) -> SearchType {
+        self.compare(SearchOperator::Like, value.into())
+    }
+
+    /// Case-insensitive search of given value inside this field
+    pub fn ilike(&self, value: impl Into<String>) -> SearchType {
+        self.compare(SearchOperator::ILike, value.into())
+    }
+
+    /// Case-sensitive match of given pattern
+    pub fn eq_like(&self, value: impl Into<String>) -> SearchType {
+        self.compare(SearchOperator::EqualLike, value.into())
+    }
+}
+
+impl DomainField<Json> {
+    /// Value at given key of this JSON field, so that `settings().key("theme")` builds the path
+    /// "settings.theme"
+    pub fn key(&self, key: &str) -> DomainJson {
+        DomainJson {
+            path: join_path(&self.path, key),
+        }
+    }
+}
+
+/// Value inside a JSON field, used to build a domain
+///
+/// JSON values don't have any declared type, so they can be compared with any string, number or
+/// boolean. Values of another type never match
+pub struct DomainJson {
+    path: Vec<String>,
+}
+
+impl DomainJson {
+    /// Value at given key of this value. Keys of arrays are their indexes
+    pub fn key(&self, key: &str) -> DomainJson {
+        DomainJson {
+            path: join_path(&self.path, key),
+        }
+    }
+
+    /// Records where this value exists and is not null
+    pub fn is_set(&self) -> SearchType {
+        make_tuple(&self.path, SearchOperator::NotEqual, RightTuple::None)
+    }
+
+    /// Records where this value doesn't exist or is null
+    pub fn is_not_set(&self) -> SearchType {
+        make_tuple(&self.path, SearchOperator::Equal, RightTuple::None)
+    }
+
+    pub fn eq(&self, value: impl Into<RightTuple>) -> SearchType {
+        make_tuple(&self.path, SearchOperator::Equal, value.into())
+    }
+
+    pub fn ne(&self, value: impl Into<RightTuple>) -> SearchType {
+        make_tuple(&self.path, SearchOperator::NotEqual, value.into())
+    }
+
+    pub fn gt(&self, value: impl Into<RightTuple>) -> SearchType {
+        make_tuple(&self.path, SearchOperator::Greater, value.into())
+    }
+
+    pub fn ge(&self, value: impl Into<RightTuple>) -> SearchType {
+        make_tuple(&self.path, SearchOperator::GreaterEqual, value.into())
+    }
+
+    pub fn lt(&self, value: impl Into<RightTuple>) -> SearchType {
+        make_tuple(&self.path, SearchOperator::Lower, value.into())
+    }
+
+    pub fn le(&self, value: impl Into<RightTuple>) -> SearchType {
+        make_tuple(&self.path, SearchOperator::LowerEqual, value.into())
+    }
+
+    pub fn is_in<V: Into<RightTuple>>(&self, values: impl IntoIterator<Item = V>) -> SearchType {
+        let values = values.into_iter().map(Into::into).collect();
+        make_tuple(&self.path, SearchOperator::In, RightTuple::Array(values))
+    }
+
+    pub fn not_in<V: Into<RightTuple>>(&self, values: impl IntoIterator<Item = V>) -> SearchType {
+        let values = values.into_iter().map(Into::into).collect();
+        make_tuple(&self.path, SearchOperator::NotIn, RightTuple::Array(values))
+    }
+
+    /// Case-sensitive search of given value inside this string
+    pub fn like(&self, value: impl Into<String>) -> SearchType {
+        make_tuple(&self.path, SearchOperator::Like, value.into().into())
+    }
+
+    /// Case-insensitive search of given value inside this string
+    pub fn ilike(&self, value: impl Into<String>) -> SearchType {
+        make_tuple(&self.path, SearchOperator::ILike, value.into().into())
+    }
+
+    /// Case-sensitive match of given pattern
+    pub fn eq_like(&self, value: impl Into<String>) -> SearchType {
+        make_tuple(&self.path, SearchOperator::EqualLike, value.into().into())
+    }
+}
+
+/// M2O or O2M field targeting given BaseModel, used to build a domain
+///
+/// Fields of the targeted model are reachable through this reference, so that
+/// `lines().total_price()` builds the path "lines.total_price"
+pub struct DomainReference<BM: BaseModelFields> {
+    path: Vec<String>,
+    fields: BM::Fields,
+}
+
+impl<BM: BaseModelFields> DomainReference<BM> {
+    pub fn new(path: &[String], field_name: &str) -> Self {
+        let path = join_path(path, field_name);
+        DomainReference {
+            fields: BM::Fields::with_path(path.clone()),
+            path,
+        }
+    }
+
+    /// Records where this field targets at least one record
+    pub fn is_set(&self) -> SearchType {
+        make_tuple(&self.path, SearchOperator::NotEqual, RightTuple::None)
+    }
+
+    /// Records where this field doesn't target any record
+    pub fn is_not_set(&self) -> SearchType {
+        make_tuple(&self.path, SearchOperator::Equal, RightTuple::None)
+    }
+
+    pub fn eq(&self, id: u64) -> SearchType {
+        make_tuple(&self.path, SearchOperator::Equal, id.into())
+    }
+
+    pub fn ne(&self, id: u64) -> SearchType {
+        make_tuple(&self.path, SearchOperator::NotEqual, id.into())
+    }
+
+    pub fn is_in(&self, ids: impl IntoIterator<Item = u64>) -> SearchType {
+        make_tuple(&self.path, SearchOperator::In, make_values::<u64, u64>(ids))
+    }
+
+    pub fn not_in(&self, ids: impl IntoIterator<Item = u64>) -> SearchType {
+        make_tuple(
+            &self.path,
+            SearchOperator::NotIn,
+            make_values::<u64, u64>(ids),
+        )
+    }
+
+    /// Given records, and all records having them as ancestor through this field
+    pub fn child_of(&self, ids: impl IntoIterator<Item = u64>) -> SearchType {
+        make_tuple(
+            &self.path,
+            SearchOperator::ChildOf,
+            make_values::<u64, u64>(ids),
+        )
+    }
+
+    /// Given records, and all their ancestors through this field
+    pub fn parent_of(&self, ids: impl IntoIterator<Item = u64>) -> SearchType {
+        make_tuple(
+            &self.path,
+            SearchOperator::ParentOf,
+            make_values::<u64, u64>(ids),
+        )
+    }
+
+    /// At least one targeted record matches given domain
+    ///
+    /// Given domain should be built from the fields of the targeted model
+    pub fn any(&self, domain: SearchType) -> SearchType {
+        make_tuple(&self.path, SearchOperator::Any, domain.into())
+    }
+
+    /// No targeted record matches given domain
+    pub fn not_any(&self, domain: SearchType) -> SearchType {
+        make_tuple(&self.path, SearchOperator::NotAny, domain.into())
+    }
+}
+
+impl<BM: BaseModelFields> Deref for DomainReference<BM> {
+    type Target = BM::Fields;
+
+    fn deref(&self) -> &Self::Target {
+        &self.fields
+    }
+}
@@ -0,0 +1,33 @@
+/// Modification applied to a references field, without having to provide all targeted ids
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    /// Link given record, if not already linked
+    Add(u64),
+    /// Unlink given record. The record itself is not deleted
+    Remove(u64),
+    /// Replace linked records with given ones
+    Replace(Vec<u64>),
+    /// Unlink all records
+    Clear,
+}
+
+impl Command {
+    /// Apply this command on given linked ids
+    pub fn apply(&self, ids: &mut Vec<u64>) {
+        match self {
+            Command::Add(id) => {
+                if !ids.contains(id) {
+                    ids.push(*id);
+                }
+            }
+            Command::Remove(id) => ids.retain(|linked_id| linked_id != id),
+            Command::Replace(new_ids) => {
+                ids.clear();
+                for id in new_ids {
+                    Command::Add(*id).apply(ids);
+                }
+            }
+            Command::Clear => ids.clear(),
+        }
+    }
+}
@@ -4,7 +4,7 @@ use std::{error, fmt};
 pub struct RequiredFieldEmpty {
     pub model_name: String,
     pub field_name: String,
-    pub id: u32,
+    pub id: u64,
 }
 
 impl fmt::Display for RequiredFieldEmpty {
@@ -18,3 +18,37 @@ impl fmt::Display for RequiredFieldEmpty {
 }
 
 impl error::Error for RequiredFieldEmpty {}
+
+#[derive(Debug, Clone)]
+pub struct UnknownOnDeleteError {
+    pub ondelete: String,
+}
+
+impl fmt::Display for UnknownOnDeleteError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Unknown ondelete policy \"{}\". Valid policies are: cascade, set_null, restrict",
+            self.ondelete,
+        )
+    }
+}
+
+impl error::Error for UnknownOnDeleteError {}
+
+#[derive(Debug, Clone)]
+pub struct InvalidCurrencyFieldError {
+    pub currency_field: String,
+}
+
+impl fmt::Display for InvalidCurrencyFieldError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Invalid currency field \"{}\". It should be the M2O of the currency, like \"currency\", optionally followed by the field containing its decimal places, like \"currency.decimal_places\"",
+            self.currency_field,
+        )
+    }
+}
+
+impl error::Error for InvalidCurrencyFieldError {}
@@ -1,11 +1,68 @@
-use crate::field::{FieldCompute, FieldReference, FieldType};
+use crate::field::{FieldCompute, FieldReference, FieldType, InvalidCurrencyFieldError};
+
+/// How the default value of a field is given when a record is created without it
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum DefaultKind {
+    /// The default value of the field
+    #[default]
+    Value,
+    /// Current date, or current date and time. Only used by Date and DateTime fields
+    Now,
+    /// No value. Only used by optional Date and DateTime fields, as there is no meaningful
+    /// default date
+    Empty,
+}
 
 #[derive(Default)]
 pub struct FieldDescriptor {
     pub name: String,
     pub default_value: Option<FieldType>,
+    pub default_kind: DefaultKind,
     pub description: Option<String>,
     pub required: bool,
+    /// Number of decimal places of a Decimal field. Values are rounded to it when written
+    pub digits: Option<u32>,
+    /// Currency of a Monetary field. Values are rounded to the decimal places of this currency
+    /// when written, or to `digits` if the record doesn't have any currency
+    pub currency_field: Option<CurrencyField>,
     pub compute: Option<FieldCompute>,
     pub field_ref: Option<FieldReference>,
 }
+
+/// Field of a currency containing its number of decimal places, when `currency_field` doesn't name
+/// another one
+pub const DEFAULT_DECIMAL_PLACES_FIELD: &str = "decimal_places";
+
+/// Currency of a Monetary field, declared as the name of the M2O targeting the currency ("currency").
+///
+/// The precision is read from the `decimal_places` field of the currency, unless another field is
+/// given after a dot ("currency.digits")
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CurrencyField {
+    /// M2O targeting the currency
+    pub field_name: String,
+    /// Integer field of the currency containing its number of decimal places
+    pub decimal_places_field: String,
+}
+
+impl TryFrom<&str> for CurrencyField {
+    type Error = InvalidCurrencyFieldError;
+
+    fn try_from(str: &str) -> Result<Self, InvalidCurrencyFieldError> {
+        let (field_name, decimal_places_field) = str
+            .split_once('.')
+            .unwrap_or((str, DEFAULT_DECIMAL_PLACES_FIELD));
+        if field_name.is_empty()
+            || decimal_places_field.is_empty()
+            || decimal_places_field.contains('.')
+        {
+            return Err(InvalidCurrencyFieldError {
+                currency_field: str.to_string(),
+            });
+        }
+        Ok(CurrencyField {
+            field_name: field_name.to_string(),
+            decimal_places_field: decimal_places_field.to_string(),
+        })
+    }
+}
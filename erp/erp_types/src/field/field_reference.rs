@@ -1,3 +1,5 @@
+use crate::field::UnknownOnDeleteError;
+
 #[derive(Clone)]
 pub struct FieldReference {
     // TODO Use &'static instead of String
@@ -9,5 +11,49 @@ pub struct FieldReference {
 pub enum FieldReferenceType {
     O2M { inverse_field: String },
     // If it's a M2O, this list will only be empty if there is no fields in the target model that is the linked O2M of this field
-    M2O { inverse_fields: Vec<String> },
+    // The ondelete policy is None if it has not been declared
+    M2O { inverse_fields: Vec<String>, ondelete: Option<OnDelete> },
+    // If it's a M2M, links are saved in the relation table, and inverse fields are fields of the target model using the same relation table
+    M2M { relation: String, inverse_fields: Vec<String> },
+}
+
+impl FieldReference {
+    /// Columns of the relation table of a M2M declared on given model.
+    ///
+    /// The first column contains ids of given model, and the second one ids of the target model
+    pub fn get_relation_columns(&self, model_name: &str) -> (String, String) {
+        (
+            format!("{model_name}_id"),
+            format!("{}_id", self.target_model),
+        )
+    }
+}
+
+/// Policy applied to a M2O when the record it targets is deleted
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OnDelete {
+    /// Delete records targeting the deleted record
+    Cascade,
+    /// Empty the M2O of records targeting the deleted record
+    #[default]
+    SetNull,
+    /// Refuse to delete a record if it's still targeted
+    Restrict,
+}
+
+impl TryFrom<&str> for OnDelete {
+    type Error = UnknownOnDeleteError;
+
+    fn try_from(str: &str) -> Result<Self, UnknownOnDeleteError> {
+        Ok(match str {
+            "cascade" => OnDelete::Cascade,
+            "set_null" => OnDelete::SetNull,
+            "restrict" => OnDelete::Restrict,
+            _ => {
+                return Err(UnknownOnDeleteError {
+                    ondelete: str.to_string(),
+                });
+            }
+        })
+    }
 }
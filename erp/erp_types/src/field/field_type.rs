@@ -1,5 +1,37 @@
+use chrono::{DateTime, NaiveDate, SubsecRound, Utc};
+use erp_search::RightTuple;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use sha2::{Digest, Sha256};
 use std::fmt::{Debug, Display, Formatter};
 
+pub use rust_decimal::Decimal;
+
+/// Amount of money, stored as a Decimal.
+///
+/// Monetary fields need a `currency_field`, and are rounded to the decimal places of the currency
+/// of their record when they or their currency are written
+pub type Monetary = Decimal;
+
+/// Structured value, stored as JSON.
+///
+/// Values inside it can be searched with a path going through its keys ("settings.theme")
+pub use serde_json::Value as Json;
+
+/// Content of a file.
+///
+/// Only the hash of the content (see `content_hash`) is stored in the table of its model, the
+/// content itself is saved in the filestore
+pub type Binary = Vec<u8>;
+
+/// SHA-256 of given content, in hexadecimal. This is the key of the content in the filestore
+pub fn content_hash(content: &[u8]) -> String {
+    Sha256::digest(content)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
 #[macro_export]
 macro_rules! field_type_make_eq {
     ( $self:expr, $other:expr, $( $path:path ),* ) => {
@@ -19,10 +51,23 @@ macro_rules! field_type_make_eq {
 pub enum FieldType {
     String(String),
     Integer(i32),
+    /// 64-bit integer, for values that overflow an Integer
+    BigInteger(i64),
     Float(f32),
+    /// Double-precision float
+    Double(f64),
     Bool(bool),
-    Ref(u32),
-    Refs(Vec<u32>),
+    /// Fixed-point number, used for prices and amounts of money
+    Decimal(Decimal),
+    Date(NaiveDate),
+    /// Date and time, in UTC
+    DateTime(DateTime<Utc>),
+    /// Structured data that doesn't deserve its own model
+    Json(Json),
+    /// Content of a file, saved in the filestore
+    Binary(Binary),
+    Ref(u64),
+    Refs(Vec<u64>),
 }
 
 impl Display for FieldType {
@@ -30,14 +75,35 @@ impl Display for FieldType {
         match self {
             FieldType::String(s) => write!(f, "{s}"),
             FieldType::Integer(i) => write!(f, "{i}"),
+            FieldType::BigInteger(i) => write!(f, "{i}"),
             FieldType::Float(fl) => write!(f, "{fl}"),
+            FieldType::Double(d) => write!(f, "{d}"),
             FieldType::Bool(b) => write!(f, "{b}"),
+            FieldType::Decimal(d) => write!(f, "{d}"),
+            FieldType::Date(d) => write!(f, "{d}"),
+            FieldType::DateTime(d) => write!(f, "{d}"),
+            FieldType::Json(j) => write!(f, "{j}"),
+            FieldType::Binary(b) => write!(f, "<{} bytes>", b.len()),
             FieldType::Ref(id) => write!(f, "{id}"),
             FieldType::Refs(ids) => write!(f, "{ids:?}"),
         }
     }
 }
 
+impl FieldType {
+    /// Current date, or current date and time, with the same type as this value.
+    ///
+    /// Date and time are truncated to the microsecond, as it's the precision stored in the
+    /// database. Return None if this value is not a Date nor a DateTime
+    pub fn now(&self) -> Option<FieldType> {
+        match self {
+            FieldType::Date(_) => Some(FieldType::Date(Utc::now().date_naive())),
+            FieldType::DateTime(_) => Some(FieldType::DateTime(Utc::now().trunc_subsecs(6))),
+            _ => None,
+        }
+    }
+}
+
 impl PartialEq for FieldType {
     fn eq(&self, other: &Self) -> bool {
         field_type_make_eq!(
@@ -45,8 +111,15 @@ impl PartialEq for FieldType {
             other,
             FieldType::String,
             FieldType::Integer,
+            FieldType::BigInteger,
             FieldType::Float,
+            FieldType::Double,
             FieldType::Bool,
+            FieldType::Decimal,
+            FieldType::Date,
+            FieldType::DateTime,
+            FieldType::Json,
+            FieldType::Binary,
             FieldType::Ref,
             FieldType::Refs
         )
@@ -103,6 +176,28 @@ impl From<&i32> for FieldType {
     }
 }
 
+// i64
+impl<'a> From<&'a FieldType> for Option<&'a i64> {
+    fn from(t: &'a FieldType) -> Self {
+        match t {
+            FieldType::BigInteger(i) => Some(i),
+            _ => None,
+        }
+    }
+}
+
+impl From<i64> for FieldType {
+    fn from(t: i64) -> Self {
+        FieldType::BigInteger(t)
+    }
+}
+
+impl From<&i64> for FieldType {
+    fn from(t: &i64) -> Self {
+        FieldType::BigInteger(*t)
+    }
+}
+
 // f32
 impl<'a> From<&'a FieldType> for Option<&'a f32> {
     fn from(t: &'a FieldType) -> Self {
@@ -125,6 +220,28 @@ impl From<&f32> for FieldType {
     }
 }
 
+// f64
+impl<'a> From<&'a FieldType> for Option<&'a f64> {
+    fn from(t: &'a FieldType) -> Self {
+        match t {
+            FieldType::Double(d) => Some(d),
+            _ => None,
+        }
+    }
+}
+
+impl From<f64> for FieldType {
+    fn from(t: f64) -> Self {
+        FieldType::Double(t)
+    }
+}
+
+impl From<&f64> for FieldType {
+    fn from(t: &f64) -> Self {
+        FieldType::Double(*t)
+    }
+}
+
 // bool
 impl<'a> From<&'a FieldType> for Option<&'a bool> {
     fn from(t: &'a FieldType) -> Self {
@@ -147,6 +264,126 @@ impl From<&bool> for FieldType {
     }
 }
 
+// Decimal
+impl<'a> From<&'a FieldType> for Option<&'a Decimal> {
+    fn from(t: &'a FieldType) -> Self {
+        match t {
+            FieldType::Decimal(d) => Some(d),
+            _ => None,
+        }
+    }
+}
+
+impl From<Decimal> for FieldType {
+    fn from(t: Decimal) -> Self {
+        FieldType::Decimal(t)
+    }
+}
+
+impl From<&Decimal> for FieldType {
+    fn from(t: &Decimal) -> Self {
+        FieldType::Decimal(*t)
+    }
+}
+
+// Date
+impl<'a> From<&'a FieldType> for Option<&'a NaiveDate> {
+    fn from(t: &'a FieldType) -> Self {
+        match t {
+            FieldType::Date(d) => Some(d),
+            _ => None,
+        }
+    }
+}
+
+impl From<NaiveDate> for FieldType {
+    fn from(t: NaiveDate) -> Self {
+        FieldType::Date(t)
+    }
+}
+
+impl From<&NaiveDate> for FieldType {
+    fn from(t: &NaiveDate) -> Self {
+        FieldType::Date(*t)
+    }
+}
+
+// DateTime
+impl<'a> From<&'a FieldType> for Option<&'a DateTime<Utc>> {
+    fn from(t: &'a FieldType) -> Self {
+        match t {
+            FieldType::DateTime(d) => Some(d),
+            _ => None,
+        }
+    }
+}
+
+impl From<DateTime<Utc>> for FieldType {
+    fn from(t: DateTime<Utc>) -> Self {
+        FieldType::DateTime(t)
+    }
+}
+
+impl From<&DateTime<Utc>> for FieldType {
+    fn from(t: &DateTime<Utc>) -> Self {
+        FieldType::DateTime(*t)
+    }
+}
+
+// Json
+impl<'a> From<&'a FieldType> for Option<&'a Json> {
+    fn from(t: &'a FieldType) -> Self {
+        match t {
+            FieldType::Json(j) => Some(j),
+            _ => None,
+        }
+    }
+}
+
+impl From<Json> for FieldType {
+    fn from(t: Json) -> Self {
+        FieldType::Json(t)
+    }
+}
+
+impl From<&Json> for FieldType {
+    fn from(t: &Json) -> Self {
+        FieldType::Json(t.clone())
+    }
+}
+
+/// Convert given value into a JSON value, to store it in a JSON field
+pub fn to_json<T: Serialize>(value: &T) -> Result<Json, serde_json::Error> {
+    serde_json::to_value(value)
+}
+
+/// Convert the JSON value of a JSON field back into given type
+pub fn from_json<T: DeserializeOwned>(value: &Json) -> Result<T, serde_json::Error> {
+    T::deserialize(value)
+}
+
+// Binary
+impl<'a> From<&'a FieldType> for Option<&'a Binary> {
+    fn from(t: &'a FieldType) -> Self {
+        match t {
+            FieldType::Binary(b) => Some(b),
+            _ => None,
+        }
+    }
+}
+
+impl From<Binary> for FieldType {
+    fn from(t: Binary) -> Self {
+        FieldType::Binary(t)
+    }
+}
+
+impl From<&Binary> for FieldType {
+    fn from(t: &Binary) -> Self {
+        FieldType::Binary(t.clone())
+    }
+}
+
 // Enums
 pub trait EnumType: Debug + PartialEq + Eq + Copy + Clone {}
 
@@ -174,7 +411,7 @@ where
 }
 
 // Ref
-impl<'a> From<&'a FieldType> for Option<&'a u32> {
+impl<'a> From<&'a FieldType> for Option<&'a u64> {
     fn from(t: &'a FieldType) -> Self {
         match t {
             FieldType::Ref(r) => Some(r),
@@ -183,20 +420,20 @@ impl<'a> From<&'a FieldType> for Option<&'a u32> {
     }
 }
 
-impl From<u32> for FieldType {
-    fn from(t: u32) -> Self {
+impl From<u64> for FieldType {
+    fn from(t: u64) -> Self {
         FieldType::Ref(t)
     }
 }
 
-impl From<&u32> for FieldType {
-    fn from(t: &u32) -> Self {
+impl From<&u64> for FieldType {
+    fn from(t: &u64) -> Self {
         FieldType::Ref(*t)
     }
 }
 
 // Refs
-impl<'a> From<&'a FieldType> for Option<&'a Vec<u32>> {
+impl<'a> From<&'a FieldType> for Option<&'a Vec<u64>> {
     fn from(t: &'a FieldType) -> Self {
         match t {
             FieldType::Refs(vec) => Some(vec),
@@ -205,14 +442,35 @@ impl<'a> From<&'a FieldType> for Option<&'a Vec<u32>> {
     }
 }
 
-impl From<Vec<u32>> for FieldType {
-    fn from(t: Vec<u32>) -> Self {
+impl From<Vec<u64>> for FieldType {
+    fn from(t: Vec<u64>) -> Self {
         FieldType::Refs(t)
     }
 }
 
-impl From<&Vec<u32>> for FieldType {
-    fn from(t: &Vec<u32>) -> Self {
+impl From<&Vec<u64>> for FieldType {
+    fn from(t: &Vec<u64>) -> Self {
         FieldType::Refs(t.clone())
     }
 }
+
+// Search
+impl From<FieldType> for RightTuple {
+    fn from(t: FieldType) -> Self {
+        match t {
+            FieldType::String(s) => RightTuple::String(s),
+            FieldType::Integer(i) => RightTuple::Integer(i),
+            FieldType::BigInteger(i) => RightTuple::BigInteger(i),
+            FieldType::Float(f) => RightTuple::Float(f),
+            FieldType::Double(d) => RightTuple::Double(d),
+            FieldType::Bool(b) => RightTuple::Boolean(b),
+            FieldType::Decimal(d) => RightTuple::Decimal(d),
+            FieldType::Date(d) => RightTuple::Date(d),
+            FieldType::DateTime(d) => RightTuple::DateTime(d),
+            FieldType::Json(j) => RightTuple::String(j.to_string()),
+            FieldType::Binary(b) => RightTuple::String(content_hash(&b)),
+            FieldType::Ref(r) => RightTuple::UInteger(r),
+            FieldType::Refs(refs) => refs.into(),
+        }
+    }
+}
@@ -7,23 +7,23 @@ use std::vec::IntoIter;
 
 #[derive(Default, Debug, Clone, Hash, Eq)]
 pub struct SingleId {
-    id: u32,
-    ids: Vec<u32>,
+    id: u64,
+    ids: Vec<u64>,
 }
 
 impl SingleId {
-    pub fn get_id(&self) -> u32 {
+    pub fn get_id(&self) -> u64 {
         self.id
     }
 
-    pub fn get_id_ref(&self) -> &u32 {
+    pub fn get_id_ref(&self) -> &u64 {
         &self.id
     }
 }
 
 #[derive(Default, Debug, Clone)]
 pub struct MultipleIds {
-    pub ids: Vec<u32>,
+    pub ids: Vec<u64>,
 }
 
 pub mod sealed {
@@ -32,16 +32,16 @@ pub mod sealed {
 
 // TODO Should we transform this into an enum, as we only have 2 structs ?
 pub trait IdMode:
-    Sealed + Clone + Into<MultipleIds> + Into<RightTuple> + IntoIterator<Item = SingleId> + AsRef<[u32]>
+    Sealed + Clone + Into<MultipleIds> + Into<RightTuple> + IntoIterator<Item = SingleId> + AsRef<[u64]>
 {
     /// Returns a vector containing ids saved in this reference
-    fn get_ids_ref(&self) -> &Vec<u32>;
+    fn get_ids_ref(&self) -> &Vec<u64>;
     /// Return the id at given pos.
     ///
-    /// If pos is < 0 or >= len(ids), return u32::MAX
-    fn get_id_at(&self, pos: usize) -> &u32;
+    /// If pos is < 0 or >= len(ids), return u64::MAX
+    fn get_id_at(&self, pos: usize) -> &u64;
     /// Check if given id is in the list
-    fn contains(&self, id: &u32) -> bool;
+    fn contains(&self, id: &u64) -> bool;
     /// Remove duplicated ids
     fn remove_dup(&mut self);
     /// Check if ids are empty
@@ -49,16 +49,16 @@ pub trait IdMode:
 }
 
 impl IdMode for SingleId {
-    fn get_ids_ref(&self) -> &Vec<u32> {
+    fn get_ids_ref(&self) -> &Vec<u64> {
         &self.ids
     }
-    fn get_id_at(&self, pos: usize) -> &u32 {
+    fn get_id_at(&self, pos: usize) -> &u64 {
         if pos != 0 {
-            return &u32::MAX;
+            return &u64::MAX;
         }
         &self.id
     }
-    fn contains(&self, id: &u32) -> bool {
+    fn contains(&self, id: &u64) -> bool {
         &self.id == id
     }
     fn remove_dup(&mut self) {
@@ -69,8 +69,8 @@ impl IdMode for SingleId {
     }
 }
 
-impl AsRef<[u32]> for SingleId {
-    fn as_ref(&self) -> &[u32] {
+impl AsRef<[u64]> for SingleId {
+    fn as_ref(&self) -> &[u64] {
         &self.ids
     }
 }
@@ -78,16 +78,16 @@ impl AsRef<[u32]> for SingleId {
 impl Sealed for SingleId {}
 
 impl IdMode for MultipleIds {
-    fn get_ids_ref(&self) -> &Vec<u32> {
+    fn get_ids_ref(&self) -> &Vec<u64> {
         &self.ids
     }
-    fn get_id_at(&self, pos: usize) -> &u32 {
+    fn get_id_at(&self, pos: usize) -> &u64 {
         if pos >= self.ids.len() {
-            return &u32::MAX;
+            return &u64::MAX;
         }
         &self.ids[pos]
     }
-    fn contains(&self, id: &u32) -> bool {
+    fn contains(&self, id: &u64) -> bool {
         self.ids.contains(id)
     }
     fn remove_dup(&mut self) {
@@ -99,8 +99,8 @@ impl IdMode for MultipleIds {
     }
 }
 
-impl AsRef<[u32]> for MultipleIds {
-    fn as_ref(&self) -> &[u32] {
+impl AsRef<[u64]> for MultipleIds {
+    fn as_ref(&self) -> &[u64] {
         &self.ids
     }
 }
@@ -108,14 +108,14 @@ impl AsRef<[u32]> for MultipleIds {
 impl Sealed for MultipleIds {}
 
 // From
-impl From<u32> for SingleId {
-    fn from(id: u32) -> Self {
+impl From<u64> for SingleId {
+    fn from(id: u64) -> Self {
         SingleId { id, ids: vec![id] }
     }
 }
 
-impl From<&u32> for SingleId {
-    fn from(id: &u32) -> Self {
+impl From<&u64> for SingleId {
+    fn from(id: &u64) -> Self {
         SingleId {
             id: *id,
             ids: vec![*id],
@@ -135,32 +135,32 @@ impl From<&SingleId> for RightTuple {
     }
 }
 
-impl From<u32> for MultipleIds {
-    fn from(id: u32) -> Self {
+impl From<u64> for MultipleIds {
+    fn from(id: u64) -> Self {
         MultipleIds { ids: vec![id] }
     }
 }
 
-impl From<&u32> for MultipleIds {
-    fn from(id: &u32) -> Self {
+impl From<&u64> for MultipleIds {
+    fn from(id: &u64) -> Self {
         MultipleIds { ids: vec![*id] }
     }
 }
 
-impl From<Vec<u32>> for MultipleIds {
-    fn from(ids: Vec<u32>) -> Self {
+impl From<Vec<u64>> for MultipleIds {
+    fn from(ids: Vec<u64>) -> Self {
         MultipleIds { ids }
     }
 }
 
-impl From<&Vec<u32>> for MultipleIds {
-    fn from(ids: &Vec<u32>) -> Self {
+impl From<&Vec<u64>> for MultipleIds {
+    fn from(ids: &Vec<u64>) -> Self {
         MultipleIds { ids: ids.clone() }
     }
 }
 
-impl From<Vec<&u32>> for MultipleIds {
-    fn from(ids: Vec<&u32>) -> Self {
+impl From<Vec<&u64>> for MultipleIds {
+    fn from(ids: Vec<&u64>) -> Self {
         MultipleIds {
             ids: ids.into_iter().copied().collect(),
         }
@@ -170,7 +170,7 @@ impl From<Vec<&u32>> for MultipleIds {
 // TODO Find a way to make it work
 // impl<E> From<E> for MultipleIds
 // where
-//     E: Into<Vec<u32>>,
+//     E: Into<Vec<u64>>,
 // {
 //     fn from(value: E) -> Self {
 //         todo!()
@@ -239,7 +239,7 @@ impl From<MultipleIds> for RightTuple {
 
 impl From<&MultipleIds> for RightTuple {
     fn from(id: &MultipleIds) -> Self {
-        id.ids.iter().copied().collect::<Vec<u32>>().into()
+        id.ids.to_vec().into()
     }
 }
 
@@ -267,7 +267,7 @@ impl IntoIterator for MultipleIds {
 }
 
 pub struct MultipleIdsIntoIterator {
-    ids: IntoIter<u32>,
+    ids: IntoIter<u64>,
 }
 
 impl Iterator for MultipleIdsIntoIterator {
@@ -301,7 +301,7 @@ impl<'a> IntoIterator for &'a MultipleIds {
 }
 
 pub struct IdsRefIntoIterator<'a> {
-    ids: Iter<'a, u32>,
+    ids: Iter<'a, u64>,
 }
 
 impl<'a> Iterator for IdsRefIntoIterator<'a> {
@@ -327,14 +327,14 @@ where
 }
 
 // Eq
-impl PartialEq<u32> for SingleId {
-    fn eq(&self, other: &u32) -> bool {
+impl PartialEq<u64> for SingleId {
+    fn eq(&self, other: &u64) -> bool {
         self.id == *other
     }
 }
 
-impl PartialEq<Vec<u32>> for SingleId {
-    fn eq(&self, other: &Vec<u32>) -> bool {
+impl PartialEq<Vec<u64>> for SingleId {
+    fn eq(&self, other: &Vec<u64>) -> bool {
         other.len() == 1 && self.id == other[0]
     }
 }
@@ -350,14 +350,14 @@ impl<Mode: IdMode> PartialEq<Mode> for SingleId {
     }
 }
 
-impl PartialEq<Vec<u32>> for MultipleIds {
-    fn eq(&self, other: &Vec<u32>) -> bool {
+impl PartialEq<Vec<u64>> for MultipleIds {
+    fn eq(&self, other: &Vec<u64>) -> bool {
         &self.ids == other
     }
 }
 
-impl PartialEq<u32> for MultipleIds {
-    fn eq(&self, other: &u32) -> bool {
+impl PartialEq<u64> for MultipleIds {
+    fn eq(&self, other: &u64) -> bool {
         self.ids.len() == 1 && self.ids[0] == *other
     }
 }
@@ -1,3 +1,4 @@
+mod command;
 mod error;
 mod field_compute;
 mod field_depends;
@@ -7,6 +8,7 @@ mod field_type;
 mod id;
 mod reference;
 
+pub use command::*;
 pub use error::*;
 pub use field_compute::*;
 pub use field_depends::*;
@@ -34,7 +34,7 @@ impl<BM: BaseModel, Mode: IdMode> Reference<BM, Mode> {
     }
 
     /// Check if the given id is contained in the current reference
-    pub fn contains(&self, id: &u32) -> bool {
+    pub fn contains(&self, id: &u64) -> bool {
         self.id_mode.contains(id)
     }
 
@@ -155,7 +155,7 @@ impl<E: BaseModel> IntoIterator for Reference<E, MultipleIds> {
 }
 
 pub struct ReferenceIntoIterator<E: BaseModel> {
-    ids: IntoIter<u32>,
+    ids: IntoIter<u64>,
     _phantom_data: PhantomData<E>,
 }
 
@@ -180,7 +180,7 @@ impl<'a, E: BaseModel> IntoIterator for &'a Reference<E, MultipleIds> {
 }
 
 pub struct ReferenceIterator<'a, E: BaseModel> {
-    ids: Iter<'a, u32>,
+    ids: Iter<'a, u64>,
     _phantom_data: PhantomData<E>,
 }
 
@@ -1,4 +1,5 @@
 pub mod cache;
+pub mod domain;
 pub mod environment;
 pub mod field;
 pub mod model;
@@ -1,8 +1,11 @@
 use crate::field::FieldDescriptor;
+use erp_search::OrderBy;
 
 pub struct ModelDescriptor {
     pub name: String,
     pub description: Option<String>,
+    /// Default order of records
+    pub order: Vec<OrderBy>,
     pub fields: Vec<FieldDescriptor>,
 }
 
@@ -12,6 +15,7 @@ impl ModelDescriptor {
         ModelDescriptor {
             name,
             description,
+            order: Vec::new(),
             fields: Vec::new(),
         }
     }
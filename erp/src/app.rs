@@ -1,13 +1,15 @@
 use crate::config::Config;
 use crate::database::cache::CacheDatabase;
-use crate::database::postgres::PostgresDatabase;
 use crate::database::{Database, DatabaseType};
 use crate::environment::Environment;
+use crate::filestore::{FileStoreType, LocalFileStore, MemoryFileStore};
 use crate::model::ModelManager;
 use crate::plugin::InternalPluginState::Installed;
-use crate::plugin::Plugin;
+use crate::plugin::MigrationStage::{PostSchema, PreSchema};
 use crate::plugin::PluginManager;
+use crate::plugin::{Migrations, Plugin};
 use std::error::Error;
+use std::time::Duration;
 
 type Result<T> = std::result::Result<T, Box<dyn Error>>;
 
@@ -17,6 +19,7 @@ pub struct Application {
     pub plugin_manager: PluginManager,
     pub is_test: bool,
     pub cache_db: CacheDatabase,
+    pub memory_filestore: MemoryFileStore,
 }
 
 impl Application {
@@ -28,20 +31,27 @@ impl Application {
             plugin_manager: PluginManager::default(),
             is_test: false,
             cache_db: CacheDatabase::connect(),
+            memory_filestore: MemoryFileStore::default(),
         }
     }
 
     /// Create a new test instance of this application.
-    /// Database used is a cache database, so saved in memory.
+    /// Database used is a cache database, and the filestore is also saved in memory.
     /// Creating new environment instances of this Application will create separated memory database, so
     ///  it's safe to perform parallel operations on multiples test applications
     pub fn new_test() -> Application {
         Application {
-            config: Config::default(),
+            // Environments of a test application can't run at the same time, so contents of the
+            //  filestore don't need to be kept for other transactions
+            config: Config {
+                filestore_grace_period: 0,
+                ..Default::default()
+            },
             model_manager: ModelManager::default(),
             plugin_manager: PluginManager::default(),
             is_test: true,
             cache_db: CacheDatabase::connect(),
+            memory_filestore: MemoryFileStore::default(),
         }
     }
 
@@ -50,7 +60,7 @@ impl Application {
         Ok(if self.is_test {
             DatabaseType::Cache(&mut self.cache_db)
         } else {
-            DatabaseType::Postgres(PostgresDatabase::connect(&self.config.database)?)
+            DatabaseType::connect(&self.config.database)?
         })
     }
 
@@ -140,16 +150,53 @@ impl Application {
         self.model_manager.post_register();
         self.model_manager.current_plugin_loading = None;
 
-        // TODO Get all registered models, to update the database
-        let _registered_models = self.model_manager.get_all_models_for_plugin(plugin_name);
+        let mut migrations = Migrations::default();
+        plugin.init_migrations(&mut migrations);
+        let version = plugin.version();
+
+        // Models registered (or extended) by this plugin need to be synced with the database
+        let mut model_names: Vec<&str> = self
+            .model_manager
+            .get_all_models_for_plugin(plugin_name)
+            .iter()
+            .map(|model| model.name.as_str())
+            .collect();
+        model_names.sort_unstable();
+        model_names.dedup();
 
         // Well, it looks like this works, but not the call to new_env ...
         let database = if self.is_test {
             DatabaseType::Cache(&mut self.cache_db)
         } else {
-            DatabaseType::Postgres(PostgresDatabase::connect(&self.config.database)?)
+            DatabaseType::connect(&self.config.database)?
+        };
+        let filestore = if self.is_test {
+            FileStoreType::Memory(&mut self.memory_filestore)
+        } else {
+            FileStoreType::Local(LocalFileStore::new(&self.config.data_dir))
         };
-        let mut env = Environment::new(&self.model_manager, database)?;
+        let grace_period = Duration::from_secs(self.config.filestore_grace_period);
+        let mut env = Environment::new(&self.model_manager, database, filestore, grace_period)?;
+        env.savepoint(|env| {
+            // Databases installed by an older version don't save the version of plugins yet
+            env.database.upgrade()?;
+            // A new installation doesn't need any migration
+            let installed_version = env.database.get_installed_plugin_version(plugin_name)?;
+            if let Some(installed_version) = &installed_version {
+                for step in migrations.get_pending_steps(PreSchema, installed_version, &version) {
+                    step.call(env)?;
+                }
+            }
+            let model_manager = env.model_manager;
+            env.database.sync_schema(&model_names, model_manager)?;
+            if let Some(installed_version) = &installed_version {
+                for step in migrations.get_pending_steps(PostSchema, installed_version, &version) {
+                    step.call(env)?;
+                }
+            }
+            env.database
+                .set_installed_plugin_version(plugin_name, &version)
+        })?;
         env.savepoint(|env| plugin.post_init(env))?;
         env.close()?;
 
@@ -168,8 +215,29 @@ impl Application {
         let db = if self.is_test {
             DatabaseType::Cache(&mut self.cache_db)
         } else {
-            DatabaseType::Postgres(PostgresDatabase::connect(&self.config.database)?)
+            DatabaseType::connect(&self.config.database)?
+        };
+        let filestore = if self.is_test {
+            FileStoreType::Memory(&mut self.memory_filestore)
+        } else {
+            FileStoreType::Local(LocalFileStore::new(&self.config.data_dir))
         };
-        Environment::new(&self.model_manager, db)
+        let grace_period = Duration::from_secs(self.config.filestore_grace_period);
+        Environment::new(&self.model_manager, db, filestore, grace_period)
+    }
+
+    /// Delete contents of the filestore that are not referenced by any record, and return the
+    /// number of deleted contents.
+    ///
+    /// Contents written less than given grace period ago are kept, as they could be referenced by
+    /// a transaction that is not committed yet. The grace period should be longer than the longest
+    /// transaction. Closing an environment that deleted records holding Binary values already does
+    /// this with the grace period of the config, but this can be called periodically so that
+    /// contents of replaced values and of rolled back transactions are also deleted
+    pub fn collect_filestore_garbage(&mut self, grace_period: Duration) -> Result<usize> {
+        let mut env = self.new_env()?;
+        let count = env.collect_filestore_garbage(grace_period)?;
+        env.close()?;
+        Ok(count)
     }
 }
@@ -4,11 +4,38 @@ use directories::ProjectDirs;
 use serde_derive::Deserialize;
 use std::path::Path;
 
-#[derive(Debug, Deserialize, Default)]
+#[derive(Debug, Deserialize)]
 #[allow(dead_code)]
 pub struct Config {
     pub database: DatabaseConfig,
     pub plugin_path: String,
+    /// Directory where files of the application are saved, as the content of Binary fields
+    #[serde(default)]
+    pub data_dir: String,
+    /// Number of seconds during which contents of the filestore are kept after being written,
+    /// even if no record references them.
+    ///
+    /// Unreferenced contents are deleted when an environment that deleted records holding Binary
+    /// values is closed. Contents of replaced values or of rolled back transactions are only
+    /// deleted then, or by calling `Application::collect_filestore_garbage`. This should be longer
+    /// than the longest transaction, as contents are written before their transaction is committed
+    #[serde(default = "default_filestore_grace_period")]
+    pub filestore_grace_period: u64,
+}
+
+fn default_filestore_grace_period() -> u64 {
+    3600
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            database: DatabaseConfig::default(),
+            plugin_path: String::default(),
+            data_dir: String::default(),
+            filestore_grace_period: default_filestore_grace_period(),
+        }
+    }
 }
 
 impl Config {
@@ -17,12 +44,15 @@ impl Config {
             panic!("Impossible to have a config");
         };
         let config_file = Path::join(config_dir.config_dir(), "config.toml");
+        let data_dir = config_dir.data_dir().to_string_lossy().to_string();
 
         println!("Loading config from {config_file:?}");
         let config = config::Config::builder()
+            .set_default("database.engine", "postgres")?
             .set_default("database.url", "localhost")?
             .set_default("database.name", "erp")?
             .set_default("database.schema", "public")?
+            .set_default("data_dir", data_dir)?
             .add_source(config::File::from(config_file).required(true))
             .add_source(
                 config::Environment::with_prefix("ERP")
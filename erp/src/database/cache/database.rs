@@ -1,9 +1,21 @@
-use crate::database::cache::{Row, Table};
-use crate::database::{Database, FieldType};
-use crate::model::ModelManager;
-use erp_search::{LeftTuple, RightTuple, SearchOperator, SearchTuple, SearchType};
-use erp_types::field::{FieldReference, FieldReferenceType};
+use crate::database::cache::{Column, Row, Table};
+use crate::database::sql::{
+    Many2manyRelation, foreign_key_target, get_many2many_relation, is_not_null, many2many_relation,
+};
+use crate::database::{
+    Aggregate, AggregateFunction, Database, FieldType, ForeignKeyViolationError, Group,
+    InvalidOrderFieldError, InvalidPathError, InvalidValueError, NotHierarchicalFieldError,
+    NotNullViolationError, RestrictViolationError, UnknownFieldError, get_read_group_types,
+};
+use crate::model::{ModelManager, ModelNotFound};
+use erp_search::{
+    LeftTuple, OrderBy, OrderDirection, RightTuple, SearchOperator, SearchOptions, SearchTuple,
+    SearchType,
+};
+use erp_types::field::{FieldReference, FieldReferenceType, OnDelete};
 use erp_types::model::MapOfFields;
+use rust_decimal::Decimal;
+use std::cmp::Ordering;
 use std::collections::{HashMap, HashSet};
 use std::error::Error;
 
@@ -18,6 +30,23 @@ pub struct CacheDatabase {
     savepoints: Vec<(Option<String>, HashMap<String, Table>)>,
 }
 
+/// Change made while deleting rows, kept to undo the deletion if it fails
+enum DeleteChange {
+    /// Row removed from given table
+    Removed {
+        table_name: String,
+        id: u64,
+        row: Row,
+    },
+    /// Cell emptied by a "set null" ondelete policy, with its previous value
+    Emptied {
+        table_name: String,
+        id: u64,
+        field_name: String,
+        value: Option<FieldType>,
+    },
+}
+
 impl CacheDatabase {
     /// Make a connection to this database
     pub fn connect() -> Self
@@ -33,24 +62,38 @@ impl CacheDatabase {
 
     /// Poorly optimized search into the cache
     ///
-    /// I know this method is not optimized, and I don't care as it's only used in tests
+    /// I know this method is not optimized, and I don't care as it's only used in tests. Each
+    /// tuple is still only evaluated once per search, and not once per row
     fn get_rows(
         &self,
         model_name: &str,
         domain: &SearchType,
         model_manager: &ModelManager,
-    ) -> Result<Vec<u32>> {
+    ) -> Result<HashSet<u64>> {
         Ok(match domain {
             SearchType::And(left, right) => {
                 let left = self.get_rows(model_name, left, model_manager)?;
                 let right = self.get_rows(model_name, right, model_manager)?;
-                left.into_iter().filter(|id| right.contains(id)).collect()
+                left.intersection(&right).copied().collect()
             }
             SearchType::Or(left, right) => {
                 let mut left = self.get_rows(model_name, left, model_manager)?;
-                let mut right = self.get_rows(model_name, right, model_manager)?;
-                left.append(&mut right);
-                HashSet::<_>::from_iter(left).into_iter().collect()
+                left.extend(self.get_rows(model_name, right, model_manager)?);
+                left
+            }
+            SearchType::Not(search_type) => {
+                let excluded = self.get_rows(model_name, search_type, model_manager)?;
+                self.tables
+                    .get(model_name)
+                    .map(|table| {
+                        table
+                            .rows
+                            .keys()
+                            .filter(|id| !excluded.contains(id))
+                            .copied()
+                            .collect()
+                    })
+                    .unwrap_or_default()
             }
             SearchType::Tuple(SearchTuple {
                 left: LeftTuple { path },
@@ -60,13 +103,212 @@ impl CacheDatabase {
                 let mut path = path.clone();
                 path.reverse();
                 let result =
-                    self._search_path(model_name, &mut path, operator, right, model_manager);
-                HashSet::<_>::from_iter(result).into_iter().collect()
+                    self._search_path(model_name, &mut path, operator, right, model_manager)?;
+                HashSet::from_iter(result)
+            }
+            SearchType::Nothing => HashSet::new(),
+        })
+    }
+
+    /// Sort given ids following given order.
+    ///
+    /// Empty values are sorted last in ascending order, and first in descending order
+    fn sort_rows(
+        &self,
+        model_name: &str,
+        ids: Vec<u64>,
+        order: &[OrderBy],
+        model_manager: &ModelManager,
+    ) -> Result<Vec<u64>> {
+        let mut rows = ids
+            .into_iter()
+            .map(|id| {
+                let values = order
+                    .iter()
+                    .map(|order| self.get_order_value(model_name, id, &order.path, model_manager))
+                    .collect::<Result<Vec<_>>>()?;
+                Ok((id, values))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        rows.sort_by(|(_, left), (_, right)| {
+            order
+                .iter()
+                .zip(left.iter().zip(right))
+                .map(|(order, (left, right))| {
+                    let ordering = compare_values(left, right);
+                    match order.direction {
+                        OrderDirection::Asc => ordering,
+                        OrderDirection::Desc => ordering.reverse(),
+                    }
+                })
+                .find(|ordering| ordering.is_ne())
+                .unwrap_or(Ordering::Equal)
+        });
+        Ok(rows.into_iter().map(|(id, _)| id).collect())
+    }
+
+    /// Compute given aggregate on given records
+    fn compute_aggregate(
+        &self,
+        model_name: &str,
+        ids: &[u64],
+        aggregate: &Aggregate,
+        model_manager: &ModelManager,
+    ) -> Result<Option<FieldType>> {
+        // Like SQL, empty values are ignored
+        let mut values = Vec::with_capacity(ids.len());
+        for id in ids {
+            if let Some(value) =
+                self.get_order_value(model_name, *id, &aggregate.path, model_manager)?
+            {
+                values.push(value);
+            }
+        }
+        if aggregate.function == AggregateFunction::Count {
+            return Ok(Some(FieldType::Integer(values.len() as i32)));
+        }
+        if values.is_empty() {
+            return Ok(None);
+        }
+        Ok(match aggregate.function {
+            AggregateFunction::Count => unreachable!(),
+            AggregateFunction::Sum => {
+                let mut integer_sum: i64 = 0;
+                let mut float_sum: f32 = 0.0;
+                let mut double_sum: f64 = 0.0;
+                let mut decimal_sum = Decimal::ZERO;
+                let mut is_float = false;
+                let mut is_double = false;
+                let mut is_decimal = false;
+                for value in &values {
+                    match value {
+                        FieldType::Integer(value) => integer_sum += *value as i64,
+                        FieldType::BigInteger(value) => {
+                            integer_sum = integer_sum.checked_add(*value).ok_or_else(|| {
+                                InvalidValueError {
+                                    value: format!("{integer_sum} + {value}"),
+                                }
+                            })?;
+                        }
+                        FieldType::Float(value) => {
+                            is_float = true;
+                            float_sum += value;
+                        }
+                        FieldType::Double(value) => {
+                            is_double = true;
+                            double_sum += value;
+                        }
+                        FieldType::Decimal(value) => {
+                            is_decimal = true;
+                            decimal_sum += value;
+                        }
+                        _ => {}
+                    }
+                }
+                if is_decimal {
+                    Some(FieldType::Decimal(decimal_sum))
+                } else if is_double {
+                    Some(FieldType::Double(double_sum))
+                } else if is_float {
+                    Some(FieldType::Float(float_sum))
+                } else {
+                    Some(FieldType::BigInteger(integer_sum))
+                }
+            }
+            AggregateFunction::Avg if matches!(values[0], FieldType::Decimal(_)) => {
+                let sum: Decimal = values
+                    .iter()
+                    .map(|value| match value {
+                        FieldType::Decimal(value) => *value,
+                        _ => Decimal::ZERO,
+                    })
+                    .sum();
+                Some(FieldType::Decimal(sum / Decimal::from(values.len())))
+            }
+            AggregateFunction::Avg => {
+                let sum: f64 = values
+                    .iter()
+                    .map(|value| match value {
+                        FieldType::Integer(value) => *value as f64,
+                        FieldType::BigInteger(value) => *value as f64,
+                        FieldType::Float(value) => *value as f64,
+                        FieldType::Double(value) => *value,
+                        _ => 0.0,
+                    })
+                    .sum();
+                let average = sum / values.len() as f64;
+                if matches!(values[0], FieldType::BigInteger(_) | FieldType::Double(_)) {
+                    Some(FieldType::Double(average))
+                } else {
+                    Some(FieldType::Float(average as f32))
+                }
             }
-            SearchType::Nothing => vec![],
+            AggregateFunction::Min => values
+                .into_iter()
+                .reduce(|min, value| if value < min { value } else { min }),
+            AggregateFunction::Max => values
+                .into_iter()
+                .reduce(|max, value| if value > max { value } else { max }),
         })
     }
 
+    /// Value of given record to sort on, following M2O fields of given path
+    fn get_order_value(
+        &self,
+        model_name: &str,
+        id: u64,
+        path: &[String],
+        model_manager: &ModelManager,
+    ) -> Result<Option<FieldType>> {
+        let (field_name, rest) = path.split_first().ok_or_else(|| UnknownFieldError {
+            model_name: model_name.to_string(),
+            field_name: "".to_string(),
+        })?;
+        if field_name == "id" && rest.is_empty() {
+            return Ok(Some(FieldType::UInteger(id)));
+        }
+        let model = model_manager.get_model(model_name);
+        let field = model
+            .fields
+            .get(field_name)
+            .ok_or_else(|| UnknownFieldError {
+                model_name: model_name.to_string(),
+                field_name: field_name.clone(),
+            })?;
+        if !field.is_stored() {
+            return Err(InvalidOrderFieldError {
+                model_name: model_name.to_string(),
+                field_name: field_name.clone(),
+            }
+            .into());
+        }
+        let value = self
+            .tables
+            .get(model_name)
+            .and_then(|table| table.get_row(&id))
+            .and_then(|row| row.get_cell(field_name).clone());
+        if rest.is_empty() {
+            return Ok(value);
+        }
+        let Some(FieldReference {
+            target_model,
+            inverse_field: FieldReferenceType::M2O { .. },
+        }) = &field.inverse
+        else {
+            return Err(InvalidPathError {
+                model_name: model_name.to_string(),
+                field_name: field_name.clone(),
+            }
+            .into());
+        };
+        match value {
+            Some(FieldType::UInteger(target_id)) => {
+                self.get_order_value(target_model, target_id, rest, model_manager)
+            }
+            _ => Ok(None),
+        }
+    }
+
     // Path should be reverted
     fn _search_path(
         &self,
@@ -75,49 +317,283 @@ impl CacheDatabase {
         operator: &SearchOperator,
         right: &RightTuple,
         model_manager: &ModelManager,
-    ) -> Vec<u32> {
+    ) -> Result<Vec<u64>> {
         let current_field = path.pop().unwrap();
         if path.is_empty() {
-            return self._get_rows(model_name, &current_field, operator, right);
+            if is_hierarchical(operator) {
+                check_hierarchical_field(model_name, &current_field, model_manager)?;
+            }
+            if is_quantifier(operator) {
+                return self._get_any_rows(
+                    model_name,
+                    &current_field,
+                    operator,
+                    right,
+                    model_manager,
+                );
+            }
+            if let Some((target_model, links)) =
+                self.get_x2many_links(model_name, &current_field, model_manager)
+            {
+                return Ok(self._get_x2many_rows(
+                    model_name,
+                    target_model,
+                    &links,
+                    operator,
+                    right,
+                ));
+            }
+            return Ok(self._get_rows(model_name, &current_field, operator, right));
         }
         let model = model_manager.get_model(model_name);
         let final_field = model.get_internal_field(&current_field);
+        if final_field.is_json() {
+            // The rest of the path are keys of the JSON value
+            let keys: Vec<String> = path.drain(..).rev().collect();
+            return Ok(self._get_json_rows(model_name, &current_field, &keys, operator, right));
+        }
 
-        let FieldReference { target_model, inverse_field } = final_field.inverse.as_ref().unwrap_or_else(|| panic!("Field {model_name}.{current_field} doesn't have any inverse fields. This should not occur, as this is checked in method get_fields_to_save"));
+        let FieldReference { target_model, .. } = final_field.inverse.as_ref().unwrap_or_else(|| panic!("Field {model_name}.{current_field} doesn't have any inverse fields. This should not occur, as this is checked in method get_fields_to_save"));
         let target_model = model_manager.get_model(target_model);
 
-        let ids = self._search_path(&target_model.name, path, operator, right, model_manager);
-
-        let ids = if matches!(
-            final_field.default_value,
-            erp_types::field::FieldType::Ref(_)
-        ) {
-            self._get_rows(
-                &model.name,
-                &final_field.name,
-                &SearchOperator::Equal,
-                &ids.into(),
-            )
-        } else {
-            let mut result: Vec<u32> = Vec::new();
-            let table = self.tables.get(&target_model.name).unwrap();
-            if let FieldReferenceType::O2M { inverse_field } = inverse_field {
-                for id in ids {
-                    let row = table.get_row(&id).unwrap();
-                    if let Some(FieldType::UInteger(id)) = row.get_cell(inverse_field) {
-                        result.push(*id)
+        let ids = self._search_path(&target_model.name, path, operator, right, model_manager)?;
+
+        Ok(
+            if matches!(
+                final_field.default_value,
+                erp_types::field::FieldType::Ref(_)
+            ) {
+                self._get_rows(
+                    &model.name,
+                    &final_field.name,
+                    &SearchOperator::In,
+                    &ids.into(),
+                )
+            } else {
+                let ids: HashSet<u64> = ids.into_iter().collect();
+                let (_, links) = self
+                    .get_x2many_links(&model.name, &current_field, model_manager)
+                    .unwrap_or_else(|| {
+                        panic!(
+                            "Field {}.{} is of type M2O. This should not be possible here",
+                            model.name, current_field
+                        )
+                    });
+                links
+                    .into_iter()
+                    .filter(|(_, target_id)| ids.contains(target_id))
+                    .map(|(id, _)| id)
+                    .collect()
+            },
+        )
+    }
+
+    /// Ids of given records and of all their children ("child_of") or parents ("parent_of"),
+    /// following given M2O field of given model
+    fn get_hierarchy_ids(
+        &self,
+        model_name: &str,
+        field_name: &str,
+        operator: &SearchOperator,
+        right: &RightTuple,
+    ) -> HashSet<u64> {
+        let Some(table) = self.tables.get(model_name) else {
+            return HashSet::new();
+        };
+        let mut ids: HashSet<u64> = right
+            .values()
+            .iter()
+            .filter_map(|value| match value {
+                RightTuple::UInteger(id) => Some(*id),
+                RightTuple::Integer(id) => u64::try_from(*id).ok(),
+                _ => None,
+            })
+            .filter(|id| table.get_row(id).is_some())
+            .collect();
+        let mut to_visit: Vec<u64> = ids.iter().copied().collect();
+        while let Some(id) = to_visit.pop() {
+            if *operator == SearchOperator::ChildOf {
+                for (child_id, row) in &table.rows {
+                    if matches!(row.get_cell(field_name), Some(FieldType::UInteger(parent_id)) if *parent_id == id)
+                        && ids.insert(*child_id)
+                    {
+                        to_visit.push(*child_id);
                     }
                 }
-                result
-            } else {
-                panic!(
-                    "Field {}.{} is of type M2O. This should not be possible here",
-                    target_model.name, current_field
-                )
+            } else if let Some(Some(FieldType::UInteger(parent_id))) =
+                table.get_row(&id).map(|row| row.get_cell(field_name))
+                && ids.insert(*parent_id)
+            {
+                to_visit.push(*parent_id);
             }
+        }
+        ids
+    }
+
+    /// Check if given cells can be saved in given table, based on its columns.
+    ///
+    /// If the schema of this table has not been synced, do not check anything
+    fn check_cells(
+        &self,
+        model_name: &str,
+        cells: &HashMap<String, Option<FieldType>>,
+        is_new: bool,
+    ) -> Result<()> {
+        let Some(table) = self.tables.get(model_name) else {
+            return Ok(());
         };
+        if table.columns.is_empty() {
+            return Ok(());
+        }
+        for (field_name, value) in cells {
+            if field_name == "id" {
+                continue;
+            }
+            let column = table
+                .columns
+                .get(field_name)
+                .ok_or_else(|| UnknownFieldError {
+                    model_name: model_name.to_string(),
+                    field_name: field_name.clone(),
+                })?;
+            match (value, &column.target_model) {
+                (None, _) if column.not_null => {
+                    return Err(NotNullViolationError {
+                        model_name: model_name.to_string(),
+                        field_name: field_name.clone(),
+                    }
+                    .into());
+                }
+                (Some(FieldType::UInteger(target_id)), Some(target_model)) => {
+                    let exists = self
+                        .tables
+                        .get(target_model)
+                        .is_some_and(|target_table| target_table.get_row(target_id).is_some());
+                    if !exists {
+                        return Err(ForeignKeyViolationError {
+                            model_name: model_name.to_string(),
+                            field_name: field_name.clone(),
+                            target_model: target_model.clone(),
+                            target_id: *target_id,
+                        }
+                        .into());
+                    }
+                }
+                _ => {}
+            }
+        }
+        if is_new {
+            for (field_name, column) in &table.columns {
+                if column.not_null && !cells.contains_key(field_name) {
+                    return Err(NotNullViolationError {
+                        model_name: model_name.to_string(),
+                        field_name: field_name.clone(),
+                    }
+                    .into());
+                }
+            }
+        }
+        Ok(())
+    }
 
-        ids
+    /// Delete given rows, and apply the ondelete policy of every column targeting them.
+    ///
+    /// Every change is added to given list, so that it can be undone. Returns the number of
+    /// deleted rows of given table
+    fn delete_rows(
+        &mut self,
+        model_name: &str,
+        ids: &[u64],
+        changes: &mut Vec<DeleteChange>,
+    ) -> Result<u64> {
+        let Some(table) = self.tables.get_mut(model_name) else {
+            return Ok(0);
+        };
+        // Rows are deleted first, so that rows targeting themselves are not taken into account
+        let mut deleted_ids = Vec::with_capacity(ids.len());
+        for id in ids {
+            if let Some(row) = table.delete_row(id) {
+                deleted_ids.push(*id);
+                changes.push(DeleteChange::Removed {
+                    table_name: model_name.to_string(),
+                    id: *id,
+                    row,
+                });
+            }
+        }
+        let ids = deleted_ids;
+        if ids.is_empty() {
+            return Ok(0);
+        }
+
+        let mut rows_to_delete: Vec<(String, Vec<u64>)> = Vec::new();
+        let mut rows_to_empty: Vec<(String, String, Vec<u64>)> = Vec::new();
+        for (table_name, table) in &self.tables {
+            for (field_name, column) in &table.columns {
+                if column.target_model.as_deref() != Some(model_name) {
+                    continue;
+                }
+                let targeting_rows: Vec<(u64, u64)> = table
+                    .rows
+                    .iter()
+                    .filter_map(|(id, row)| match row.get_cell(field_name) {
+                        Some(FieldType::UInteger(target_id)) if ids.contains(target_id) => {
+                            Some((*id, *target_id))
+                        }
+                        _ => None,
+                    })
+                    .collect();
+                let Some((_, target_id)) = targeting_rows.first() else {
+                    continue;
+                };
+                match column.ondelete {
+                    OnDelete::Cascade => rows_to_delete.push((
+                        table_name.clone(),
+                        targeting_rows.iter().map(|(id, _)| *id).collect(),
+                    )),
+                    OnDelete::SetNull if !column.not_null => rows_to_empty.push((
+                        table_name.clone(),
+                        field_name.clone(),
+                        targeting_rows.iter().map(|(id, _)| *id).collect(),
+                    )),
+                    OnDelete::SetNull => {
+                        return Err(NotNullViolationError {
+                            model_name: table_name.clone(),
+                            field_name: field_name.clone(),
+                        }
+                        .into());
+                    }
+                    OnDelete::Restrict => {
+                        return Err(RestrictViolationError {
+                            model_name: table_name.clone(),
+                            field_name: field_name.clone(),
+                            target_model: model_name.to_string(),
+                            target_id: *target_id,
+                        }
+                        .into());
+                    }
+                }
+            }
+        }
+
+        for (table_name, field_name, row_ids) in rows_to_empty {
+            let table = self.tables.get_mut(&table_name).unwrap();
+            for id in row_ids {
+                if let Some(row) = table.get_row_mut(&id) {
+                    changes.push(DeleteChange::Emptied {
+                        table_name: table_name.clone(),
+                        id,
+                        field_name: field_name.clone(),
+                        value: row.get_cell(&field_name).clone(),
+                    });
+                    row.set_cell(&field_name, None);
+                }
+            }
+        }
+        for (table_name, row_ids) in rows_to_delete {
+            self.delete_rows(&table_name, &row_ids, changes)?;
+        }
+        Ok(ids.len() as u64)
     }
 
     fn _get_rows(
@@ -126,7 +602,13 @@ impl CacheDatabase {
         field_name: &str,
         operator: &SearchOperator,
         right: &RightTuple,
-    ) -> Vec<u32> {
+    ) -> Vec<u64> {
+        if is_hierarchical(operator) {
+            return self
+                .get_hierarchy_ids(model_name, field_name, operator, right)
+                .into_iter()
+                .collect();
+        }
         let mut result = Vec::new();
         if let Some(table) = self.tables.get(model_name) {
             for (id, row) in &table.rows {
@@ -137,6 +619,261 @@ impl CacheDatabase {
         }
         result
     }
+
+    /// Ids of records whose value at given key path of given JSON field matches given domain
+    fn _get_json_rows(
+        &self,
+        model_name: &str,
+        field_name: &str,
+        keys: &[String],
+        operator: &SearchOperator,
+        right: &RightTuple,
+    ) -> Vec<u64> {
+        let mut result = Vec::new();
+        if let Some(table) = self.tables.get(model_name) {
+            for (id, row) in &table.rows {
+                if row.is_json_valid(field_name, keys, operator, right) {
+                    result.push(*id);
+                }
+            }
+        }
+        result
+    }
+
+    /// Ids of records matching given "any" or "not any" condition on given M2O, O2M or M2M field.
+    ///
+    /// A record matches "any" if at least one of its targeted records matches the sub-domain, and
+    /// matches "not any" otherwise
+    fn _get_any_rows(
+        &self,
+        model_name: &str,
+        field_name: &str,
+        operator: &SearchOperator,
+        right: &RightTuple,
+        model_manager: &ModelManager,
+    ) -> Result<Vec<u64>> {
+        let field = model_manager
+            .get_model(model_name)
+            .fields
+            .get(field_name)
+            .ok_or_else(|| UnknownFieldError {
+                model_name: model_name.to_string(),
+                field_name: field_name.to_string(),
+            })?;
+        let Some(FieldReference {
+            target_model,
+            inverse_field,
+        }) = &field.inverse
+        else {
+            return Err(InvalidPathError {
+                model_name: model_name.to_string(),
+                field_name: field_name.to_string(),
+            }
+            .into());
+        };
+        let domain = match right {
+            RightTuple::Domain(domain) => domain.as_ref(),
+            _ => &SearchType::Nothing,
+        };
+        // An empty domain matches all target records
+        let target_ids = match domain {
+            SearchType::Nothing => self
+                .tables
+                .get(target_model)
+                .map(|table| table.rows.keys().copied().collect())
+                .unwrap_or_default(),
+            domain => self.get_rows(target_model, domain, model_manager)?,
+        };
+        let Some(table) = self.tables.get(model_name) else {
+            return Ok(vec![]);
+        };
+
+        let any_ids: HashSet<u64> = match inverse_field {
+            FieldReferenceType::M2O { .. } => table
+                .rows
+                .iter()
+                .filter(|(_, row)| {
+                    matches!(row.get_cell(field_name), Some(FieldType::UInteger(target_id)) if target_ids.contains(target_id))
+                })
+                .map(|(id, _)| *id)
+                .collect(),
+            FieldReferenceType::O2M { .. } | FieldReferenceType::M2M { .. } => self
+                .get_x2many_links(model_name, field_name, model_manager)
+                .map(|(_, links)| {
+                    links
+                        .into_iter()
+                        .filter(|(_, target_id)| target_ids.contains(target_id))
+                        .map(|(id, _)| id)
+                        .collect()
+                })
+                .unwrap_or_default(),
+        };
+        Ok(table
+            .rows
+            .keys()
+            .filter(|id| any_ids.contains(id) == (*operator == SearchOperator::Any))
+            .copied()
+            .collect())
+    }
+
+    /// Ids of records matching given condition on a O2M or M2M field, as the SQL database does.
+    ///
+    /// "= None" matches records without any linked record, "!= None" matches records with at
+    /// least one linked record, and other conditions are applied on ids of linked records
+    fn _get_x2many_rows(
+        &self,
+        model_name: &str,
+        target_model: &str,
+        links: &[(u64, u64)],
+        operator: &SearchOperator,
+        right: &RightTuple,
+    ) -> Vec<u64> {
+        let mut linked_ids = HashSet::new();
+        let mut valid_ids = HashSet::new();
+        let target_table = self.tables.get(target_model);
+        for (id, target_id) in links {
+            linked_ids.insert(*id);
+            let target_row = target_table.and_then(|table| table.get_row(target_id));
+            if target_row.is_some_and(|row| row.is_valid("id", operator, right)) {
+                valid_ids.insert(*id);
+            }
+        }
+        match (operator, right) {
+            (SearchOperator::Equal, RightTuple::None) => self
+                .tables
+                .get(model_name)
+                .map(|table| {
+                    table
+                        .rows
+                        .keys()
+                        .filter(|id| !linked_ids.contains(id))
+                        .copied()
+                        .collect()
+                })
+                .unwrap_or_default(),
+            (SearchOperator::NotEqual, RightTuple::None) => linked_ids.into_iter().collect(),
+            _ => valid_ids.into_iter().collect(),
+        }
+    }
+
+    /// Target model and links (record, targeted record) of given field if it's a O2M or a M2M
+    fn get_x2many_links<'mm>(
+        &self,
+        model_name: &str,
+        field_name: &str,
+        model_manager: &'mm ModelManager,
+    ) -> Option<(&'mm str, Vec<(u64, u64)>)> {
+        let field = model_manager.get_model(model_name).fields.get(field_name)?;
+        match &field.inverse {
+            Some(FieldReference {
+                target_model,
+                inverse_field: FieldReferenceType::O2M { inverse_field },
+            }) => {
+                let links = self
+                    .tables
+                    .get(target_model)
+                    .map(|table| {
+                        table
+                            .rows
+                            .iter()
+                            .filter_map(|(target_id, row)| match row.get_cell(inverse_field) {
+                                Some(FieldType::UInteger(id)) => Some((*id, *target_id)),
+                                _ => None,
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                Some((target_model, links))
+            }
+            Some(FieldReference {
+                target_model,
+                inverse_field: FieldReferenceType::M2M { .. },
+            }) => {
+                let relation = many2many_relation(model_name, field)?;
+                Some((target_model, self.get_relation_links(&relation)))
+            }
+            _ => None,
+        }
+    }
+
+    /// Links (record, targeted record) saved in given relation table
+    fn get_relation_links(&self, relation: &Many2manyRelation) -> Vec<(u64, u64)> {
+        self.tables
+            .get(relation.table)
+            .map(|table| {
+                table
+                    .rows
+                    .values()
+                    .filter_map(|row| {
+                        match (
+                            row.get_cell(&relation.column1),
+                            row.get_cell(&relation.column2),
+                        ) {
+                            (
+                                Some(FieldType::UInteger(id)),
+                                Some(FieldType::UInteger(target_id)),
+                            ) => Some((*id, *target_id)),
+                            _ => None,
+                        }
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+fn is_quantifier(operator: &SearchOperator) -> bool {
+    matches!(operator, SearchOperator::Any | SearchOperator::NotAny)
+}
+
+fn is_hierarchical(operator: &SearchOperator) -> bool {
+    matches!(operator, SearchOperator::ChildOf | SearchOperator::ParentOf)
+}
+
+/// Check that given field is a M2O to its own model, so that it can be used with "child_of" and
+/// "parent_of"
+fn check_hierarchical_field(
+    model_name: &str,
+    field_name: &str,
+    model_manager: &ModelManager,
+) -> Result<()> {
+    let field = model_manager.get_model(model_name).fields.get(field_name);
+    if field.and_then(foreign_key_target) == Some(model_name) {
+        Ok(())
+    } else {
+        Err(NotHierarchicalFieldError {
+            model_name: model_name.to_string(),
+            field_name: field_name.to_string(),
+        }
+        .into())
+    }
+}
+
+/// Compare given values, empty values being the greatest ones
+fn compare_values(left: &Option<FieldType>, right: &Option<FieldType>) -> Ordering {
+    match (left, right) {
+        (None, None) => Ordering::Equal,
+        (None, Some(_)) => Ordering::Greater,
+        (Some(_), None) => Ordering::Less,
+        (Some(left), Some(right)) => left.partial_cmp(right).unwrap_or(Ordering::Equal),
+    }
+}
+
+/// Table containing links of given M2M relation, declared on given model.
+///
+/// Links are removed when one of their records is deleted, as SQL databases do
+fn relation_table(model_name: &str, relation: &Many2manyRelation) -> Table {
+    let column = |target_model: &str| Column {
+        not_null: true,
+        target_model: Some(target_model.to_string()),
+        ondelete: OnDelete::Cascade,
+    };
+    let mut table = Table::default();
+    table.columns = HashMap::from([
+        (relation.column1.clone(), column(model_name)),
+        (relation.column2.clone(), column(relation.target_model)),
+    ]);
+    table
 }
 
 impl Database for CacheDatabase {
@@ -151,26 +888,82 @@ impl Database for CacheDatabase {
         Ok(())
     }
 
+    /// Rows don't have any fixed column, so there is nothing to upgrade
+    fn upgrade(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Create missing tables and columns of given models
+    ///
+    /// Columns added to an existing table are only NOT NULL if the table is empty, as existing
+    /// records don't have any value for them
+    fn sync_schema(&mut self, model_names: &[&str], model_manager: &ModelManager) -> Result<()> {
+        for model_name in model_names {
+            let model =
+                model_manager
+                    .get_models()
+                    .get(*model_name)
+                    .ok_or_else(|| ModelNotFound {
+                        model_name: model_name.to_string(),
+                    })?;
+            let table = self.tables.entry(model_name.to_string()).or_default();
+            let is_empty = table.rows.is_empty();
+            for field in model.fields.values() {
+                if !field.is_stored() {
+                    continue;
+                }
+                table
+                    .columns
+                    .entry(field.name.clone())
+                    .or_insert_with(|| Column {
+                        not_null: is_empty && is_not_null(field),
+                        target_model: foreign_key_target(field).map(|target| target.to_string()),
+                        ondelete: field.get_ondelete().unwrap_or_default(),
+                    });
+            }
+        }
+        for model_name in model_names {
+            for field in model_manager.get_model(model_name).fields.values() {
+                if let Some(relation) = many2many_relation(model_name, field) {
+                    self.tables
+                        .entry(relation.table.to_string())
+                        .or_insert_with(|| relation_table(model_name, &relation));
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// Make a search request to a specific model, and only return ids that match this search request
     fn browse(
         &mut self,
         model_name: &str,
         domain: &SearchType,
+        options: &SearchOptions,
         model_manager: &ModelManager,
-    ) -> Result<Vec<u32>> {
-        self.get_rows(model_name, domain, model_manager)
+    ) -> Result<Vec<u64>> {
+        let ids = Vec::from_iter(self.get_rows(model_name, &domain.normalize(), model_manager)?);
+        let order = model_manager.get_model(model_name).get_order(options);
+        let ids = self.sort_rows(model_name, ids, &order, model_manager)?;
+        Ok(ids
+            .into_iter()
+            .skip(options.offset)
+            .take(options.limit.unwrap_or(usize::MAX))
+            .collect())
     }
 
     /// Make a search request to a specific model, and return ids and fields that match this search request
+    #[allow(clippy::type_complexity)]
     fn search<'a>(
         &mut self,
         model_name: &str,
         fields: &[&'a str],
         domain: &SearchType,
+        options: &SearchOptions,
         model_manager: &ModelManager,
-    ) -> Result<Vec<(u32, HashMap<&'a str, Option<FieldType>>)>> {
+    ) -> Result<Vec<(u64, HashMap<&'a str, Option<FieldType>>)>> {
         // We don't care about searching 2 times (one to retrieve ids and one to retrieve fields), as it's cache
-        let ids = self.browse(model_name, domain, model_manager)?;
+        let ids = self.browse(model_name, domain, options, model_manager)?;
         if ids.is_empty() {
             return Ok(vec![]);
         }
@@ -198,9 +991,83 @@ impl Database for CacheDatabase {
         Ok(result)
     }
 
-    fn create(&mut self, model_name: &str, data: &Vec<&MapOfFields>) -> Result<Vec<u32>> {
-        let table = self.tables.entry(model_name.to_string()).or_default();
-        let mut ids = Vec::with_capacity(data.len());
+    fn count(
+        &mut self,
+        model_name: &str,
+        domain: &SearchType,
+        model_manager: &ModelManager,
+    ) -> Result<u64> {
+        let ids = self.get_rows(model_name, &domain.normalize(), model_manager)?;
+        Ok(ids.len() as u64)
+    }
+
+    fn exists(
+        &mut self,
+        model_name: &str,
+        domain: &SearchType,
+        model_manager: &ModelManager,
+    ) -> Result<bool> {
+        let ids = self.get_rows(model_name, &domain.normalize(), model_manager)?;
+        Ok(!ids.is_empty())
+    }
+
+    fn read_group(
+        &mut self,
+        model_name: &str,
+        domain: &SearchType,
+        group_by: &[&str],
+        aggregates: &[Aggregate],
+        model_manager: &ModelManager,
+    ) -> Result<Vec<Group>> {
+        // Fail on invalid fields or aggregates, as SQL databases do
+        get_read_group_types(model_name, group_by, aggregates, model_manager)?;
+        let group_by: Vec<Vec<String>> = group_by
+            .iter()
+            .map(|path| path.split('.').map(|str| str.to_string()).collect())
+            .collect();
+
+        let mut groups: Vec<(Vec<Option<FieldType>>, Vec<u64>)> = Vec::new();
+        if group_by.is_empty() {
+            // A single group, even if there is no record
+            groups.push((vec![], vec![]));
+        }
+        for id in self.get_rows(model_name, &domain.normalize(), model_manager)? {
+            let keys = group_by
+                .iter()
+                .map(|path| self.get_order_value(model_name, id, path, model_manager))
+                .collect::<Result<Vec<_>>>()?;
+            match groups
+                .iter_mut()
+                .find(|(group_keys, _)| *group_keys == keys)
+            {
+                Some((_, ids)) => ids.push(id),
+                None => groups.push((keys, vec![id])),
+            }
+        }
+        groups.sort_by(|(left, _), (right, _)| {
+            left.iter()
+                .zip(right)
+                .map(|(left, right)| compare_values(left, right))
+                .find(|ordering| ordering.is_ne())
+                .unwrap_or(Ordering::Equal)
+        });
+
+        groups
+            .into_iter()
+            .map(|(keys, ids)| {
+                let aggregates = aggregates
+                    .iter()
+                    .map(|aggregate| {
+                        self.compute_aggregate(model_name, &ids, aggregate, model_manager)
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(Group { keys, aggregates })
+            })
+            .collect()
+    }
+
+    fn create(&mut self, model_name: &str, data: &[&MapOfFields]) -> Result<Vec<u64>> {
+        let mut rows = Vec::with_capacity(data.len());
         for d in data {
             let cells = d
                 .fields
@@ -210,14 +1077,22 @@ impl Database for CacheDatabase {
                     (k.clone(), v)
                 })
                 .collect::<HashMap<_, _>>();
-            let row = Row { id: 0, cells };
-            let id = table.add_row(row);
-            ids.push(id);
+            self.check_cells(model_name, &cells, true)?;
+            rows.push(Row { id: 0, cells });
         }
-        Ok(ids)
+        let table = self.tables.entry(model_name.to_string()).or_default();
+        Ok(rows.into_iter().map(|row| table.add_row(row)).collect())
     }
 
-    fn update(&mut self, model_name: &str, data: &HashMap<u32, &MapOfFields>) -> Result<u32> {
+    fn update(&mut self, model_name: &str, data: &HashMap<u64, &MapOfFields>) -> Result<u64> {
+        for map_of_field in data.values() {
+            let cells = map_of_field
+                .fields
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone().map(|value| value.into())))
+                .collect::<HashMap<_, _>>();
+            self.check_cells(model_name, &cells, false)?;
+        }
         let mut number_of_updates = 0;
         if let Some(table) = self.tables.get_mut(model_name) {
             for (id, map_of_field) in data {
@@ -237,6 +1112,99 @@ impl Database for CacheDatabase {
         Ok(number_of_updates)
     }
 
+    fn delete(&mut self, model_name: &str, ids: &[u64]) -> Result<u64> {
+        let mut changes = Vec::new();
+        let result = self.delete_rows(model_name, ids, &mut changes);
+        if result.is_err() {
+            // A failing deletion should not modify anything, like a real database would do
+            for change in changes.into_iter().rev() {
+                match change {
+                    DeleteChange::Removed {
+                        table_name,
+                        id,
+                        row,
+                    } => {
+                        if let Some(table) = self.tables.get_mut(&table_name) {
+                            table.rows.insert(id, row);
+                        }
+                    }
+                    DeleteChange::Emptied {
+                        table_name,
+                        id,
+                        field_name,
+                        value,
+                    } => {
+                        if let Some(row) = self
+                            .tables
+                            .get_mut(&table_name)
+                            .and_then(|table| table.get_row_mut(&id))
+                        {
+                            row.set_cell(&field_name, value);
+                        }
+                    }
+                }
+            }
+        }
+        result
+    }
+
+    fn read_many2many(
+        &mut self,
+        model_name: &str,
+        field_name: &str,
+        ids: &[u64],
+        model_manager: &ModelManager,
+    ) -> Result<HashMap<u64, Vec<u64>>> {
+        let relation = get_many2many_relation(model_name, field_name, model_manager)?;
+        let mut result: HashMap<u64, Vec<u64>> = HashMap::new();
+        for (id, target_id) in self.get_relation_links(&relation) {
+            if ids.contains(&id) {
+                result.entry(id).or_default().push(target_id);
+            }
+        }
+        result
+            .values_mut()
+            .for_each(|target_ids| target_ids.sort_unstable());
+        Ok(result)
+    }
+
+    fn write_many2many(
+        &mut self,
+        model_name: &str,
+        field_name: &str,
+        data: &HashMap<u64, Vec<u64>>,
+        model_manager: &ModelManager,
+    ) -> Result<()> {
+        let relation = get_many2many_relation(model_name, field_name, model_manager)?;
+        let mut rows = Vec::new();
+        for (id, target_ids) in data {
+            for target_id in target_ids {
+                let cells = HashMap::from([
+                    (relation.column1.clone(), Some(FieldType::UInteger(*id))),
+                    (
+                        relation.column2.clone(),
+                        Some(FieldType::UInteger(*target_id)),
+                    ),
+                ]);
+                rows.push(Row { id: 0, cells });
+            }
+        }
+        self.tables
+            .entry(relation.table.to_string())
+            .or_insert_with(|| relation_table(model_name, &relation));
+        for row in &rows {
+            self.check_cells(relation.table, &row.cells, true)?;
+        }
+        let table = self.tables.get_mut(relation.table).unwrap();
+        table.rows.retain(|_, row| {
+            !matches!(row.get_cell(&relation.column1), Some(FieldType::UInteger(id)) if data.contains_key(id))
+        });
+        for row in rows {
+            table.add_row(row);
+        }
+        Ok(())
+    }
+
     fn get_installed_plugins(&mut self) -> Result<Vec<String>> {
         if !self.installed {
             return Ok(vec![]);
@@ -262,6 +1230,52 @@ impl Database for CacheDatabase {
         }
     }
 
+    fn get_installed_plugin_version(&mut self, plugin_name: &str) -> Result<Option<String>> {
+        let Some(table) = self.tables.get("plugin") else {
+            return Ok(None);
+        };
+        for row in table.rows.values() {
+            match (row.get_cell("name"), row.get_cell("state")) {
+                (Some(FieldType::String(name)), Some(FieldType::String(state)))
+                    if name == plugin_name && state == "installed" =>
+                {
+                    // An installed plugin without any version has been installed before versions
+                    //  were saved
+                    return Ok(Some(match row.get_cell("version") {
+                        Some(FieldType::String(version)) => version.clone(),
+                        _ => "0".to_string(),
+                    }));
+                }
+                _ => {}
+            }
+        }
+        Ok(None)
+    }
+
+    fn set_installed_plugin_version(&mut self, plugin_name: &str, version: &str) -> Result<()> {
+        let table = self.tables.entry("plugin".to_string()).or_default();
+        let row = table.rows.values_mut().find(|row| {
+            matches!(row.get_cell("name"), Some(FieldType::String(name)) if name == plugin_name)
+        });
+        let state = Some(FieldType::String("installed".to_string()));
+        let version = Some(FieldType::String(version.to_string()));
+        if let Some(row) = row {
+            row.set_cell("state", state);
+            row.set_cell("version", version);
+        } else {
+            let cells = HashMap::from([
+                (
+                    "name".to_string(),
+                    Some(FieldType::String(plugin_name.to_string())),
+                ),
+                ("state".to_string(), state),
+                ("version".to_string(), version),
+            ]);
+            table.add_row(Row { id: 0, cells });
+        }
+        Ok(())
+    }
+
     fn savepoint(&mut self, name: &str) -> Result<()> {
         let tables = self.tables.clone();
         self.savepoints.push((Some(name.to_string()), tables));
@@ -1,10 +1,11 @@
-use crate::database::FieldType;
+use crate::database::{FieldType, decimal_right, get_json_value, json_to_field_type};
 use erp_search::{RightTuple, SearchOperator};
 use std::collections::HashMap;
 
 #[derive(Default, Clone)]
 pub(crate) struct Row {
-    pub(crate) id: u32,
+    #[allow(dead_code)]
+    pub(crate) id: u64,
     pub(crate) cells: HashMap<String, Option<FieldType>>,
 }
 
@@ -24,82 +25,247 @@ impl Row {
         operator: &SearchOperator,
         right: &RightTuple,
     ) -> bool {
-        let cell_value = self.get_cell(field_name);
-        match operator {
-            SearchOperator::Equal => match (right, cell_value) {
-                (RightTuple::None, None) => true,
-                (left, Some(right)) => left == right,
-                _ => false,
-            },
-            SearchOperator::NotEqual => match (right, cell_value) {
-                (RightTuple::None, None) => false,
-                (left, Some(right)) => left != right,
-                _ => true,
-            },
-            SearchOperator::Greater => match (right, cell_value) {
-                (RightTuple::None, None) => false,
-                (RightTuple::Integer(right), Some(FieldType::Integer(cell_value))) => {
-                    cell_value > right
-                }
-                (RightTuple::UInteger(right), Some(FieldType::UInteger(cell_value))) => {
-                    cell_value > right
-                }
-                (RightTuple::Float(right), Some(FieldType::Float(cell_value))) => {
-                    cell_value > right
-                }
-                (RightTuple::Boolean(right), Some(FieldType::Boolean(cell_value))) => {
-                    cell_value > right
-                }
-                _ => false,
-            },
-            SearchOperator::GreaterEqual => match (right, cell_value) {
-                (RightTuple::None, None) => false,
-                (RightTuple::Integer(right), Some(FieldType::Integer(cell_value))) => {
-                    cell_value >= right
-                }
-                (RightTuple::UInteger(right), Some(FieldType::UInteger(cell_value))) => {
-                    cell_value >= right
-                }
-                (RightTuple::Float(right), Some(FieldType::Float(cell_value))) => {
-                    cell_value >= right
-                }
-                (RightTuple::Boolean(right), Some(FieldType::Boolean(cell_value))) => {
-                    cell_value >= right
-                }
-                _ => false,
-            },
-            SearchOperator::Lower => match (right, cell_value) {
-                (RightTuple::None, None) => false,
-                (RightTuple::Integer(right), Some(FieldType::Integer(cell_value))) => {
-                    cell_value < right
-                }
-                (RightTuple::UInteger(right), Some(FieldType::UInteger(cell_value))) => {
-                    cell_value < right
-                }
-                (RightTuple::Float(right), Some(FieldType::Float(cell_value))) => {
-                    cell_value < right
-                }
-                (RightTuple::Boolean(right), Some(FieldType::Boolean(cell_value))) => {
-                    cell_value < right
-                }
-                _ => false,
-            },
-            SearchOperator::LowerEqual => match (right, cell_value) {
-                (RightTuple::None, None) => false,
-                (RightTuple::Integer(right), Some(FieldType::Integer(cell_value))) => {
-                    cell_value <= right
-                }
-                (RightTuple::UInteger(right), Some(FieldType::UInteger(cell_value))) => {
-                    cell_value <= right
-                }
-                (RightTuple::Float(right), Some(FieldType::Float(cell_value))) => {
-                    cell_value <= right
-                }
-                (RightTuple::Boolean(right), Some(FieldType::Boolean(cell_value))) => {
-                    cell_value <= right
+        is_value_valid(self.get_cell(field_name).as_ref(), operator, right)
+    }
+
+    /// Check if the value at given key path of the JSON value of given field is valid for given
+    /// domain
+    pub(crate) fn is_json_valid(
+        &self,
+        field_name: &str,
+        keys: &[String],
+        operator: &SearchOperator,
+        right: &RightTuple,
+    ) -> bool {
+        is_json_value_valid(self.get_cell(field_name).as_ref(), keys, operator, right)
+    }
+}
+
+/// Check if given value is valid for given domain.
+///
+/// This is the comparison used by the cache database, and by the environment when filtering
+/// records in memory
+pub(crate) fn is_value_valid(
+    cell_value: Option<&FieldType>,
+    operator: &SearchOperator,
+    right: &RightTuple,
+) -> bool {
+    match operator {
+        SearchOperator::Equal => match (right, cell_value) {
+            (RightTuple::None, None) => true,
+            (left, Some(right)) => left == right,
+            _ => false,
+        },
+        SearchOperator::NotEqual => match (right, cell_value) {
+            (RightTuple::None, None) => false,
+            (left, Some(right)) => left != right,
+            _ => true,
+        },
+        SearchOperator::Greater => match (right, cell_value) {
+            (RightTuple::None, None) => false,
+            (RightTuple::Integer(right), Some(FieldType::Integer(cell_value))) => {
+                cell_value > right
+            }
+            (RightTuple::UInteger(right), Some(FieldType::UInteger(cell_value))) => {
+                cell_value > right
+            }
+            (RightTuple::BigInteger(right), Some(FieldType::BigInteger(cell_value))) => {
+                cell_value > right
+            }
+            (RightTuple::Float(right), Some(FieldType::Float(cell_value))) => cell_value > right,
+            (RightTuple::Double(right), Some(FieldType::Double(cell_value))) => cell_value > right,
+            (RightTuple::Boolean(right), Some(FieldType::Boolean(cell_value))) => {
+                cell_value > right
+            }
+            (RightTuple::Decimal(right), Some(FieldType::Decimal(cell_value))) => {
+                cell_value > right
+            }
+            (RightTuple::Date(right), Some(FieldType::Date(cell_value))) => cell_value > right,
+            (RightTuple::DateTime(right), Some(FieldType::DateTime(cell_value))) => {
+                cell_value > right
+            }
+            _ => false,
+        },
+        SearchOperator::GreaterEqual => match (right, cell_value) {
+            (RightTuple::None, None) => false,
+            (RightTuple::Integer(right), Some(FieldType::Integer(cell_value))) => {
+                cell_value >= right
+            }
+            (RightTuple::UInteger(right), Some(FieldType::UInteger(cell_value))) => {
+                cell_value >= right
+            }
+            (RightTuple::BigInteger(right), Some(FieldType::BigInteger(cell_value))) => {
+                cell_value >= right
+            }
+            (RightTuple::Float(right), Some(FieldType::Float(cell_value))) => cell_value >= right,
+            (RightTuple::Double(right), Some(FieldType::Double(cell_value))) => cell_value >= right,
+            (RightTuple::Boolean(right), Some(FieldType::Boolean(cell_value))) => {
+                cell_value >= right
+            }
+            (RightTuple::Decimal(right), Some(FieldType::Decimal(cell_value))) => {
+                cell_value >= right
+            }
+            (RightTuple::Date(right), Some(FieldType::Date(cell_value))) => cell_value >= right,
+            (RightTuple::DateTime(right), Some(FieldType::DateTime(cell_value))) => {
+                cell_value >= right
+            }
+            _ => false,
+        },
+        SearchOperator::Lower => match (right, cell_value) {
+            (RightTuple::None, None) => false,
+            (RightTuple::Integer(right), Some(FieldType::Integer(cell_value))) => {
+                cell_value < right
+            }
+            (RightTuple::UInteger(right), Some(FieldType::UInteger(cell_value))) => {
+                cell_value < right
+            }
+            (RightTuple::BigInteger(right), Some(FieldType::BigInteger(cell_value))) => {
+                cell_value < right
+            }
+            (RightTuple::Float(right), Some(FieldType::Float(cell_value))) => cell_value < right,
+            (RightTuple::Double(right), Some(FieldType::Double(cell_value))) => cell_value < right,
+            (RightTuple::Boolean(right), Some(FieldType::Boolean(cell_value))) => {
+                cell_value < right
+            }
+            (RightTuple::Decimal(right), Some(FieldType::Decimal(cell_value))) => {
+                cell_value < right
+            }
+            (RightTuple::Date(right), Some(FieldType::Date(cell_value))) => cell_value < right,
+            (RightTuple::DateTime(right), Some(FieldType::DateTime(cell_value))) => {
+                cell_value < right
+            }
+            _ => false,
+        },
+        SearchOperator::LowerEqual => match (right, cell_value) {
+            (RightTuple::None, None) => false,
+            (RightTuple::Integer(right), Some(FieldType::Integer(cell_value))) => {
+                cell_value <= right
+            }
+            (RightTuple::UInteger(right), Some(FieldType::UInteger(cell_value))) => {
+                cell_value <= right
+            }
+            (RightTuple::BigInteger(right), Some(FieldType::BigInteger(cell_value))) => {
+                cell_value <= right
+            }
+            (RightTuple::Float(right), Some(FieldType::Float(cell_value))) => cell_value <= right,
+            (RightTuple::Double(right), Some(FieldType::Double(cell_value))) => cell_value <= right,
+            (RightTuple::Boolean(right), Some(FieldType::Boolean(cell_value))) => {
+                cell_value <= right
+            }
+            (RightTuple::Decimal(right), Some(FieldType::Decimal(cell_value))) => {
+                cell_value <= right
+            }
+            (RightTuple::Date(right), Some(FieldType::Date(cell_value))) => cell_value <= right,
+            (RightTuple::DateTime(right), Some(FieldType::DateTime(cell_value))) => {
+                cell_value <= right
+            }
+            _ => false,
+        },
+        SearchOperator::In => right
+            .values()
+            .iter()
+            .any(|value| matches!(cell_value, Some(cell_value) if value == cell_value)),
+        SearchOperator::NotIn => !right
+            .values()
+            .iter()
+            .any(|value| matches!(cell_value, Some(cell_value) if value == cell_value)),
+        SearchOperator::Like => match (right, cell_value) {
+            (RightTuple::String(right), Some(FieldType::String(cell_value))) => {
+                cell_value.contains(right.as_str())
+            }
+            _ => false,
+        },
+        SearchOperator::ILike => match (right, cell_value) {
+            (RightTuple::String(right), Some(FieldType::String(cell_value))) => {
+                // Only ASCII letters are case-insensitive, as with SQLite's LIKE
+                cell_value
+                    .to_ascii_lowercase()
+                    .contains(&right.to_ascii_lowercase())
+            }
+            _ => false,
+        },
+        SearchOperator::EqualLike => match (right, cell_value) {
+            (RightTuple::String(right), Some(FieldType::String(cell_value))) => {
+                like_match(right, cell_value)
+            }
+            _ => false,
+        },
+        // Those operators depend on other rows, so they are handled by the database
+        SearchOperator::ChildOf
+        | SearchOperator::ParentOf
+        | SearchOperator::Any
+        | SearchOperator::NotAny => false,
+    }
+}
+
+/// Check if the value at given key path of given JSON value is valid for given domain
+pub(crate) fn is_json_value_valid(
+    value: Option<&FieldType>,
+    keys: &[String],
+    operator: &SearchOperator,
+    right: &RightTuple,
+) -> bool {
+    let value = match value {
+        Some(FieldType::Json(json)) => get_json_value(json, keys).and_then(json_to_field_type),
+        _ => None,
+    };
+    is_value_valid(value.as_ref(), operator, &decimal_right(right))
+}
+
+#[derive(PartialEq)]
+enum LikeToken {
+    /// "%", any sequence of characters
+    Any,
+    /// "_", any single character
+    One,
+    Char(char),
+}
+
+/// Check if given value matches given LIKE pattern.
+///
+/// "%" matches any sequence of characters, "_" matches any single character, and `\` escapes
+/// the next character
+fn like_match(pattern: &str, value: &str) -> bool {
+    let mut tokens = Vec::new();
+    let mut chars = pattern.chars();
+    while let Some(char) = chars.next() {
+        tokens.push(match char {
+            '%' => LikeToken::Any,
+            '_' => LikeToken::One,
+            '\\' => LikeToken::Char(chars.next().unwrap_or('\\')),
+            char => LikeToken::Char(char),
+        });
+    }
+    let value: Vec<char> = value.chars().collect();
+
+    // Greedy matching, going back to the last "%" on failure
+    let (mut token_index, mut value_index) = (0, 0);
+    let mut last_any: Option<(usize, usize)> = None;
+    while value_index < value.len() {
+        match tokens.get(token_index) {
+            Some(LikeToken::One) => {
+                token_index += 1;
+                value_index += 1;
+            }
+            Some(LikeToken::Char(char)) if *char == value[value_index] => {
+                token_index += 1;
+                value_index += 1;
+            }
+            Some(LikeToken::Any) => {
+                last_any = Some((token_index, value_index));
+                token_index += 1;
+            }
+            _ => match last_any {
+                Some((any_index, any_value_index)) => {
+                    token_index = any_index + 1;
+                    value_index = any_value_index + 1;
+                    last_any = Some((any_index, any_value_index + 1));
                 }
-                _ => false,
+                None => return false,
             },
         }
     }
+    tokens[token_index..]
+        .iter()
+        .all(|token| *token == LikeToken::Any)
 }
@@ -1,23 +1,34 @@
-use crate::database::cache::Row;
 use crate::database::FieldType;
+use crate::database::cache::Row;
+use erp_types::field::OnDelete;
 use std::collections::HashMap;
 
+/// Definition of a column, used to check data before saving them
+#[derive(Clone)]
+pub(crate) struct Column {
+    pub(crate) not_null: bool,
+    pub(crate) target_model: Option<String>,
+    /// Policy applied when the targeted record is deleted. Only used if there is a target model
+    pub(crate) ondelete: OnDelete,
+}
+
 #[derive(Default, Clone)]
 pub(crate) struct Table {
-    last_id: u32,
-    pub(crate) rows: HashMap<u32, Row>,
+    last_id: u64,
+    pub(crate) rows: HashMap<u64, Row>,
+    pub(crate) columns: HashMap<String, Column>,
 }
 
 impl Table {
-    pub(crate) fn get_row(&self, id: &u32) -> Option<&Row> {
+    pub(crate) fn get_row(&self, id: &u64) -> Option<&Row> {
         self.rows.get(id)
     }
 
-    pub(crate) fn get_row_mut(&mut self, id: &u32) -> Option<&mut Row> {
+    pub(crate) fn get_row_mut(&mut self, id: &u64) -> Option<&mut Row> {
         self.rows.get_mut(id)
     }
 
-    pub(crate) fn add_row(&mut self, mut row: Row) -> u32 {
+    pub(crate) fn add_row(&mut self, mut row: Row) -> u64 {
         self.last_id += 1;
         let id = self.last_id;
         row.cells
@@ -30,7 +41,8 @@ impl Table {
         id
     }
 
-    pub(crate) fn delete_row(&mut self, id: &u32) {
-        self.rows.remove(id);
+    /// Remove given row, and return it if it was present
+    pub(crate) fn delete_row(&mut self, id: &u64) -> Option<Row> {
+        self.rows.remove(id)
     }
 }
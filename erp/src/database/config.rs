@@ -1,11 +1,36 @@
 use serde::Deserialize;
 
+/// Engine used to store data
+#[derive(Debug, Deserialize, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DatabaseEngine {
+    #[default]
+    Postgres,
+    Sqlite,
+}
+
 #[derive(Debug, Deserialize, Default)]
 #[allow(dead_code)]
 pub struct DatabaseConfig {
+    #[serde(default)]
+    pub(crate) engine: DatabaseEngine,
     pub(crate) url: String,
+    /// Name of the database.
+    ///
+    /// For SQLite, this is the path to the database file
     pub(crate) name: String,
     pub(crate) schema: String,
     pub(crate) user: String,
     pub(crate) password: String,
 }
+
+impl DatabaseConfig {
+    /// Create a config targeting a SQLite database stored in given file
+    pub fn sqlite(path: &str) -> Self {
+        DatabaseConfig {
+            engine: DatabaseEngine::Sqlite,
+            name: path.to_string(),
+            ..Default::default()
+        }
+    }
+}
@@ -1,6 +1,6 @@
-use crate::database::FieldType;
+use crate::database::{Aggregate, FieldType, Group};
 use crate::model::ModelManager;
-use erp_search::SearchType;
+use erp_search::{SearchOptions, SearchType};
 use erp_types::model::MapOfFields;
 use std::collections::HashMap;
 use std::error::Error;
@@ -11,6 +11,7 @@ type Result<T> = std::result::Result<T, Box<dyn Error>>;
 #[derive(Debug)]
 pub enum ErrorType {
     Postgres(postgres::Error),
+    Sqlite(rusqlite::Error),
     Other(Box<dyn Error>),
 }
 
@@ -20,6 +21,12 @@ impl From<postgres::Error> for ErrorType {
     }
 }
 
+impl From<rusqlite::Error> for ErrorType {
+    fn from(e: rusqlite::Error) -> Self {
+        ErrorType::Sqlite(e)
+    }
+}
+
 impl From<Box<dyn Error>> for ErrorType {
     fn from(e: Box<dyn Error>) -> Self {
         ErrorType::Other(e)
@@ -30,6 +37,7 @@ impl Display for ErrorType {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
             ErrorType::Postgres(e) => e.fmt(f),
+            ErrorType::Sqlite(e) => e.fmt(f),
             ErrorType::Other(e) => e.fmt(f),
         }
     }
@@ -44,38 +52,129 @@ pub trait Database {
     /// Initialize this database
     fn initialize(&mut self) -> Result<()>;
 
+    /// Add columns that are missing from the tables created by `initialize`, for databases that
+    /// have been installed by an older version.
+    ///
+    /// Does nothing if those tables are up to date, so this can be called each time plugins are
+    /// loaded
+    fn upgrade(&mut self) -> Result<()>;
+
+    /// Create missing tables and columns of given models, based on their current definition in
+    /// given ModelManager
+    fn sync_schema(&mut self, model_names: &[&str], model_manager: &ModelManager) -> Result<()>;
+
     /// Make a search request to a specific model, and only return ids that match this search request
     ///
+    /// Ids are sorted, limited and offset following given options
+    ///
     /// ModelManager is needed to know the current structure of the database, and to make correct
     /// links between the domain and the database
     fn browse(
         &mut self,
         model_name: &str,
         domain: &SearchType,
+        options: &SearchOptions,
         model_manager: &ModelManager,
-    ) -> Result<Vec<u32>>;
+    ) -> Result<Vec<u64>>;
 
     /// Make a search request to a specific model, and return ids and fields that match this search request
     ///
+    /// Records are sorted, limited and offset following given options
+    ///
     /// ModelManager is needed to know the current structure of the database, and to make correct
     /// links between the domain and the database
+    #[allow(clippy::type_complexity)]
     fn search<'a>(
         &mut self,
         model_name: &str,
         fields: &[&'a str],
         domain: &SearchType,
+        options: &SearchOptions,
+        model_manager: &ModelManager,
+    ) -> Result<Vec<(u64, HashMap<&'a str, Option<FieldType>>)>>;
+
+    /// Count records of a specific model that match given search request
+    ///
+    /// ModelManager is needed to know the current structure of the database, and to make correct
+    /// links between the domain and the database
+    fn count(
+        &mut self,
+        model_name: &str,
+        domain: &SearchType,
+        model_manager: &ModelManager,
+    ) -> Result<u64>;
+
+    /// Check if at least one record of a specific model matches given search request
+    ///
+    /// ModelManager is needed to know the current structure of the database, and to make correct
+    /// links between the domain and the database
+    fn exists(
+        &mut self,
+        model_name: &str,
+        domain: &SearchType,
         model_manager: &ModelManager,
-    ) -> Result<Vec<(u32, HashMap<&'a str, Option<FieldType>>)>>;
+    ) -> Result<bool>;
+
+    /// Group records of a specific model that match given search request by given fields, and
+    /// compute given aggregates on each group
+    ///
+    /// Grouped fields can go through M2O fields ("order.state"). Groups are sorted by their keys,
+    /// empty values last. Without any grouped field, a single group containing all records is
+    /// returned
+    ///
+    /// ModelManager is needed to know the current structure of the database, and to make correct
+    /// links between the domain and the database
+    fn read_group(
+        &mut self,
+        model_name: &str,
+        domain: &SearchType,
+        group_by: &[&str],
+        aggregates: &[Aggregate],
+        model_manager: &ModelManager,
+    ) -> Result<Vec<Group>>;
 
     /// Create one new record per given data for given model
-    fn create(&mut self, model_name: &str, data: &Vec<&MapOfFields>) -> Result<Vec<u32>>;
+    fn create(&mut self, model_name: &str, data: &[&MapOfFields]) -> Result<Vec<u64>>;
 
     /// Update given data for given model
-    fn update(&mut self, model_name: &str, data: &HashMap<u32, &MapOfFields>) -> Result<u32>;
+    fn update(&mut self, model_name: &str, data: &HashMap<u64, &MapOfFields>) -> Result<u64>;
+
+    /// Delete given records of given model
+    ///
+    /// Returns the number of deleted records
+    fn delete(&mut self, model_name: &str, ids: &[u64]) -> Result<u64>;
+
+    /// Retrieve ids targeted by given M2M field of given records, from its relation table
+    ///
+    /// Targeted ids are sorted, and records without any link are not returned
+    fn read_many2many(
+        &mut self,
+        model_name: &str,
+        field_name: &str,
+        ids: &[u64],
+        model_manager: &ModelManager,
+    ) -> Result<HashMap<u64, Vec<u64>>>;
+
+    /// Replace ids targeted by given M2M field of given records, in its relation table
+    fn write_many2many(
+        &mut self,
+        model_name: &str,
+        field_name: &str,
+        data: &HashMap<u64, Vec<u64>>,
+        model_manager: &ModelManager,
+    ) -> Result<()>;
 
     /// Retrieves installed plugins
     fn get_installed_plugins(&mut self) -> Result<Vec<String>>;
 
+    /// Retrieves the installed version of given plugin.
+    ///
+    /// Return None if this plugin is not installed
+    fn get_installed_plugin_version(&mut self, plugin_name: &str) -> Result<Option<String>>;
+
+    /// Mark given plugin as installed with given version
+    fn set_installed_plugin_version(&mut self, plugin_name: &str, version: &str) -> Result<()>;
+
     /// Create a new savepoint
     fn savepoint(&mut self, name: &str) -> Result<()>;
 
@@ -1,17 +1,30 @@
 use crate::database::cache::CacheDatabase;
 use crate::database::postgres::PostgresDatabase;
-use crate::database::{Database, FieldType};
+use crate::database::sqlite::SqliteDatabase;
+use crate::database::{Aggregate, Database, DatabaseConfig, DatabaseEngine, FieldType, Group};
 use crate::model::ModelManager;
-use erp_search::SearchType;
+use erp_search::{SearchOptions, SearchType};
 use erp_types::model::MapOfFields;
 use std::collections::HashMap;
 use std::error::Error;
 
 type Result<T> = std::result::Result<T, Box<dyn Error>>;
 
+#[allow(clippy::large_enum_variant)]
 pub enum DatabaseType<'db> {
     Cache(&'db mut CacheDatabase),
     Postgres(PostgresDatabase),
+    Sqlite(SqliteDatabase),
+}
+
+impl<'db> DatabaseType<'db> {
+    /// Make a connection to the database described by given config
+    pub(crate) fn connect(config: &DatabaseConfig) -> Result<Self> {
+        Ok(match config.engine {
+            DatabaseEngine::Postgres => DatabaseType::Postgres(PostgresDatabase::connect(config)?),
+            DatabaseEngine::Sqlite => DatabaseType::Sqlite(SqliteDatabase::connect(config)?),
+        })
+    }
 }
 
 impl<'db> Database for DatabaseType<'db> {
@@ -19,6 +32,7 @@ impl<'db> Database for DatabaseType<'db> {
         match self {
             DatabaseType::Cache(cache) => cache.is_installed(),
             DatabaseType::Postgres(postgres) => postgres.is_installed(),
+            DatabaseType::Sqlite(sqlite) => sqlite.is_installed(),
         }
     }
 
@@ -26,6 +40,23 @@ impl<'db> Database for DatabaseType<'db> {
         match self {
             DatabaseType::Cache(cache) => cache.initialize(),
             DatabaseType::Postgres(postgres) => postgres.initialize(),
+            DatabaseType::Sqlite(sqlite) => sqlite.initialize(),
+        }
+    }
+
+    fn upgrade(&mut self) -> Result<()> {
+        match self {
+            DatabaseType::Cache(cache) => cache.upgrade(),
+            DatabaseType::Postgres(postgres) => postgres.upgrade(),
+            DatabaseType::Sqlite(sqlite) => sqlite.upgrade(),
+        }
+    }
+
+    fn sync_schema(&mut self, model_names: &[&str], model_manager: &ModelManager) -> Result<()> {
+        match self {
+            DatabaseType::Cache(cache) => cache.sync_schema(model_names, model_manager),
+            DatabaseType::Postgres(postgres) => postgres.sync_schema(model_names, model_manager),
+            DatabaseType::Sqlite(sqlite) => sqlite.sync_schema(model_names, model_manager),
         }
     }
 
@@ -33,40 +64,150 @@ impl<'db> Database for DatabaseType<'db> {
         &mut self,
         model_name: &str,
         domain: &SearchType,
+        options: &SearchOptions,
         model_manager: &ModelManager,
-    ) -> Result<Vec<u32>> {
+    ) -> Result<Vec<u64>> {
         match self {
-            DatabaseType::Cache(cache) => cache.browse(model_name, domain, model_manager),
-            DatabaseType::Postgres(postgres) => postgres.browse(model_name, domain, model_manager),
+            DatabaseType::Cache(cache) => cache.browse(model_name, domain, options, model_manager),
+            DatabaseType::Postgres(postgres) => {
+                postgres.browse(model_name, domain, options, model_manager)
+            }
+            DatabaseType::Sqlite(sqlite) => {
+                sqlite.browse(model_name, domain, options, model_manager)
+            }
         }
     }
 
+    #[allow(clippy::type_complexity)]
     fn search<'a>(
         &mut self,
         model_name: &str,
         fields: &[&'a str],
         domain: &SearchType,
+        options: &SearchOptions,
         model_manager: &ModelManager,
-    ) -> Result<Vec<(u32, HashMap<&'a str, Option<FieldType>>)>> {
+    ) -> Result<Vec<(u64, HashMap<&'a str, Option<FieldType>>)>> {
         match self {
-            DatabaseType::Cache(cache) => cache.search(model_name, fields, domain, model_manager),
+            DatabaseType::Cache(cache) => {
+                cache.search(model_name, fields, domain, options, model_manager)
+            }
             DatabaseType::Postgres(postgres) => {
-                postgres.search(model_name, fields, domain, model_manager)
+                postgres.search(model_name, fields, domain, options, model_manager)
+            }
+            DatabaseType::Sqlite(sqlite) => {
+                sqlite.search(model_name, fields, domain, options, model_manager)
             }
         }
     }
 
-    fn create(&mut self, model_name: &str, data: &Vec<&MapOfFields>) -> Result<Vec<u32>> {
+    fn count(
+        &mut self,
+        model_name: &str,
+        domain: &SearchType,
+        model_manager: &ModelManager,
+    ) -> Result<u64> {
+        match self {
+            DatabaseType::Cache(cache) => cache.count(model_name, domain, model_manager),
+            DatabaseType::Postgres(postgres) => postgres.count(model_name, domain, model_manager),
+            DatabaseType::Sqlite(sqlite) => sqlite.count(model_name, domain, model_manager),
+        }
+    }
+
+    fn exists(
+        &mut self,
+        model_name: &str,
+        domain: &SearchType,
+        model_manager: &ModelManager,
+    ) -> Result<bool> {
+        match self {
+            DatabaseType::Cache(cache) => cache.exists(model_name, domain, model_manager),
+            DatabaseType::Postgres(postgres) => postgres.exists(model_name, domain, model_manager),
+            DatabaseType::Sqlite(sqlite) => sqlite.exists(model_name, domain, model_manager),
+        }
+    }
+
+    fn read_group(
+        &mut self,
+        model_name: &str,
+        domain: &SearchType,
+        group_by: &[&str],
+        aggregates: &[Aggregate],
+        model_manager: &ModelManager,
+    ) -> Result<Vec<Group>> {
+        match self {
+            DatabaseType::Cache(cache) => {
+                cache.read_group(model_name, domain, group_by, aggregates, model_manager)
+            }
+            DatabaseType::Postgres(postgres) => {
+                postgres.read_group(model_name, domain, group_by, aggregates, model_manager)
+            }
+            DatabaseType::Sqlite(sqlite) => {
+                sqlite.read_group(model_name, domain, group_by, aggregates, model_manager)
+            }
+        }
+    }
+
+    fn create(&mut self, model_name: &str, data: &[&MapOfFields]) -> Result<Vec<u64>> {
         match self {
             DatabaseType::Cache(cache) => cache.create(model_name, data),
             DatabaseType::Postgres(postgres) => postgres.create(model_name, data),
+            DatabaseType::Sqlite(sqlite) => sqlite.create(model_name, data),
         }
     }
 
-    fn update(&mut self, model_name: &str, data: &HashMap<u32, &MapOfFields>) -> Result<u32> {
+    fn update(&mut self, model_name: &str, data: &HashMap<u64, &MapOfFields>) -> Result<u64> {
         match self {
             DatabaseType::Cache(cache) => cache.update(model_name, data),
             DatabaseType::Postgres(postgres) => postgres.update(model_name, data),
+            DatabaseType::Sqlite(sqlite) => sqlite.update(model_name, data),
+        }
+    }
+
+    fn delete(&mut self, model_name: &str, ids: &[u64]) -> Result<u64> {
+        match self {
+            DatabaseType::Cache(cache) => cache.delete(model_name, ids),
+            DatabaseType::Postgres(postgres) => postgres.delete(model_name, ids),
+            DatabaseType::Sqlite(sqlite) => sqlite.delete(model_name, ids),
+        }
+    }
+
+    fn read_many2many(
+        &mut self,
+        model_name: &str,
+        field_name: &str,
+        ids: &[u64],
+        model_manager: &ModelManager,
+    ) -> Result<HashMap<u64, Vec<u64>>> {
+        match self {
+            DatabaseType::Cache(cache) => {
+                cache.read_many2many(model_name, field_name, ids, model_manager)
+            }
+            DatabaseType::Postgres(postgres) => {
+                postgres.read_many2many(model_name, field_name, ids, model_manager)
+            }
+            DatabaseType::Sqlite(sqlite) => {
+                sqlite.read_many2many(model_name, field_name, ids, model_manager)
+            }
+        }
+    }
+
+    fn write_many2many(
+        &mut self,
+        model_name: &str,
+        field_name: &str,
+        data: &HashMap<u64, Vec<u64>>,
+        model_manager: &ModelManager,
+    ) -> Result<()> {
+        match self {
+            DatabaseType::Cache(cache) => {
+                cache.write_many2many(model_name, field_name, data, model_manager)
+            }
+            DatabaseType::Postgres(postgres) => {
+                postgres.write_many2many(model_name, field_name, data, model_manager)
+            }
+            DatabaseType::Sqlite(sqlite) => {
+                sqlite.write_many2many(model_name, field_name, data, model_manager)
+            }
         }
     }
 
@@ -74,6 +215,27 @@ impl<'db> Database for DatabaseType<'db> {
         match self {
             DatabaseType::Cache(cache) => cache.get_installed_plugins(),
             DatabaseType::Postgres(postgres) => postgres.get_installed_plugins(),
+            DatabaseType::Sqlite(sqlite) => sqlite.get_installed_plugins(),
+        }
+    }
+
+    fn get_installed_plugin_version(&mut self, plugin_name: &str) -> Result<Option<String>> {
+        match self {
+            DatabaseType::Cache(cache) => cache.get_installed_plugin_version(plugin_name),
+            DatabaseType::Postgres(postgres) => postgres.get_installed_plugin_version(plugin_name),
+            DatabaseType::Sqlite(sqlite) => sqlite.get_installed_plugin_version(plugin_name),
+        }
+    }
+
+    fn set_installed_plugin_version(&mut self, plugin_name: &str, version: &str) -> Result<()> {
+        match self {
+            DatabaseType::Cache(cache) => cache.set_installed_plugin_version(plugin_name, version),
+            DatabaseType::Postgres(postgres) => {
+                postgres.set_installed_plugin_version(plugin_name, version)
+            }
+            DatabaseType::Sqlite(sqlite) => {
+                sqlite.set_installed_plugin_version(plugin_name, version)
+            }
         }
     }
 
@@ -81,6 +243,7 @@ impl<'db> Database for DatabaseType<'db> {
         match self {
             DatabaseType::Cache(cache) => cache.savepoint(name),
             DatabaseType::Postgres(postgres) => postgres.savepoint(name),
+            DatabaseType::Sqlite(sqlite) => sqlite.savepoint(name),
         }
     }
 
@@ -88,6 +251,7 @@ impl<'db> Database for DatabaseType<'db> {
         match self {
             DatabaseType::Cache(cache) => cache.savepoint_commit(name),
             DatabaseType::Postgres(postgres) => postgres.savepoint_commit(name),
+            DatabaseType::Sqlite(sqlite) => sqlite.savepoint_commit(name),
         }
     }
 
@@ -95,6 +259,7 @@ impl<'db> Database for DatabaseType<'db> {
         match self {
             DatabaseType::Cache(cache) => cache.savepoint_rollback(name),
             DatabaseType::Postgres(postgres) => postgres.savepoint_rollback(name),
+            DatabaseType::Sqlite(sqlite) => sqlite.savepoint_rollback(name),
         }
     }
 
@@ -102,6 +267,7 @@ impl<'db> Database for DatabaseType<'db> {
         match self {
             DatabaseType::Cache(cache) => cache.start_transaction(),
             DatabaseType::Postgres(postgres) => postgres.start_transaction(),
+            DatabaseType::Sqlite(sqlite) => sqlite.start_transaction(),
         }
     }
 
@@ -109,6 +275,7 @@ impl<'db> Database for DatabaseType<'db> {
         match self {
             DatabaseType::Cache(cache) => cache.commit_transaction(),
             DatabaseType::Postgres(postgres) => postgres.commit_transaction(),
+            DatabaseType::Sqlite(sqlite) => sqlite.commit_transaction(),
         }
     }
 
@@ -116,6 +283,7 @@ impl<'db> Database for DatabaseType<'db> {
         match self {
             DatabaseType::Cache(cache) => cache.rollback_transaction(),
             DatabaseType::Postgres(postgres) => postgres.rollback_transaction(),
+            DatabaseType::Sqlite(sqlite) => sqlite.rollback_transaction(),
         }
     }
 }
@@ -0,0 +1,242 @@
+use crate::database::AggregateFunction;
+use erp_search::{RightTuple, SearchOperator};
+use std::{error, fmt};
+
+#[derive(Debug, Clone)]
+pub struct UnknownFieldError {
+    pub model_name: String,
+    pub field_name: String,
+}
+
+impl fmt::Display for UnknownFieldError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Field \"{}\" is not present in model \"{}\"",
+            self.field_name, self.model_name
+        )
+    }
+}
+
+impl error::Error for UnknownFieldError {}
+
+#[derive(Debug, Clone)]
+pub struct InvalidPathError {
+    pub model_name: String,
+    pub field_name: String,
+}
+
+impl fmt::Display for InvalidPathError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Field \"{}\".\"{}\" is used as an intermediate path, but is not a M2O nor a O2M",
+            self.model_name, self.field_name
+        )
+    }
+}
+
+impl error::Error for InvalidPathError {}
+
+#[derive(Debug, Clone)]
+pub struct InvalidOrderFieldError {
+    pub model_name: String,
+    pub field_name: String,
+}
+
+impl fmt::Display for InvalidOrderFieldError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Field \"{}\".\"{}\" cannot be used to sort records, as it's not stored",
+            self.model_name, self.field_name
+        )
+    }
+}
+
+impl error::Error for InvalidOrderFieldError {}
+
+#[derive(Debug, Clone)]
+pub struct NotHierarchicalFieldError {
+    pub model_name: String,
+    pub field_name: String,
+}
+
+impl fmt::Display for NotHierarchicalFieldError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Field \"{}\".\"{}\" is not a M2O to its own model, so it cannot be used with \"child_of\" or \"parent_of\"",
+            self.model_name, self.field_name
+        )
+    }
+}
+
+impl error::Error for NotHierarchicalFieldError {}
+
+#[derive(Debug, Clone)]
+pub struct NotMany2manyFieldError {
+    pub model_name: String,
+    pub field_name: String,
+}
+
+impl fmt::Display for NotMany2manyFieldError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Field \"{}\".\"{}\" is not a M2M, so it doesn't have any relation table",
+            self.model_name, self.field_name
+        )
+    }
+}
+
+impl error::Error for NotMany2manyFieldError {}
+
+#[derive(Debug, Clone)]
+pub struct InvalidAggregateError {
+    pub model_name: String,
+    pub field_name: String,
+    pub function: AggregateFunction,
+}
+
+impl fmt::Display for InvalidAggregateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Cannot compute the {} of field \"{}\".\"{}\", as its type doesn't allow it",
+            self.function, self.model_name, self.field_name
+        )
+    }
+}
+
+impl error::Error for InvalidAggregateError {}
+
+#[derive(Debug, Clone)]
+pub struct InvalidGroupFieldError {
+    pub model_name: String,
+    pub field_name: String,
+}
+
+impl fmt::Display for InvalidGroupFieldError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Field \"{}\".\"{}\" cannot be used to group or aggregate records, as it's not stored",
+            self.model_name, self.field_name
+        )
+    }
+}
+
+impl error::Error for InvalidGroupFieldError {}
+
+#[derive(Debug, Clone)]
+pub struct InvalidSearchValueError {
+    pub model_name: String,
+    pub field_name: String,
+    pub operator: SearchOperator,
+    pub value: RightTuple,
+}
+
+impl fmt::Display for InvalidSearchValueError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Value {:?} cannot be compared with field \"{}\".\"{}\" using operator \"{}\", as its type is not compatible",
+            self.value, self.model_name, self.field_name, self.operator
+        )
+    }
+}
+
+impl error::Error for InvalidSearchValueError {}
+
+#[derive(Debug, Clone)]
+pub struct InvalidValueError {
+    pub value: String,
+}
+
+impl fmt::Display for InvalidValueError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Value \"{}\" cannot be converted to a database value",
+            self.value
+        )
+    }
+}
+
+impl error::Error for InvalidValueError {}
+
+#[derive(Debug, Clone)]
+pub struct IdOutOfRangeError {
+    pub id: String,
+}
+
+impl fmt::Display for IdOutOfRangeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Id {} is out of range, as ids are stored as positive 64-bit signed integers",
+            self.id
+        )
+    }
+}
+
+impl error::Error for IdOutOfRangeError {}
+
+#[derive(Debug, Clone)]
+pub struct NotNullViolationError {
+    pub model_name: String,
+    pub field_name: String,
+}
+
+impl fmt::Display for NotNullViolationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Field \"{}\".\"{}\" is required, but no value is given",
+            self.model_name, self.field_name
+        )
+    }
+}
+
+impl error::Error for NotNullViolationError {}
+
+#[derive(Debug, Clone)]
+pub struct ForeignKeyViolationError {
+    pub model_name: String,
+    pub field_name: String,
+    pub target_model: String,
+    pub target_id: u64,
+}
+
+impl fmt::Display for ForeignKeyViolationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Field \"{}\".\"{}\" is targeting record \"{}\" of model \"{}\", but this record doesn't exist",
+            self.model_name, self.field_name, self.target_id, self.target_model
+        )
+    }
+}
+
+impl error::Error for ForeignKeyViolationError {}
+
+#[derive(Debug, Clone)]
+pub struct RestrictViolationError {
+    pub model_name: String,
+    pub field_name: String,
+    pub target_model: String,
+    pub target_id: u64,
+}
+
+impl fmt::Display for RestrictViolationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Record \"{}\" of model \"{}\" cannot be deleted, as field \"{}\".\"{}\" is still targeting it",
+            self.target_id, self.target_model, self.model_name, self.field_name
+        )
+    }
+}
+
+impl error::Error for RestrictViolationError {}
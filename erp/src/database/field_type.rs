@@ -1,5 +1,10 @@
+use crate::database::InvalidValueError;
+use chrono::{DateTime, NaiveDate, Utc};
 use erp_search::RightTuple;
 use erp_types::field as field_type;
+use erp_types::field::{Json, content_hash};
+use rust_decimal::Decimal;
+use std::cmp::Ordering;
 use std::fmt::{Display, Formatter};
 
 #[macro_export]
@@ -21,9 +26,15 @@ macro_rules! database_field_type_make_eq {
 pub enum FieldType {
     String(String),
     Integer(i32),
-    UInteger(u32),
+    UInteger(u64),
+    BigInteger(i64),
     Float(f32),
+    Double(f64),
     Boolean(bool),
+    Decimal(Decimal),
+    Date(NaiveDate),
+    DateTime(DateTime<Utc>),
+    Json(Json),
 }
 
 impl Display for FieldType {
@@ -32,8 +43,14 @@ impl Display for FieldType {
             FieldType::String(s) => write!(f, "{}", s),
             FieldType::Integer(i) => write!(f, "{}", i),
             FieldType::UInteger(b) => write!(f, "{}", b),
+            FieldType::BigInteger(i) => write!(f, "{}", i),
             FieldType::Float(fl) => write!(f, "{}", fl),
+            FieldType::Double(d) => write!(f, "{}", d),
             FieldType::Boolean(e) => write!(f, "{}", e),
+            FieldType::Decimal(d) => write!(f, "{}", d),
+            FieldType::Date(d) => write!(f, "{}", d),
+            FieldType::DateTime(d) => write!(f, "{}", d),
+            FieldType::Json(j) => write!(f, "{}", j),
         }
     }
 }
@@ -46,20 +63,70 @@ impl PartialEq for FieldType {
             FieldType::String,
             FieldType::Integer,
             FieldType::UInteger,
+            FieldType::BigInteger,
             FieldType::Float,
-            FieldType::Boolean
+            FieldType::Double,
+            FieldType::Boolean,
+            FieldType::Decimal,
+            FieldType::Date,
+            FieldType::DateTime,
+            FieldType::Json
         )
     }
 }
 
+impl PartialOrd for FieldType {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (self, other) {
+            (FieldType::String(value), FieldType::String(other_value)) => {
+                value.partial_cmp(other_value)
+            }
+            (FieldType::Integer(value), FieldType::Integer(other_value)) => {
+                value.partial_cmp(other_value)
+            }
+            (FieldType::UInteger(value), FieldType::UInteger(other_value)) => {
+                value.partial_cmp(other_value)
+            }
+            (FieldType::BigInteger(value), FieldType::BigInteger(other_value)) => {
+                value.partial_cmp(other_value)
+            }
+            (FieldType::Float(value), FieldType::Float(other_value)) => {
+                value.partial_cmp(other_value)
+            }
+            (FieldType::Double(value), FieldType::Double(other_value)) => {
+                value.partial_cmp(other_value)
+            }
+            (FieldType::Boolean(value), FieldType::Boolean(other_value)) => {
+                value.partial_cmp(other_value)
+            }
+            (FieldType::Decimal(value), FieldType::Decimal(other_value)) => {
+                value.partial_cmp(other_value)
+            }
+            (FieldType::Date(value), FieldType::Date(other_value)) => {
+                value.partial_cmp(other_value)
+            }
+            (FieldType::DateTime(value), FieldType::DateTime(other_value)) => {
+                value.partial_cmp(other_value)
+            }
+            _ => None,
+        }
+    }
+}
+
 impl From<FieldType> for RightTuple {
     fn from(other: FieldType) -> Self {
         match other {
             FieldType::String(value) => RightTuple::String(value),
             FieldType::Integer(value) => RightTuple::Integer(value),
             FieldType::UInteger(value) => RightTuple::UInteger(value),
+            FieldType::BigInteger(value) => RightTuple::BigInteger(value),
             FieldType::Float(value) => RightTuple::Float(value),
+            FieldType::Double(value) => RightTuple::Double(value),
             FieldType::Boolean(value) => RightTuple::Boolean(value),
+            FieldType::Decimal(value) => RightTuple::Decimal(value),
+            FieldType::Date(value) => RightTuple::Date(value),
+            FieldType::DateTime(value) => RightTuple::DateTime(value),
+            FieldType::Json(value) => RightTuple::String(value.to_string()),
         }
     }
 }
@@ -70,9 +137,15 @@ impl PartialEq<RightTuple> for FieldType {
             (FieldType::String(value), RightTuple::String(other_value)) => value == other_value,
             (FieldType::Integer(value), RightTuple::Integer(other_value)) => value == other_value,
             (FieldType::UInteger(value), RightTuple::UInteger(other_value)) => value == other_value,
+            (FieldType::BigInteger(value), RightTuple::BigInteger(other_value)) => {
+                value == other_value
+            }
             (FieldType::Float(value), RightTuple::Float(other_value)) => value == other_value,
+            (FieldType::Double(value), RightTuple::Double(other_value)) => value == other_value,
             (FieldType::Boolean(value), RightTuple::Boolean(other_value)) => value == other_value,
-            (value, RightTuple::Array(other_value)) => other_value.contains(&value.clone().into()),
+            (FieldType::Decimal(value), RightTuple::Decimal(other_value)) => value == other_value,
+            (FieldType::Date(value), RightTuple::Date(other_value)) => value == other_value,
+            (FieldType::DateTime(value), RightTuple::DateTime(other_value)) => value == other_value,
             _ => false,
         }
     }
@@ -90,8 +163,16 @@ impl From<field_type::FieldType> for FieldType {
         match value {
             field_type::FieldType::String(v) => FieldType::String(v),
             field_type::FieldType::Integer(v) => FieldType::Integer(v),
+            field_type::FieldType::BigInteger(v) => FieldType::BigInteger(v),
             field_type::FieldType::Float(v) => FieldType::Float(v),
+            field_type::FieldType::Double(v) => FieldType::Double(v),
             field_type::FieldType::Bool(v) => FieldType::Boolean(v),
+            field_type::FieldType::Decimal(v) => FieldType::Decimal(v),
+            field_type::FieldType::Date(v) => FieldType::Date(v),
+            field_type::FieldType::DateTime(v) => FieldType::DateTime(v),
+            field_type::FieldType::Json(v) => FieldType::Json(v),
+            // The content is saved in the filestore, only its key is stored
+            field_type::FieldType::Binary(v) => FieldType::String(content_hash(&v)),
             field_type::FieldType::Ref(v) => FieldType::UInteger(v),
             // This should not occur
             field_type::FieldType::Refs(_v) => {
@@ -107,8 +188,38 @@ impl From<FieldType> for field_type::FieldType {
             FieldType::String(v) => field_type::FieldType::String(v),
             FieldType::Integer(v) => field_type::FieldType::Integer(v),
             FieldType::UInteger(v) => field_type::FieldType::Ref(v),
+            FieldType::BigInteger(v) => field_type::FieldType::BigInteger(v),
             FieldType::Float(v) => field_type::FieldType::Float(v),
+            FieldType::Double(v) => field_type::FieldType::Double(v),
             FieldType::Boolean(v) => field_type::FieldType::Bool(v),
+            FieldType::Decimal(v) => field_type::FieldType::Decimal(v),
+            FieldType::Date(v) => field_type::FieldType::Date(v),
+            FieldType::DateTime(v) => field_type::FieldType::DateTime(v),
+            FieldType::Json(v) => field_type::FieldType::Json(v),
         }
     }
 }
+
+impl TryFrom<&RightTuple> for FieldType {
+    type Error = InvalidValueError;
+
+    fn try_from(value: &RightTuple) -> Result<Self, Self::Error> {
+        Ok(match value {
+            RightTuple::String(v) => FieldType::String(v.clone()),
+            RightTuple::Integer(v) => FieldType::Integer(*v),
+            RightTuple::UInteger(v) => FieldType::UInteger(*v),
+            RightTuple::BigInteger(v) => FieldType::BigInteger(*v),
+            RightTuple::Float(v) => FieldType::Float(*v),
+            RightTuple::Double(v) => FieldType::Double(*v),
+            RightTuple::Boolean(v) => FieldType::Boolean(*v),
+            RightTuple::Decimal(v) => FieldType::Decimal(*v),
+            RightTuple::Date(v) => FieldType::Date(*v),
+            RightTuple::DateTime(v) => FieldType::DateTime(*v),
+            RightTuple::Array(_) | RightTuple::Domain(_) | RightTuple::None => {
+                return Err(InvalidValueError {
+                    value: format!("{value:?}"),
+                });
+            }
+        })
+    }
+}
@@ -0,0 +1,87 @@
+use crate::database::FieldType;
+use erp_search::RightTuple;
+use erp_types::field::Json;
+use rust_decimal::Decimal;
+
+/// Type of the values a JSON value is compared with.
+///
+/// JSON values of another type never match the comparison
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JsonType {
+    String,
+    Number,
+    Boolean,
+    /// Only compared with empty values
+    Any,
+}
+
+impl From<&RightTuple> for JsonType {
+    fn from(value: &RightTuple) -> Self {
+        match value {
+            RightTuple::String(_) => JsonType::String,
+            RightTuple::Integer(_)
+            | RightTuple::UInteger(_)
+            | RightTuple::BigInteger(_)
+            | RightTuple::Float(_)
+            | RightTuple::Double(_)
+            | RightTuple::Decimal(_) => JsonType::Number,
+            RightTuple::Boolean(_) => JsonType::Boolean,
+            RightTuple::Array(values) => values
+                .iter()
+                .map(JsonType::from)
+                .find(|json_type| *json_type != JsonType::Any)
+                .unwrap_or(JsonType::Any),
+            _ => JsonType::Any,
+        }
+    }
+}
+
+/// Value at given key path of given JSON value.
+///
+/// Keys of arrays are their indexes. Return None if one of the keys doesn't exist
+pub fn get_json_value<'a>(value: &'a Json, keys: &[String]) -> Option<&'a Json> {
+    keys.iter().try_fold(value, |value, key| match value {
+        Json::Object(map) => map.get(key),
+        Json::Array(values) => key.parse::<usize>().ok().and_then(|idx| values.get(idx)),
+        _ => None,
+    })
+}
+
+/// Convert a value found inside a JSON value, so that it can be compared with a domain value.
+///
+/// Numbers are converted to decimals (see `decimal_right`), and null is an empty value
+pub fn json_to_field_type(value: &Json) -> Option<FieldType> {
+    match value {
+        Json::Null => None,
+        Json::Bool(value) => Some(FieldType::Boolean(*value)),
+        Json::String(value) => Some(FieldType::String(value.clone())),
+        Json::Number(number) => {
+            let number = number.to_string();
+            Decimal::from_str_exact(&number)
+                .or_else(|_| Decimal::from_scientific(&number))
+                .map(FieldType::Decimal)
+                .ok()
+                .or_else(|| Some(FieldType::Json(value.clone())))
+        }
+        value => Some(FieldType::Json(value.clone())),
+    }
+}
+
+/// Convert numbers of given domain value to decimals.
+///
+/// JSON numbers don't have any type, so they are compared as decimals with any type of number
+pub fn decimal_right(right: &RightTuple) -> RightTuple {
+    match right {
+        RightTuple::Integer(value) => RightTuple::Decimal((*value).into()),
+        RightTuple::UInteger(value) => RightTuple::Decimal((*value).into()),
+        RightTuple::BigInteger(value) => RightTuple::Decimal((*value).into()),
+        RightTuple::Float(value) => Decimal::try_from(*value)
+            .map(RightTuple::Decimal)
+            .unwrap_or_else(|_| right.clone()),
+        RightTuple::Double(value) => Decimal::try_from(*value)
+            .map(RightTuple::Decimal)
+            .unwrap_or_else(|_| right.clone()),
+        RightTuple::Array(values) => RightTuple::Array(values.iter().map(decimal_right).collect()),
+        right => right.clone(),
+    }
+}
@@ -1,11 +1,22 @@
 pub mod cache;
 mod config;
+#[allow(clippy::module_inception)]
 mod database;
 mod database_type;
+mod errors;
 mod field_type;
+mod json;
 pub mod postgres;
+mod read_group;
+pub mod sql;
+pub mod sqlite;
+mod validate;
 
 pub use config::*;
 pub use database::*;
 pub use database_type::*;
+pub use errors::*;
 pub use field_type::*;
+pub use json::*;
+pub use read_group::*;
+pub use validate::*;
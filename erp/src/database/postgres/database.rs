@@ -1,9 +1,23 @@
-use crate::database::{Database, DatabaseConfig, ErrorType, FieldType};
-use crate::model::ModelManager;
-use erp_search::SearchType;
+use crate::database::sql::{
+    Dialect, QueryBuilder, SqlQuery, column_definition, delete_queries, foreign_key_action,
+    foreign_key_definition, foreign_key_name, get_many2many_relation, insert_queries, is_not_null,
+    many2many_relation, quote, relation_select_queries, relation_table_definition,
+    relation_write_queries, update_queries,
+};
+use crate::database::{
+    Aggregate, Database, DatabaseConfig, ErrorType, FieldType, Group, IdOutOfRangeError,
+    UnknownFieldError, get_read_group_types,
+};
+use crate::model::{ModelManager, ModelNotFound};
+use chrono::{NaiveDate, NaiveDateTime};
+use erp_search::{SearchOptions, SearchType};
+use erp_types::field;
+use erp_types::field::Json;
 use erp_types::model::MapOfFields;
-use postgres::{Client, NoTls};
-use std::collections::HashMap;
+use postgres::types::ToSql;
+use postgres::{Client, NoTls, Row};
+use rust_decimal::Decimal;
+use std::collections::{HashMap, HashSet};
 use std::error::Error;
 
 type Result<T> = std::result::Result<T, Box<dyn Error>>;
@@ -33,6 +47,130 @@ impl PostgresDatabase {
             is_transaction: false,
         })
     }
+
+    /// Execute given query, and return the resulting rows
+    fn query(&mut self, query: &SqlQuery) -> Result<Vec<Row>> {
+        let params: Vec<Box<dyn ToSql + Sync>> = query
+            .params
+            .iter()
+            .map(to_sql_param)
+            .collect::<Result<_>>()?;
+        let params: Vec<&(dyn ToSql + Sync)> = params.iter().map(|param| param.as_ref()).collect();
+        Ok(self.client.query(&query.sql, &params)?)
+    }
+
+    /// Retrieves the name of all columns of given table.
+    ///
+    /// If the table doesn't exist, return an empty set
+    fn get_columns(&mut self, table_name: &str) -> Result<HashSet<String>> {
+        let mut result = HashSet::new();
+        for row in self.client.query(
+            "SELECT \"column_name\" FROM \"information_schema\".\"columns\" WHERE \"table_schema\"=$1 AND \"table_name\"=$2",
+            &[&self.schema, &table_name],
+        )? {
+            let name: &str = row.get(0);
+            result.insert(name.to_string());
+        }
+        Ok(result)
+    }
+
+    /// Retrieves the action executed on delete by given FOREIGN KEY constraint.
+    ///
+    /// If the constraint doesn't exist, return None
+    fn get_foreign_key_action(&mut self, constraint_name: &str) -> Result<Option<String>> {
+        let result = self.client.query_opt(
+            "SELECT \"delete_rule\" FROM \"information_schema\".\"referential_constraints\" WHERE \"constraint_schema\"=$1 AND \"constraint_name\"=$2",
+            &[&self.schema, &constraint_name],
+        )?;
+        Ok(match result {
+            Some(row) => Some(row.try_get(0)?),
+            None => None,
+        })
+    }
+
+    /// Execute given query, and return the number of modified rows
+    fn execute(&mut self, query: &SqlQuery) -> Result<u64> {
+        let params: Vec<Box<dyn ToSql + Sync>> = query
+            .params
+            .iter()
+            .map(to_sql_param)
+            .collect::<Result<_>>()?;
+        let params: Vec<&(dyn ToSql + Sync)> = params.iter().map(|param| param.as_ref()).collect();
+        Ok(self.client.execute(&query.sql, &params)?)
+    }
+}
+
+/// Convert given id to the BIGINT it is stored in.
+///
+/// Ids that don't fit are refused, instead of being wrapped around to a negative id
+fn id_to_sql(id: u64) -> Result<i64> {
+    i64::try_from(id).map_err(|_| IdOutOfRangeError { id: id.to_string() }.into())
+}
+
+/// Read the id stored as a BIGINT in the column at given index.
+///
+/// Negative ids are refused, instead of being wrapped around to a huge id
+fn id_from_sql(row: &Row, idx: usize) -> Result<u64> {
+    let id = row.try_get::<_, i64>(idx)?;
+    u64::try_from(id).map_err(|_| IdOutOfRangeError { id: id.to_string() }.into())
+}
+
+/// Convert given value to a value that can be sent to Postgres.
+///
+/// Ids are stored as BIGINT, so UInteger are sent as i64. Dates and times are stored in UTC in
+/// a TIMESTAMP, without any time zone
+fn to_sql_param(value: &FieldType) -> Result<Box<dyn ToSql + Sync>> {
+    Ok(match value {
+        FieldType::String(v) => Box::new(v.clone()),
+        FieldType::Integer(v) => Box::new(*v),
+        FieldType::UInteger(v) => Box::new(id_to_sql(*v)?),
+        FieldType::BigInteger(v) => Box::new(*v),
+        FieldType::Float(v) => Box::new(*v),
+        FieldType::Double(v) => Box::new(*v),
+        FieldType::Boolean(v) => Box::new(*v),
+        FieldType::Decimal(v) => Box::new(*v),
+        FieldType::Date(v) => Box::new(*v),
+        FieldType::DateTime(v) => Box::new(v.naive_utc()),
+        FieldType::Json(v) => Box::new(v.clone()),
+    })
+}
+
+/// Read the column at given index, and convert it based on the type of the field
+fn from_sql_column(
+    row: &Row,
+    idx: usize,
+    field_type: &field::FieldType,
+) -> Result<Option<FieldType>> {
+    Ok(match field_type {
+        // Binary fields store the key of their content in the filestore
+        field::FieldType::String(_) | field::FieldType::Binary(_) => row
+            .try_get::<_, Option<String>>(idx)?
+            .map(FieldType::String),
+        field::FieldType::Integer(_) => row.try_get::<_, Option<i32>>(idx)?.map(FieldType::Integer),
+        field::FieldType::BigInteger(_) => row
+            .try_get::<_, Option<i64>>(idx)?
+            .map(FieldType::BigInteger),
+        field::FieldType::Float(_) => row.try_get::<_, Option<f32>>(idx)?.map(FieldType::Float),
+        field::FieldType::Double(_) => row.try_get::<_, Option<f64>>(idx)?.map(FieldType::Double),
+        field::FieldType::Bool(_) => row.try_get::<_, Option<bool>>(idx)?.map(FieldType::Boolean),
+        field::FieldType::Decimal(_) => row
+            .try_get::<_, Option<Decimal>>(idx)?
+            .map(FieldType::Decimal),
+        field::FieldType::Date(_) => row
+            .try_get::<_, Option<NaiveDate>>(idx)?
+            .map(FieldType::Date),
+        field::FieldType::DateTime(_) => row
+            .try_get::<_, Option<NaiveDateTime>>(idx)?
+            .map(|value| FieldType::DateTime(value.and_utc())),
+        field::FieldType::Json(_) => row.try_get::<_, Option<Json>>(idx)?.map(FieldType::Json),
+        field::FieldType::Ref(_) => match row.try_get::<_, Option<i64>>(idx)? {
+            Some(_) => Some(FieldType::UInteger(id_from_sql(row, idx)?)),
+            None => None,
+        },
+        field::FieldType::Refs(_) => {
+            return Err("Refs fields are not stored in the database".into());
+        }
+    })
 }
 
 impl Database for PostgresDatabase {
@@ -53,45 +191,323 @@ impl Database for PostgresDatabase {
         self.client.batch_execute(
             "
             CREATE TABLE plugin (
-                id              SERIAL PRIMARY KEY,
+                id              BIGSERIAL PRIMARY KEY,
                 name            VARCHAR NOT NULL,
                 description     TEXT,
                 website         TEXT,
                 url             TEXT,
-                state           VARCHAR NOT NULL
+                state           VARCHAR NOT NULL,
+                version         VARCHAR
             )
             ",
         )?;
         Ok(())
     }
 
+    /// Add the version of plugins, which was not saved by older versions
+    fn upgrade(&mut self) -> Result<()> {
+        if !self.get_columns("plugin")?.contains("version") {
+            self.client.batch_execute(
+                "ALTER TABLE \"plugin\" ADD COLUMN IF NOT EXISTS \"version\" VARCHAR",
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Create missing tables and columns of given models
+    ///
+    /// Columns added to an existing table are only NOT NULL if the table is empty, as existing
+    /// records don't have any value for them
+    fn sync_schema(&mut self, model_names: &[&str], model_manager: &ModelManager) -> Result<()> {
+        let mut models = Vec::with_capacity(model_names.len());
+        for model_name in model_names {
+            models.push(model_manager.get_models().get(*model_name).ok_or_else(|| {
+                ModelNotFound {
+                    model_name: model_name.to_string(),
+                }
+            })?);
+        }
+
+        // First, create tables and columns
+        for model in &models {
+            let table = quote(&model.name);
+            let existing_columns = self.get_columns(&model.name)?;
+            let mut fields: Vec<_> = model.fields.values().collect();
+            fields.sort_by(|a, b| a.name.cmp(&b.name));
+            if existing_columns.is_empty() {
+                let mut definitions = vec!["\"id\" BIGSERIAL PRIMARY KEY".to_string()];
+                definitions.extend(
+                    fields
+                        .iter()
+                        .filter_map(|field| {
+                            column_definition(Dialect::Postgres, field, is_not_null(field))
+                        }),
+                );
+                self.client.batch_execute(&format!(
+                    "CREATE TABLE {table} ({})",
+                    definitions.join(", ")
+                ))?;
+                continue;
+            }
+            let is_empty = !self
+                .client
+                .query_one(&format!("SELECT EXISTS (SELECT FROM {table})"), &[])?
+                .try_get::<_, bool>(0)?;
+            for field in fields {
+                if existing_columns.contains(&field.name) {
+                    continue;
+                }
+                if let Some(definition) =
+                    column_definition(Dialect::Postgres, field, is_empty && is_not_null(field))
+                {
+                    self.client
+                        .batch_execute(&format!("ALTER TABLE {table} ADD COLUMN {definition}"))?;
+                }
+            }
+        }
+
+        // Then, link M2O once all tables exist
+        for model in &models {
+            for field in model.fields.values() {
+                let Some(foreign_key) = foreign_key_definition(field) else {
+                    continue;
+                };
+                let table = quote(&model.name);
+                let constraint_name = foreign_key_name(&model.name, &field.name);
+                let action = foreign_key_action(field.get_ondelete().unwrap_or_default());
+                match self.get_foreign_key_action(&constraint_name)? {
+                    Some(existing_action) if existing_action == action => continue,
+                    // The ondelete policy has changed, the constraint needs to be created again
+                    Some(_) => self.client.batch_execute(&format!(
+                        "ALTER TABLE {table} DROP CONSTRAINT {}",
+                        quote(&constraint_name)
+                    ))?,
+                    None => {}
+                }
+                self.client.batch_execute(&format!(
+                    "ALTER TABLE {table} ADD CONSTRAINT {} FOREIGN KEY ({}) {foreign_key}",
+                    quote(&constraint_name),
+                    quote(&field.name),
+                ))?;
+            }
+        }
+
+        // Finally, create relation tables of M2M once both linked tables exist
+        for model in &models {
+            for field in model.fields.values() {
+                let Some(relation) = many2many_relation(&model.name, field) else {
+                    continue;
+                };
+                if !self.get_columns(relation.target_model)?.is_empty() {
+                    self.client
+                        .batch_execute(&relation_table_definition(&model.name, &relation))?;
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// Make a search request to a specific model, and only return ids that match this search request
     fn browse(
         &mut self,
-        _model_name: &str,
-        _domain: &SearchType,
-        _model_manager: &ModelManager,
-    ) -> Result<Vec<u32>> {
-        todo!()
+        model_name: &str,
+        domain: &SearchType,
+        options: &SearchOptions,
+        model_manager: &ModelManager,
+    ) -> Result<Vec<u64>> {
+        let query = QueryBuilder::new(model_manager, Dialect::Postgres).select(
+            model_name,
+            &["id"],
+            domain,
+            options,
+        )?;
+        self.query(&query)?
+            .iter()
+            .map(|row| id_from_sql(row, 0))
+            .collect()
     }
 
     /// Make a search request to a specific model, and return ids and fields that match this search request
+    #[allow(clippy::type_complexity)]
     fn search<'a>(
         &mut self,
-        _model_name: &str,
-        _fields: &[&'a str],
-        _domain: &SearchType,
-        _model_manager: &ModelManager,
-    ) -> Result<Vec<(u32, HashMap<&'a str, Option<FieldType>>)>> {
-        todo!()
+        model_name: &str,
+        fields: &[&'a str],
+        domain: &SearchType,
+        options: &SearchOptions,
+        model_manager: &ModelManager,
+    ) -> Result<Vec<(u64, HashMap<&'a str, Option<FieldType>>)>> {
+        let model = model_manager
+            .get_models()
+            .get(model_name)
+            .ok_or_else(|| ModelNotFound {
+                model_name: model_name.to_string(),
+            })?;
+        // Type of each field, used to convert the value returned by the database
+        let field_types = fields
+            .iter()
+            .map(|field_name| {
+                if *field_name == "id" {
+                    return Ok(field::FieldType::Ref(0));
+                }
+                model
+                    .fields
+                    .get(*field_name)
+                    .map(|field| field.default_value.clone())
+                    .ok_or_else(|| UnknownFieldError {
+                        model_name: model_name.to_string(),
+                        field_name: field_name.to_string(),
+                    })
+            })
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        // First column is always the id
+        let mut columns = Vec::with_capacity(fields.len() + 1);
+        columns.push("id");
+        columns.extend_from_slice(fields);
+        let query = QueryBuilder::new(model_manager, Dialect::Postgres)
+            .select(model_name, &columns, domain, options)?;
+
+        let mut result = vec![];
+        for row in self.query(&query)? {
+            let id = id_from_sql(&row, 0)?;
+            let mut fields_result = HashMap::with_capacity(fields.len());
+            for (idx, (field_name, field_type)) in fields.iter().zip(&field_types).enumerate() {
+                fields_result.insert(*field_name, from_sql_column(&row, idx + 1, field_type)?);
+            }
+            result.push((id, fields_result));
+        }
+        Ok(result)
+    }
+
+    fn count(
+        &mut self,
+        model_name: &str,
+        domain: &SearchType,
+        model_manager: &ModelManager,
+    ) -> Result<u64> {
+        let query =
+            QueryBuilder::new(model_manager, Dialect::Postgres).count(model_name, domain)?;
+        let rows = self.query(&query)?;
+        Ok(u64::try_from(rows[0].try_get::<_, i64>(0)?)?)
+    }
+
+    fn exists(
+        &mut self,
+        model_name: &str,
+        domain: &SearchType,
+        model_manager: &ModelManager,
+    ) -> Result<bool> {
+        let query =
+            QueryBuilder::new(model_manager, Dialect::Postgres).exists(model_name, domain)?;
+        let rows = self.query(&query)?;
+        Ok(rows[0].try_get::<_, bool>(0)?)
+    }
+
+    fn read_group(
+        &mut self,
+        model_name: &str,
+        domain: &SearchType,
+        group_by: &[&str],
+        aggregates: &[Aggregate],
+        model_manager: &ModelManager,
+    ) -> Result<Vec<Group>> {
+        let column_types = get_read_group_types(model_name, group_by, aggregates, model_manager)?;
+        let query = QueryBuilder::new(model_manager, Dialect::Postgres)
+            .read_group(model_name, domain, group_by, aggregates)?;
+        let mut result = vec![];
+        for row in self.query(&query)? {
+            let mut values = column_types
+                .iter()
+                .enumerate()
+                .map(|(idx, column_type)| from_sql_column(&row, idx, column_type))
+                .collect::<Result<Vec<_>>>()?;
+            let aggregates = values.split_off(group_by.len());
+            result.push(Group {
+                keys: values,
+                aggregates,
+            });
+        }
+        Ok(result)
+    }
+
+    fn create(&mut self, model_name: &str, data: &[&MapOfFields]) -> Result<Vec<u64>> {
+        if data.is_empty() {
+            return Ok(vec![]);
+        }
+        // Postgres doesn't guarantee the order of rows returned by an INSERT, so ids are reserved
+        //  first, and records are inserted with them
+        let ids = self
+            .query(&SqlQuery {
+                sql: format!(
+                    "SELECT nextval(pg_get_serial_sequence('{}', 'id')) FROM generate_series(1, CAST($1 AS BIGINT))",
+                    quote(model_name)
+                ),
+                params: vec![FieldType::BigInteger(data.len() as i64)],
+            })?
+            .iter()
+            .map(|row| id_from_sql(row, 0))
+            .collect::<Result<Vec<_>>>()?;
+        for (indexes, query) in insert_queries(Dialect::Postgres, model_name, data, Some(&ids)) {
+            let number_of_creations = self.execute(&query)?;
+            if number_of_creations != indexes.len() as u64 {
+                return Err(format!(
+                    "{} records should have been created in {model_name}, but {number_of_creations} were created",
+                    indexes.len(),
+                )
+                .into());
+            }
+        }
+        Ok(ids)
     }
 
-    fn create(&mut self, _model_name: &str, _data: &Vec<&MapOfFields>) -> Result<Vec<u32>> {
-        todo!()
+    fn update(&mut self, model_name: &str, data: &HashMap<u64, &MapOfFields>) -> Result<u64> {
+        let mut number_of_updates = 0;
+        for query in update_queries(Dialect::Postgres, model_name, data) {
+            number_of_updates += self.execute(&query)?;
+        }
+        Ok(number_of_updates)
+    }
+
+    fn delete(&mut self, model_name: &str, ids: &[u64]) -> Result<u64> {
+        let mut number_of_deletions = 0;
+        for query in delete_queries(Dialect::Postgres, model_name, ids) {
+            number_of_deletions += self.execute(&query)?;
+        }
+        Ok(number_of_deletions)
+    }
+
+    fn read_many2many(
+        &mut self,
+        model_name: &str,
+        field_name: &str,
+        ids: &[u64],
+        model_manager: &ModelManager,
+    ) -> Result<HashMap<u64, Vec<u64>>> {
+        let relation = get_many2many_relation(model_name, field_name, model_manager)?;
+        let mut result: HashMap<u64, Vec<u64>> = HashMap::new();
+        for query in relation_select_queries(Dialect::Postgres, &relation, ids) {
+            for row in self.query(&query)? {
+                let id = id_from_sql(&row, 0)?;
+                let target_id = id_from_sql(&row, 1)?;
+                result.entry(id).or_default().push(target_id);
+            }
+        }
+        Ok(result)
     }
 
-    fn update(&mut self, _model_name: &str, _data: &HashMap<u32, &MapOfFields>) -> Result<u32> {
-        todo!()
+    fn write_many2many(
+        &mut self,
+        model_name: &str,
+        field_name: &str,
+        data: &HashMap<u64, Vec<u64>>,
+        model_manager: &ModelManager,
+    ) -> Result<()> {
+        let relation = get_many2many_relation(model_name, field_name, model_manager)?;
+        for query in relation_write_queries(Dialect::Postgres, &relation, data) {
+            self.execute(&query)?;
+        }
+        Ok(())
     }
 
     fn get_installed_plugins(&mut self) -> Result<Vec<String>> {
@@ -106,6 +522,29 @@ impl Database for PostgresDatabase {
         Ok(result)
     }
 
+    fn get_installed_plugin_version(&mut self, plugin_name: &str) -> Result<Option<String>> {
+        let row = self.client.query_opt(
+            "SELECT \"version\" FROM \"plugin\" WHERE \"name\"=$1 AND \"state\"=\'installed\'",
+            &[&plugin_name],
+        )?;
+        // An installed plugin without any version has been installed before versions were saved
+        Ok(row.map(|row| row.get::<_, Option<String>>(0).unwrap_or("0".to_string())))
+    }
+
+    fn set_installed_plugin_version(&mut self, plugin_name: &str, version: &str) -> Result<()> {
+        let updated = self.client.execute(
+            "UPDATE \"plugin\" SET \"version\"=$2, \"state\"=\'installed\' WHERE \"name\"=$1",
+            &[&plugin_name, &version],
+        )?;
+        if updated == 0 {
+            self.client.execute(
+                "INSERT INTO \"plugin\" (\"name\", \"state\", \"version\") VALUES ($1, \'installed\', $2)",
+                &[&plugin_name, &version],
+            )?;
+        }
+        Ok(())
+    }
+
     fn savepoint(&mut self, name: &str) -> Result<()> {
         Ok(self.client.batch_execute(&format!("SAVEPOINT {name}"))?)
     }
@@ -115,9 +554,7 @@ impl Database for PostgresDatabase {
     }
 
     fn savepoint_rollback(&mut self, name: &str) -> Result<()> {
-        Ok(self
-            .client
-            .batch_execute(&format!("ROLLBACK TO {name}"))?)
+        Ok(self.client.batch_execute(&format!("ROLLBACK TO {name}"))?)
     }
 
     fn start_transaction(&mut self) -> Result<()> {
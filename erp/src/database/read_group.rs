@@ -0,0 +1,203 @@
+use crate::database::{
+    FieldType, InvalidAggregateError, InvalidGroupFieldError, InvalidPathError, UnknownFieldError,
+};
+use crate::model::{ModelManager, ModelNotFound};
+use erp_types::field::{self, FieldReference, FieldReferenceType};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AggregateFunction {
+    Count,
+    Sum,
+    Avg,
+    Min,
+    Max,
+}
+
+impl Display for AggregateFunction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AggregateFunction::Count => write!(f, "count"),
+            AggregateFunction::Sum => write!(f, "sum"),
+            AggregateFunction::Avg => write!(f, "avg"),
+            AggregateFunction::Min => write!(f, "min"),
+            AggregateFunction::Max => write!(f, "max"),
+        }
+    }
+}
+
+/// Aggregate computed on a field for each group of a `read_group`.
+///
+/// Path can go through M2O fields ("order.total_price")
+#[derive(Debug, Clone, PartialEq)]
+pub struct Aggregate {
+    pub path: Vec<String>,
+    pub function: AggregateFunction,
+}
+
+impl Aggregate {
+    pub fn new(path: &str, function: AggregateFunction) -> Self {
+        Self {
+            path: path.split('.').map(|str| str.to_string()).collect(),
+            function,
+        }
+    }
+
+    /// Number of records in the group
+    pub fn count() -> Self {
+        Self::new("id", AggregateFunction::Count)
+    }
+
+    pub fn sum(path: &str) -> Self {
+        Self::new(path, AggregateFunction::Sum)
+    }
+
+    pub fn avg(path: &str) -> Self {
+        Self::new(path, AggregateFunction::Avg)
+    }
+
+    pub fn min(path: &str) -> Self {
+        Self::new(path, AggregateFunction::Min)
+    }
+
+    pub fn max(path: &str) -> Self {
+        Self::new(path, AggregateFunction::Max)
+    }
+
+    /// Type of the value returned by this aggregate.
+    ///
+    /// Count always returns an integer, sum returns a big integer for integer fields, average
+    /// returns a decimal for decimal fields and a float otherwise, and other aggregates return the
+    /// type of the aggregated field
+    pub fn get_result_type(
+        &self,
+        model_name: &str,
+        model_manager: &ModelManager,
+    ) -> Result<field::FieldType> {
+        let field_type = get_path_type(model_name, &self.path, model_manager)?;
+        let result = match (self.function, &field_type) {
+            (AggregateFunction::Count, _) => Some(field::FieldType::Integer(0)),
+            // Sums of integers easily exceed the range of an i32
+            (
+                AggregateFunction::Sum,
+                field::FieldType::Integer(_) | field::FieldType::BigInteger(_),
+            ) => Some(field::FieldType::BigInteger(0)),
+            (
+                AggregateFunction::Sum,
+                field::FieldType::Float(_)
+                | field::FieldType::Double(_)
+                | field::FieldType::Decimal(_),
+            ) => Some(field_type.clone()),
+            (AggregateFunction::Avg, field::FieldType::Decimal(_)) => Some(field_type.clone()),
+            (
+                AggregateFunction::Avg,
+                field::FieldType::BigInteger(_) | field::FieldType::Double(_),
+            ) => Some(field::FieldType::Double(0.0)),
+            (AggregateFunction::Avg, field::FieldType::Integer(_) | field::FieldType::Float(_)) => {
+                Some(field::FieldType::Float(0.0))
+            }
+            (
+                AggregateFunction::Min | AggregateFunction::Max,
+                field::FieldType::Integer(_)
+                | field::FieldType::BigInteger(_)
+                | field::FieldType::Float(_)
+                | field::FieldType::Double(_)
+                | field::FieldType::String(_)
+                | field::FieldType::Decimal(_)
+                | field::FieldType::Date(_)
+                | field::FieldType::DateTime(_)
+                | field::FieldType::Ref(_),
+            ) => Some(field_type.clone()),
+            _ => None,
+        };
+        result.ok_or_else(|| {
+            InvalidAggregateError {
+                model_name: model_name.to_string(),
+                field_name: self.path.join("."),
+                function: self.function,
+            }
+            .into()
+        })
+    }
+}
+
+/// Result of a single group of a `read_group`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Group {
+    /// Value of each grouped field, in the same order as requested
+    pub keys: Vec<Option<FieldType>>,
+    /// Value of each aggregate, in the same order as requested
+    pub aggregates: Vec<Option<FieldType>>,
+}
+
+/// Type of the field at the end of given path, following M2O fields.
+///
+/// Only stored fields can be used
+pub fn get_path_type(
+    model_name: &str,
+    path: &[String],
+    model_manager: &ModelManager,
+) -> Result<field::FieldType> {
+    let model = model_manager
+        .get_models()
+        .get(model_name)
+        .ok_or_else(|| ModelNotFound {
+            model_name: model_name.to_string(),
+        })?;
+    let (field_name, rest) = path.split_first().ok_or_else(|| UnknownFieldError {
+        model_name: model_name.to_string(),
+        field_name: "".to_string(),
+    })?;
+    if field_name == "id" && rest.is_empty() {
+        return Ok(field::FieldType::Ref(0));
+    }
+    let field = model
+        .fields
+        .get(field_name)
+        .ok_or_else(|| UnknownFieldError {
+            model_name: model_name.to_string(),
+            field_name: field_name.clone(),
+        })?;
+    if !field.is_stored() {
+        return Err(InvalidGroupFieldError {
+            model_name: model_name.to_string(),
+            field_name: field_name.clone(),
+        }
+        .into());
+    }
+    if rest.is_empty() {
+        return Ok(field.default_value.clone());
+    }
+    match &field.inverse {
+        Some(FieldReference {
+            target_model,
+            inverse_field: FieldReferenceType::M2O { .. },
+        }) => get_path_type(target_model, rest, model_manager),
+        _ => Err(InvalidPathError {
+            model_name: model_name.to_string(),
+            field_name: field_name.clone(),
+        }
+        .into()),
+    }
+}
+
+/// Type of each column returned by a `read_group`: grouped fields first, then aggregates
+pub fn get_read_group_types(
+    model_name: &str,
+    group_by: &[&str],
+    aggregates: &[Aggregate],
+    model_manager: &ModelManager,
+) -> Result<Vec<field::FieldType>> {
+    let mut result = Vec::with_capacity(group_by.len() + aggregates.len());
+    for path in group_by {
+        let path: Vec<String> = path.split('.').map(|str| str.to_string()).collect();
+        result.push(get_path_type(model_name, &path, model_manager)?);
+    }
+    for aggregate in aggregates {
+        result.push(aggregate.get_result_type(model_name, model_manager)?);
+    }
+    Ok(result)
+}
@@ -0,0 +1,138 @@
+use crate::database::JsonType;
+
+/// SQL dialect used to build requests
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dialect {
+    Postgres,
+    Sqlite,
+}
+
+impl Dialect {
+    /// Placeholder of the parameter at given index (starting at 1)
+    pub fn placeholder(&self, index: usize) -> String {
+        match self {
+            Dialect::Postgres => format!("${index}"),
+            Dialect::Sqlite => format!("?{index}"),
+        }
+    }
+
+    /// Maximum number of parameters in a single request
+    pub fn max_params(&self) -> usize {
+        match self {
+            Dialect::Postgres => 65535,
+            Dialect::Sqlite => 32766,
+        }
+    }
+
+    /// LIMIT and OFFSET clauses of a request, with a leading space.
+    ///
+    /// Empty if there is no limit nor offset
+    pub fn limit_offset(&self, limit: Option<usize>, offset: usize) -> String {
+        match (self, limit, offset) {
+            (_, None, 0) => String::new(),
+            (_, Some(limit), 0) => format!(" LIMIT {limit}"),
+            (_, Some(limit), offset) => format!(" LIMIT {limit} OFFSET {offset}"),
+            (Dialect::Postgres, None, offset) => format!(" OFFSET {offset}"),
+            // SQLite doesn't allow an OFFSET without a LIMIT, and a negative LIMIT means no limit
+            (Dialect::Sqlite, None, offset) => format!(" LIMIT -1 OFFSET {offset}"),
+        }
+    }
+
+    /// Condition matching given column against the LIKE pattern at given placeholder.
+    ///
+    /// SQLite's LIKE is case-insensitive, so GLOB is used for case-sensitive matches: the pattern
+    /// should then be converted with `like_pattern`
+    pub fn like(&self, column: &str, placeholder: &str, case_sensitive: bool) -> String {
+        match (self, case_sensitive) {
+            (Dialect::Postgres, true) => format!("{column} LIKE {placeholder} ESCAPE '\\'"),
+            (Dialect::Postgres, false) => format!("{column} ILIKE {placeholder} ESCAPE '\\'"),
+            (Dialect::Sqlite, true) => format!("{column} GLOB {placeholder}"),
+            (Dialect::Sqlite, false) => format!("{column} LIKE {placeholder} ESCAPE '\\'"),
+        }
+    }
+
+    /// Value at given key path of the JSON value stored in given column, to compare it with
+    /// values of given type.
+    ///
+    /// JSON values of another type are NULL, so that they never match the comparison. Numbers are
+    /// compared as NUMERIC in Postgres
+    pub fn json_value(&self, column: &str, keys: &[String], json_type: JsonType) -> String {
+        match self {
+            Dialect::Postgres => {
+                let path = keys
+                    .iter()
+                    .map(|key| quote_literal(key))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let path = format!("ARRAY[{path}]::TEXT[]");
+                let value = format!("({column} #>> {path})");
+                let (name, cast) = match json_type {
+                    JsonType::String => ("string", ""),
+                    JsonType::Number => ("number", "::NUMERIC"),
+                    JsonType::Boolean => ("boolean", "::BOOLEAN"),
+                    JsonType::Any => return value,
+                };
+                format!(
+                    "(CASE WHEN jsonb_typeof({column} #> {path}) = '{name}' THEN {value}{cast} END)"
+                )
+            }
+            Dialect::Sqlite => {
+                let path: String = keys
+                    .iter()
+                    .map(|key| {
+                        if !key.is_empty() && key.chars().all(|char| char.is_ascii_digit()) {
+                            format!("[{key}]")
+                        } else {
+                            // Keys are written as JSON strings, escaping their quotes and
+                            // backslashes
+                            format!(".{}", serde_json::Value::from(key.as_str()))
+                        }
+                    })
+                    .collect();
+                let path = quote_literal(&format!("${path}"));
+                let value = format!("json_extract({column}, {path})");
+                let types = match json_type {
+                    JsonType::String => "'text'",
+                    JsonType::Number => "'integer', 'real'",
+                    JsonType::Boolean => "'true', 'false'",
+                    JsonType::Any => return value,
+                };
+                format!("(CASE WHEN json_type({column}, {path}) IN ({types}) THEN {value} END)")
+            }
+        }
+    }
+
+    /// Convert given LIKE pattern (where `\` escapes the next character) into the pattern
+    /// expected by `like`
+    pub fn like_pattern(&self, pattern: &str, case_sensitive: bool) -> String {
+        if !case_sensitive || *self == Dialect::Postgres {
+            return pattern.to_string();
+        }
+        let mut result = String::with_capacity(pattern.len());
+        let mut chars = pattern.chars();
+        while let Some(char) = chars.next() {
+            match char {
+                '%' => result.push('*'),
+                '_' => result.push('?'),
+                char => {
+                    let char = if char == '\\' {
+                        chars.next().unwrap_or('\\')
+                    } else {
+                        char
+                    };
+                    if matches!(char, '*' | '?' | '[') {
+                        result.push_str(&format!("[{char}]"));
+                    } else {
+                        result.push(char);
+                    }
+                }
+            }
+        }
+        result
+    }
+}
+
+/// Quote given string as an SQL literal
+fn quote_literal(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
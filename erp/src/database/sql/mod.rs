@@ -0,0 +1,9 @@
+mod dialect;
+mod query_builder;
+mod schema;
+mod write_query;
+
+pub use dialect::*;
+pub use query_builder::*;
+pub use schema::*;
+pub use write_query::*;
@@ -0,0 +1,745 @@
+use crate::database::sql::{Dialect, foreign_key_target, many2many_relation};
+use crate::database::{
+    Aggregate, AggregateFunction, FieldType, InvalidOrderFieldError, InvalidPathError, JsonType,
+    NotHierarchicalFieldError, UnknownFieldError, decimal_right,
+};
+use crate::model::{ModelManager, ModelNotFound};
+use erp_internal_types::{FinalInternalField, FinalInternalModel};
+use erp_search::{
+    LeftTuple, OrderBy, OrderDirection, RightTuple, SearchOperator, SearchOptions, SearchTuple,
+    SearchType,
+};
+use erp_types::field::{self, FieldReference, FieldReferenceType};
+use rust_decimal::prelude::ToPrimitive;
+use std::error::Error;
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+/// Parameterized SQL request, ready to be sent to the database
+#[derive(Debug)]
+pub struct SqlQuery {
+    pub sql: String,
+    pub params: Vec<FieldType>,
+}
+
+/// Translate a domain into a parameterized SQL request.
+///
+/// Paths going through a M2O are resolved with a LEFT JOIN on the target table, and paths going
+/// through a O2M or a M2M are resolved with a sub-request on the target table (joined with the
+/// relation table of a M2M). "any" and "not any" are
+/// resolved with an EXISTS sub-request. Order paths can only go through M2O fields.
+///
+/// Values are never written in the request, they are all passed as parameters
+pub struct QueryBuilder<'mm> {
+    model_manager: &'mm ModelManager,
+    dialect: Dialect,
+    params: Vec<FieldType>,
+}
+
+/// Tables joined for a single SELECT
+#[derive(Default)]
+struct Scope {
+    joins: Vec<(String, String)>,
+}
+
+impl Scope {
+    fn add_join(&mut self, alias: &str, join: String) {
+        if !self.joins.iter().any(|(a, _)| a == alias) {
+            self.joins.push((alias.to_string(), join));
+        }
+    }
+
+    fn to_sql(&self) -> String {
+        self.joins
+            .iter()
+            .map(|(_, join)| format!(" {join}"))
+            .collect()
+    }
+}
+
+/// Quote given identifier
+pub fn quote(identifier: &str) -> String {
+    format!("\"{}\"", identifier.replace('"', "\"\""))
+}
+
+fn column(alias: &str, field_name: &str) -> String {
+    format!("{}.{}", quote(alias), quote(field_name))
+}
+
+impl<'mm> QueryBuilder<'mm> {
+    pub fn new(model_manager: &'mm ModelManager, dialect: Dialect) -> Self {
+        Self {
+            model_manager,
+            dialect,
+            params: Vec::new(),
+        }
+    }
+
+    /// Build a SELECT request retrieving given fields of records of given model that match given
+    /// domain, sorted, limited and offset following given options
+    pub fn select(
+        mut self,
+        model_name: &str,
+        fields: &[&str],
+        domain: &SearchType,
+        options: &SearchOptions,
+    ) -> Result<SqlQuery> {
+        let mut scope = Scope::default();
+        let condition =
+            self.compile_domain(model_name, model_name, &domain.normalize(), &mut scope)?;
+        let order = self
+            .get_model(model_name)?
+            .get_order(options)
+            .iter()
+            .map(|order| self.compile_order(model_name, model_name, order, &mut scope))
+            .collect::<Result<Vec<_>>>()?;
+        let order = if order.is_empty() {
+            "".to_string()
+        } else {
+            format!(" ORDER BY {}", order.join(", "))
+        };
+        let columns = fields
+            .iter()
+            .map(|field_name| column(model_name, field_name))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let sql = format!(
+            "SELECT {columns} FROM {}{} WHERE {condition}{order}{}",
+            quote(model_name),
+            scope.to_sql(),
+            self.dialect.limit_offset(options.limit, options.offset),
+        );
+        Ok(SqlQuery {
+            sql,
+            params: self.params,
+        })
+    }
+
+    /// Build a SELECT request counting records of given model that match given domain
+    pub fn count(mut self, model_name: &str, domain: &SearchType) -> Result<SqlQuery> {
+        let mut scope = Scope::default();
+        let condition =
+            self.compile_domain(model_name, model_name, &domain.normalize(), &mut scope)?;
+        let sql = format!(
+            "SELECT COUNT(*) FROM {}{} WHERE {condition}",
+            quote(model_name),
+            scope.to_sql(),
+        );
+        Ok(SqlQuery {
+            sql,
+            params: self.params,
+        })
+    }
+
+    /// Build a SELECT request checking if at least one record of given model matches given domain
+    pub fn exists(mut self, model_name: &str, domain: &SearchType) -> Result<SqlQuery> {
+        let mut scope = Scope::default();
+        let condition =
+            self.compile_domain(model_name, model_name, &domain.normalize(), &mut scope)?;
+        let sql = format!(
+            "SELECT EXISTS (SELECT 1 FROM {}{} WHERE {condition})",
+            quote(model_name),
+            scope.to_sql(),
+        );
+        Ok(SqlQuery {
+            sql,
+            params: self.params,
+        })
+    }
+
+    /// Build a SELECT request grouping records of given model that match given domain by given
+    /// fields, and computing given aggregates on each group.
+    ///
+    /// Groups are sorted by their keys. Without any grouped field, a single group is returned
+    pub fn read_group(
+        mut self,
+        model_name: &str,
+        domain: &SearchType,
+        group_by: &[&str],
+        aggregates: &[Aggregate],
+    ) -> Result<SqlQuery> {
+        let mut scope = Scope::default();
+        let condition =
+            self.compile_domain(model_name, model_name, &domain.normalize(), &mut scope)?;
+        let keys = group_by
+            .iter()
+            .map(|path| {
+                let path: Vec<String> = path.split('.').map(|str| str.to_string()).collect();
+                self.compile_order_path(model_name, model_name, &path, &mut scope)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let mut columns = keys.clone();
+        for aggregate in aggregates {
+            let result_type = aggregate.get_result_type(model_name, self.model_manager)?;
+            let column =
+                self.compile_order_path(model_name, model_name, &aggregate.path, &mut scope)?;
+            columns.push(match (aggregate.function, result_type) {
+                (AggregateFunction::Count, _) => format!("CAST(COUNT({column}) AS INTEGER)"),
+                (AggregateFunction::Sum, field::FieldType::Float(_)) => {
+                    format!("CAST(SUM({column}) AS REAL)")
+                }
+                (AggregateFunction::Sum, field::FieldType::Double(_)) => {
+                    format!("CAST(SUM({column}) AS DOUBLE PRECISION)")
+                }
+                (AggregateFunction::Sum, field::FieldType::Decimal(_)) => {
+                    format!("CAST(SUM({column}) AS NUMERIC)")
+                }
+                (AggregateFunction::Sum, _) => format!("CAST(SUM({column}) AS BIGINT)"),
+                (AggregateFunction::Avg, field::FieldType::Decimal(_)) => {
+                    format!("CAST(AVG({column}) AS NUMERIC)")
+                }
+                (AggregateFunction::Avg, field::FieldType::Double(_)) => {
+                    format!("CAST(AVG({column}) AS DOUBLE PRECISION)")
+                }
+                (AggregateFunction::Avg, _) => format!("CAST(AVG({column}) AS REAL)"),
+                (AggregateFunction::Min, _) => format!("MIN({column})"),
+                (AggregateFunction::Max, _) => format!("MAX({column})"),
+            });
+        }
+
+        let mut sql = format!(
+            "SELECT {} FROM {}{} WHERE {condition}",
+            columns.join(", "),
+            quote(model_name),
+            scope.to_sql(),
+        );
+        if !keys.is_empty() {
+            let order = keys
+                .iter()
+                .map(|key| format!("{key} ASC NULLS LAST"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            sql.push_str(&format!(" GROUP BY {} ORDER BY {order}", keys.join(", ")));
+        }
+        Ok(SqlQuery {
+            sql,
+            params: self.params,
+        })
+    }
+
+    fn get_model(&self, model_name: &str) -> Result<&'mm FinalInternalModel> {
+        self.model_manager
+            .get_models()
+            .get(model_name)
+            .ok_or_else(|| {
+                ModelNotFound {
+                    model_name: model_name.to_string(),
+                }
+                .into()
+            })
+    }
+
+    fn compile_domain(
+        &mut self,
+        model_name: &str,
+        alias: &str,
+        domain: &SearchType,
+        scope: &mut Scope,
+    ) -> Result<String> {
+        Ok(match domain {
+            SearchType::And(left, right) => {
+                let left = self.compile_domain(model_name, alias, left, scope)?;
+                let right = self.compile_domain(model_name, alias, right, scope)?;
+                format!("({left} AND {right})")
+            }
+            SearchType::Or(left, right) => {
+                let left = self.compile_domain(model_name, alias, left, scope)?;
+                let right = self.compile_domain(model_name, alias, right, scope)?;
+                format!("({left} OR {right})")
+            }
+            SearchType::Not(search_type) => {
+                let condition = self.compile_domain(model_name, alias, search_type, scope)?;
+                // Records where the condition is NULL don't match it, so they should match here,
+                // as the cache database does
+                format!("NOT COALESCE({condition}, FALSE)")
+            }
+            SearchType::Tuple(SearchTuple {
+                left: LeftTuple { path },
+                operator,
+                right,
+            }) => self.compile_path(model_name, alias, path, operator, right, scope)?,
+            // Same behavior as the cache database: an empty domain does not match anything
+            SearchType::Nothing => "FALSE".to_string(),
+        })
+    }
+
+    fn compile_path(
+        &mut self,
+        model_name: &str,
+        alias: &str,
+        path: &[String],
+        operator: &SearchOperator,
+        right: &RightTuple,
+        scope: &mut Scope,
+    ) -> Result<String> {
+        let model = self.get_model(model_name)?;
+        let (field_name, rest) = path.split_first().ok_or_else(|| UnknownFieldError {
+            model_name: model_name.to_string(),
+            field_name: "".to_string(),
+        })?;
+        if rest.is_empty() && matches!(operator, SearchOperator::ChildOf | SearchOperator::ParentOf)
+        {
+            return self.compile_hierarchy(model_name, alias, field_name, operator, right);
+        }
+        if rest.is_empty() && matches!(operator, SearchOperator::Any | SearchOperator::NotAny) {
+            return self.compile_any(model_name, alias, field_name, operator, right);
+        }
+        if field_name == "id" && rest.is_empty() {
+            return self.compile_condition(&column(alias, "id"), operator, right);
+        }
+        let field = model
+            .fields
+            .get(field_name)
+            .ok_or_else(|| UnknownFieldError {
+                model_name: model_name.to_string(),
+                field_name: field_name.clone(),
+            })?;
+        if field.is_json() && !rest.is_empty() {
+            // The rest of the path are keys of the JSON value
+            let right = json_right(self.dialect, right);
+            let value =
+                self.dialect
+                    .json_value(&column(alias, field_name), rest, JsonType::from(&right));
+            return self.compile_condition(&value, operator, &right);
+        }
+
+        match &field.inverse {
+            Some(FieldReference {
+                target_model,
+                inverse_field: FieldReferenceType::O2M { .. } | FieldReferenceType::M2M { .. },
+            }) => {
+                let sub_alias = format!("{alias}__{field_name}");
+                let mut sub_scope = Scope::default();
+                let link_column = self.link_x2many(model_name, field, &sub_alias, &mut sub_scope);
+                // "lines = None" means "no line", and "lines != None" means "at least one line"
+                let (negate, condition) = match (rest.is_empty(), operator, right) {
+                    (true, SearchOperator::Equal, RightTuple::None) => {
+                        (true, format!("{link_column} IS NOT NULL"))
+                    }
+                    (true, SearchOperator::NotEqual, RightTuple::None) => {
+                        (false, format!("{link_column} IS NOT NULL"))
+                    }
+                    (true, _, _) => (
+                        false,
+                        self.compile_condition(&column(&sub_alias, "id"), operator, right)?,
+                    ),
+                    (false, _, _) => (
+                        false,
+                        self.compile_path(
+                            target_model,
+                            &sub_alias,
+                            rest,
+                            operator,
+                            right,
+                            &mut sub_scope,
+                        )?,
+                    ),
+                };
+                Ok(format!(
+                    "{} {}IN (SELECT {link_column} FROM {} AS {}{} WHERE {condition})",
+                    column(alias, "id"),
+                    if negate { "NOT " } else { "" },
+                    quote(target_model),
+                    quote(&sub_alias),
+                    sub_scope.to_sql(),
+                ))
+            }
+            _ if rest.is_empty() => {
+                self.compile_condition(&column(alias, field_name), operator, right)
+            }
+            Some(FieldReference {
+                target_model,
+                inverse_field: FieldReferenceType::M2O { .. },
+            }) => {
+                let join_alias = format!("{alias}__{field_name}");
+                scope.add_join(
+                    &join_alias,
+                    format!(
+                        "LEFT JOIN {} AS {} ON {} = {}",
+                        quote(target_model),
+                        quote(&join_alias),
+                        column(&join_alias, "id"),
+                        column(alias, field_name),
+                    ),
+                );
+                let condition =
+                    self.compile_path(target_model, &join_alias, rest, operator, right, scope)?;
+                // Records without any linked record should not match, whatever the condition is
+                Ok(format!(
+                    "({} IS NOT NULL AND {condition})",
+                    column(&join_alias, "id")
+                ))
+            }
+            None => Err(InvalidPathError {
+                model_name: model_name.to_string(),
+                field_name: field_name.clone(),
+            }
+            .into()),
+        }
+    }
+
+    /// Compile a single element of an ORDER BY clause.
+    ///
+    /// Empty values are sorted last in ascending order, and first in descending order, as the
+    /// cache database does
+    fn compile_order(
+        &mut self,
+        model_name: &str,
+        alias: &str,
+        order: &OrderBy,
+        scope: &mut Scope,
+    ) -> Result<String> {
+        let column = self.compile_order_path(model_name, alias, &order.path, scope)?;
+        Ok(match order.direction {
+            OrderDirection::Asc => format!("{column} ASC NULLS LAST"),
+            OrderDirection::Desc => format!("{column} DESC NULLS FIRST"),
+        })
+    }
+
+    /// Return the column to sort on for given path, joining M2O target tables if needed
+    fn compile_order_path(
+        &mut self,
+        model_name: &str,
+        alias: &str,
+        path: &[String],
+        scope: &mut Scope,
+    ) -> Result<String> {
+        let model = self.get_model(model_name)?;
+        let (field_name, rest) = path.split_first().ok_or_else(|| UnknownFieldError {
+            model_name: model_name.to_string(),
+            field_name: "".to_string(),
+        })?;
+        if field_name == "id" && rest.is_empty() {
+            return Ok(column(alias, "id"));
+        }
+        let field = model
+            .fields
+            .get(field_name)
+            .ok_or_else(|| UnknownFieldError {
+                model_name: model_name.to_string(),
+                field_name: field_name.clone(),
+            })?;
+
+        if !field.is_stored() {
+            return Err(InvalidOrderFieldError {
+                model_name: model_name.to_string(),
+                field_name: field_name.clone(),
+            }
+            .into());
+        }
+
+        match &field.inverse {
+            _ if rest.is_empty() => Ok(column(alias, field_name)),
+            Some(FieldReference {
+                target_model,
+                inverse_field: FieldReferenceType::M2O { .. },
+            }) => {
+                let join_alias = format!("{alias}__{field_name}");
+                scope.add_join(
+                    &join_alias,
+                    format!(
+                        "LEFT JOIN {} AS {} ON {} = {}",
+                        quote(target_model),
+                        quote(&join_alias),
+                        column(&join_alias, "id"),
+                        column(alias, field_name),
+                    ),
+                );
+                self.compile_order_path(target_model, &join_alias, rest, scope)
+            }
+            // O2M fields are not stored, so they are already rejected
+            _ => Err(InvalidPathError {
+                model_name: model_name.to_string(),
+                field_name: field_name.clone(),
+            }
+            .into()),
+        }
+    }
+
+    /// Compile a single condition on given column.
+    ///
+    /// This should follow the behavior of the cache database (see `Row::is_valid`)
+    fn compile_condition(
+        &mut self,
+        column: &str,
+        operator: &SearchOperator,
+        right: &RightTuple,
+    ) -> Result<String> {
+        Ok(match (operator, right) {
+            (SearchOperator::In, right) => match self.push_array(right.values())? {
+                Some(placeholders) => format!("{column} IN ({placeholders})"),
+                None => "FALSE".to_string(),
+            },
+            (SearchOperator::NotIn, right) => match self.push_array(right.values())? {
+                Some(placeholders) => {
+                    format!("({column} IS NULL OR {column} NOT IN ({placeholders}))")
+                }
+                None => "TRUE".to_string(),
+            },
+            (SearchOperator::Equal, RightTuple::None) => format!("{column} IS NULL"),
+            (SearchOperator::NotEqual, RightTuple::None) => format!("{column} IS NOT NULL"),
+            (_, RightTuple::None) => "FALSE".to_string(),
+            // A single value is never equal to an array, "in" and "not in" should be used instead
+            (SearchOperator::NotEqual, RightTuple::Array(_)) => "TRUE".to_string(),
+            (_, RightTuple::Array(_)) => "FALSE".to_string(),
+            (SearchOperator::Like, RightTuple::String(value)) => {
+                self.compile_like(column, &format!("%{}%", escape_like(value)), true)?
+            }
+            (SearchOperator::ILike, RightTuple::String(value)) => {
+                self.compile_like(column, &format!("%{}%", escape_like(value)), false)?
+            }
+            (SearchOperator::EqualLike, RightTuple::String(pattern)) => {
+                self.compile_like(column, pattern, true)?
+            }
+            (SearchOperator::Like | SearchOperator::ILike | SearchOperator::EqualLike, _) => {
+                "FALSE".to_string()
+            }
+            // Those operators need the model of the column, so they are handled in compile_path
+            (
+                SearchOperator::ChildOf
+                | SearchOperator::ParentOf
+                | SearchOperator::Any
+                | SearchOperator::NotAny,
+                _,
+            ) => "FALSE".to_string(),
+            (SearchOperator::Equal, value) => format!("{column} = {}", self.push_param(value)?),
+            (SearchOperator::NotEqual, value) => {
+                let placeholder = self.push_param(value)?;
+                format!("({column} IS NULL OR {column} <> {placeholder})")
+            }
+            (SearchOperator::Greater, value) => format!("{column} > {}", self.push_param(value)?),
+            (SearchOperator::GreaterEqual, value) => {
+                format!("{column} >= {}", self.push_param(value)?)
+            }
+            (SearchOperator::Lower, value) => format!("{column} < {}", self.push_param(value)?),
+            (SearchOperator::LowerEqual, value) => {
+                format!("{column} <= {}", self.push_param(value)?)
+            }
+        })
+    }
+
+    /// Compile a LIKE condition on given column, with given LIKE pattern
+    fn compile_like(
+        &mut self,
+        column: &str,
+        pattern: &str,
+        case_sensitive: bool,
+    ) -> Result<String> {
+        let pattern = self.dialect.like_pattern(pattern, case_sensitive);
+        let placeholder = self.push_param(&RightTuple::String(pattern))?;
+        Ok(self.dialect.like(column, &placeholder, case_sensitive))
+    }
+
+    /// Compile a "child_of" or "parent_of" condition on given M2O field targeting its own model.
+    ///
+    /// The whole hierarchy is retrieved with a recursive query
+    fn compile_hierarchy(
+        &mut self,
+        model_name: &str,
+        alias: &str,
+        field_name: &str,
+        operator: &SearchOperator,
+        right: &RightTuple,
+    ) -> Result<String> {
+        let model = self.get_model(model_name)?;
+        if model.fields.get(field_name).and_then(foreign_key_target) != Some(model_name) {
+            return Err(NotHierarchicalFieldError {
+                model_name: model_name.to_string(),
+                field_name: field_name.to_string(),
+            }
+            .into());
+        }
+        let Some(placeholders) = self.push_array(right.values())? else {
+            return Ok("FALSE".to_string());
+        };
+        let hierarchy = format!("{alias}__{field_name}__hierarchy");
+        let recursive_query = if *operator == SearchOperator::ChildOf {
+            format!(
+                "SELECT {} FROM {} JOIN {} ON {} = {}",
+                column(model_name, "id"),
+                quote(model_name),
+                quote(&hierarchy),
+                column(model_name, field_name),
+                column(&hierarchy, "id"),
+            )
+        } else {
+            format!(
+                "SELECT {} FROM {} JOIN {} ON {} = {} WHERE {} IS NOT NULL",
+                column(model_name, field_name),
+                quote(model_name),
+                quote(&hierarchy),
+                column(model_name, "id"),
+                column(&hierarchy, "id"),
+                column(model_name, field_name),
+            )
+        };
+        Ok(format!(
+            "{} IN (WITH RECURSIVE {} (\"id\") AS (SELECT \"id\" FROM {} WHERE \"id\" IN ({placeholders}) UNION {recursive_query}) SELECT \"id\" FROM {})",
+            column(alias, "id"),
+            quote(&hierarchy),
+            quote(model_name),
+            quote(&hierarchy),
+        ))
+    }
+
+    /// Compile a "any" or "not any" condition on given M2O, O2M or M2M field, with a sub-request
+    /// on the target model
+    fn compile_any(
+        &mut self,
+        model_name: &str,
+        alias: &str,
+        field_name: &str,
+        operator: &SearchOperator,
+        right: &RightTuple,
+    ) -> Result<String> {
+        let model = self.get_model(model_name)?;
+        let field = model
+            .fields
+            .get(field_name)
+            .ok_or_else(|| UnknownFieldError {
+                model_name: model_name.to_string(),
+                field_name: field_name.to_string(),
+            })?;
+        let sub_alias = format!("{alias}__{field_name}__any");
+        let mut sub_scope = Scope::default();
+        let (target_model, link) = match &field.inverse {
+            Some(FieldReference {
+                target_model,
+                inverse_field: FieldReferenceType::M2O { .. },
+            }) => (
+                target_model,
+                format!(
+                    "{} = {}",
+                    column(&sub_alias, "id"),
+                    column(alias, field_name)
+                ),
+            ),
+            Some(FieldReference {
+                target_model,
+                inverse_field: FieldReferenceType::O2M { .. } | FieldReferenceType::M2M { .. },
+            }) => (
+                target_model,
+                format!(
+                    "{} = {}",
+                    self.link_x2many(model_name, field, &sub_alias, &mut sub_scope),
+                    column(alias, "id")
+                ),
+            ),
+            None => {
+                return Err(InvalidPathError {
+                    model_name: model_name.to_string(),
+                    field_name: field_name.to_string(),
+                }
+                .into());
+            }
+        };
+        let domain = match right {
+            RightTuple::Domain(domain) => domain.as_ref(),
+            _ => &SearchType::Nothing,
+        };
+        // An empty domain matches all target records
+        let condition = match domain {
+            SearchType::Nothing => "TRUE".to_string(),
+            domain => self.compile_domain(target_model, &sub_alias, domain, &mut sub_scope)?,
+        };
+        Ok(format!(
+            "{}EXISTS (SELECT 1 FROM {} AS {}{} WHERE {link} AND {condition})",
+            if *operator == SearchOperator::NotAny {
+                "NOT "
+            } else {
+                ""
+            },
+            quote(target_model),
+            quote(&sub_alias),
+            sub_scope.to_sql(),
+        ))
+    }
+
+    /// Return the column linking records of the target model (aliased by given alias) to records
+    /// of given O2M or M2M field.
+    ///
+    /// The relation table of a M2M is joined to the target model in given scope
+    fn link_x2many(
+        &self,
+        model_name: &str,
+        field: &FinalInternalField,
+        target_alias: &str,
+        scope: &mut Scope,
+    ) -> String {
+        if let Some(relation) = many2many_relation(model_name, field) {
+            let relation_alias = format!("{target_alias}__rel");
+            scope.add_join(
+                &relation_alias,
+                format!(
+                    "JOIN {} AS {} ON {} = {}",
+                    quote(relation.table),
+                    quote(&relation_alias),
+                    column(&relation_alias, &relation.column2),
+                    column(target_alias, "id"),
+                ),
+            );
+            return column(&relation_alias, &relation.column1);
+        }
+        match &field.inverse {
+            Some(FieldReference {
+                inverse_field: FieldReferenceType::O2M { inverse_field },
+                ..
+            }) => column(target_alias, inverse_field),
+            _ => panic!(
+                "Field {}.{} is not a O2M nor a M2M. This should not be possible here",
+                model_name, field.name
+            ),
+        }
+    }
+
+    /// Add given value as a parameter, and return its placeholder
+    fn push_param(&mut self, value: &RightTuple) -> Result<String> {
+        self.params.push(value.try_into()?);
+        Ok(self.dialect.placeholder(self.params.len()))
+    }
+
+    /// Add all values of given array as parameters, and return their placeholders.
+    ///
+    /// Return None if there is no value to add. "None" values are ignored, as they never match
+    fn push_array(&mut self, values: &[RightTuple]) -> Result<Option<String>> {
+        let placeholders = values
+            .iter()
+            .filter(|value| !matches!(value, RightTuple::None))
+            .map(|value| self.push_param(value))
+            .collect::<Result<Vec<_>>>()?;
+        if placeholders.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(placeholders.join(", ")))
+        }
+    }
+}
+
+/// Escape given value, so that it can be used in a LIKE pattern without matching anything else
+fn escape_like(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+/// Convert given value, so that it can be compared with a value inside a JSON column.
+///
+/// Postgres compares JSON numbers as NUMERIC, while SQLite compares them as numbers, and decimals
+/// are sent to SQLite as texts
+fn json_right(dialect: Dialect, right: &RightTuple) -> RightTuple {
+    match (dialect, decimal_right(right)) {
+        (Dialect::Postgres, right) => right,
+        (Dialect::Sqlite, RightTuple::Decimal(value)) => value
+            .to_f64()
+            .map(RightTuple::Double)
+            .unwrap_or(RightTuple::Decimal(value)),
+        (Dialect::Sqlite, RightTuple::Array(values)) => RightTuple::Array(
+            values
+                .iter()
+                .map(|value| json_right(dialect, value))
+                .collect(),
+        ),
+        (Dialect::Sqlite, right) => right,
+    }
+}
@@ -0,0 +1,162 @@
+use crate::database::sql::{Dialect, quote};
+use crate::database::{NotMany2manyFieldError, UnknownFieldError};
+use crate::model::ModelManager;
+use erp_internal_types::FinalInternalField;
+use erp_types::field::{FieldReference, FieldReferenceType, FieldType, OnDelete};
+use std::error::Error;
+
+/// SQL type of the column storing given type of field.
+///
+/// JSON values are stored as TEXT in SQLite, as any other type would give the column a NUMERIC
+/// affinity. Binary fields only store the key of their content in the filestore. Return None if
+/// this type of field is not stored in the database
+pub fn column_type(dialect: Dialect, field_type: &FieldType) -> Option<&'static str> {
+    match (dialect, field_type) {
+        (_, FieldType::String(_)) => Some("VARCHAR"),
+        (_, FieldType::Integer(_)) => Some("INTEGER"),
+        (_, FieldType::BigInteger(_)) => Some("BIGINT"),
+        (_, FieldType::Float(_)) => Some("REAL"),
+        (_, FieldType::Double(_)) => Some("DOUBLE PRECISION"),
+        (_, FieldType::Bool(_)) => Some("BOOLEAN"),
+        (_, FieldType::Decimal(_)) => Some("NUMERIC"),
+        (_, FieldType::Date(_)) => Some("DATE"),
+        (_, FieldType::DateTime(_)) => Some("TIMESTAMP"),
+        (Dialect::Postgres, FieldType::Json(_)) => Some("JSONB"),
+        (Dialect::Sqlite, FieldType::Json(_)) => Some("TEXT"),
+        (_, FieldType::Binary(_)) => Some("VARCHAR"),
+        (_, FieldType::Ref(_)) => Some("BIGINT"),
+        (_, FieldType::Refs(_)) => None,
+    }
+}
+
+/// Return true if given field should be NOT NULL in the database.
+///
+/// Computed fields are never NOT NULL, as they could be computed after the record is created
+pub fn is_not_null(field: &FinalInternalField) -> bool {
+    field.required && field.compute.is_none()
+}
+
+/// Target model of given field if it's a M2O
+pub fn foreign_key_target(field: &FinalInternalField) -> Option<&str> {
+    match &field.inverse {
+        Some(FieldReference {
+            target_model,
+            inverse_field: FieldReferenceType::M2O { .. },
+        }) => Some(target_model),
+        _ => None,
+    }
+}
+
+/// Action executed by the database on records targeting a deleted record, as named in
+/// information_schema
+pub fn foreign_key_action(ondelete: OnDelete) -> &'static str {
+    match ondelete {
+        OnDelete::Cascade => "CASCADE",
+        OnDelete::SetNull => "SET NULL",
+        OnDelete::Restrict => "RESTRICT",
+    }
+}
+
+/// REFERENCES clause of the FOREIGN KEY of given field, if it's a M2O
+pub fn foreign_key_definition(field: &FinalInternalField) -> Option<String> {
+    let target_model = foreign_key_target(field)?;
+    let action = foreign_key_action(field.get_ondelete().unwrap_or_default());
+    Some(format!(
+        "REFERENCES {} (\"id\") ON DELETE {action}",
+        quote(target_model)
+    ))
+}
+
+/// Definition of the column of given field, as used in CREATE TABLE or ALTER TABLE ADD COLUMN.
+///
+/// Return None if this field is not stored in the database
+pub fn column_definition(
+    dialect: Dialect,
+    field: &FinalInternalField,
+    not_null: bool,
+) -> Option<String> {
+    let column_type = column_type(dialect, &field.default_value)?;
+    let mut definition = format!("{} {column_type}", quote(&field.name));
+    if not_null {
+        definition.push_str(" NOT NULL");
+    }
+    Some(definition)
+}
+
+/// Name of the FOREIGN KEY constraint of given field
+pub fn foreign_key_name(model_name: &str, field_name: &str) -> String {
+    format!("{model_name}_{field_name}_fkey")
+}
+
+/// Relation table of a M2M field, linking records of its model to records of its target model
+pub struct Many2manyRelation<'a> {
+    pub table: &'a str,
+    /// Column containing ids of the model declaring the field
+    pub column1: String,
+    /// Column containing ids of the target model
+    pub column2: String,
+    pub target_model: &'a str,
+}
+
+/// Relation table of given field of given model, if it's a M2M
+pub fn many2many_relation<'a>(
+    model_name: &str,
+    field: &'a FinalInternalField,
+) -> Option<Many2manyRelation<'a>> {
+    match &field.inverse {
+        Some(
+            reference @ FieldReference {
+                target_model,
+                inverse_field: FieldReferenceType::M2M { relation, .. },
+            },
+        ) => {
+            let (column1, column2) = reference.get_relation_columns(model_name);
+            Some(Many2manyRelation {
+                table: relation,
+                column1,
+                column2,
+                target_model,
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Relation table of given M2M field of given model.
+///
+/// Fail if this field doesn't exist, or is not a M2M
+pub fn get_many2many_relation<'mm>(
+    model_name: &str,
+    field_name: &str,
+    model_manager: &'mm ModelManager,
+) -> Result<Many2manyRelation<'mm>, Box<dyn Error>> {
+    let field = model_manager
+        .get_models()
+        .get(model_name)
+        .and_then(|model| model.fields.get(field_name))
+        .ok_or_else(|| UnknownFieldError {
+            model_name: model_name.to_string(),
+            field_name: field_name.to_string(),
+        })?;
+    many2many_relation(model_name, field).ok_or_else(|| {
+        NotMany2manyFieldError {
+            model_name: model_name.to_string(),
+            field_name: field_name.to_string(),
+        }
+        .into()
+    })
+}
+
+/// CREATE TABLE request of the relation table of a M2M declared on given model.
+///
+/// Each link is unique, and is removed when one of its records is deleted
+pub fn relation_table_definition(model_name: &str, relation: &Many2manyRelation) -> String {
+    format!(
+        "CREATE TABLE IF NOT EXISTS {table} ({column1} BIGINT NOT NULL REFERENCES {model} (\"id\") ON DELETE CASCADE, {column2} BIGINT NOT NULL REFERENCES {target} (\"id\") ON DELETE CASCADE, PRIMARY KEY ({column1}, {column2}))",
+        table = quote(relation.table),
+        column1 = quote(&relation.column1),
+        column2 = quote(&relation.column2),
+        model = quote(model_name),
+        target = quote(relation.target_model),
+    )
+}
@@ -0,0 +1,305 @@
+use crate::database::FieldType;
+use crate::database::sql::{Dialect, Many2manyRelation, SqlQuery, quote};
+use erp_types::model::MapOfFields;
+use std::collections::{BTreeMap, HashMap};
+
+/// SQL type used to cast given value.
+///
+/// Values in a VALUES list do not have any type, so we need to cast them to allow the database to
+/// compare or assign them. SQLite stores dates, times and JSON values as texts
+fn cast_type(dialect: Dialect, value: &FieldType) -> &'static str {
+    match (dialect, value) {
+        (_, FieldType::String(_)) => "TEXT",
+        (_, FieldType::Integer(_)) => "INTEGER",
+        (_, FieldType::UInteger(_) | FieldType::BigInteger(_)) => "BIGINT",
+        (_, FieldType::Float(_)) => "REAL",
+        (_, FieldType::Double(_)) => "DOUBLE PRECISION",
+        (_, FieldType::Boolean(_)) => "BOOLEAN",
+        (_, FieldType::Decimal(_)) => "NUMERIC",
+        (Dialect::Postgres, FieldType::Date(_)) => "DATE",
+        (Dialect::Postgres, FieldType::DateTime(_)) => "TIMESTAMP",
+        (Dialect::Sqlite, FieldType::Date(_) | FieldType::DateTime(_)) => "TEXT",
+        (Dialect::Postgres, FieldType::Json(_)) => "JSONB",
+        (Dialect::Sqlite, FieldType::Json(_)) => "TEXT",
+    }
+}
+
+/// Stored columns of given data, sorted by name. "id" is never returned
+fn get_columns(data: &MapOfFields) -> Vec<&str> {
+    let mut columns: Vec<&str> = data
+        .fields
+        .keys()
+        .map(|key| key.as_str())
+        .filter(|key| *key != "id")
+        .collect();
+    columns.sort_unstable();
+    columns
+}
+
+fn get_value(data: &MapOfFields, column: &str) -> Option<FieldType> {
+    data.fields
+        .get(column)
+        .and_then(|value| value.clone().map(|value| value.into()))
+}
+
+/// Build INSERT requests creating one record per given data.
+///
+/// Records with the same set of fields are inserted in the same request. Each request returns
+/// created ids, and is linked to the index of the records it creates in given data.
+///
+/// If ids are given (one per data), records are inserted with them. Otherwise, ids are generated
+/// by the database
+pub fn insert_queries(
+    dialect: Dialect,
+    model_name: &str,
+    data: &[&MapOfFields],
+    ids: Option<&[u64]>,
+) -> Vec<(Vec<usize>, SqlQuery)> {
+    let mut groups: BTreeMap<Vec<&str>, Vec<usize>> = BTreeMap::new();
+    for (idx, d) in data.iter().enumerate() {
+        groups.entry(get_columns(d)).or_default().push(idx);
+    }
+
+    let table = quote(model_name);
+    let mut result = Vec::new();
+    for (columns, indexes) in groups {
+        if columns.is_empty() && ids.is_none() {
+            // Not a single field, only default values can be inserted
+            for idx in indexes {
+                result.push((
+                    vec![idx],
+                    SqlQuery {
+                        sql: format!("INSERT INTO {table} DEFAULT VALUES RETURNING \"id\""),
+                        params: vec![],
+                    },
+                ));
+            }
+            continue;
+        }
+        let columns: Vec<&str> = ids.iter().map(|_| "id").chain(columns).collect();
+        let columns_sql = columns
+            .iter()
+            .map(|column| quote(column))
+            .collect::<Vec<_>>()
+            .join(", ");
+        for chunk in indexes.chunks(dialect.max_params() / columns.len()) {
+            let mut params = Vec::with_capacity(chunk.len() * columns.len());
+            let mut values = Vec::with_capacity(chunk.len());
+            for idx in chunk {
+                let row = columns
+                    .iter()
+                    .map(|column| {
+                        let value = match ids {
+                            Some(ids) if *column == "id" => Some(FieldType::UInteger(ids[*idx])),
+                            _ => get_value(data[*idx], column),
+                        };
+                        match value {
+                            Some(value) => {
+                                params.push(value);
+                                dialect.placeholder(params.len())
+                            }
+                            None => "NULL".to_string(),
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                values.push(format!("({row})"));
+            }
+            result.push((
+                chunk.to_vec(),
+                SqlQuery {
+                    sql: format!(
+                        "INSERT INTO {table} ({columns_sql}) VALUES {} RETURNING \"id\"",
+                        values.join(", ")
+                    ),
+                    params,
+                },
+            ));
+        }
+    }
+    result
+}
+
+/// Build UPDATE requests updating given records.
+///
+/// Records with the same set of fields are updated in the same request, by joining the table with
+/// a VALUES list containing the id and the new values of each record.
+pub fn update_queries(
+    dialect: Dialect,
+    model_name: &str,
+    data: &HashMap<u64, &MapOfFields>,
+) -> Vec<SqlQuery> {
+    let mut groups: BTreeMap<Vec<&str>, Vec<u64>> = BTreeMap::new();
+    for (id, d) in data {
+        let columns = get_columns(d);
+        if !columns.is_empty() {
+            groups.entry(columns).or_default().push(*id);
+        }
+    }
+
+    let table = quote(model_name);
+    let mut result = Vec::new();
+    for (columns, mut ids) in groups {
+        ids.sort_unstable();
+        for chunk in ids.chunks(dialect.max_params() / (columns.len() + 1)) {
+            // Type of each column, based on the first non-empty value.
+            // If a column only contains empty values, it's directly set to NULL
+            let types: Vec<Option<&str>> = columns
+                .iter()
+                .map(|column| {
+                    chunk
+                        .iter()
+                        .find_map(|id| get_value(data[id], column))
+                        .map(|value| cast_type(dialect, &value))
+                })
+                .collect();
+
+            let mut params = Vec::with_capacity(chunk.len() * (columns.len() + 1));
+            let mut values = Vec::with_capacity(chunk.len());
+            for id in chunk {
+                params.push(FieldType::UInteger(*id));
+                let mut row = vec![format!(
+                    "CAST({} AS BIGINT)",
+                    dialect.placeholder(params.len())
+                )];
+                for (column, column_type) in columns.iter().zip(&types) {
+                    let Some(column_type) = column_type else {
+                        continue;
+                    };
+                    match get_value(data[id], column) {
+                        Some(value) => {
+                            params.push(value);
+                            row.push(format!(
+                                "CAST({} AS {column_type})",
+                                dialect.placeholder(params.len())
+                            ));
+                        }
+                        None => row.push(format!("CAST(NULL AS {column_type})")),
+                    }
+                }
+                values.push(format!("({})", row.join(", ")));
+            }
+
+            // Columns of a VALUES list are named "column1", "column2", ...
+            let mut set = Vec::with_capacity(columns.len());
+            let mut values_column = 1;
+            for (column, column_type) in columns.iter().zip(&types) {
+                if column_type.is_some() {
+                    values_column += 1;
+                    set.push(format!(
+                        "{} = \"values\".\"column{values_column}\"",
+                        quote(column)
+                    ));
+                } else {
+                    set.push(format!("{} = NULL", quote(column)));
+                }
+            }
+            result.push(SqlQuery {
+                sql: format!(
+                    "UPDATE {table} SET {} FROM (VALUES {}) AS \"values\" WHERE {table}.\"id\" = \"values\".\"column1\"",
+                    set.join(", "),
+                    values.join(", "),
+                ),
+                params,
+            });
+        }
+    }
+    result
+}
+
+/// Build DELETE requests removing given records.
+pub fn delete_queries(dialect: Dialect, model_name: &str, ids: &[u64]) -> Vec<SqlQuery> {
+    let table = quote(model_name);
+    ids.chunks(dialect.max_params())
+        .map(|chunk| {
+            let placeholders = (1..=chunk.len())
+                .map(|idx| dialect.placeholder(idx))
+                .collect::<Vec<_>>()
+                .join(", ");
+            SqlQuery {
+                sql: format!("DELETE FROM {table} WHERE \"id\" IN ({placeholders})"),
+                params: chunk.iter().map(|id| FieldType::UInteger(*id)).collect(),
+            }
+        })
+        .collect()
+}
+
+/// Build SELECT requests retrieving links of given records in given relation table.
+///
+/// Each returned row contains the id of a record, and the id of a record it targets
+pub fn relation_select_queries(
+    dialect: Dialect,
+    relation: &Many2manyRelation,
+    ids: &[u64],
+) -> Vec<SqlQuery> {
+    let table = quote(relation.table);
+    let column1 = quote(&relation.column1);
+    let column2 = quote(&relation.column2);
+    ids.chunks(dialect.max_params())
+        .map(|chunk| {
+            let placeholders = (1..=chunk.len())
+                .map(|idx| dialect.placeholder(idx))
+                .collect::<Vec<_>>()
+                .join(", ");
+            SqlQuery {
+                sql: format!(
+                    "SELECT {column1}, {column2} FROM {table} WHERE {column1} IN ({placeholders}) ORDER BY {column1}, {column2}"
+                ),
+                params: chunk.iter().map(|id| FieldType::UInteger(*id)).collect(),
+            }
+        })
+        .collect()
+}
+
+/// Build requests replacing links of given records in given relation table.
+///
+/// Existing links of those records are deleted, then given links are inserted
+pub fn relation_write_queries(
+    dialect: Dialect,
+    relation: &Many2manyRelation,
+    data: &HashMap<u64, Vec<u64>>,
+) -> Vec<SqlQuery> {
+    let table = quote(relation.table);
+    let column1 = quote(&relation.column1);
+    let column2 = quote(&relation.column2);
+    let mut ids: Vec<u64> = data.keys().copied().collect();
+    ids.sort_unstable();
+
+    let mut result = Vec::new();
+    for chunk in ids.chunks(dialect.max_params()) {
+        let placeholders = (1..=chunk.len())
+            .map(|idx| dialect.placeholder(idx))
+            .collect::<Vec<_>>()
+            .join(", ");
+        result.push(SqlQuery {
+            sql: format!("DELETE FROM {table} WHERE {column1} IN ({placeholders})"),
+            params: chunk.iter().map(|id| FieldType::UInteger(*id)).collect(),
+        });
+    }
+
+    let links: Vec<(u64, u64)> = ids
+        .iter()
+        .flat_map(|id| data[id].iter().map(|target_id| (*id, *target_id)))
+        .collect();
+    for chunk in links.chunks(dialect.max_params() / 2) {
+        let mut params = Vec::with_capacity(chunk.len() * 2);
+        let mut values = Vec::with_capacity(chunk.len());
+        for (id, target_id) in chunk {
+            params.push(FieldType::UInteger(*id));
+            params.push(FieldType::UInteger(*target_id));
+            values.push(format!(
+                "({}, {})",
+                dialect.placeholder(params.len() - 1),
+                dialect.placeholder(params.len())
+            ));
+        }
+        result.push(SqlQuery {
+            sql: format!(
+                "INSERT INTO {table} ({column1}, {column2}) VALUES {}",
+                values.join(", ")
+            ),
+            params,
+        });
+    }
+    result
+}
@@ -0,0 +1,665 @@
+use crate::database::sql::{
+    Dialect, QueryBuilder, SqlQuery, column_definition, delete_queries, foreign_key_definition,
+    get_many2many_relation, insert_queries, is_not_null, many2many_relation, quote,
+    relation_select_queries, relation_table_definition, relation_write_queries, update_queries,
+};
+use crate::database::{
+    Aggregate, Database, DatabaseConfig, ErrorType, FieldType, Group, IdOutOfRangeError,
+    InvalidValueError, UnknownFieldError, get_read_group_types,
+};
+use crate::model::{ModelManager, ModelNotFound};
+use chrono::{NaiveDate, NaiveDateTime};
+use erp_internal_types::FinalInternalModel;
+use erp_search::{SearchOptions, SearchType};
+use erp_search_code_gen::make_domain;
+use erp_types::field;
+use erp_types::field::{CurrencyField, FieldReference};
+use erp_types::model::MapOfFields;
+use rusqlite::types::{Value, ValueRef};
+use rusqlite::{Connection, OptionalExtension, Row, params_from_iter};
+use rust_decimal::Decimal;
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+/// SQLite database, stored in a single file.
+///
+/// Used for small deployments, or to run tests on a real SQL engine without any server
+pub struct SqliteDatabase {
+    pub connection: Connection,
+    is_transaction: bool,
+}
+
+impl SqliteDatabase {
+    /// Make a connection to this database
+    pub(crate) fn connect(config: &DatabaseConfig) -> std::result::Result<Self, ErrorType>
+    where
+        Self: Sized,
+    {
+        let connection = Connection::open(&config.name)?;
+        // Foreign keys are not checked by default in SQLite
+        connection.execute_batch("PRAGMA foreign_keys = ON")?;
+        Ok(Self {
+            connection,
+            is_transaction: false,
+        })
+    }
+
+    /// Execute given query, and convert each resulting row with given method
+    fn query<T, F>(&mut self, query: &SqlQuery, mut map: F) -> Result<Vec<T>>
+    where
+        F: FnMut(&Row) -> Result<T>,
+    {
+        let params = query
+            .params
+            .iter()
+            .map(to_sql_param)
+            .collect::<Result<Vec<_>>>()?;
+        let mut statement = self.connection.prepare(&query.sql)?;
+        let mut rows = statement.query(params_from_iter(params))?;
+        let mut result = Vec::new();
+        while let Some(row) = rows.next()? {
+            result.push(map(row)?);
+        }
+        Ok(result)
+    }
+
+    /// Execute given query, and return the number of modified rows
+    fn execute(&mut self, query: &SqlQuery) -> Result<usize> {
+        let params = query
+            .params
+            .iter()
+            .map(to_sql_param)
+            .collect::<Result<Vec<_>>>()?;
+        Ok(self
+            .connection
+            .execute(&query.sql, params_from_iter(params))?)
+    }
+
+    /// Retrieves the name of all columns of given table.
+    ///
+    /// If the table doesn't exist, return an empty set
+    fn get_columns(&mut self, table_name: &str) -> Result<HashSet<String>> {
+        let mut statement = self
+            .connection
+            .prepare("SELECT \"name\" FROM pragma_table_info(?1)")?;
+        let columns = statement
+            .query_map([table_name], |row| row.get::<_, String>(0))?
+            .collect::<rusqlite::Result<HashSet<_>>>()?;
+        Ok(columns)
+    }
+
+    /// Decimal places of given currencies, read from the currency model of given currency field
+    fn get_decimal_places(
+        &mut self,
+        model: &FinalInternalModel,
+        currency_field: &CurrencyField,
+        currency_ids: HashSet<u64>,
+        model_manager: &ModelManager,
+    ) -> Result<HashMap<u64, u32>> {
+        let Some(FieldReference { target_model, .. }) =
+            &model.get_internal_field(&currency_field.field_name).inverse
+        else {
+            return Ok(HashMap::new());
+        };
+        if currency_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+        let currency_ids: Vec<u64> = currency_ids.into_iter().collect();
+        let query = QueryBuilder::new(model_manager, Dialect::Sqlite).select(
+            target_model,
+            &["id", &currency_field.decimal_places_field],
+            &make_domain!([("id", "in", currency_ids)]),
+            &SearchOptions::unordered(),
+        )?;
+        let decimal_places = self.query(&query, |row| {
+            Ok((row.get::<_, u64>(0)?, row.get::<_, Option<i64>>(1)?))
+        })?;
+        Ok(decimal_places
+            .into_iter()
+            .filter_map(|(id, decimal_places)| Some((id, u32::try_from(decimal_places?).ok()?)))
+            .collect())
+    }
+}
+
+/// Format of dates stored in SQLite
+const DATE_FORMAT: &str = "%Y-%m-%d";
+/// Format of dates and times stored in SQLite, in UTC.
+///
+/// The number of decimals is fixed, so that comparing those texts gives the same result as
+/// comparing dates and times
+const DATETIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S%.6f";
+
+/// Convert given value to a value that can be sent to SQLite.
+///
+/// SQLite doesn't have any temporal type, so dates and times are sent as texts. Decimals are also
+/// sent as texts, and are converted by the NUMERIC affinity of their column. Ids that don't fit in
+/// a 64-bit signed integer are refused
+fn to_sql_param(value: &FieldType) -> Result<Value> {
+    Ok(match value {
+        FieldType::String(v) => Value::Text(v.clone()),
+        FieldType::Integer(v) => Value::Integer(*v as i64),
+        FieldType::UInteger(v) => {
+            Value::Integer(i64::try_from(*v).map_err(|_| IdOutOfRangeError { id: v.to_string() })?)
+        }
+        FieldType::BigInteger(v) => Value::Integer(*v),
+        FieldType::Float(v) => Value::Real(*v as f64),
+        FieldType::Double(v) => Value::Real(*v),
+        FieldType::Boolean(v) => Value::Integer(*v as i64),
+        FieldType::Decimal(v) => Value::Text(v.to_string()),
+        FieldType::Date(v) => Value::Text(v.format(DATE_FORMAT).to_string()),
+        FieldType::DateTime(v) => Value::Text(v.format(DATETIME_FORMAT).to_string()),
+        FieldType::Json(v) => Value::Text(v.to_string()),
+    })
+}
+
+/// Read the column at given index, and convert it based on the type of the field
+fn from_sql_column(
+    row: &Row,
+    idx: usize,
+    field_type: &field::FieldType,
+) -> Result<Option<FieldType>> {
+    Ok(match field_type {
+        // Binary fields store the key of their content in the filestore
+        field::FieldType::String(_) | field::FieldType::Binary(_) => {
+            row.get::<_, Option<String>>(idx)?.map(FieldType::String)
+        }
+        field::FieldType::Integer(_) => row.get::<_, Option<i32>>(idx)?.map(FieldType::Integer),
+        field::FieldType::BigInteger(_) => {
+            row.get::<_, Option<i64>>(idx)?.map(FieldType::BigInteger)
+        }
+        field::FieldType::Float(_) => row
+            .get::<_, Option<f64>>(idx)?
+            .map(|value| FieldType::Float(value as f32)),
+        field::FieldType::Double(_) => row.get::<_, Option<f64>>(idx)?.map(FieldType::Double),
+        field::FieldType::Bool(_) => row.get::<_, Option<bool>>(idx)?.map(FieldType::Boolean),
+        field::FieldType::Decimal(_) => {
+            decimal_from_sql(row.get_ref(idx)?)?.map(FieldType::Decimal)
+        }
+        field::FieldType::Date(_) => match row.get::<_, Option<String>>(idx)? {
+            Some(value) => Some(FieldType::Date(
+                NaiveDate::parse_from_str(&value, DATE_FORMAT)
+                    .map_err(|_| InvalidValueError { value })?,
+            )),
+            None => None,
+        },
+        field::FieldType::DateTime(_) => match row.get::<_, Option<String>>(idx)? {
+            Some(value) => Some(FieldType::DateTime(
+                NaiveDateTime::parse_from_str(&value, DATETIME_FORMAT)
+                    .map_err(|_| InvalidValueError { value })?
+                    .and_utc(),
+            )),
+            None => None,
+        },
+        field::FieldType::Json(_) => match row.get::<_, Option<String>>(idx)? {
+            Some(value) => Some(FieldType::Json(
+                serde_json::from_str(&value).map_err(|_| InvalidValueError { value })?,
+            )),
+            None => None,
+        },
+        field::FieldType::Ref(_) => row.get::<_, Option<u64>>(idx)?.map(FieldType::UInteger),
+        field::FieldType::Refs(_) => {
+            return Err("Refs fields are not stored in the database".into());
+        }
+    })
+}
+
+/// Convert a value of a NUMERIC column to a Decimal.
+///
+/// SQLite stores decimals as integers, or as reals when they can be converted back to the same
+/// text. Reals are read with 15 significant digits, to remove the rounding errors of aggregates.
+/// Other decimals are kept as texts
+fn decimal_from_sql(value: ValueRef) -> Result<Option<Decimal>> {
+    let value = match value {
+        ValueRef::Null => return Ok(None),
+        ValueRef::Integer(value) => Decimal::from(value),
+        ValueRef::Real(value) => Decimal::from_scientific(&format!("{value:.14e}"))
+            .map_err(|_| InvalidValueError {
+                value: value.to_string(),
+            })?
+            .normalize(),
+        ValueRef::Text(value) => {
+            let value = String::from_utf8_lossy(value);
+            Decimal::from_str_exact(&value).map_err(|_| InvalidValueError {
+                value: value.to_string(),
+            })?
+        }
+        ValueRef::Blob(_) => {
+            return Err("Decimals can't be read from a BLOB".into());
+        }
+    };
+    Ok(Some(value))
+}
+
+impl Database for SqliteDatabase {
+    /// Check if given database is already installed
+    fn is_installed(&mut self) -> Result<bool> {
+        Ok(self.connection.query_row(
+            "SELECT EXISTS (
+            SELECT 1 FROM \"sqlite_master\" WHERE \"type\"='table' AND \"name\"='plugin'
+        )",
+            [],
+            |row| row.get(0),
+        )?)
+    }
+
+    /// Initialize this database
+    fn initialize(&mut self) -> Result<()> {
+        self.connection.execute_batch(
+            "
+            CREATE TABLE plugin (
+                id              INTEGER PRIMARY KEY AUTOINCREMENT,
+                name            VARCHAR NOT NULL,
+                description     TEXT,
+                website         TEXT,
+                url             TEXT,
+                state           VARCHAR NOT NULL,
+                version         VARCHAR
+            )
+            ",
+        )?;
+        Ok(())
+    }
+
+    /// Add the version of plugins, which was not saved by older versions
+    fn upgrade(&mut self) -> Result<()> {
+        if !self.get_columns("plugin")?.contains("version") {
+            self.connection
+                .execute_batch("ALTER TABLE \"plugin\" ADD COLUMN \"version\" VARCHAR")?;
+        }
+        Ok(())
+    }
+
+    /// Create missing tables and columns of given models
+    ///
+    /// SQLite cannot add a NOT NULL column without a default value, so columns added to an
+    /// existing table are never NOT NULL
+    fn sync_schema(&mut self, model_names: &[&str], model_manager: &ModelManager) -> Result<()> {
+        for model_name in model_names {
+            let model =
+                model_manager
+                    .get_models()
+                    .get(*model_name)
+                    .ok_or_else(|| ModelNotFound {
+                        model_name: model_name.to_string(),
+                    })?;
+            let table = quote(&model.name);
+            let existing_columns = self.get_columns(&model.name)?;
+            let is_new = existing_columns.is_empty();
+            let mut fields: Vec<_> = model.fields.values().collect();
+            fields.sort_by(|a, b| a.name.cmp(&b.name));
+
+            // SQLite cannot add constraints to an existing table, so foreign keys are declared
+            //  along with the column
+            let mut definitions = Vec::new();
+            for field in fields {
+                if existing_columns.contains(&field.name) {
+                    continue;
+                }
+                let Some(mut definition) =
+                    column_definition(Dialect::Sqlite, field, is_new && is_not_null(field))
+                else {
+                    continue;
+                };
+                if let Some(foreign_key) = foreign_key_definition(field) {
+                    definition.push(' ');
+                    definition.push_str(&foreign_key);
+                }
+                definitions.push(definition);
+            }
+
+            if is_new {
+                definitions.insert(0, "\"id\" INTEGER PRIMARY KEY AUTOINCREMENT".to_string());
+                self.connection.execute_batch(&format!(
+                    "CREATE TABLE {table} ({})",
+                    definitions.join(", ")
+                ))?;
+            } else {
+                for definition in definitions {
+                    self.connection
+                        .execute_batch(&format!("ALTER TABLE {table} ADD COLUMN {definition}"))?;
+                }
+            }
+        }
+
+        // Then, create relation tables of M2M once both linked tables exist
+        for model_name in model_names {
+            let model = model_manager.get_model(model_name);
+            for field in model.fields.values() {
+                let Some(relation) = many2many_relation(model_name, field) else {
+                    continue;
+                };
+                if !self.get_columns(relation.target_model)?.is_empty() {
+                    self.connection
+                        .execute_batch(&relation_table_definition(model_name, &relation))?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Make a search request to a specific model, and only return ids that match this search request
+    fn browse(
+        &mut self,
+        model_name: &str,
+        domain: &SearchType,
+        options: &SearchOptions,
+        model_manager: &ModelManager,
+    ) -> Result<Vec<u64>> {
+        let query = QueryBuilder::new(model_manager, Dialect::Sqlite).select(
+            model_name,
+            &["id"],
+            domain,
+            options,
+        )?;
+        self.query(&query, |row| Ok(row.get::<_, u64>(0)?))
+    }
+
+    /// Make a search request to a specific model, and return ids and fields that match this search request
+    #[allow(clippy::type_complexity)]
+    fn search<'a>(
+        &mut self,
+        model_name: &str,
+        fields: &[&'a str],
+        domain: &SearchType,
+        options: &SearchOptions,
+        model_manager: &ModelManager,
+    ) -> Result<Vec<(u64, HashMap<&'a str, Option<FieldType>>)>> {
+        let model = model_manager
+            .get_models()
+            .get(model_name)
+            .ok_or_else(|| ModelNotFound {
+                model_name: model_name.to_string(),
+            })?;
+        // Type and digits of each field, used to convert the value returned by the database
+        let field_types = fields
+            .iter()
+            .map(|field_name| {
+                if *field_name == "id" {
+                    return Ok((field::FieldType::Ref(0), None));
+                }
+                model
+                    .fields
+                    .get(*field_name)
+                    .map(|field| (field.default_value.clone(), field.digits))
+                    .ok_or_else(|| UnknownFieldError {
+                        model_name: model_name.to_string(),
+                        field_name: field_name.to_string(),
+                    })
+            })
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        // Currencies of Monetary fields, also fetched to know the decimal places of their amounts
+        let mut currency_fields: Vec<&CurrencyField> = Vec::new();
+        for field_name in fields {
+            if let Some(currency_field) = model
+                .fields
+                .get(*field_name)
+                .and_then(|field| field.currency_field.as_ref())
+                && !currency_fields.contains(&currency_field)
+            {
+                currency_fields.push(currency_field);
+            }
+        }
+
+        // First column is always the id, and currencies are after the given fields
+        let mut columns = Vec::with_capacity(fields.len() + currency_fields.len() + 1);
+        columns.push("id");
+        columns.extend_from_slice(fields);
+        columns.extend(
+            currency_fields
+                .iter()
+                .map(|currency_field| currency_field.field_name.as_str()),
+        );
+        let query = QueryBuilder::new(model_manager, Dialect::Sqlite)
+            .select(model_name, &columns, domain, options)?;
+
+        let mut records = self.query(&query, |row| {
+            let id = row.get::<_, u64>(0)?;
+            let mut fields_result = HashMap::with_capacity(fields.len());
+            for (idx, (field_name, (field_type, digits))) in
+                fields.iter().zip(&field_types).enumerate()
+            {
+                let mut value = from_sql_column(row, idx + 1, field_type)?;
+                // The NUMERIC affinity doesn't keep trailing zeros
+                if let (Some(FieldType::Decimal(value)), Some(digits)) = (&mut value, digits) {
+                    value.rescale(*digits);
+                }
+                fields_result.insert(*field_name, value);
+            }
+            let currency_ids = (0..currency_fields.len())
+                .map(|idx| row.get::<_, Option<u64>>(fields.len() + idx + 1))
+                .collect::<rusqlite::Result<Vec<_>>>()?;
+            Ok((id, fields_result, currency_ids))
+        })?;
+
+        // Amounts of Monetary fields lose their trailing zeros too, and get them back from the
+        // decimal places of their currency
+        for (idx, currency_field) in currency_fields.iter().enumerate() {
+            let currency_ids = records
+                .iter()
+                .filter_map(|(_, _, currency_ids)| currency_ids[idx])
+                .collect::<HashSet<_>>();
+            let decimal_places =
+                self.get_decimal_places(model, currency_field, currency_ids, model_manager)?;
+            for (_, fields_result, currency_ids) in &mut records {
+                let Some(decimal_places) =
+                    currency_ids[idx].and_then(|currency_id| decimal_places.get(&currency_id))
+                else {
+                    continue;
+                };
+                for (field_name, value) in fields_result.iter_mut() {
+                    if let Some(FieldType::Decimal(value)) = value
+                        && value.scale() < *decimal_places
+                        && model.fields.get(*field_name).is_some_and(|field| {
+                            field.currency_field.as_ref() == Some(*currency_field)
+                        })
+                    {
+                        value.rescale(*decimal_places);
+                    }
+                }
+            }
+        }
+        Ok(records
+            .into_iter()
+            .map(|(id, fields_result, _)| (id, fields_result))
+            .collect())
+    }
+
+    fn count(
+        &mut self,
+        model_name: &str,
+        domain: &SearchType,
+        model_manager: &ModelManager,
+    ) -> Result<u64> {
+        let query = QueryBuilder::new(model_manager, Dialect::Sqlite).count(model_name, domain)?;
+        let counts = self.query(&query, |row| Ok(row.get::<_, u64>(0)?))?;
+        Ok(counts[0])
+    }
+
+    fn exists(
+        &mut self,
+        model_name: &str,
+        domain: &SearchType,
+        model_manager: &ModelManager,
+    ) -> Result<bool> {
+        let query = QueryBuilder::new(model_manager, Dialect::Sqlite).exists(model_name, domain)?;
+        let exists = self.query(&query, |row| Ok(row.get::<_, bool>(0)?))?;
+        Ok(exists[0])
+    }
+
+    fn read_group(
+        &mut self,
+        model_name: &str,
+        domain: &SearchType,
+        group_by: &[&str],
+        aggregates: &[Aggregate],
+        model_manager: &ModelManager,
+    ) -> Result<Vec<Group>> {
+        let column_types = get_read_group_types(model_name, group_by, aggregates, model_manager)?;
+        let query = QueryBuilder::new(model_manager, Dialect::Sqlite)
+            .read_group(model_name, domain, group_by, aggregates)?;
+        self.query(&query, |row| {
+            let mut values = column_types
+                .iter()
+                .enumerate()
+                .map(|(idx, column_type)| from_sql_column(row, idx, column_type))
+                .collect::<Result<Vec<_>>>()?;
+            let aggregates = values.split_off(group_by.len());
+            Ok(Group {
+                keys: values,
+                aggregates,
+            })
+        })
+    }
+
+    fn create(&mut self, model_name: &str, data: &[&MapOfFields]) -> Result<Vec<u64>> {
+        let mut ids = vec![0; data.len()];
+        for (indexes, query) in insert_queries(Dialect::Sqlite, model_name, data, None) {
+            let mut created_ids = self.query(&query, |row| Ok(row.get::<_, u64>(0)?))?;
+            if created_ids.len() != indexes.len() {
+                return Err(format!(
+                    "{} records should have been created in {model_name}, but {} were created",
+                    indexes.len(),
+                    created_ids.len()
+                )
+                .into());
+            }
+            // SQLite doesn't guarantee the order of returned rows, but ids are generated in the
+            //  same order as the VALUES list
+            created_ids.sort_unstable();
+            for (idx, id) in indexes.into_iter().zip(created_ids) {
+                ids[idx] = id;
+            }
+        }
+        Ok(ids)
+    }
+
+    fn update(&mut self, model_name: &str, data: &HashMap<u64, &MapOfFields>) -> Result<u64> {
+        let mut number_of_updates = 0;
+        for query in update_queries(Dialect::Sqlite, model_name, data) {
+            number_of_updates += self.execute(&query)?;
+        }
+        Ok(number_of_updates as u64)
+    }
+
+    fn delete(&mut self, model_name: &str, ids: &[u64]) -> Result<u64> {
+        let mut number_of_deletions = 0;
+        for query in delete_queries(Dialect::Sqlite, model_name, ids) {
+            number_of_deletions += self.execute(&query)?;
+        }
+        Ok(number_of_deletions as u64)
+    }
+
+    fn read_many2many(
+        &mut self,
+        model_name: &str,
+        field_name: &str,
+        ids: &[u64],
+        model_manager: &ModelManager,
+    ) -> Result<HashMap<u64, Vec<u64>>> {
+        let relation = get_many2many_relation(model_name, field_name, model_manager)?;
+        let mut result: HashMap<u64, Vec<u64>> = HashMap::new();
+        for query in relation_select_queries(Dialect::Sqlite, &relation, ids) {
+            for (id, target_id) in self.query(&query, |row| {
+                Ok((row.get::<_, u64>(0)?, row.get::<_, u64>(1)?))
+            })? {
+                result.entry(id).or_default().push(target_id);
+            }
+        }
+        Ok(result)
+    }
+
+    fn write_many2many(
+        &mut self,
+        model_name: &str,
+        field_name: &str,
+        data: &HashMap<u64, Vec<u64>>,
+        model_manager: &ModelManager,
+    ) -> Result<()> {
+        let relation = get_many2many_relation(model_name, field_name, model_manager)?;
+        for query in relation_write_queries(Dialect::Sqlite, &relation, data) {
+            self.execute(&query)?;
+        }
+        Ok(())
+    }
+
+    fn get_installed_plugins(&mut self) -> Result<Vec<String>> {
+        let mut statement = self
+            .connection
+            .prepare("SELECT \"name\" FROM \"plugin\" WHERE \"state\"='installed'")?;
+        let result = statement
+            .query_map([], |row| row.get::<_, String>(0))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(result)
+    }
+
+    fn get_installed_plugin_version(&mut self, plugin_name: &str) -> Result<Option<String>> {
+        let version = self
+            .connection
+            .query_row(
+                "SELECT \"version\" FROM \"plugin\" WHERE \"name\"=?1 AND \"state\"='installed'",
+                [plugin_name],
+                |row| row.get::<_, Option<String>>(0),
+            )
+            .optional()?;
+        // An installed plugin without any version has been installed before versions were saved
+        Ok(version.map(|version| version.unwrap_or("0".to_string())))
+    }
+
+    fn set_installed_plugin_version(&mut self, plugin_name: &str, version: &str) -> Result<()> {
+        let updated = self.connection.execute(
+            "UPDATE \"plugin\" SET \"version\"=?2, \"state\"='installed' WHERE \"name\"=?1",
+            [plugin_name, version],
+        )?;
+        if updated == 0 {
+            self.connection.execute(
+                "INSERT INTO \"plugin\" (\"name\", \"state\", \"version\") VALUES (?1, 'installed', ?2)",
+                [plugin_name, version],
+            )?;
+        }
+        Ok(())
+    }
+
+    fn savepoint(&mut self, name: &str) -> Result<()> {
+        Ok(self
+            .connection
+            .execute_batch(&format!("SAVEPOINT {}", quote(name)))?)
+    }
+
+    fn savepoint_commit(&mut self, name: &str) -> Result<()> {
+        Ok(self
+            .connection
+            .execute_batch(&format!("RELEASE {}", quote(name)))?)
+    }
+
+    fn savepoint_rollback(&mut self, name: &str) -> Result<()> {
+        Ok(self
+            .connection
+            .execute_batch(&format!("ROLLBACK TO {}", quote(name)))?)
+    }
+
+    fn start_transaction(&mut self) -> Result<()> {
+        self.is_transaction = true;
+        Ok(self.connection.execute_batch("BEGIN")?)
+    }
+
+    fn commit_transaction(&mut self) -> Result<()> {
+        self.is_transaction = false;
+        Ok(self.connection.execute_batch("COMMIT")?)
+    }
+
+    fn rollback_transaction(&mut self) -> Result<()> {
+        self.is_transaction = false;
+        Ok(self.connection.execute_batch("ROLLBACK")?)
+    }
+}
+
+impl Drop for SqliteDatabase {
+    fn drop(&mut self) {
+        // Rollback if needed
+        if self.is_transaction {
+            let _ = self.rollback_transaction();
+        }
+    }
+}
@@ -0,0 +1,218 @@
+use crate::database::sql::foreign_key_target;
+use crate::database::{
+    InvalidPathError, InvalidSearchValueError, NotHierarchicalFieldError, UnknownFieldError,
+};
+use crate::model::{ModelManager, ModelNotFound};
+use erp_internal_types::FinalInternalModel;
+use erp_search::{RightTuple, SearchOperator, SearchTuple, SearchType};
+use erp_types::field::{FieldReference, FieldType};
+use std::error::Error;
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+/// Check that given domain can be executed on given model.
+///
+/// Each path should only contain existing fields, every field except the last one should be a
+/// M2O or a O2M, and the value of each tuple should have the same type as its field.
+/// The rest of a path going through a JSON field are keys of its value, which can be compared
+/// with any string, number or boolean. Sub-domains of "any" and "not any" are validated on the model targeted by their field
+pub fn validate(domain: &SearchType, model_name: &str, model_manager: &ModelManager) -> Result<()> {
+    match domain {
+        SearchType::And(left, right) | SearchType::Or(left, right) => {
+            validate(left, model_name, model_manager)?;
+            validate(right, model_name, model_manager)
+        }
+        SearchType::Not(search_type) => validate(search_type, model_name, model_manager),
+        SearchType::Tuple(tuple) => validate_tuple(tuple, model_name, model_manager),
+        SearchType::Nothing => Ok(()),
+    }
+}
+
+fn get_model<'mm>(
+    model_name: &str,
+    model_manager: &'mm ModelManager,
+) -> Result<&'mm FinalInternalModel> {
+    Ok(model_manager
+        .get_models()
+        .get(model_name)
+        .ok_or_else(|| ModelNotFound {
+            model_name: model_name.to_string(),
+        })?)
+}
+
+fn validate_tuple(
+    tuple: &SearchTuple,
+    model_name: &str,
+    model_manager: &ModelManager,
+) -> Result<()> {
+    let mut model = get_model(model_name, model_manager)?;
+    let Some((last, path)) = tuple.left.path.split_last() else {
+        return Err(UnknownFieldError {
+            model_name: model_name.to_string(),
+            field_name: "".to_string(),
+        }
+        .into());
+    };
+    for field_name in path {
+        let field = model
+            .fields
+            .get(field_name)
+            .ok_or_else(|| UnknownFieldError {
+                model_name: model.name.clone(),
+                field_name: field_name.clone(),
+            })?;
+        if field.is_json() {
+            // The rest of the path are keys of the JSON value, that can't be checked
+            return if is_valid_json_value(&tuple.operator, &tuple.right) {
+                Ok(())
+            } else {
+                Err(InvalidSearchValueError {
+                    model_name: model.name.clone(),
+                    field_name: field_name.clone(),
+                    operator: tuple.operator.clone(),
+                    value: tuple.right.clone(),
+                }
+                .into())
+            };
+        }
+        let Some(FieldReference { target_model, .. }) = &field.inverse else {
+            return Err(InvalidPathError {
+                model_name: model.name.clone(),
+                field_name: field_name.clone(),
+            }
+            .into());
+        };
+        model = get_model(target_model, model_manager)?;
+    }
+
+    if matches!(tuple.operator, SearchOperator::Any | SearchOperator::NotAny) {
+        // The sub-domain is validated on the model targeted by the field
+        let target_model = model
+            .fields
+            .get(last)
+            .and_then(|field| field.inverse.as_ref())
+            .map(|inverse| inverse.target_model.as_str());
+        return match (target_model, &tuple.right) {
+            (Some(target_model), RightTuple::Domain(domain)) => {
+                validate(domain, target_model, model_manager)
+            }
+            (None, _) if last != "id" && !model.fields.contains_key(last) => {
+                Err(UnknownFieldError {
+                    model_name: model.name.clone(),
+                    field_name: last.clone(),
+                }
+                .into())
+            }
+            _ => Err(InvalidSearchValueError {
+                model_name: model.name.clone(),
+                field_name: last.clone(),
+                operator: tuple.operator.clone(),
+                value: tuple.right.clone(),
+            }
+            .into()),
+        };
+    }
+
+    let field_type = if last == "id" {
+        FieldType::Ref(0)
+    } else {
+        let field = model.fields.get(last).ok_or_else(|| UnknownFieldError {
+            model_name: model.name.clone(),
+            field_name: last.clone(),
+        })?;
+        if matches!(
+            tuple.operator,
+            SearchOperator::ChildOf | SearchOperator::ParentOf
+        ) && foreign_key_target(field) != Some(model.name.as_str())
+        {
+            return Err(NotHierarchicalFieldError {
+                model_name: model.name.clone(),
+                field_name: last.clone(),
+            }
+            .into());
+        }
+        field.default_value.clone()
+    };
+
+    if is_valid_value(&field_type, &tuple.operator, &tuple.right) {
+        Ok(())
+    } else {
+        Err(InvalidSearchValueError {
+            model_name: model.name.clone(),
+            field_name: last.clone(),
+            operator: tuple.operator.clone(),
+            value: tuple.right.clone(),
+        }
+        .into())
+    }
+}
+
+/// Check if given value can be compared with a field of given type, using given operator.
+///
+/// Values are not converted when comparing them, so they should have the exact same type. M2O
+/// and O2M fields are compared with ids, and Binary fields with the key of their content
+fn is_valid_value(field_type: &FieldType, operator: &SearchOperator, value: &RightTuple) -> bool {
+    match (operator, value) {
+        (_, RightTuple::Domain(_)) => false,
+        (_, RightTuple::None) => true,
+        (_, RightTuple::Array(values)) => values
+            .iter()
+            .all(|value| is_valid_value(field_type, operator, value)),
+        (SearchOperator::Like | SearchOperator::ILike | SearchOperator::EqualLike, value) => {
+            matches!(
+                (field_type, value),
+                (FieldType::String(_), RightTuple::String(_))
+            )
+        }
+        (_, value) => matches!(
+            (field_type, value),
+            (
+                FieldType::String(_) | FieldType::Binary(_),
+                RightTuple::String(_)
+            ) | (FieldType::Integer(_), RightTuple::Integer(_))
+                | (FieldType::BigInteger(_), RightTuple::BigInteger(_))
+                | (FieldType::Float(_), RightTuple::Float(_))
+                | (FieldType::Double(_), RightTuple::Double(_))
+                | (FieldType::Bool(_), RightTuple::Boolean(_))
+                | (FieldType::Decimal(_), RightTuple::Decimal(_))
+                | (FieldType::Date(_), RightTuple::Date(_))
+                | (FieldType::DateTime(_), RightTuple::DateTime(_))
+                | (
+                    FieldType::Ref(_) | FieldType::Refs(_),
+                    RightTuple::UInteger(_)
+                )
+        ),
+    }
+}
+
+/// Check if given value can be compared with a value inside a JSON field, using given operator
+fn is_valid_json_value(operator: &SearchOperator, value: &RightTuple) -> bool {
+    match (operator, value) {
+        (
+            SearchOperator::ChildOf
+            | SearchOperator::ParentOf
+            | SearchOperator::Any
+            | SearchOperator::NotAny,
+            _,
+        ) => false,
+        (_, RightTuple::Domain(_)) => false,
+        (_, RightTuple::None) => true,
+        (_, RightTuple::Array(values)) => values
+            .iter()
+            .all(|value| is_valid_json_value(operator, value)),
+        (SearchOperator::Like | SearchOperator::ILike | SearchOperator::EqualLike, value) => {
+            matches!(value, RightTuple::String(_))
+        }
+        (_, value) => matches!(
+            value,
+            RightTuple::String(_)
+                | RightTuple::Integer(_)
+                | RightTuple::UInteger(_)
+                | RightTuple::BigInteger(_)
+                | RightTuple::Float(_)
+                | RightTuple::Double(_)
+                | RightTuple::Decimal(_)
+                | RightTuple::Boolean(_)
+        ),
+    }
+}
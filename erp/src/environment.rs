@@ -1,18 +1,24 @@
-use crate::database::{Database, DatabaseType};
+use crate::database::{
+    self, Aggregate, Database, DatabaseType, Group, InvalidPathError, RestrictViolationError,
+    UnknownFieldError, cache, validate,
+};
 use crate::errors::MaximumRecursionDepthCompute;
-use crate::model::{Model, ModelManager};
+use crate::filestore::{FileStore, FileStoreType};
+use crate::model::{ATTACHMENT_MODEL, Model, ModelManager};
 use erp_cache::{Cache, CacheField, CacheModels};
-use erp_search::{LeftTuple, SearchType};
+use erp_internal_types::FinalInternalField;
+use erp_search::{LeftTuple, RightTuple, SearchOperator, SearchOptions, SearchTuple, SearchType};
 use erp_search_code_gen::make_domain;
 use erp_types::cache::{Dirty, Update};
+use erp_types::environment::ErasedEnvironment;
 use erp_types::field::FieldType;
-use erp_types::field::{FieldDepend, FieldReference, FieldReferenceType};
+use erp_types::field::{FieldDepend, FieldReference, FieldReferenceType, OnDelete};
 use erp_types::field::{IdMode, MultipleIds, SingleId};
 use erp_types::model::MapOfFields;
 use std::collections::{HashMap, HashSet};
 use std::error::Error;
+use std::time::{Duration, SystemTime};
 use uuid::Uuid;
-use erp_types::environment::ErasedEnvironment;
 
 const MAX_NUMBER_OF_RECURSION: i32 = 1024;
 
@@ -30,6 +36,12 @@ pub struct Environment<'mm, 'db> {
     pub cache: Cache,
     pub model_manager: &'mm ModelManager,
     pub database: DatabaseType<'db>,
+    pub filestore: FileStoreType<'db>,
+    /// Contents of the filestore written less than this duration ago are not collected
+    pub filestore_grace_period: Duration,
+    /// If records holding Binary values have been deleted, so that their contents could now be
+    /// orphans
+    has_orphan_blobs: bool,
 }
 
 impl Drop for Environment<'_, '_> {
@@ -40,11 +52,19 @@ impl Drop for Environment<'_, '_> {
 }
 
 impl<'mm, 'db> Environment<'mm, 'db> {
-    pub fn new(model_manager: &'mm ModelManager, database: DatabaseType<'db>) -> Result<Self> {
+    pub fn new(
+        model_manager: &'mm ModelManager,
+        database: DatabaseType<'db>,
+        filestore: FileStoreType<'db>,
+        filestore_grace_period: Duration,
+    ) -> Result<Self> {
         let mut env = Environment {
             cache: make_cache(model_manager),
             model_manager,
             database,
+            filestore,
+            filestore_grace_period,
+            has_orphan_blobs: false,
         };
         env.database.start_transaction()?;
         Ok(env)
@@ -57,17 +77,26 @@ impl<'mm, 'db> Environment<'mm, 'db> {
     // ------------------------------------------
 
     /// Flush cache to the database, commit, and close the transaction.
+    ///
+    /// If records holding Binary values have been deleted, contents of the filestore that are no
+    /// longer referenced are then deleted, in a new transaction
     pub fn close(mut self) -> Result<()> {
         self.save_all_to_db()?;
         // Commiting here ensures everything is saved to the database, so we can take back the
         //  database and replace it with a cache one
         self.database.commit_transaction()?;
+        if self.has_orphan_blobs {
+            self.database.start_transaction()?;
+            self.collect_filestore_garbage(self.filestore_grace_period)?;
+            self.database.commit_transaction()?;
+        }
         Ok(())
     }
 
     /// Load given records from the database to the cache.
     ///
     /// If the record is already present in cache, do nothing
+    #[allow(dead_code)]
     fn load_records_from_db<Mode: IdMode>(&mut self, model_name: &str, ids: &Mode) -> Result<()> {
         let internal_model = self.model_manager.get_model(model_name);
         let fields = internal_model.get_stored_fields();
@@ -116,15 +145,23 @@ impl<'mm, 'db> Environment<'mm, 'db> {
         for field in fields {
             let mut current_model = model;
             for elem in &field.path {
+                if elem == "id" {
+                    // Ids are always saved
+                    break;
+                }
                 let final_field = current_model.get_internal_field(elem);
                 let is_stored = final_field.is_stored();
-                if is_stored {
-                    // If stored field, we need to save it to the database
+                if is_stored || final_field.is_many2many() {
+                    // If stored field (or M2M), we need to save it to the database
                     fields_to_save
                         .entry(&current_model.name)
                         .or_default()
                         .insert(&final_field.name);
                 }
+                if final_field.is_json() {
+                    // The rest of the path are keys of the JSON value
+                    break;
+                }
                 if let Some(FieldReference {
                     target_model,
                     inverse_field,
@@ -134,18 +171,23 @@ impl<'mm, 'db> Environment<'mm, 'db> {
                     // Also, if this field (or the target one) is a stored field, save it
                     let target_model = self.model_manager.get_model(target_model);
                     current_model = target_model;
-                    if !is_stored {
-                        if let FieldReferenceType::O2M { inverse_field } = inverse_field {
-                            // If there is an inverse field, and it's a stored field, save it
-                            let target_field = target_model.get_internal_field(inverse_field);
-                            if target_field.is_stored() {
-                                fields_to_save
-                                    .entry(&target_model.name)
-                                    .or_default()
-                                    .insert(&target_field.name);
-                            }
+                    if !is_stored && let FieldReferenceType::O2M { inverse_field } = inverse_field {
+                        // If there is an inverse field, and it's a stored field, save it
+                        let target_field = target_model.get_internal_field(inverse_field);
+                        if target_field.is_stored() {
+                            fields_to_save
+                                .entry(&target_model.name)
+                                .or_default()
+                                .insert(&target_field.name);
                         }
                     }
+                    if let FieldReferenceType::M2M { inverse_fields, .. } = inverse_field {
+                        // Inverse fields of a M2M use the same relation table, save them too
+                        fields_to_save
+                            .entry(&target_model.name)
+                            .or_default()
+                            .extend(inverse_fields.iter().map(|field| field.as_str()));
+                    }
                 }
             }
         }
@@ -158,27 +200,142 @@ impl<'mm, 'db> Environment<'mm, 'db> {
     }
 
     /// Save fields linked to a specific domain into the database
-    pub fn save_domain_fields_to_db(&mut self, model_name: &str, domain: &SearchType) -> Result<()> {
+    pub fn save_domain_fields_to_db(
+        &mut self,
+        model_name: &str,
+        domain: &SearchType,
+    ) -> Result<()> {
         let fields = domain.get_fields();
         let fields_to_save = self.get_fields_to_save(model_name, &fields)?;
         for (model_name, fields) in fields_to_save {
             self.save_fields_to_db(model_name, &fields)?;
         }
+        // Sub-domains of "any" and "not any" are applied on the model targeted by their path
+        for (path, sub_domain) in get_sub_domains(domain) {
+            let mut current_model = self.model_manager.get_model(model_name);
+            for field_name in path {
+                let Some(FieldReference { target_model, .. }) =
+                    &current_model.get_internal_field(field_name).inverse
+                else {
+                    break;
+                };
+                current_model = self.model_manager.get_model(target_model);
+            }
+            self.save_domain_fields_to_db(&current_model.name, sub_domain)?;
+        }
         Ok(())
     }
 
-    /// Save all data from cache to the database
+    /// Save all data from cache to the database.
+    ///
+    /// Stored fields that still need to be computed are computed first. Models are then saved in
+    /// dependency order: a model targeted by a M2O is saved before the models targeting it
     pub fn save_all_to_db(&mut self) -> Result<()> {
-        // TODO Save all cache to database
+        let model_names = self.get_models_in_dependency_order();
+        for i in 0..=MAX_NUMBER_OF_RECURSION {
+            for model_name in &model_names {
+                self.call_computed_method_on_all_fields(model_name)?;
+            }
+            // Computing a field could mark fields of other models as to recompute
+            let Some(model_name) = model_names
+                .iter()
+                .find(|model_name| self.has_stored_fields_to_recompute(model_name))
+            else {
+                break;
+            };
+            if i == MAX_NUMBER_OF_RECURSION {
+                let cache_models = self.cache.get_cache_models(model_name);
+                return Err(MaximumRecursionDepthCompute {
+                    model_name: model_name.to_string(),
+                    fields_name: cache_models.to_recompute.keys().cloned().collect(),
+                    ids: cache_models
+                        .to_recompute
+                        .values()
+                        .flatten()
+                        .copied()
+                        .collect::<Vec<u64>>(),
+                }
+                .into());
+            }
+        }
+
+        for model_name in &model_names {
+            self.save_model_to_db(model_name)?;
+        }
         Ok(())
     }
 
+    /// Return all models, sorted so that a model targeted by a M2O comes before the models
+    /// targeting it.
+    ///
+    /// In case of a cycle, the first model (by name) of this cycle is saved last
+    fn get_models_in_dependency_order(&self) -> Vec<&'mm str> {
+        fn visit<'mm>(
+            model_manager: &'mm ModelManager,
+            model_name: &'mm str,
+            visited: &mut HashSet<&'mm str>,
+            result: &mut Vec<&'mm str>,
+        ) {
+            if !visited.insert(model_name) {
+                return;
+            }
+            let mut targets: Vec<&'mm str> = model_manager
+                .get_model(model_name)
+                .fields
+                .values()
+                .filter(|field| field.is_stored())
+                .filter_map(|field| match &field.inverse {
+                    Some(FieldReference {
+                        target_model,
+                        inverse_field: FieldReferenceType::M2O { .. },
+                    }) => Some(target_model.as_str()),
+                    _ => None,
+                })
+                .collect();
+            targets.sort_unstable();
+            for target in targets {
+                visit(model_manager, target, visited, result);
+            }
+            result.push(model_name);
+        }
+
+        let mut model_names: Vec<&'mm str> = self
+            .model_manager
+            .get_models()
+            .keys()
+            .map(|name| name.as_str())
+            .collect();
+        model_names.sort_unstable();
+        let mut visited = HashSet::new();
+        let mut result = Vec::with_capacity(model_names.len());
+        for model_name in model_names {
+            visit(self.model_manager, model_name, &mut visited, &mut result);
+        }
+        result
+    }
+
+    /// Check if there is at least one stored field of given model that needs to be computed
+    fn has_stored_fields_to_recompute(&self, model_name: &str) -> bool {
+        let model = self.model_manager.get_model(model_name);
+        self.cache
+            .get_cache_models(model_name)
+            .to_recompute
+            .iter()
+            .any(|(field_name, ids)| model.is_stored(field_name) && !ids.is_empty())
+    }
+
     /// Save all data related to given model to database.
     ///
     /// Compute non-stored fields related to this model if needed.
     pub fn save_model_to_db(&mut self, model_name: &str) -> Result<()> {
         self.call_computed_method_on_all_fields(model_name)?;
 
+        let model = self.model_manager.get_model(model_name);
+        let dirty_many2many = self
+            .cache
+            .get_dirty_models(model_name, |field_name| model.is_many2many(field_name));
+        self.save_many2many_to_db(model_name, &dirty_many2many)?;
+
         let dirty_map_of_fields = self.get_dirty_stored_models(model_name);
 
         if dirty_map_of_fields.is_empty() {
@@ -200,9 +357,18 @@ impl<'mm, 'db> Environment<'mm, 'db> {
     ///
     /// Compute them if needed.
     ///
-    /// Remove from the original list non-stored fields
+    /// Remove from the original list non-stored fields, except M2M that are saved in their relation
+    /// table
     pub fn save_fields_to_db(&mut self, model_name: &str, fields: &[&str]) -> Result<()> {
         let model = self.model_manager.get_model(model_name);
+        let many2many_fields: Vec<&str> = fields
+            .iter()
+            .copied()
+            .filter(|field_name| model.is_many2many(field_name))
+            .collect();
+        let dirty_many2many = self.get_dirty_fields(model_name, &many2many_fields);
+        self.save_many2many_to_db(model_name, &dirty_many2many)?;
+
         let fields = fields
             .iter()
             .filter_map(|&f| {
@@ -246,6 +412,11 @@ impl<'mm, 'db> Environment<'mm, 'db> {
     pub fn save_records_to_db<Mode: IdMode>(&mut self, model_name: &str, ids: &Mode) -> Result<()> {
         self.call_computed_method_on_ids(model_name, ids.get_ids_ref())?;
 
+        let model = self.model_manager.get_model(model_name);
+        let dirty_many2many = self
+            .get_dirty_filtered_records(model_name, ids.get_ids_ref(), |f| model.is_many2many(f));
+        self.save_many2many_to_db(model_name, &dirty_many2many)?;
+
         let dirty_map_of_fields = self.get_dirty_stored_records(model_name, ids.get_ids_ref());
 
         if dirty_map_of_fields.is_empty() {
@@ -264,19 +435,19 @@ impl<'mm, 'db> Environment<'mm, 'db> {
     }
 
     /// Get all dirty stored fields for given model
-    fn get_dirty_stored_models(&self, model_name: &str) -> HashMap<u32, MapOfFields> {
+    fn get_dirty_stored_models(&self, model_name: &str) -> HashMap<u64, MapOfFields> {
         let model = self.model_manager.get_model(model_name);
         self.cache
             .get_dirty_models(model_name, |field_name| model.is_stored(field_name))
     }
 
     /// Get dirty fields from given list of fields
-    fn get_dirty_fields(&self, model_name: &str, fields: &[&str]) -> HashMap<u32, MapOfFields> {
+    fn get_dirty_fields(&self, model_name: &str, fields: &[&str]) -> HashMap<u64, MapOfFields> {
         self.cache.get_dirty_fields(model_name, fields)
     }
 
     /// Get all dirty stored fields for given records
-    fn get_dirty_stored_records(&self, model_name: &str, ids: &[u32]) -> HashMap<u32, MapOfFields> {
+    fn get_dirty_stored_records(&self, model_name: &str, ids: &[u64]) -> HashMap<u64, MapOfFields> {
         let model = self.model_manager.get_model(model_name);
         self.get_dirty_filtered_records(model_name, ids, |field_name| model.is_stored(field_name))
     }
@@ -285,9 +456,9 @@ impl<'mm, 'db> Environment<'mm, 'db> {
     fn get_dirty_filtered_records<F>(
         &self,
         model_name: &str,
-        ids: &[u32],
+        ids: &[u64],
         field_filter: F,
-    ) -> HashMap<u32, MapOfFields>
+    ) -> HashMap<u64, MapOfFields>
     where
         F: Fn(&str) -> bool,
     {
@@ -303,22 +474,35 @@ impl<'mm, 'db> Environment<'mm, 'db> {
         if ids.is_empty() {
             return Ok(HashMap::new());
         }
-        let domain = make_domain!([("id", "=", ids.clone())]);
-        let data = self
-            .database
-            .search(model_name, fields, &domain, self.model_manager)?;
-        Ok(data
-            .into_iter()
-            .map(|(id, map)| {
-                let map: HashMap<String, Option<FieldType>> = map
-                    .into_iter()
-                    .map(|(key, value)| (key.to_string(), value.map(|v| v.into())))
-                    .collect();
-                let mut map_of_fields = MapOfFields::new(map);
-                map_of_fields.insert("id", id);
-                (id.into(), map_of_fields)
-            })
-            .collect())
+        let domain = make_domain!([("id", "in", ids.clone())]);
+        let data = self.database.search(
+            model_name,
+            fields,
+            &domain,
+            &SearchOptions::unordered(),
+            self.model_manager,
+        )?;
+        let model = self.model_manager.get_model(model_name);
+        let mut result = HashMap::new();
+        for (id, map) in data {
+            let mut fields = HashMap::new();
+            for (field_name, value) in map {
+                let value = match value {
+                    // Only the key of the content is stored in the database
+                    Some(crate::database::FieldType::String(key))
+                        if model.is_binary(field_name) =>
+                    {
+                        Some(FieldType::Binary(self.filestore.read(&key)?))
+                    }
+                    value => value.map(|v| v.into()),
+                };
+                fields.insert(field_name.to_string(), value);
+            }
+            let mut map_of_fields = MapOfFields::new(fields);
+            map_of_fields.insert("id", id);
+            result.insert(id.into(), map_of_fields);
+        }
+        Ok(result)
     }
 
     /// Save existing data to the database.
@@ -331,8 +515,9 @@ impl<'mm, 'db> Environment<'mm, 'db> {
     fn save_data_to_db(
         &mut self,
         model_name: &str,
-        data: &HashMap<u32, &MapOfFields>,
-    ) -> Result<u32> {
+        data: &HashMap<u64, &MapOfFields>,
+    ) -> Result<u64> {
+        self.write_blobs_to_filestore(data.values().copied())?;
         self.database.update(model_name, data)
     }
 
@@ -344,10 +529,136 @@ impl<'mm, 'db> Environment<'mm, 'db> {
         &mut self,
         model_name: &str,
         data: &Vec<&MapOfFields>,
-    ) -> Result<Vec<u32>> {
+    ) -> Result<Vec<u64>> {
+        self.write_blobs_to_filestore(data.iter().copied())?;
         self.database.create(model_name, data)
     }
 
+    /// Save contents of Binary fields of given data to the filestore. The database only stores
+    /// their key
+    fn write_blobs_to_filestore<'a>(
+        &mut self,
+        data: impl Iterator<Item = &'a MapOfFields>,
+    ) -> Result<()> {
+        for map_of_fields in data {
+            for value in map_of_fields.fields.values() {
+                if let Some(FieldType::Binary(content)) = value {
+                    self.filestore.write(content)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Delete contents of the filestore that are not referenced by any saved record, and return
+    /// the number of deleted contents.
+    ///
+    /// Contents written less than given grace period ago are kept, as they could be referenced by
+    /// a transaction that is not committed yet
+    pub fn collect_filestore_garbage(&mut self, grace_period: Duration) -> Result<usize> {
+        let written_before = SystemTime::now()
+            .checked_sub(grace_period)
+            .unwrap_or(SystemTime::UNIX_EPOCH);
+        let referenced_blobs = self.get_referenced_blobs()?;
+        // Contents are listed after retrieving references, so that contents written again by a
+        //  transaction committed in the meantime have a recent write time and are kept
+        let mut count = 0;
+        for (key, written_at) in self.filestore.list()? {
+            if written_at <= written_before && !referenced_blobs.contains(&key) {
+                self.filestore.delete(&key)?;
+                count += 1;
+            }
+        }
+        Ok(count)
+    }
+
+    /// Keys of contents referenced by a Binary field of any record.
+    ///
+    /// Values are retrieved from the database, so contents referenced by records that are not yet
+    /// saved are not returned
+    fn get_referenced_blobs(&mut self) -> Result<HashSet<String>> {
+        let model_manager = self.model_manager;
+        let mut referenced_blobs = HashSet::new();
+        for model in model_manager.get_models().values() {
+            for field_name in model.get_binary_fields() {
+                let data = self.database.search(
+                    &model.name,
+                    &[field_name],
+                    &make_domain!([(field_name, "!=", None)]),
+                    &SearchOptions::unordered(),
+                    model_manager,
+                )?;
+                for (_, mut map) in data {
+                    if let Some(Some(crate::database::FieldType::String(key))) =
+                        map.remove(field_name)
+                    {
+                        referenced_blobs.insert(key);
+                    }
+                }
+            }
+        }
+        Ok(referenced_blobs)
+    }
+
+    /// Save values of M2M fields of given data to their relation table, and clear their dirty
+    /// state.
+    ///
+    /// Other fields are ignored
+    fn save_many2many_to_db(
+        &mut self,
+        model_name: &str,
+        data: &HashMap<u64, MapOfFields>,
+    ) -> Result<()> {
+        let model = self.model_manager.get_model(model_name);
+        let mut links: HashMap<&str, HashMap<u64, Vec<u64>>> = HashMap::new();
+        for (id, map_of_fields) in data {
+            for (field_name, value) in &map_of_fields.fields {
+                if !model.is_many2many(field_name) {
+                    continue;
+                }
+                let target_ids = match value {
+                    Some(FieldType::Refs(ids)) => ids.clone(),
+                    Some(FieldType::Ref(id)) => vec![*id],
+                    _ => vec![],
+                };
+                links.entry(field_name).or_default().insert(*id, target_ids);
+            }
+        }
+        for (field_name, data) in links {
+            self.database
+                .write_many2many(model_name, field_name, &data, self.model_manager)?;
+            let ids: MultipleIds = data.keys().collect();
+            self.cache
+                .clear_dirty_fields(model_name, &[field_name], &ids);
+        }
+        Ok(())
+    }
+
+    /// Retrieve ids targeted by given M2M field of given records from the database.
+    ///
+    /// Inverse fields use the same relation table, so they are saved first
+    fn get_many2many_from_db(
+        &mut self,
+        model_name: &str,
+        field_name: &str,
+        ids: &[u64],
+    ) -> Result<HashMap<u64, Vec<u64>>> {
+        let model_manager = self.model_manager;
+        let field = model_manager
+            .get_model(model_name)
+            .get_internal_field(field_name);
+        if let Some(FieldReference {
+            target_model,
+            inverse_field: FieldReferenceType::M2M { inverse_fields, .. },
+        }) = &field.inverse
+        {
+            let inverse_fields: Vec<&str> = inverse_fields.iter().map(|f| f.as_str()).collect();
+            self.save_fields_to_db(target_model, &inverse_fields)?;
+        }
+        self.database
+            .read_many2many(model_name, field_name, ids, model_manager)
+    }
+
     // ------------------------------------------
     // |             Retrieve Logic             |
     // ------------------------------------------
@@ -376,16 +687,440 @@ impl<'mm, 'db> Environment<'mm, 'db> {
     where
         M: Model<MultipleIds>,
     {
-        // TODO Add limit
+        self.search_with_options(domain, &SearchOptions::default())
+    }
+
+    /// Search given domain for given model, sorted, limited and offset following given options.
+    ///
+    /// If no order is given, the default order of the model is used
+    ///
+    /// The domain is validated first (see `validate`), so that an invalid path or value returns
+    /// an error instead of panicking.
+    ///
+    /// Before performing any search, save any data related to any field given in the domain or in
+    /// the order.
+    pub fn search_with_options<M>(
+        &mut self,
+        domain: &SearchType,
+        options: &SearchOptions,
+    ) -> Result<M>
+    where
+        M: Model<MultipleIds>,
+    {
         let model_name = M::_get_model_name();
+        validate(domain, model_name, self.model_manager)?;
         self.save_domain_fields_to_db(model_name, domain)?;
+        let order = self.model_manager.get_model(model_name).get_order(options);
+        let order_fields = order
+            .iter()
+            .map(|order| LeftTuple {
+                path: order.path.clone(),
+            })
+            .collect::<Vec<_>>();
+        let fields_to_save = self.get_fields_to_save(model_name, &order_fields.iter().collect())?;
+        for (model_name, fields) in fields_to_save {
+            self.save_fields_to_db(model_name, &fields)?;
+        }
 
         let ids = self
             .database
-            .browse(M::_get_model_name(), domain, self.model_manager)?;
+            .browse(model_name, domain, options, self.model_manager)?;
+        Ok(M::create_instance(ids.into()))
+    }
+
+    /// Count records of given model that match given domain
+    ///
+    /// Before counting, save any data related to any field given in the domain.
+    pub fn search_count<M>(&mut self, domain: &SearchType) -> Result<u64>
+    where
+        M: Model<MultipleIds>,
+    {
+        let model_name = M::_get_model_name();
+        validate(domain, model_name, self.model_manager)?;
+        self.save_domain_fields_to_db(model_name, domain)?;
+        self.database.count(model_name, domain, self.model_manager)
+    }
+
+    /// Check if at least one record of given model matches given domain
+    ///
+    /// Before searching, save any data related to any field given in the domain.
+    pub fn search_exists<M>(&mut self, domain: &SearchType) -> Result<bool>
+    where
+        M: Model<MultipleIds>,
+    {
+        let model_name = M::_get_model_name();
+        validate(domain, model_name, self.model_manager)?;
+        self.save_domain_fields_to_db(model_name, domain)?;
+        self.database.exists(model_name, domain, self.model_manager)
+    }
+
+    /// Group records of given model that match given domain, and compute given aggregates on each
+    /// group
+    ///
+    /// Groups are sorted by their keys, in the order given in `group_by`. Without any `group_by`,
+    /// a single group containing all matching records is returned.
+    ///
+    /// Before grouping, save any data related to any field given in the domain, in `group_by` or
+    /// in aggregates.
+    pub fn read_group<M>(
+        &mut self,
+        domain: &SearchType,
+        group_by: &[&str],
+        aggregates: &[Aggregate],
+    ) -> Result<Vec<Group>>
+    where
+        M: Model<MultipleIds>,
+    {
+        let model_name = M::_get_model_name();
+        validate(domain, model_name, self.model_manager)?;
+        self.save_domain_fields_to_db(model_name, domain)?;
+        let fields = group_by
+            .iter()
+            .map(|path| LeftTuple {
+                path: path.split('.').map(|str| str.to_string()).collect(),
+            })
+            .chain(aggregates.iter().map(|aggregate| LeftTuple {
+                path: aggregate.path.clone(),
+            }))
+            .collect::<Vec<_>>();
+        let fields_to_save = self.get_fields_to_save(model_name, &fields.iter().collect())?;
+        for (model_name, fields) in fields_to_save {
+            self.save_fields_to_db(model_name, &fields)?;
+        }
+
+        self.database
+            .read_group(model_name, domain, group_by, aggregates, self.model_manager)
+    }
+
+    /// Keep given records that match given domain.
+    ///
+    /// The domain is evaluated in memory on values of the cache, without saving anything to the
+    /// database. Missing values are loaded (or computed) first, including the ones of records
+    /// targeted by a path ("order.name"). Values are compared as the database does (see
+    /// `cache::is_value_valid`), so the result is the same as searching given domain on those
+    /// records.
+    ///
+    /// The order of given records is kept
+    pub fn filtered_domain<M>(&mut self, records: &M, domain: &SearchType) -> Result<M>
+    where
+        M: Model<MultipleIds>,
+    {
+        let model_name = M::_get_model_name();
+        validate(domain, model_name, self.model_manager)?;
+        let ids = records.get_id_mode().get_ids_ref();
+        let valid_ids = self.filter_ids(model_name, ids, domain)?;
+        let ids: Vec<u64> = ids
+            .iter()
+            .filter(|id| valid_ids.contains(id))
+            .copied()
+            .collect();
         Ok(M::create_instance(ids.into()))
     }
 
+    /// Ids among given ones that match given domain
+    fn filter_ids(
+        &mut self,
+        model_name: &str,
+        ids: &[u64],
+        domain: &SearchType,
+    ) -> Result<HashSet<u64>> {
+        Ok(match domain {
+            SearchType::And(left, right) => {
+                let left = self.filter_ids(model_name, ids, left)?;
+                let ids: Vec<u64> = ids.iter().filter(|id| left.contains(id)).copied().collect();
+                self.filter_ids(model_name, &ids, right)?
+            }
+            SearchType::Or(left, right) => {
+                let mut left = self.filter_ids(model_name, ids, left)?;
+                let ids: Vec<u64> = ids
+                    .iter()
+                    .filter(|id| !left.contains(id))
+                    .copied()
+                    .collect();
+                left.extend(self.filter_ids(model_name, &ids, right)?);
+                left
+            }
+            SearchType::Not(search_type) => {
+                let excluded = self.filter_ids(model_name, ids, search_type)?;
+                ids.iter()
+                    .filter(|id| !excluded.contains(id))
+                    .copied()
+                    .collect()
+            }
+            SearchType::Tuple(SearchTuple {
+                left: LeftTuple { path },
+                operator,
+                right,
+            }) => self.filter_path(model_name, ids, path, operator, right)?,
+            SearchType::Nothing => HashSet::new(),
+        })
+    }
+
+    /// Ids among given ones that match given condition.
+    ///
+    /// Values of each field of the path are loaded for all records at once, then the rest of the
+    /// path is evaluated on the targeted records
+    fn filter_path(
+        &mut self,
+        model_name: &str,
+        ids: &[u64],
+        path: &[String],
+        operator: &SearchOperator,
+        right: &RightTuple,
+    ) -> Result<HashSet<u64>> {
+        if ids.is_empty() {
+            return Ok(HashSet::new());
+        }
+        let (field_name, rest) = path.split_first().ok_or_else(|| UnknownFieldError {
+            model_name: model_name.to_string(),
+            field_name: "".to_string(),
+        })?;
+        if field_name == "id" && rest.is_empty() {
+            return Ok(ids
+                .iter()
+                .filter(|id| is_value_valid(Some(&FieldType::Ref(**id)), operator, right))
+                .copied()
+                .collect());
+        }
+        if rest.is_empty() && matches!(operator, SearchOperator::ChildOf | SearchOperator::ParentOf)
+        {
+            return self.filter_hierarchy(model_name, ids, field_name, operator, right);
+        }
+        if rest.is_empty() && matches!(operator, SearchOperator::Any | SearchOperator::NotAny) {
+            return self.filter_any(model_name, ids, field_name, operator, right);
+        }
+
+        let field = self
+            .model_manager
+            .get_model(model_name)
+            .get_internal_field(field_name);
+        let inverse = field.inverse.clone();
+        let is_json = field.is_json();
+        let values: Vec<Option<FieldType>> = self
+            .get_fields_value(model_name, field_name, &MultipleIds::from(ids.to_vec()))?
+            .into_iter()
+            .map(|value| value.cloned())
+            .collect();
+        let records = ids.iter().zip(values);
+
+        Ok(match inverse {
+            // The rest of the path are keys of the JSON value
+            _ if is_json && !rest.is_empty() => records
+                .filter(|(_, value)| is_json_value_valid(value.as_ref(), rest, operator, right))
+                .map(|(id, _)| *id)
+                .collect(),
+            Some(FieldReference {
+                target_model,
+                inverse_field: FieldReferenceType::O2M { .. } | FieldReferenceType::M2M { .. },
+            }) => {
+                let get_targets = |value: &Option<FieldType>| match value {
+                    Some(FieldType::Refs(ids)) => ids.clone(),
+                    _ => vec![],
+                };
+                if rest.is_empty() {
+                    // "lines = None" means "no line", "lines != None" means "at least one line",
+                    // and other conditions are applied on ids of lines
+                    records
+                        .filter(|(_, value)| {
+                            let targets = get_targets(value);
+                            match (operator, right) {
+                                (SearchOperator::Equal, RightTuple::None) => targets.is_empty(),
+                                (SearchOperator::NotEqual, RightTuple::None) => !targets.is_empty(),
+                                _ => targets.iter().any(|target| {
+                                    is_value_valid(Some(&FieldType::Ref(*target)), operator, right)
+                                }),
+                            }
+                        })
+                        .map(|(id, _)| *id)
+                        .collect()
+                } else {
+                    let records: Vec<(u64, Vec<u64>)> = records
+                        .map(|(id, value)| (*id, get_targets(&value)))
+                        .collect();
+                    let targets: HashSet<u64> = records
+                        .iter()
+                        .flat_map(|(_, targets)| targets.iter().copied())
+                        .collect();
+                    let targets: Vec<u64> = targets.into_iter().collect();
+                    let valid_targets =
+                        self.filter_path(&target_model, &targets, rest, operator, right)?;
+                    records
+                        .into_iter()
+                        .filter(|(_, targets)| {
+                            targets.iter().any(|target| valid_targets.contains(target))
+                        })
+                        .map(|(id, _)| id)
+                        .collect()
+                }
+            }
+            _ if rest.is_empty() => records
+                .filter(|(_, value)| is_value_valid(value.as_ref(), operator, right))
+                .map(|(id, _)| *id)
+                .collect(),
+            Some(FieldReference {
+                target_model,
+                inverse_field: FieldReferenceType::M2O { .. },
+            }) => {
+                let records: Vec<(u64, Option<u64>)> = records
+                    .map(|(id, value)| match value {
+                        Some(FieldType::Ref(target)) => (*id, Some(target)),
+                        _ => (*id, None),
+                    })
+                    .collect();
+                let targets: HashSet<u64> =
+                    records.iter().filter_map(|(_, target)| *target).collect();
+                let targets: Vec<u64> = targets.into_iter().collect();
+                // Records without any linked record don't match, whatever the condition is
+                let valid_targets =
+                    self.filter_path(&target_model, &targets, rest, operator, right)?;
+                records
+                    .into_iter()
+                    .filter(|(_, target)| {
+                        target.is_some_and(|target| valid_targets.contains(&target))
+                    })
+                    .map(|(id, _)| id)
+                    .collect()
+            }
+            None => {
+                return Err(InvalidPathError {
+                    model_name: model_name.to_string(),
+                    field_name: field_name.clone(),
+                }
+                .into());
+            }
+        })
+    }
+
+    /// Ids among given ones where at least one ("any") or none ("not any") of the records
+    /// targeted by given M2O, O2M or M2M field matches the sub-domain
+    fn filter_any(
+        &mut self,
+        model_name: &str,
+        ids: &[u64],
+        field_name: &str,
+        operator: &SearchOperator,
+        right: &RightTuple,
+    ) -> Result<HashSet<u64>> {
+        let target_model = self
+            .model_manager
+            .get_model(model_name)
+            .get_internal_field(field_name)
+            .inverse
+            .as_ref()
+            .map(|inverse| inverse.target_model.clone())
+            .ok_or_else(|| InvalidPathError {
+                model_name: model_name.to_string(),
+                field_name: field_name.to_string(),
+            })?;
+        let domain = match right {
+            RightTuple::Domain(domain) => domain.as_ref(),
+            _ => &SearchType::Nothing,
+        };
+        let records: Vec<(u64, Vec<u64>)> = ids
+            .iter()
+            .zip(self.get_fields_value(model_name, field_name, &MultipleIds::from(ids.to_vec()))?)
+            .map(|(id, value)| match value {
+                Some(FieldType::Ref(target)) => (*id, vec![*target]),
+                Some(FieldType::Refs(targets)) => (*id, targets.clone()),
+                _ => (*id, vec![]),
+            })
+            .collect();
+        let targets: HashSet<u64> = records
+            .iter()
+            .flat_map(|(_, targets)| targets.iter().copied())
+            .collect();
+        let targets: Vec<u64> = targets.into_iter().collect();
+        // An empty domain matches all target records
+        let valid_targets = match domain {
+            SearchType::Nothing => HashSet::from_iter(targets),
+            domain => self.filter_ids(&target_model, &targets, domain)?,
+        };
+        Ok(records
+            .into_iter()
+            .filter(|(_, targets)| {
+                targets.iter().any(|target| valid_targets.contains(target))
+                    == (*operator == SearchOperator::Any)
+            })
+            .map(|(id, _)| id)
+            .collect())
+    }
+
+    /// Ids among given ones that are children ("child_of") or parents ("parent_of") of records
+    /// given in the condition, following given M2O field
+    fn filter_hierarchy(
+        &mut self,
+        model_name: &str,
+        ids: &[u64],
+        field_name: &str,
+        operator: &SearchOperator,
+        right: &RightTuple,
+    ) -> Result<HashSet<u64>> {
+        let targets: Vec<u64> = right
+            .values()
+            .iter()
+            .filter_map(|value| match value {
+                RightTuple::UInteger(id) => Some(*id),
+                RightTuple::Integer(id) => u64::try_from(*id).ok(),
+                _ => None,
+            })
+            .collect();
+        if *operator == SearchOperator::ChildOf {
+            let parents = self.get_parents(model_name, field_name, ids)?;
+            Ok(ids
+                .iter()
+                .filter(|id| {
+                    get_ancestors(&parents, **id)
+                        .iter()
+                        .any(|ancestor| targets.contains(ancestor))
+                })
+                .copied()
+                .collect())
+        } else {
+            let parents = self.get_parents(model_name, field_name, &targets)?;
+            let ancestors: HashSet<u64> = targets
+                .iter()
+                .flat_map(|target| get_ancestors(&parents, *target))
+                .collect();
+            Ok(ids
+                .iter()
+                .filter(|id| ancestors.contains(id))
+                .copied()
+                .collect())
+        }
+    }
+
+    /// Parent of given records and of all their ancestors, following given M2O field
+    fn get_parents(
+        &mut self,
+        model_name: &str,
+        field_name: &str,
+        ids: &[u64],
+    ) -> Result<HashMap<u64, Option<u64>>> {
+        let mut parents: HashMap<u64, Option<u64>> = HashMap::new();
+        let mut to_load: Vec<u64> = ids.to_vec();
+        while !to_load.is_empty() {
+            let values =
+                self.get_fields_value(model_name, field_name, &MultipleIds::from(to_load.clone()))?;
+            let loaded: Vec<(u64, Option<u64>)> = to_load
+                .iter()
+                .zip(values)
+                .map(|(id, value)| match value {
+                    Some(FieldType::Ref(parent)) => (*id, Some(*parent)),
+                    _ => (*id, None),
+                })
+                .collect();
+            parents.extend(loaded);
+            let missing: HashSet<u64> = parents
+                .values()
+                .flatten()
+                .filter(|parent| !parents.contains_key(parent))
+                .copied()
+                .collect();
+            to_load = missing.into_iter().collect();
+        }
+        Ok(parents)
+    }
+
     /// Get the value of given field for given id.
     ///
     /// If field is not in cache, load it
@@ -429,7 +1164,8 @@ impl<'mm, 'db> Environment<'mm, 'db> {
     ///
     /// If some ids are invalid or need to be loaded, load them (or compute them if needed)
     ///
-    /// If given field_name is a O2M, load it along with its M2O
+    /// If given field_name is a O2M, load it along with its M2O. If it's a M2M, load it from its
+    /// relation table
     fn ensure_fields_in_cache<Mode: IdMode>(
         &mut self,
         model_name: &str,
@@ -470,6 +1206,21 @@ impl<'mm, 'db> Environment<'mm, 'db> {
                 // TODO Check if a O2M computed field is correctly handled here
                 // This could be a computed one. Call it
                 self.call_compute_method(model_name, &ids_not_in_cache, &[field_name])?;
+            } else if field_info.is_many2many() {
+                // M2M, load the field from its relation table
+                let mut result =
+                    self.get_many2many_from_db(model_name, field_name, ids_not_in_cache.as_ref())?;
+                for id in &ids_not_in_cache {
+                    let field_value = result.remove(&id.get_id()).map(FieldType::Refs);
+                    self.cache.insert_field_in_cache(
+                        model_name,
+                        field_name,
+                        &[id.get_id()],
+                        field_value,
+                        &Dirty::NotUpdateDirty,
+                        &Update::UpdateIfExists,
+                    );
+                }
             } else if let Some(FieldReference {
                 target_model,
                 inverse_field: FieldReferenceType::O2M { inverse_field },
@@ -479,7 +1230,7 @@ impl<'mm, 'db> Environment<'mm, 'db> {
                 // TODO Add search_group(...)
                 self.save_fields_to_db(target_model, &[inverse_field])?;
                 // Load from database
-                let mut result: HashMap<u32, Vec<u32>> =
+                let mut result: HashMap<u64, Vec<u64>> =
                     HashMap::with_capacity(ids_not_in_cache.get_ids_ref().len());
                 for id in &ids_not_in_cache {
                     result.insert(id.get_id(), vec![]);
@@ -488,7 +1239,8 @@ impl<'mm, 'db> Environment<'mm, 'db> {
                 let database_result = self.database.search(
                     target_model,
                     &[inverse_field],
-                    &make_domain!([(inverse_field, "=", ids_not_in_cache)]),
+                    &make_domain!([(inverse_field, "in", ids_not_in_cache)]),
+                    &SearchOptions::unordered(),
                     self.model_manager,
                 )?;
                 for (id, mut map) in database_result {
@@ -555,6 +1307,30 @@ impl<'mm, 'db> Environment<'mm, 'db> {
         E: Into<FieldType>,
     {
         let field_type: Option<FieldType> = value.map(|value| value.into());
+        let internal_model = self.model_manager.get_model(model_name);
+        let field_type = match internal_model.fields.get(field_name) {
+            Some(field) if field.is_rounded() && field.currency_field.is_some() => {
+                // Monetary fields are rounded to the currency of each record
+                for id in ids.get_ids_ref() {
+                    let currency_id = self.get_currency_id(model_name, field, *id)?;
+                    let value =
+                        self.round_decimal(model_name, field, currency_id, field_type.clone())?;
+                    self.save_field_to_cache::<SingleId>(
+                        model_name,
+                        field_name,
+                        &(*id).into(),
+                        value,
+                        &Dirty::UpdateDirty,
+                        &Update::UpdateIfExists,
+                    )?;
+                }
+                return Ok(());
+            }
+            Some(field) if field.is_rounded() => {
+                self.round_decimal(model_name, field, None, field_type)?
+            }
+            _ => field_type,
+        };
 
         self.save_field_to_cache(
             model_name,
@@ -563,9 +1339,129 @@ impl<'mm, 'db> Environment<'mm, 'db> {
             field_type,
             &Dirty::UpdateDirty,
             &Update::UpdateIfExists,
+        )?;
+        self.round_monetary_values(model_name, field_name, ids)
+    }
+
+    /// Round again Monetary fields of given records whose currency is given field, as their
+    /// currency has been changed. Nothing is done if given field isn't the currency of any
+    /// Monetary field
+    fn round_monetary_values<Mode: IdMode>(
+        &mut self,
+        model_name: &str,
+        currency_field: &str,
+        ids: &Mode,
+    ) -> Result<()> {
+        let model_manager = self.model_manager;
+        let internal_model = model_manager.get_model(model_name);
+        for field_name in model_manager.get_monetary_fields(model_name, currency_field) {
+            let field = internal_model.get_internal_field(field_name);
+            for id in ids.get_ids_ref() {
+                let value = self
+                    .get_field_value(model_name, field_name, &(*id).into())?
+                    .cloned();
+                let currency_id = self.get_currency_id(model_name, field, *id)?;
+                let rounded_value =
+                    self.round_decimal(model_name, field, currency_id, value.clone())?;
+                if rounded_value != value {
+                    self.save_field_to_cache::<SingleId>(
+                        model_name,
+                        field_name,
+                        &(*id).into(),
+                        rounded_value,
+                        &Dirty::UpdateDirty,
+                        &Update::UpdateIfExists,
+                    )?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Id of the currency of given record, used to round given Monetary field
+    fn get_currency_id(
+        &mut self,
+        model_name: &str,
+        field: &FinalInternalField,
+        id: u64,
+    ) -> Result<Option<u64>> {
+        let Some(currency_field) = &field.currency_field else {
+            return Ok(None);
+        };
+        Ok(
+            match self.get_field_value(model_name, &currency_field.field_name, &id.into())? {
+                Some(FieldType::Ref(currency_id)) if *currency_id != 0 => Some(*currency_id),
+                _ => None,
+            },
         )
     }
 
+    /// Round given value if it's written on a Decimal field that needs to be rounded.
+    ///
+    /// Monetary fields are rounded to the decimal places of given currency. Without any currency,
+    /// values are rounded to the digits of the field, if any
+    fn round_decimal(
+        &mut self,
+        model_name: &str,
+        field: &FinalInternalField,
+        currency_id: Option<u64>,
+        value: Option<FieldType>,
+    ) -> Result<Option<FieldType>> {
+        let Some(FieldType::Decimal(mut value)) = value else {
+            return Ok(value);
+        };
+        let mut decimal_places = field.digits;
+        if let (Some(currency_field), Some(currency_id)) = (&field.currency_field, currency_id) {
+            let internal_model = self.model_manager.get_model(model_name);
+            if let Some(FieldReference { target_model, .. }) = &internal_model
+                .get_internal_field(&currency_field.field_name)
+                .inverse
+                && let Some(FieldType::Integer(currency_decimal_places)) = self.get_field_value(
+                    target_model,
+                    &currency_field.decimal_places_field,
+                    &currency_id.into(),
+                )?
+            {
+                decimal_places = u32::try_from(*currency_decimal_places).ok();
+            }
+        }
+        if let Some(decimal_places) = decimal_places {
+            // Rescaling rounds half away from zero
+            value.rescale(decimal_places);
+        }
+        Ok(Some(FieldType::Decimal(value)))
+    }
+
+    /// Round Decimal values of given data before creating a record.
+    ///
+    /// Monetary fields are rounded to the currency given in this data
+    fn round_decimal_values_on_map(
+        &mut self,
+        model_name: &str,
+        data: &mut MapOfFields,
+    ) -> Result<()> {
+        let final_internal_model = self.model_manager.get_model(model_name);
+        for field in final_internal_model.fields.values() {
+            if !field.is_rounded() {
+                continue;
+            }
+            let Some(value) = data.fields.get(&field.name).cloned() else {
+                continue;
+            };
+            let currency_id = field.currency_field.as_ref().and_then(|currency_field| {
+                match data.fields.get(&currency_field.field_name) {
+                    Some(Some(FieldType::Ref(currency_id))) if *currency_id != 0 => {
+                        Some(*currency_id)
+                    }
+                    _ => None,
+                }
+            });
+            let value = self.round_decimal(model_name, field, currency_id, value)?;
+            data.fields.insert(field.name.clone(), value);
+        }
+        Ok(())
+    }
+
     /// Retrieve given field from the cache, or from the database if not loaded in cache
     ///
     /// If field is retrieved from the database, it will not be added to the cache
@@ -582,9 +1478,9 @@ impl<'mm, 'db> Environment<'mm, 'db> {
         ids: &Mode,
     ) -> Result<Vec<(bool, Option<FieldType>)>> {
         let size = ids.get_ids_ref().len();
-        let mut map_result: HashMap<u32, (bool, Option<FieldType>)> = HashMap::with_capacity(size);
+        let mut map_result: HashMap<u64, (bool, Option<FieldType>)> = HashMap::with_capacity(size);
         let cache_model = self.cache.get_cache_models(model_name);
-        let mut ids_not_in_cache: Vec<u32> = Vec::with_capacity(size);
+        let mut ids_not_in_cache: Vec<u64> = Vec::with_capacity(size);
         for id in ids.get_ids_ref() {
             if let Some(model) = cache_model.get_model(id) {
                 if let Some(field_value) = model.get_field(field_name) {
@@ -606,13 +1502,20 @@ impl<'mm, 'db> Environment<'mm, 'db> {
                 let database_result = self.database.search(
                     model_name,
                     &[field_name],
-                    &make_domain!([("id", "=", ids_not_in_cache)]),
+                    &make_domain!([("id", "in", ids_not_in_cache)]),
+                    &SearchOptions::unordered(),
                     self.model_manager,
                 )?;
                 for (id, mut map) in database_result {
                     let field_value = map.remove(field_name).unwrap();
                     map_result.insert(id, (false, field_value.map(|value| value.into())));
                 }
+            } else if field_info.is_many2many() {
+                let mut result =
+                    self.get_many2many_from_db(model_name, field_name, &ids_not_in_cache)?;
+                for id in ids_not_in_cache {
+                    map_result.insert(id, (false, result.remove(&id).map(FieldType::Refs)));
+                }
             } else if let Some(FieldReference {
                 target_model,
                 inverse_field: FieldReferenceType::O2M { inverse_field },
@@ -622,7 +1525,7 @@ impl<'mm, 'db> Environment<'mm, 'db> {
                 // O2M, save data to the database, and then make a request
                 self.save_fields_to_db(target_model, &[inverse_field])?;
                 // Load from database
-                let mut result: HashMap<u32, Vec<u32>> =
+                let mut result: HashMap<u64, Vec<u64>> =
                     HashMap::with_capacity(ids_not_in_cache.len());
                 for id in &ids_not_in_cache {
                     result.insert(*id, vec![]);
@@ -631,7 +1534,8 @@ impl<'mm, 'db> Environment<'mm, 'db> {
                 let database_result = self.database.search(
                     target_model,
                     &[inverse_field],
-                    &make_domain!([(inverse_field, "=", ids_not_in_cache)]),
+                    &make_domain!([(inverse_field, "in", ids_not_in_cache)]),
+                    &SearchOptions::unordered(),
                     self.model_manager,
                 )?;
                 for (id, mut map) in database_result {
@@ -687,7 +1591,8 @@ impl<'mm, 'db> Environment<'mm, 'db> {
 
     /// Save given field to cache.
     ///
-    /// This method ensure M2O & O2M are correctly linked in cache (if those fields are loaded)
+    /// This method ensure M2O & O2M, and both sides of M2M, are correctly linked in cache (if those
+    /// fields are loaded)
     fn save_field_to_cache<Mode: IdMode>(
         &mut self,
         model_name: &str,
@@ -708,12 +1613,89 @@ impl<'mm, 'db> Environment<'mm, 'db> {
             inverse_field,
         }) = &field_info.inverse
         {
-            // M2O or O2M
+            // M2O, O2M or M2M
             return match inverse_field {
-                // For now, M2M is not handled, so it's a O2M (as there is an inverse field)
-                // Call this method for each field that has been modified.
-                // To be able to do this, we need to retrieve old data, and compare it with the new data
-                //FieldReferenceType::M2M { ... } => { ... }
+                FieldReferenceType::M2M { inverse_fields, .. } => {
+                    let mut new_ids = match value.clone() {
+                        None => vec![],
+                        Some(FieldType::Ref(id)) => vec![id],
+                        Some(FieldType::Refs(ids)) => ids,
+                        _ => panic!(
+                            "Only Ref and Refs are accepted field type, and not {:?}",
+                            value
+                        ),
+                    };
+                    // A record can only be linked once
+                    let mut seen = HashSet::new();
+                    new_ids.retain(|id| seen.insert(*id));
+
+                    let old_values =
+                        self.retrieve_field_from_cache_or_database(model_name, field_name, ids)?;
+                    let mut ids_to_update = Vec::with_capacity(old_values.len());
+                    for (id, (in_cache, old_value)) in ids.get_ids_ref().iter().zip(old_values) {
+                        // If we don't have to update loaded fields, skip them
+                        if in_cache && !is_update_if_exists {
+                            continue;
+                        }
+                        ids_to_update.push(*id);
+                        let old_ids: HashSet<u64> = match old_value {
+                            Some(FieldType::Ref(id)) => HashSet::from([id]),
+                            Some(FieldType::Refs(ids)) => ids.into_iter().collect(),
+                            _ => HashSet::new(),
+                        };
+
+                        // Inverse fields use the same relation table, update them if they are
+                        //  loaded in cache. They don't need to be saved, as this field will be
+                        let cache_models = self.cache.get_cache_models_mut(target_model);
+                        let removed_ids = old_ids.iter().filter(|target| !seen.contains(*target));
+                        let added_ids = new_ids.iter().filter(|target| !old_ids.contains(*target));
+                        for (target_id, is_added) in removed_ids
+                            .map(|target| (target, false))
+                            .chain(added_ids.map(|target| (target, true)))
+                        {
+                            let Some(cache_model) = cache_models.get_model_mut(target_id) else {
+                                continue;
+                            };
+                            for inverse_field in inverse_fields {
+                                let Some(cache_field) = cache_model.get_field_mut(inverse_field)
+                                else {
+                                    continue;
+                                };
+                                let mut inverse_ids = match cache_field.get() {
+                                    Some(FieldType::Refs(ids)) => ids.clone(),
+                                    _ => vec![],
+                                };
+                                inverse_ids.retain(|inverse_id| inverse_id != id);
+                                if is_added {
+                                    inverse_ids.push(*id);
+                                }
+                                if inverse_ids.is_empty() {
+                                    cache_field.clear();
+                                } else {
+                                    cache_field.set(FieldType::Refs(inverse_ids));
+                                }
+                            }
+                        }
+                    }
+
+                    let value = if new_ids.is_empty() {
+                        None
+                    } else {
+                        Some(FieldType::Refs(new_ids))
+                    };
+                    let modified_ids = self.cache.insert_field_in_cache(
+                        model_name,
+                        field_name,
+                        &ids_to_update,
+                        value,
+                        update_dirty,
+                        update_field,
+                    );
+                    if is_update_if_exists {
+                        self.check_compute_on_field(model_name, field_name, &modified_ids)?;
+                    }
+                    Ok(())
+                }
                 FieldReferenceType::O2M { inverse_field } => {
                     let new_ids = match value.clone() {
                         None => HashSet::new(),
@@ -729,8 +1711,8 @@ impl<'mm, 'db> Environment<'mm, 'db> {
                         self.retrieve_field_from_cache_or_database(model_name, field_name, ids)?;
 
                     // For removed ids, we can batch the save call
-                    let mut ids_removed: Vec<u32> = Vec::new();
-                    let mut ids_added: HashMap<u32, Vec<u32>> = HashMap::new();
+                    let mut ids_removed: Vec<u64> = Vec::new();
+                    let mut ids_added: HashMap<u64, Vec<u64>> = HashMap::new();
 
                     for (i, d) in old_values.into_iter().enumerate() {
                         let old_ids = match d {
@@ -772,7 +1754,7 @@ impl<'mm, 'db> Environment<'mm, 'db> {
                     }
                     Ok(())
                 }
-                FieldReferenceType::M2O { inverse_fields } => {
+                FieldReferenceType::M2O { inverse_fields, .. } => {
                     // TODO Later, when we will be able to create a O2M linked to a M2O but with a domain, we need to adapt this code to filter it
 
                     let new_id = match value.clone() {
@@ -814,7 +1796,7 @@ impl<'mm, 'db> Environment<'mm, 'db> {
                                     Some(data)
                                 }
                             })
-                            .collect::<Vec<u32>>();
+                            .collect::<Vec<u64>>();
                     }
                     // As those fields could be modified, we need to set them and their dependencies as to_recompute
                     if is_update_if_exists {
@@ -905,7 +1887,7 @@ impl<'mm, 'db> Environment<'mm, 'db> {
                         );
                     }
 
-                    let mut old_values_ids: Vec<u32> = Vec::new();
+                    let mut old_values_ids: Vec<u64> = Vec::new();
 
                     for (_, old_value) in old_values {
                         if let Some(old_value) = old_value {
@@ -950,7 +1932,7 @@ impl<'mm, 'db> Environment<'mm, 'db> {
         &mut self,
         model_name: &str,
         field_name: &str,
-        ids: &[u32],
+        ids: &[u64],
     ) -> Result<()> {
         let internal_model = self.model_manager.get_model(model_name);
         let internal_field = internal_model.get_internal_field(field_name);
@@ -974,7 +1956,8 @@ impl<'mm, 'db> Environment<'mm, 'db> {
                         let database_result = self.database.search(
                             target_model,
                             &[target_field],
-                            &make_domain!([(target_field, "=", current_ids.clone())]),
+                            &make_domain!([(target_field, "in", current_ids.clone())]),
+                            &SearchOptions::unordered(),
                             self.model_manager,
                         )?;
                         current_ids = database_result
@@ -1033,6 +2016,7 @@ impl<'mm, 'db> Environment<'mm, 'db> {
     }
 
     /// Create new records for a specific model and multiple lists of fields
+    #[allow(dead_code)]
     fn create_new_records_from_maps<M>(&mut self, data: Vec<MapOfFields>) -> Result<M>
     where
         M: Model<MultipleIds>,
@@ -1053,7 +2037,8 @@ impl<'mm, 'db> Environment<'mm, 'db> {
         // Add missing fields
         for d in data.iter_mut() {
             let missing_fields = self.fill_default_values_on_map(model_name, d);
-            missing_fields_lst.push(missing_fields)
+            missing_fields_lst.push(missing_fields);
+            self.round_decimal_values_on_map(model_name, d)?;
         }
         // Create a list that will only contain stored fields (to save in db)
         let mut stored_data = data.clone();
@@ -1117,18 +2102,190 @@ impl<'mm, 'db> Environment<'mm, 'db> {
         let final_internal_model = self.model_manager.get_model(model_name);
         let missing_fields_to_load = final_internal_model.get_missing_fields(data.get_keys());
         for missing_field_to_load in &missing_fields_to_load {
-            let default_value = final_internal_model.get_default_value(missing_field_to_load);
-            if matches!(default_value, FieldType::Ref(0)) {
-                // Do not insert a reference if it's 0 (the default value)
-                // TODO Do not handle this here, but add a real default value to "None"
-                data.insert_none(missing_field_to_load);
-            } else {
-                data.insert_field_type(missing_field_to_load, default_value);
+            match final_internal_model.get_default_value(missing_field_to_load) {
+                Some(FieldType::Ref(0)) | None => {
+                    // Do not insert a reference if it's 0 (the default value)
+                    // TODO Do not handle this here, but add a real default value to "None"
+                    data.insert_none(missing_field_to_load);
+                }
+                Some(default_value) => {
+                    data.insert_field_type(missing_field_to_load, default_value);
+                }
             }
         }
         Some(missing_fields_to_load)
     }
 
+    /// Delete given records of a specific model.
+    ///
+    /// Records are removed from the database and evicted from the cache. Loaded O2M targeting
+    /// those records are updated, and fields depending on them are set as to_recompute.
+    ///
+    /// Records targeting deleted records are deleted, emptied or prevent the deletion, based on the
+    /// ondelete policy of their M2O
+    pub fn unlink<Mode: IdMode>(&mut self, model_name: &str, ids: &Mode) -> Result<()> {
+        let ids = ids.get_ids_ref().clone();
+        self.savepoint(|env| env._unlink(model_name, ids, &mut HashMap::new()))
+    }
+
+    fn _unlink(
+        &mut self,
+        model_name: &str,
+        mut ids: Vec<u64>,
+        unlinking: &mut HashMap<String, HashSet<u64>>,
+    ) -> Result<()> {
+        // Skip records that are already being deleted, as cascades could loop
+        let unlinking_ids = unlinking.entry(model_name.to_string()).or_default();
+        ids.retain(|id| unlinking_ids.insert(*id));
+        if ids.is_empty() {
+            return Ok(());
+        }
+        ids.sort_unstable();
+
+        // Apply the ondelete policy of each M2O targeting those records
+        let model_manager = self.model_manager;
+        for (targeting_model, field_name) in model_manager.get_many2one_targeting(model_name) {
+            let model = model_manager.get_model(targeting_model);
+            let field = model.get_internal_field(field_name);
+            // Cache could contain values that are not yet saved
+            self.save_fields_to_db(&model.name, &[field_name])?;
+            let targeting_records = self.database.search(
+                &model.name,
+                &[field_name],
+                &make_domain!([(field_name, "in", ids.clone())]),
+                &SearchOptions::unordered(),
+                model_manager,
+            )?;
+            let targeting_records: Vec<_> = targeting_records
+                .into_iter()
+                .filter(|(id, _)| {
+                    !unlinking
+                        .get(&model.name)
+                        .is_some_and(|unlinking_ids| unlinking_ids.contains(id))
+                })
+                .collect();
+            let Some((_, first_record)) = targeting_records.first() else {
+                continue;
+            };
+            let targeting_ids: Vec<u64> = targeting_records.iter().map(|(id, _)| *id).collect();
+            match field.get_ondelete().unwrap_or_default() {
+                OnDelete::Cascade => self._unlink(&model.name, targeting_ids, unlinking)?,
+                OnDelete::SetNull => {
+                    self.save_field_to_cache::<MultipleIds>(
+                        &model.name,
+                        &field.name,
+                        &targeting_ids.into(),
+                        None,
+                        &Dirty::UpdateDirty,
+                        &Update::UpdateIfExists,
+                    )?;
+                    self.save_fields_to_db(&model.name, &[&field.name])?;
+                }
+                OnDelete::Restrict => {
+                    let target_id = match first_record.get(field.name.as_str()) {
+                        Some(Some(crate::database::FieldType::UInteger(id))) => *id,
+                        _ => ids[0],
+                    };
+                    return Err(RestrictViolationError {
+                        model_name: model.name.clone(),
+                        field_name: field.name.clone(),
+                        target_model: model_name.to_string(),
+                        target_id,
+                    }
+                    .into());
+                }
+            }
+        }
+
+        // Attachments of those records are deleted with them
+        if model_name != ATTACHMENT_MODEL && model_manager.is_valid_model(ATTACHMENT_MODEL) {
+            self.save_fields_to_db(ATTACHMENT_MODEL, &["res_model", "res_id"])?;
+            let attachment_ids = self.database.browse(
+                ATTACHMENT_MODEL,
+                &make_domain!([
+                    ("res_model", "=", model_name),
+                    ("res_id", "in", ids.clone())
+                ]),
+                &SearchOptions::unordered(),
+                model_manager,
+            )?;
+            self._unlink(ATTACHMENT_MODEL, attachment_ids, unlinking)?;
+        }
+
+        let final_model = self.model_manager.get_model(model_name);
+
+        // Dependencies are retrieved from the database, so fields depending on those records need
+        //  to be set as to_recompute before deleting them
+        for (field_name, final_field) in &final_model.fields {
+            if final_field.is_stored() {
+                self.check_compute_on_field(&final_model.name, field_name, &ids)?;
+            }
+        }
+
+        // Emptying M2O removes those records from related O2M loaded in cache
+        for (field_name, final_field) in &final_model.fields {
+            if let Some(FieldReference {
+                inverse_field: FieldReferenceType::M2O { inverse_fields, .. },
+                ..
+            }) = &final_field.inverse
+                && !inverse_fields.is_empty()
+            {
+                self.save_field_to_cache::<MultipleIds>(
+                    model_name,
+                    field_name,
+                    &ids.clone().into(),
+                    None,
+                    &Dirty::NotUpdateDirty,
+                    &Update::UpdateIfExists,
+                )?;
+            }
+        }
+
+        // Deleted records are removed from loaded M2M targeting them. Links are removed from
+        //  relation tables by the database
+        for model in model_manager.get_models().values() {
+            for field in model.fields.values() {
+                let Some(FieldReference {
+                    target_model,
+                    inverse_field: FieldReferenceType::M2M { .. },
+                }) = &field.inverse
+                else {
+                    continue;
+                };
+                if target_model != model_name {
+                    continue;
+                }
+                let cache_models = self.cache.get_cache_models_mut(&model.name);
+                for cache_model in cache_models.models.values_mut() {
+                    if let Some(cache_field) = cache_model.get_field_mut(&field.name)
+                        && let Some(FieldType::Refs(target_ids)) = &mut cache_field.value
+                    {
+                        target_ids.retain(|target_id| !ids.contains(target_id));
+                        if target_ids.is_empty() {
+                            cache_field.clear();
+                        }
+                    }
+                }
+            }
+        }
+
+        // Contents of Binary fields of those records could now be orphans
+        for field_name in final_model.get_binary_fields() {
+            if self.has_orphan_blobs {
+                break;
+            }
+            self.has_orphan_blobs = self.database.exists(
+                model_name,
+                &make_domain!([("id", "in", ids.clone()), (field_name, "!=", None)]),
+                model_manager,
+            )?;
+        }
+
+        self.database.delete(model_name, &ids)?;
+        self.cache.remove_records_from_cache(model_name, &ids);
+        Ok(())
+    }
+
     // ------------------------------------------
     // |              Other Logic               |
     // ------------------------------------------
@@ -1183,7 +2340,7 @@ impl<'mm, 'db> Environment<'mm, 'db> {
                 .filter(|key| model.is_stored(key))
                 .peekable()
                 .peek()
-                .is_some()
+                .is_none()
             {
                 break;
             }
@@ -1196,7 +2353,7 @@ impl<'mm, 'db> Environment<'mm, 'db> {
                         .values()
                         .flatten()
                         .copied()
-                        .collect::<Vec<u32>>(),
+                        .collect::<Vec<u64>>(),
                 }
                 .into());
             }
@@ -1236,7 +2393,7 @@ impl<'mm, 'db> Environment<'mm, 'db> {
                         .values()
                         .flatten()
                         .copied()
-                        .collect::<Vec<u32>>(),
+                        .collect::<Vec<u64>>(),
                 }
                 .into());
             }
@@ -1246,7 +2403,7 @@ impl<'mm, 'db> Environment<'mm, 'db> {
     }
 
     /// Call computed method on non-stored fields that need to be computed for given model, for given ids
-    fn call_computed_method_on_ids(&mut self, model_name: &str, ids: &[u32]) -> Result<()> {
+    fn call_computed_method_on_ids(&mut self, model_name: &str, ids: &[u64]) -> Result<()> {
         let model = self.model_manager.get_model(model_name);
         for i in 0..=MAX_NUMBER_OF_RECURSION {
             let cache_models = self.cache.get_cache_models(model_name);
@@ -1256,7 +2413,7 @@ impl<'mm, 'db> Environment<'mm, 'db> {
                         let result = value
                             .iter()
                             .filter(|id| ids.contains(id))
-                            .collect::<Vec<&u32>>();
+                            .collect::<Vec<&u64>>();
                         if result.is_empty() {
                             None
                         } else {
@@ -1287,7 +2444,7 @@ impl<'mm, 'db> Environment<'mm, 'db> {
                         .values()
                         .flatten()
                         .copied()
-                        .collect::<Vec<u32>>(),
+                        .collect::<Vec<u64>>(),
                 }
                 .into());
             }
@@ -1317,3 +2474,55 @@ impl<'mm, 'db> Environment<'mm, 'db> {
 }
 
 impl ErasedEnvironment for Environment<'_, '_> {}
+
+/// Check if given value matches given condition, using the same comparison as the database
+fn is_value_valid(
+    value: Option<&FieldType>,
+    operator: &SearchOperator,
+    right: &RightTuple,
+) -> bool {
+    let value: Option<database::FieldType> = value.cloned().map(Into::into);
+    cache::is_value_valid(value.as_ref(), operator, right)
+}
+
+/// Check if the value at given key path of given JSON value is valid for given domain
+fn is_json_value_valid(
+    value: Option<&FieldType>,
+    keys: &[String],
+    operator: &SearchOperator,
+    right: &RightTuple,
+) -> bool {
+    let value: Option<database::FieldType> = value.cloned().map(Into::into);
+    cache::is_json_value_valid(value.as_ref(), keys, operator, right)
+}
+
+/// Given record followed by all its ancestors
+fn get_ancestors(parents: &HashMap<u64, Option<u64>>, id: u64) -> Vec<u64> {
+    let mut result = vec![id];
+    let mut current = id;
+    while let Some(Some(parent)) = parents.get(&current)
+        && !result.contains(parent)
+    {
+        result.push(*parent);
+        current = *parent;
+    }
+    result
+}
+
+/// Sub-domains of "any" and "not any" conditions of given domain, with the path of their field
+fn get_sub_domains(domain: &SearchType) -> Vec<(&Vec<String>, &SearchType)> {
+    match domain {
+        SearchType::And(left, right) | SearchType::Or(left, right) => {
+            let mut result = get_sub_domains(left);
+            result.append(&mut get_sub_domains(right));
+            result
+        }
+        SearchType::Not(search_type) => get_sub_domains(search_type),
+        SearchType::Tuple(SearchTuple {
+            left: LeftTuple { path },
+            right: RightTuple::Domain(sub_domain),
+            ..
+        }) => vec![(path, sub_domain)],
+        SearchType::Tuple(_) | SearchType::Nothing => vec![],
+    }
+}
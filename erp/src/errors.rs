@@ -4,7 +4,7 @@ use std::{error, fmt};
 pub struct MaximumRecursionDepthCompute {
     pub model_name: String,
     pub fields_name: Vec<String>,
-    pub ids: Vec<u32>,
+    pub ids: Vec<u64>,
 }
 
 impl fmt::Display for MaximumRecursionDepthCompute {
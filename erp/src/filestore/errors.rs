@@ -0,0 +1,18 @@
+use std::{error, fmt};
+
+#[derive(Debug, Clone)]
+pub struct BlobNotFoundError {
+    pub key: String,
+}
+
+impl fmt::Display for BlobNotFoundError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Content \"{}\" is not present in the filestore",
+            self.key
+        )
+    }
+}
+
+impl error::Error for BlobNotFoundError {}
@@ -0,0 +1,31 @@
+use erp_types::field::Binary;
+use std::error::Error;
+use std::time::SystemTime;
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+/// Storage of the content of Binary fields.
+///
+/// Contents are identified by their hash (see `erp_types::field::content_hash`), so saving the
+/// same content twice only stores it once
+pub trait FileStore {
+    /// Save given content, and return the key to retrieve it.
+    ///
+    /// If this content is already present, it is not saved again, but its write time is updated
+    /// so that it's not collected before the transaction referencing it is committed
+    fn write(&mut self, content: &[u8]) -> Result<String>;
+
+    /// Retrieve the content saved with given key
+    fn read(&self, key: &str) -> Result<Binary>;
+
+    /// Return true if a content is saved with given key
+    fn contains(&self, key: &str) -> Result<bool>;
+
+    /// Delete the content saved with given key.
+    ///
+    /// Do nothing if there is no content for this key
+    fn delete(&mut self, key: &str) -> Result<()>;
+
+    /// Keys of all saved contents, with the last time each of them was written
+    fn list(&self) -> Result<Vec<(String, SystemTime)>>;
+}
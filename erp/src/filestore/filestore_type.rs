@@ -0,0 +1,48 @@
+use crate::filestore::{FileStore, LocalFileStore, MemoryFileStore};
+use erp_types::field::Binary;
+use std::error::Error;
+use std::time::SystemTime;
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+pub enum FileStoreType<'fs> {
+    Memory(&'fs mut MemoryFileStore),
+    Local(LocalFileStore),
+}
+
+impl<'fs> FileStore for FileStoreType<'fs> {
+    fn write(&mut self, content: &[u8]) -> Result<String> {
+        match self {
+            FileStoreType::Memory(memory) => memory.write(content),
+            FileStoreType::Local(local) => local.write(content),
+        }
+    }
+
+    fn read(&self, key: &str) -> Result<Binary> {
+        match self {
+            FileStoreType::Memory(memory) => memory.read(key),
+            FileStoreType::Local(local) => local.read(key),
+        }
+    }
+
+    fn contains(&self, key: &str) -> Result<bool> {
+        match self {
+            FileStoreType::Memory(memory) => memory.contains(key),
+            FileStoreType::Local(local) => local.contains(key),
+        }
+    }
+
+    fn delete(&mut self, key: &str) -> Result<()> {
+        match self {
+            FileStoreType::Memory(memory) => memory.delete(key),
+            FileStoreType::Local(local) => local.delete(key),
+        }
+    }
+
+    fn list(&self) -> Result<Vec<(String, SystemTime)>> {
+        match self {
+            FileStoreType::Memory(memory) => memory.list(),
+            FileStoreType::Local(local) => local.list(),
+        }
+    }
+}
@@ -0,0 +1,108 @@
+use crate::filestore::{BlobNotFoundError, FileStore};
+use erp_types::field::{Binary, content_hash};
+use std::error::Error;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+/// Filestore saving contents as files in a local directory.
+///
+/// Each content is saved in `<path>/<first 2 characters of its key>/<key>`
+pub struct LocalFileStore {
+    path: PathBuf,
+}
+
+impl LocalFileStore {
+    /// Create a filestore saving contents in the "filestore" folder of given data directory
+    pub fn new<P: AsRef<Path>>(data_dir: P) -> Self {
+        LocalFileStore {
+            path: data_dir.as_ref().join("filestore"),
+        }
+    }
+
+    /// Path of the file containing the content saved with given key.
+    ///
+    /// Keys are read from the database, so anything else than a hash is refused to never access a
+    /// file outside of this filestore
+    fn get_path(&self, key: &str) -> Result<PathBuf> {
+        if key.len() < 2 || !key.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(BlobNotFoundError {
+                key: key.to_string(),
+            }
+            .into());
+        }
+        Ok(self.path.join(&key[..2]).join(key))
+    }
+}
+
+impl FileStore for LocalFileStore {
+    fn write(&mut self, content: &[u8]) -> Result<String> {
+        let key = content_hash(content);
+        let path = self.get_path(&key)?;
+        if path.exists() {
+            fs::File::options()
+                .write(true)
+                .open(&path)?
+                .set_modified(SystemTime::now())?;
+            return Ok(key);
+        }
+        let Some(folder) = path.parent() else {
+            unreachable!("Path of a content always has a parent");
+        };
+        fs::create_dir_all(folder)?;
+        // Write to a temporary file first, so that a partially written file is never read
+        let temp_path = folder.join(format!(".{key}.{}.tmp", std::process::id()));
+        fs::write(&temp_path, content)?;
+        fs::rename(&temp_path, &path)?;
+        Ok(key)
+    }
+
+    fn read(&self, key: &str) -> Result<Binary> {
+        match fs::read(self.get_path(key)?) {
+            Ok(content) => Ok(content),
+            Err(err) if err.kind() == ErrorKind::NotFound => Err(BlobNotFoundError {
+                key: key.to_string(),
+            }
+            .into()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    fn contains(&self, key: &str) -> Result<bool> {
+        Ok(self.get_path(key).is_ok_and(|path| path.exists()))
+    }
+
+    fn delete(&mut self, key: &str) -> Result<()> {
+        let Ok(path) = self.get_path(key) else {
+            return Ok(());
+        };
+        match fs::remove_file(path) {
+            Err(err) if err.kind() != ErrorKind::NotFound => Err(err.into()),
+            _ => Ok(()),
+        }
+    }
+
+    fn list(&self) -> Result<Vec<(String, SystemTime)>> {
+        let mut result = Vec::new();
+        let folders = match fs::read_dir(&self.path) {
+            Ok(folders) => folders,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(result),
+            Err(err) => return Err(err.into()),
+        };
+        for folder in folders {
+            for file in fs::read_dir(folder?.path())? {
+                let file = file?;
+                let key = file.file_name().to_string_lossy().to_string();
+                // Temporary files of contents being written are not contents yet
+                if self.get_path(&key).is_err() {
+                    continue;
+                }
+                result.push((key, file.metadata()?.modified()?));
+            }
+        }
+        Ok(result)
+    }
+}
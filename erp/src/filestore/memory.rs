@@ -0,0 +1,64 @@
+use crate::filestore::{BlobNotFoundError, FileStore};
+use erp_types::field::{Binary, content_hash};
+use std::collections::HashMap;
+use std::error::Error;
+use std::time::SystemTime;
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+/// Filestore saving contents in memory, used by test applications
+#[derive(Default)]
+pub struct MemoryFileStore {
+    blobs: HashMap<String, (Binary, SystemTime)>,
+}
+
+impl MemoryFileStore {
+    /// Number of contents saved in this filestore
+    pub fn len(&self) -> usize {
+        self.blobs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.blobs.is_empty()
+    }
+}
+
+impl FileStore for MemoryFileStore {
+    fn write(&mut self, content: &[u8]) -> Result<String> {
+        let key = content_hash(content);
+        self.blobs
+            .entry(key.clone())
+            .and_modify(|(_, written_at)| *written_at = SystemTime::now())
+            .or_insert_with(|| (content.to_vec(), SystemTime::now()));
+        Ok(key)
+    }
+
+    fn read(&self, key: &str) -> Result<Binary> {
+        self.blobs
+            .get(key)
+            .map(|(content, _)| content.clone())
+            .ok_or_else(|| {
+                BlobNotFoundError {
+                    key: key.to_string(),
+                }
+                .into()
+            })
+    }
+
+    fn contains(&self, key: &str) -> Result<bool> {
+        Ok(self.blobs.contains_key(key))
+    }
+
+    fn delete(&mut self, key: &str) -> Result<()> {
+        self.blobs.remove(key);
+        Ok(())
+    }
+
+    fn list(&self) -> Result<Vec<(String, SystemTime)>> {
+        Ok(self
+            .blobs
+            .iter()
+            .map(|(key, (_, written_at))| (key.clone(), *written_at))
+            .collect())
+    }
+}
@@ -0,0 +1,12 @@
+mod errors;
+#[allow(clippy::module_inception)]
+mod filestore;
+mod filestore_type;
+mod local;
+mod memory;
+
+pub use errors::*;
+pub use filestore::*;
+pub use filestore_type::*;
+pub use local::*;
+pub use memory::*;
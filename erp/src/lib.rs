@@ -3,8 +3,10 @@ pub mod config;
 pub mod database;
 pub mod environment;
 pub mod errors;
+pub mod filestore;
 pub mod model;
 pub mod plugin;
 pub mod util;
 
+pub use erp_search as search;
 pub use erp_types as types;
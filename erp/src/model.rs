@@ -7,10 +7,12 @@ pub use iterator::*;
 pub use model_manager::*;
 
 use crate::environment::Environment;
-use erp_types::field::{FieldType, Reference};
+use erp_types::field::{from_json, to_json, Command, FieldType, Json, Reference};
 use erp_types::field::RequiredFieldEmpty;
 use erp_types::field::{IdMode, MultipleIds, SingleId};
 use erp_types::model::{BaseModel, CommonModel};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
 use std::error::Error;
 
 // We need to make another trait here to be able to implement methods, as we are in another crate.
@@ -72,6 +74,36 @@ impl<BM: BaseModel> dyn Model<SingleId, BaseModel = BM> {
         Ok(result.and_then(|result| result.into()))
     }
 
+    /// Returns the given JSON field, converted into the given type.
+    ///
+    /// If error, returns the error
+    pub fn get_json<E>(
+        &self,
+        field_name: &str,
+        env: &mut Environment,
+    ) -> Result<E, Box<dyn Error>>
+    where
+        E: DeserializeOwned,
+    {
+        let result: &Json = self.get(field_name, env)?;
+        Ok(from_json(result)?)
+    }
+
+    /// Returns the given optional JSON field, converted into the given type.
+    ///
+    /// If error, returns the error
+    pub fn get_json_option<E>(
+        &self,
+        field_name: &str,
+        env: &mut Environment,
+    ) -> Result<Option<E>, Box<dyn Error>>
+    where
+        E: DeserializeOwned,
+    {
+        let result: Option<&Json> = self.get_option(field_name, env)?;
+        Ok(result.map(from_json).transpose()?)
+    }
+
     /// Returns the given optional reference field.
     ///
     /// If error, returns the error
@@ -159,6 +191,39 @@ impl<BM: BaseModel> dyn Model<MultipleIds, BaseModel = BM> {
             .map(|res| res.and_then(|res| res.into()))
             .collect())
     }
+
+    /// Returns the given JSON field, converted into the given type.
+    ///
+    /// If error, returns the error
+    pub fn gets_json<E>(
+        &self,
+        field_name: &str,
+        env: &mut Environment,
+    ) -> Result<Vec<E>, Box<dyn Error>>
+    where
+        E: DeserializeOwned,
+    {
+        let result: Vec<&Json> = self.gets(field_name, env)?;
+        Ok(result.into_iter().map(from_json).collect::<Result<_, _>>()?)
+    }
+
+    /// Returns the given optional JSON field, converted into the given type.
+    ///
+    /// If error, returns the error
+    pub fn get_json_options<E>(
+        &self,
+        field_name: &str,
+        env: &mut Environment,
+    ) -> Result<Vec<Option<E>>, Box<dyn Error>>
+    where
+        E: DeserializeOwned,
+    {
+        let result: Vec<Option<&Json>> = self.get_options(field_name, env)?;
+        Ok(result
+            .into_iter()
+            .map(|res| res.map(from_json).transpose())
+            .collect::<Result<_, _>>()?)
+    }
 }
 
 impl<Mode: IdMode, BM: BaseModel> dyn Model<Mode, BaseModel = BM> {
@@ -181,7 +246,7 @@ impl<Mode: IdMode, BM: BaseModel> dyn Model<Mode, BaseModel = BM> {
         let model_name = Self::get_model_name();
         let ids = self.get_id_mode();
         let result: Vec<Option<&FieldType>> = env.get_fields_value(model_name, field_name, ids)?;
-        let ids: Vec<u32> = result
+        let ids: Vec<u64> = result
             .iter()
             .flat_map(|field_type| {
                 if let Some(field_type) = field_type {
@@ -201,6 +266,13 @@ impl<Mode: IdMode, BM: BaseModel> dyn Model<Mode, BaseModel = BM> {
         Ok(reference.get_multiple::<M>())
     }
 
+    /// Delete records of this recordset
+    pub fn unlink(&self, env: &mut Environment) -> Result<(), Box<dyn Error>> {
+        let model_name = Self::get_model_name();
+        let id_mode = self.get_id_mode();
+        env.unlink(model_name, id_mode)
+    }
+
     /// Changes the value of the given field to the given value
     pub fn set<E>(
         &self,
@@ -231,6 +303,20 @@ impl<Mode: IdMode, BM: BaseModel> dyn Model<Mode, BaseModel = BM> {
         env.save_option_to_cache(model_name, field_name, id_mode, value)
     }
 
+    /// Changes the value of the given JSON field to the given optional value, converted into JSON
+    pub fn set_json<E>(
+        &self,
+        field_name: &str,
+        value: Option<E>,
+        env: &mut Environment,
+    ) -> Result<(), Box<dyn Error>>
+    where
+        E: Serialize,
+    {
+        let value = value.map(|value| to_json(&value)).transpose()?;
+        self.set_option(field_name, value, env)
+    }
+
     /// Changes the value of the given field to the given reference
     pub fn set_reference<E>(
         &self,
@@ -261,6 +347,29 @@ impl<Mode: IdMode, BM: BaseModel> dyn Model<Mode, BaseModel = BM> {
         env.save_value_to_cache(model_name, field_name, id_mode, value)
     }
 
+    /// Applies given commands on the given references field of each record
+    pub fn update_references(
+        &self,
+        field_name: &str,
+        commands: &[Command],
+        env: &mut Environment,
+    ) -> Result<(), Box<dyn Error>> {
+        let model_name = Self::get_model_name();
+        for id in self.get_id_mode().get_ids_ref() {
+            let id: SingleId = (*id).into();
+            let mut ids = match env.get_field_value(model_name, field_name, &id)? {
+                Some(FieldType::Ref(id)) => vec![*id],
+                Some(FieldType::Refs(ids)) => ids.clone(),
+                _ => vec![],
+            };
+            for command in commands {
+                command.apply(&mut ids);
+            }
+            env.save_value_to_cache(model_name, field_name, &id, FieldType::Refs(ids))?;
+        }
+        Ok(())
+    }
+
     /// Convert this model into another one, but from the same base
     pub fn convert<TO>(&self) -> TO
     where
@@ -5,7 +5,7 @@ use std::slice::Iter;
 use std::vec::IntoIter;
 
 pub struct ModelIntoIterator<M: Model<SingleId>> {
-    pub ids: IntoIter<u32>,
+    pub ids: IntoIter<u64>,
     pub _phantom_data: PhantomData<M>,
 }
 
@@ -18,7 +18,7 @@ impl<M: Model<SingleId>> Iterator for ModelIntoIterator<M> {
 }
 
 pub struct ModelIterator<'a, M: Model<SingleId>> {
-    pub ids: Iter<'a, u32>,
+    pub ids: Iter<'a, u64>,
     pub _phantom_data: PhantomData<M>,
 }
 
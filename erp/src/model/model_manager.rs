@@ -1,13 +1,22 @@
 use crate::model::Model;
-use erp_internal_types::{FinalInternalModel, InternalModel};
-use erp_types::field::FieldCompute;
+use erp_internal_types::{FinalInternalField, FinalInternalModel, InternalModel};
 use erp_types::field::MultipleIds;
+use erp_types::field::{CurrencyField, FieldCompute, FieldType};
 use erp_types::field::{FieldDepend, FieldReference, FieldReferenceType};
 use std::collections::{HashMap, HashSet};
 
+/// Model of attachments, linked to any record with its `res_model` and `res_id` fields.
+///
+/// Attachments of a record are deleted with it
+pub const ATTACHMENT_MODEL: &str = "attachment";
+
 #[derive(Default)]
 pub struct ModelManager {
     models: HashMap<String, FinalInternalModel>,
+    /// M2O fields (model name, field name) targeting each model
+    many2one_targeting: HashMap<String, Vec<(String, String)>>,
+    /// Monetary fields of each model, by the name of the M2O containing their currency
+    monetary_fields: HashMap<String, HashMap<String, Vec<String>>>,
     pub(crate) current_plugin_loading: Option<String>,
 }
 
@@ -30,9 +39,72 @@ impl ModelManager {
 
     /// Execute some final modification when models are registered, like:
     /// - Linking M2O => O2M (as there is already a link between O2M => M2O)
+    /// - Linking M2M using the same relation table
+    /// - Listing M2O fields targeting each model
+    /// - Checking the currency of Monetary fields
+    /// - Listing Monetary fields of each currency field
     pub fn post_register(&mut self) {
         self._post_register_m2o_links();
+        self._post_register_m2m_links();
+        self._post_register_m2o_targeting();
         self._post_register_compute_links();
+        self._post_register_check_currency_fields();
+        self._post_register_monetary_fields();
+    }
+
+    fn _post_register_check_currency_fields(&self) {
+        for model in self.models.values() {
+            for field in model.fields.values() {
+                let Some(CurrencyField {
+                    field_name: currency_field,
+                    decimal_places_field,
+                }) = &field.currency_field
+                else {
+                    continue;
+                };
+                let target_model = match model.fields.get(currency_field) {
+                    Some(FinalInternalField {
+                        inverse:
+                            Some(FieldReference {
+                                target_model,
+                                inverse_field: FieldReferenceType::M2O { .. },
+                            }),
+                        ..
+                    }) => target_model,
+                    _ => panic!(
+                        "Currency field {}.{} of Monetary field {} is not a M2O",
+                        model.name, currency_field, field.name
+                    ),
+                };
+                let has_decimal_places = self
+                    .models
+                    .get(target_model)
+                    .and_then(|target_model| target_model.fields.get(decimal_places_field))
+                    .is_some_and(|field| matches!(field.default_value, FieldType::Integer(_)));
+                if !has_decimal_places {
+                    panic!(
+                        "Currency model {} targeted by {}.{} should have an integer field \"{}\"",
+                        target_model, model.name, currency_field, decimal_places_field
+                    );
+                }
+            }
+        }
+    }
+
+    fn _post_register_monetary_fields(&mut self) {
+        self.monetary_fields.clear();
+        for model in self.models.values() {
+            for field in model.fields.values() {
+                if let Some(currency_field) = &field.currency_field {
+                    self.monetary_fields
+                        .entry(model.name.clone())
+                        .or_default()
+                        .entry(currency_field.field_name.clone())
+                        .or_default()
+                        .push(field.name.clone());
+                }
+            }
+        }
     }
 
     fn _post_register_m2o_links(&mut self) {
@@ -40,7 +112,7 @@ impl ModelManager {
         for model in self.models.values_mut() {
             for field in model.fields.values_mut() {
                 if let Some(FieldReference {
-                    inverse_field: FieldReferenceType::M2O { inverse_fields },
+                    inverse_field: FieldReferenceType::M2O { inverse_fields, .. },
                     ..
                 }) = &mut field.inverse
                 {
@@ -71,7 +143,7 @@ impl ModelManager {
             for (field_name, mut fields_to_add) in model_to_add {
                 let field = model.get_internal_field_mut(&field_name);
                 if let Some(FieldReference {
-                    inverse_field: FieldReferenceType::M2O { inverse_fields },
+                    inverse_field: FieldReferenceType::M2O { inverse_fields, .. },
                     ..
                 }) = &mut field.inverse
                 {
@@ -89,6 +161,85 @@ impl ModelManager {
         }
     }
 
+    fn _post_register_m2m_links(&mut self) {
+        // Get M2M fields (model, field, target model) of each relation table
+        let mut relations: HashMap<String, Vec<(String, String, String)>> = HashMap::new();
+        for model in self.models.values() {
+            for field in model.fields.values() {
+                if let Some(FieldReference {
+                    target_model,
+                    inverse_field: FieldReferenceType::M2M { relation, .. },
+                }) = &field.inverse
+                {
+                    if *target_model == model.name {
+                        panic!(
+                            "M2M {}.{} targets its own model, but both columns of relation table {} would have the same name",
+                            model.name, field.name, relation
+                        );
+                    }
+                    relations.entry(relation.clone()).or_default().push((
+                        model.name.clone(),
+                        field.name.clone(),
+                        target_model.clone(),
+                    ));
+                }
+            }
+        }
+
+        // Inverse fields of a M2M are the ones on the target model using the same relation table
+        for (relation, fields) in &relations {
+            let (first_model, _, first_target) = &fields[0];
+            for (model_name, field_name, target_model) in fields {
+                let is_same_link = (model_name == first_model && target_model == first_target)
+                    || (model_name == first_target && target_model == first_model);
+                if !is_same_link {
+                    panic!(
+                        "Relation table {} of M2M {}.{} is already used to link {} and {}",
+                        relation, model_name, field_name, first_model, first_target
+                    );
+                }
+                let mut inverse: Vec<String> = fields
+                    .iter()
+                    .filter(|(other_model, _, _)| other_model == target_model)
+                    .map(|(_, other_field, _)| other_field.clone())
+                    .collect();
+                let field = self
+                    .get_model_mut(model_name)
+                    .get_internal_field_mut(field_name);
+                if let Some(FieldReference {
+                    inverse_field: FieldReferenceType::M2M { inverse_fields, .. },
+                    ..
+                }) = &mut field.inverse
+                {
+                    inverse_fields.clear();
+                    inverse_fields.append(&mut inverse);
+                }
+            }
+        }
+    }
+
+    fn _post_register_m2o_targeting(&mut self) {
+        self.many2one_targeting.clear();
+        for model in self.models.values() {
+            for field in model.fields.values() {
+                if let Some(FieldReference {
+                    target_model,
+                    inverse_field: FieldReferenceType::M2O { .. },
+                }) = &field.inverse
+                {
+                    self.many2one_targeting
+                        .entry(target_model.clone())
+                        .or_default()
+                        .push((model.name.clone(), field.name.clone()));
+                }
+            }
+        }
+        // Sort them, so that ondelete policies are always applied in the same order
+        for fields in self.many2one_targeting.values_mut() {
+            fields.sort_unstable();
+        }
+    }
+
     fn _post_register_compute_links(&mut self) {
         // Clear depends
         for model in self.models.values_mut() {
@@ -161,6 +312,12 @@ impl ModelManager {
                                             target_field: field.name.clone(),
                                         });
                                     }
+                                    FieldReferenceType::M2M { .. } => {
+                                        panic!(
+                                            "Field {}.{} has invalid depends! (Field \"{}\" of depends \"{:?}\" is a M2M, which cannot be traversed)",
+                                            model.name, field.name, d, depend
+                                        )
+                                    }
                                 }
                                 current_model = self.get_model(target_model);
                             } else {
@@ -196,6 +353,23 @@ impl ModelManager {
         self.models.get_mut(model_name).unwrap()
     }
 
+    /// M2O fields (model name, field name) targeting given model
+    pub fn get_many2one_targeting(&self, model_name: &str) -> &[(String, String)] {
+        self.many2one_targeting
+            .get(model_name)
+            .map(|fields| fields.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Monetary fields of given model whose currency is given M2O field
+    pub fn get_monetary_fields(&self, model_name: &str, currency_field: &str) -> &[String] {
+        self.monetary_fields
+            .get(model_name)
+            .and_then(|fields| fields.get(currency_field))
+            .map(|fields| fields.as_slice())
+            .unwrap_or_default()
+    }
+
     pub fn is_valid_model(&self, model_name: &str) -> bool {
         self.models.contains_key(model_name)
     }
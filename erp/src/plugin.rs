@@ -1,10 +1,12 @@
 pub mod errors;
 mod internal_plugin;
+mod migration;
 mod plugin_manager;
 
 pub(crate) use internal_plugin::InternalPlugin;
 pub(crate) use internal_plugin::InternalPluginState;
 pub(crate) use internal_plugin::InternalPluginType;
+pub use migration::*;
 pub use plugin_manager::PluginManager;
 
 use crate::environment::Environment;
@@ -16,6 +18,14 @@ pub trait Plugin: Any + Send + Sync {
     /// Get the name of this plugin
     fn name(&self) -> String;
 
+    /// Get the version of this plugin
+    ///
+    /// This version is saved in the database once the plugin is installed, and is used to know
+    /// which migration steps need to be executed when the plugin is upgraded
+    fn version(&self) -> String {
+        "1.0.0".to_string()
+    }
+
     /// Pre-Initialize this plugin
     ///
     /// This method is called before models initialized (before the call to init_models)
@@ -24,6 +34,12 @@ pub trait Plugin: Any + Send + Sync {
     /// Register models created in this plugin
     fn init_models(&self, model_manager: &mut ModelManager);
 
+    /// Register migration steps of this plugin
+    ///
+    /// Steps are only executed when upgrading an already installed plugin, and only if their
+    /// version is greater than the installed version and lower or equal to the current version
+    fn init_migrations(&self, _migrations: &mut Migrations) {}
+
     /// Post-Initialize this plugin
     ///
     /// This method is called once this plugin is fully initialized (after the call to init_models)
@@ -0,0 +1,86 @@
+use crate::environment::Environment;
+use crate::util::version::compare_versions;
+use std::cmp::Ordering;
+use std::error::Error;
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+type MigrationMethod = Box<dyn Fn(&mut Environment) -> Result<()> + Send + Sync>;
+
+/// When a migration step is executed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MigrationStage {
+    /// Before the database schema is updated with the new definition of models
+    PreSchema,
+    /// After the database schema is updated with the new definition of models
+    PostSchema,
+}
+
+pub struct MigrationStep {
+    pub version: String,
+    pub stage: MigrationStage,
+    method: MigrationMethod,
+}
+
+impl MigrationStep {
+    pub fn call(&self, env: &mut Environment) -> Result<()> {
+        (self.method)(env)
+    }
+}
+
+/// Migration steps of a single plugin
+#[derive(Default)]
+pub struct Migrations {
+    steps: Vec<MigrationStep>,
+}
+
+impl Migrations {
+    /// Register a step executed before the schema is updated, when upgrading the plugin to given
+    /// version
+    pub fn pre_schema<F>(&mut self, version: &str, method: F)
+    where
+        F: Fn(&mut Environment) -> Result<()> + Send + Sync + 'static,
+    {
+        self.add_step(version, MigrationStage::PreSchema, Box::new(method));
+    }
+
+    /// Register a step executed after the schema is updated, when upgrading the plugin to given
+    /// version
+    pub fn post_schema<F>(&mut self, version: &str, method: F)
+    where
+        F: Fn(&mut Environment) -> Result<()> + Send + Sync + 'static,
+    {
+        self.add_step(version, MigrationStage::PostSchema, Box::new(method));
+    }
+
+    fn add_step(&mut self, version: &str, stage: MigrationStage, method: MigrationMethod) {
+        self.steps.push(MigrationStep {
+            version: version.to_string(),
+            stage,
+            method,
+        });
+    }
+
+    /// Return steps of given stage to execute when upgrading from version "from" to version "to",
+    /// sorted by version.
+    ///
+    /// Steps of the same version are returned in the order they were registered
+    pub fn get_pending_steps(
+        &self,
+        stage: MigrationStage,
+        from: &str,
+        to: &str,
+    ) -> Vec<&MigrationStep> {
+        let mut steps: Vec<&MigrationStep> = self
+            .steps
+            .iter()
+            .filter(|step| {
+                step.stage == stage
+                    && compare_versions(&step.version, from) == Ordering::Greater
+                    && compare_versions(&step.version, to) != Ordering::Greater
+            })
+            .collect();
+        steps.sort_by(|a, b| compare_versions(&a.version, &b.version));
+        steps
+    }
+}
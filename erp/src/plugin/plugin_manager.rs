@@ -1,6 +1,6 @@
-use crate::plugin::errors::{PluginAlreadyRegisteredError, PluginNotFoundError};
 use crate::plugin::InternalPluginState::{Installed, NotInstalled};
 use crate::plugin::Plugin;
+use crate::plugin::errors::{PluginAlreadyRegisteredError, PluginNotFoundError};
 use crate::plugin::{InternalPlugin, InternalPluginType};
 use crate::util::dependency;
 use libloading::{Error, Library, Symbol};
@@ -0,0 +1,41 @@
+use std::cmp::Ordering;
+
+/// Compare two versions, like "1.2.0" and "1.10.0".
+///
+/// Each part is compared as a number. Missing parts are considered as 0, so "1.2" == "1.2.0".
+/// Parts that are not a number are compared as strings
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+    let a_parts: Vec<&str> = a.split('.').collect();
+    let b_parts: Vec<&str> = b.split('.').collect();
+    for i in 0..a_parts.len().max(b_parts.len()) {
+        let a_part = a_parts.get(i).copied().unwrap_or("0");
+        let b_part = b_parts.get(i).copied().unwrap_or("0");
+        let ordering = match (a_part.parse::<u64>(), b_part.parse::<u64>()) {
+            (Ok(a_number), Ok(b_number)) => a_number.cmp(&b_number),
+            _ => a_part.cmp(b_part),
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    Ordering::Equal
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::util::version::compare_versions;
+    use std::cmp::Ordering;
+
+    #[test]
+    fn test_compare_versions() {
+        assert_eq!(compare_versions("1.0.0", "1.0.0"), Ordering::Equal);
+        assert_eq!(compare_versions("1.0", "1.0.0"), Ordering::Equal);
+        assert_eq!(compare_versions("1.0.0", "1.0.1"), Ordering::Less);
+        assert_eq!(compare_versions("1.2.0", "1.10.0"), Ordering::Less);
+        assert_eq!(compare_versions("2.0", "1.10.5"), Ordering::Greater);
+        assert_eq!(
+            compare_versions("1.0.0-beta", "1.0.0-alpha"),
+            Ordering::Greater
+        );
+    }
+}
@@ -0,0 +1,115 @@
+use crate::TestLibPlugin;
+use erp::app::Application;
+use erp::config::Config;
+use erp::database::{Database, DatabaseConfig};
+use std::error::Error;
+use std::ops::{Deref, DerefMut};
+use std::path::PathBuf;
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+/// Database on which a test is run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    /// Cache database, with files saved in memory
+    Cache,
+    /// SQLite database in a temporary file, with files saved in a temporary directory
+    Sqlite,
+}
+
+/// Application running on a new database of a given backend.
+///
+/// The database and the files of a SQLite backend are removed once the test is over
+pub struct TestApp {
+    app: Application,
+    backend: Backend,
+    paths: Option<(PathBuf, PathBuf)>,
+}
+
+impl TestApp {
+    pub fn new(backend: Backend) -> Result<Self> {
+        let (app, paths) = match backend {
+            Backend::Cache => (Application::new_test(), None),
+            Backend::Sqlite => {
+                let name = format!("erp_test_{}", uuid::Uuid::new_v4());
+                let path = std::env::temp_dir().join(format!("{name}.sqlite"));
+                let data_dir = std::env::temp_dir().join(name);
+                let mut app = Application::new(Config {
+                    database: DatabaseConfig::sqlite(path.to_str().unwrap()),
+                    plugin_path: String::new(),
+                    data_dir: data_dir.to_str().unwrap().to_string(),
+                    // Environments of a test can't run at the same time
+                    filestore_grace_period: 0,
+                });
+                app.create_new_database()?.initialize()?;
+                (app, Some((path, data_dir)))
+            }
+        };
+        Ok(TestApp {
+            app,
+            backend,
+            paths,
+        })
+    }
+
+    /// New application with the models of `TestLibPlugin` (sale orders and their lines)
+    pub fn with_sale_orders(backend: Backend) -> Result<Self> {
+        let mut app = Self::new(backend)?;
+        app.register_plugin(Box::new(TestLibPlugin))?;
+        app.load_plugin("test_lib_plugin")?;
+        Ok(app)
+    }
+
+    pub fn backend(&self) -> Backend {
+        self.backend
+    }
+}
+
+impl Deref for TestApp {
+    type Target = Application;
+
+    fn deref(&self) -> &Application {
+        &self.app
+    }
+}
+
+impl DerefMut for TestApp {
+    fn deref_mut(&mut self) -> &mut Application {
+        &mut self.app
+    }
+}
+
+impl Drop for TestApp {
+    fn drop(&mut self) {
+        if let Some((path, data_dir)) = &self.paths {
+            let _ = std::fs::remove_file(path);
+            let _ = std::fs::remove_dir_all(data_dir);
+        }
+    }
+}
+
+/// Run each given test, taking a `Backend`, on every backend.
+///
+/// Tests are generated in a `cache` and a `sqlite` module
+#[macro_export]
+macro_rules! backend_tests {
+    ($($test:ident),* $(,)?) => {
+        mod cache {
+            $(
+                #[test]
+                fn $test() -> std::result::Result<(), Box<dyn std::error::Error>> {
+                    super::$test($crate::backend::Backend::Cache)
+                }
+            )*
+        }
+
+        mod sqlite {
+            $(
+                #[test]
+                fn $test() -> std::result::Result<(), Box<dyn std::error::Error>> {
+                    super::$test($crate::backend::Backend::Sqlite)
+                }
+            )*
+        }
+    };
+}
@@ -2,6 +2,7 @@ use ::config::ConfigError;
 use erp::model::ModelManager;
 use erp::plugin::Plugin;
 
+pub mod backend;
 pub mod config;
 pub mod models;
 
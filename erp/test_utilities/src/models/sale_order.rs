@@ -1,7 +1,9 @@
 use crate::models::{BaseSaleOrderLine, SaleOrderLine};
+use chrono::{DateTime, NaiveDate, Utc};
 use code_gen::Model;
 use erp::environment::Environment;
 use erp::types::field::{EnumType, IdMode, MultipleIds, Reference};
+use serde::{Deserialize, Serialize};
 use std::error::Error;
 
 #[derive(Debug, PartialEq, Eq, Default, Copy, Clone)]
@@ -38,22 +40,35 @@ impl From<&str> for &SaleOrderState {
 
 impl EnumType for SaleOrderState {}
 
+#[derive(Debug, PartialEq, Default, Clone, Serialize, Deserialize)]
+pub struct Delivery {
+    pub carrier: String,
+    pub weight: f64,
+    pub express: bool,
+}
+
 #[derive(Model)]
-#[erp(table_name = "sale_order")]
+#[erp(table_name = "sale_order", order = "name asc, id desc")]
 #[allow(dead_code)]
 pub struct SaleOrder<Mode: IdMode> {
     pub id: Mode,
     #[erp(default = "0ddlyoko")]
     name: String,
     state: SaleOrderState,
+    #[erp(default = "now")]
+    date_order: Option<DateTime<Utc>>,
+    validity_date: Option<NaiveDate>,
     #[erp(compute="compute_total_price", depends=["lines.total_price"])]
     total_price: i32,
     #[erp(inverse = "order")]
     lines: Reference<BaseSaleOrderLine, MultipleIds>,
+    metadata: Option<serde_json::Value>,
+    #[erp(json)]
+    delivery: Option<Delivery>,
 }
 
 impl SaleOrder<MultipleIds> {
-    pub fn compute_total_price<'mm>(&self, env: &mut Environment) -> Result<(), Box<dyn Error>> {
+    pub fn compute_total_price(&self, env: &mut Environment) -> Result<(), Box<dyn Error>> {
         for sale_order in self {
             let lines: SaleOrderLine<_> = sale_order.get_lines(env)?;
             let total_prices = lines.get_total_price(env)?;
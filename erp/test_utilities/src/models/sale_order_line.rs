@@ -1,7 +1,7 @@
 use crate::models::sale_order::BaseSaleOrder;
 use code_gen::Model;
 use erp::environment::Environment;
-use erp::types::field::{IdMode, MultipleIds, Reference, SingleId};
+use erp::types::field::{Decimal, IdMode, MultipleIds, Reference, SingleId};
 use std::error::Error;
 
 #[derive(Model, Debug)]
@@ -9,17 +9,23 @@ use std::error::Error;
 #[allow(dead_code)]
 pub struct SaleOrderLine<Mode: IdMode> {
     pub id: Mode,
+    #[erp(ondelete = "cascade")]
     order: Reference<BaseSaleOrder, SingleId>,
     #[erp(default = 42)]
     price: i32,
     #[erp(default = 10)]
     amount: i32,
+    #[erp(digits = 2)]
+    discount: Option<Decimal>,
+    #[erp(default = 5000000000)]
+    quantity: Option<i64>,
+    weight: Option<f64>,
     #[erp(compute="compute_total_price", depends=["price", "amount"])]
     total_price: i32,
 }
 
 impl SaleOrderLine<MultipleIds> {
-    pub fn compute_total_price<'mm>(&self, env: &mut Environment) -> Result<(), Box<dyn Error>> {
+    pub fn compute_total_price(&self, env: &mut Environment) -> Result<(), Box<dyn Error>> {
         for sale_order_line in self {
             let price = *sale_order_line.get_price(env)?;
             let amount = *sale_order_line.get_amount(env)?;
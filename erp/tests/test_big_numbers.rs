@@ -0,0 +1,77 @@
+use erp::database::{Aggregate, FieldType, InvalidSearchValueError};
+use erp_search_code_gen::make_domain;
+use erp_types::field::{MultipleIds, SingleId};
+use erp_types::model::MapOfFields;
+use std::error::Error;
+use test_utilities::backend::{Backend, TestApp};
+use test_utilities::backend_tests;
+use test_utilities::models::{SaleOrder, SaleOrderLine};
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+backend_tests!(test_big_integer_and_double);
+
+fn test_big_integer_and_double(backend: Backend) -> Result<()> {
+    let mut app = TestApp::with_sale_orders(backend)?;
+    let mut env = app.new_env()?;
+
+    let sale_order: SaleOrder<SingleId> = env.create_new_record_from_map(MapOfFields::default())?;
+    let mut ids = Vec::new();
+    for (quantity, weight) in [(None, 0.1), (Some(-3_000_000_000i64), 1e300)] {
+        let mut map = MapOfFields::default();
+        map.insert::<&u64>("order", &sale_order.get_id());
+        // Without any value, the default value of the field is used
+        if let Some(quantity) = quantity {
+            map.insert("quantity", quantity);
+        }
+        map.insert("weight", weight);
+        let line: SaleOrderLine<SingleId> = env.create_new_record_from_map(map)?;
+        ids.push(line.get_id());
+    }
+    let lines: SaleOrderLine<MultipleIds> = env.get_record(MultipleIds::from(ids.clone()));
+
+    // Values don't overflow, and don't lose precision
+    let line: SaleOrderLine<SingleId> = env.get_record(ids[0].into());
+    assert_eq!(line.get_quantity(&mut env)?, Some(&5_000_000_000));
+    assert_eq!(line.get_weight(&mut env)?, Some(&0.1));
+    let line: SaleOrderLine<SingleId> = env.get_record(ids[1].into());
+    assert_eq!(line.get_quantity(&mut env)?, Some(&-3_000_000_000));
+    assert_eq!(line.get_weight(&mut env)?, Some(&1e300));
+
+    let domain = make_domain!([("quantity", ">", 4_000_000_000i64)]);
+    let sale_order_lines: SaleOrderLine<MultipleIds> = env.search(&domain)?;
+    assert_eq!(sale_order_lines.get_ids(), vec![ids[0]]);
+    let filtered = env.filtered_domain(&lines, &domain)?;
+    assert_eq!(filtered.get_ids(), vec![ids[0]]);
+    let sale_order_lines: SaleOrderLine<MultipleIds> =
+        env.search(&SaleOrderLine::fields().weight().gt(1e100))?;
+    assert_eq!(sale_order_lines.get_ids(), vec![ids[1]]);
+
+    let groups = env.read_group::<SaleOrderLine<_>>(
+        &make_domain!([("id", "in", ids.clone())]),
+        &[],
+        &[
+            Aggregate::sum("quantity"),
+            Aggregate::avg("quantity"),
+            Aggregate::max("weight"),
+        ],
+    )?;
+    assert_eq!(
+        groups[0].aggregates,
+        vec![
+            Some(FieldType::BigInteger(2_000_000_000)),
+            Some(FieldType::Double(1e9)),
+            Some(FieldType::Double(1e300)),
+        ]
+    );
+
+    // Values should be 64-bit numbers
+    let result: Result<SaleOrderLine<MultipleIds>> =
+        env.search(&make_domain!([("quantity", ">", 42)]));
+    assert!(result.is_err_and(|error| error.is::<InvalidSearchValueError>()));
+    let result: Result<SaleOrderLine<MultipleIds>> =
+        env.search(&make_domain!([("weight", ">", 4.2)]));
+    assert!(result.is_err_and(|error| error.is::<InvalidSearchValueError>()));
+
+    Ok(())
+}
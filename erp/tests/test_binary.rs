@@ -0,0 +1,212 @@
+use base::BasePlugin;
+use base::models::{Attachment, Contact};
+use erp::app::Application;
+use erp::environment::Environment;
+use erp::filestore::{BlobNotFoundError, FileStore, LocalFileStore};
+use erp_search_code_gen::make_domain;
+use erp_types::field::{MultipleIds, SingleId, content_hash};
+use erp_types::model::MapOfFields;
+use std::error::Error;
+use std::time::{Duration, SystemTime};
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+fn create_attachment(
+    env: &mut Environment,
+    name: &str,
+    content: &[u8],
+) -> Result<Attachment<SingleId>> {
+    let mut map = MapOfFields::default();
+    map.insert("name", name);
+    map.insert("content", content.to_vec());
+    env.create_new_record_from_map(map)
+}
+
+fn load_base(app: &mut Application) -> Result<()> {
+    app.register_plugin(Box::new(BasePlugin {}))?;
+    app.load_plugin("base")
+}
+
+#[test]
+fn test_binary_values() -> Result<()> {
+    let mut app = Application::new_test();
+    load_base(&mut app)?;
+
+    let mut env = app.new_env()?;
+    let invoice = create_attachment(&mut env, "invoice.pdf", b"%PDF-1.7")?;
+    let copy = create_attachment(&mut env, "copy.pdf", b"%PDF-1.7")?;
+    let logo = create_attachment(&mut env, "logo.png", b"\x89PNG")?;
+    let empty: Attachment<SingleId> = env.create_new_record_from_map({
+        let mut map = MapOfFields::default();
+        map.insert("name", "empty.txt");
+        map
+    })?;
+    assert_eq!(invoice.get_content(&mut env)?, Some(&b"%PDF-1.7".to_vec()));
+    // Optional binaries are empty by default
+    assert_eq!(empty.get_content(&mut env)?, None);
+    env.close()?;
+
+    // Same contents are only saved once
+    assert_eq!(app.memory_filestore.len(), 2);
+
+    // Open a new environment, so data is read from the database and the filestore
+    let mut env = app.new_env()?;
+    assert_eq!(invoice.get_content(&mut env)?, Some(&b"%PDF-1.7".to_vec()));
+    assert_eq!(copy.get_content(&mut env)?, Some(&b"%PDF-1.7".to_vec()));
+    assert_eq!(logo.get_content(&mut env)?, Some(&b"\x89PNG".to_vec()));
+    assert_eq!(empty.get_content(&mut env)?, None);
+
+    // Binary fields are searched with the key of their content
+    let attachments: Attachment<MultipleIds> =
+        env.search(&make_domain!([("content", "=", content_hash(b"%PDF-1.7"))]))?;
+    let mut ids = attachments.get_ids();
+    ids.sort();
+    assert_eq!(ids, vec![invoice.get_id(), copy.get_id()]);
+    let attachments: Attachment<MultipleIds> =
+        env.search(&make_domain!([("content", "=", None)]))?;
+    assert_eq!(attachments.get_ids(), vec![empty.get_id()]);
+    Ok(())
+}
+
+#[test]
+fn test_binary_garbage_collection() -> Result<()> {
+    let mut app = Application::new_test();
+    load_base(&mut app)?;
+
+    let mut env = app.new_env()?;
+    let invoice = create_attachment(&mut env, "invoice.pdf", b"%PDF-1.7")?;
+    let copy = create_attachment(&mut env, "copy.pdf", b"%PDF-1.7")?;
+    let logo = create_attachment(&mut env, "logo.png", b"\x89PNG")?;
+    env.close()?;
+    assert_eq!(app.memory_filestore.len(), 2);
+
+    // Contents still referenced by another record are kept
+    let mut env = app.new_env()?;
+    invoice.unlink(&mut env)?;
+    env.close()?;
+    assert_eq!(app.memory_filestore.len(), 2);
+
+    // Replaced contents are only deleted by collecting the filestore, once they are older than the
+    // grace period
+    let mut env = app.new_env()?;
+    logo.set_content(Some(b"GIF89a".to_vec()), &mut env)?;
+    env.close()?;
+    assert_eq!(app.memory_filestore.len(), 3);
+    assert_eq!(app.collect_filestore_garbage(Duration::from_secs(3600))?, 0);
+    assert_eq!(app.collect_filestore_garbage(Duration::ZERO)?, 1);
+    assert!(!app.memory_filestore.contains(&content_hash(b"\x89PNG"))?);
+    assert!(app.memory_filestore.contains(&content_hash(b"GIF89a"))?);
+
+    // Nothing is deleted if the environment is not closed
+    let mut env = app.new_env()?;
+    copy.unlink(&mut env)?;
+    drop(env);
+    assert_eq!(app.collect_filestore_garbage(Duration::ZERO)?, 0);
+    assert!(app.memory_filestore.contains(&content_hash(b"%PDF-1.7"))?);
+
+    // Contents of deleted records are deleted when closing the environment
+    let mut env = app.new_env()?;
+    copy.unlink(&mut env)?;
+    env.close()?;
+    assert!(!app.memory_filestore.contains(&content_hash(b"%PDF-1.7"))?);
+    assert_eq!(app.memory_filestore.len(), 1);
+    Ok(())
+}
+
+#[test]
+fn test_binary_of_rolled_back_records_collected() -> Result<()> {
+    let mut app = Application::new_test();
+    load_base(&mut app)?;
+
+    // Contents are written when records are saved, even if the transaction is then rolled back
+    let mut env = app.new_env()?;
+    create_attachment(&mut env, "draft.txt", b"draft")?;
+    env.save_all_to_db()?;
+    drop(env);
+    assert!(app.memory_filestore.contains(&content_hash(b"draft"))?);
+
+    assert_eq!(app.collect_filestore_garbage(Duration::ZERO)?, 1);
+    assert!(app.memory_filestore.is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_attachments_unlinked_with_their_record() -> Result<()> {
+    let mut app = Application::new_test();
+    load_base(&mut app)?;
+
+    let mut env = app.new_env()?;
+    let mut map = MapOfFields::default();
+    map.insert("name", "0ddlyoko");
+    let contact: Contact<SingleId> = env.create_new_record_from_map(map)?;
+    let mut map = MapOfFields::default();
+    map.insert("name", "0ddlyoko's friend");
+    let other_contact: Contact<SingleId> = env.create_new_record_from_map(map)?;
+    let mut attachment_ids = Vec::new();
+    for (contact_id, content) in [
+        (contact.get_id(), b"avatar".to_vec()),
+        (contact.get_id(), b"resume".to_vec()),
+        (other_contact.get_id(), b"avatar".to_vec()),
+    ] {
+        let mut map = MapOfFields::default();
+        map.insert("name", "file");
+        map.insert("res_model", "contact");
+        map.insert("res_id", contact_id);
+        map.insert("content", content);
+        let attachment: Attachment<SingleId> = env.create_new_record_from_map(map)?;
+        attachment_ids.push(attachment.get_id());
+    }
+    env.close()?;
+    assert_eq!(app.memory_filestore.len(), 2);
+
+    let mut env = app.new_env()?;
+    contact.unlink(&mut env)?;
+    let attachments: Attachment<MultipleIds> =
+        env.search(&make_domain!([("name", "=", "file")]))?;
+    assert_eq!(attachments.get_ids(), vec![attachment_ids[2]]);
+    env.close()?;
+    assert!(app.memory_filestore.contains(&content_hash(b"avatar"))?);
+    assert!(!app.memory_filestore.contains(&content_hash(b"resume"))?);
+    Ok(())
+}
+
+#[test]
+fn test_local_filestore() -> Result<()> {
+    let data_dir = std::env::temp_dir().join(format!("erp_test_{}", uuid::Uuid::new_v4()));
+    let mut filestore = LocalFileStore::new(&data_dir);
+
+    let key = filestore.write(b"%PDF-1.7")?;
+    assert_eq!(key, content_hash(b"%PDF-1.7"));
+    assert!(
+        data_dir
+            .join("filestore")
+            .join(&key[..2])
+            .join(&key)
+            .exists()
+    );
+    // Writing the same content again doesn't fail, and updates its write time so that it's not
+    // collected while a new transaction references it
+    let path = data_dir.join("filestore").join(&key[..2]).join(&key);
+    std::fs::File::options()
+        .write(true)
+        .open(&path)?
+        .set_modified(SystemTime::UNIX_EPOCH)?;
+    assert_eq!(filestore.write(b"%PDF-1.7")?, key);
+    assert!(path.metadata()?.modified()? > SystemTime::UNIX_EPOCH);
+    assert_eq!(filestore.read(&key)?, b"%PDF-1.7".to_vec());
+    assert!(filestore.contains(&key)?);
+    let keys: Vec<String> = filestore.list()?.into_iter().map(|(key, _)| key).collect();
+    assert_eq!(keys, vec![key.clone()]);
+
+    filestore.delete(&key)?;
+    assert!(!filestore.contains(&key)?);
+    assert!(filestore.list()?.is_empty());
+    let result = filestore.read(&key);
+    assert!(result.is_err_and(|error| error.is::<BlobNotFoundError>()));
+    // Keys that are not hashes never target a file outside of the filestore
+    let result = filestore.read("../../etc/passwd");
+    assert!(result.is_err_and(|error| error.is::<BlobNotFoundError>()));
+
+    std::fs::remove_dir_all(&data_dir)?;
+    Ok(())
+}
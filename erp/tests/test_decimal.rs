@@ -0,0 +1,180 @@
+use base::BasePlugin;
+use base::models::{Contact, Currency};
+use erp::database::{Aggregate, FieldType, InvalidSearchValueError};
+use erp_search_code_gen::make_domain;
+use erp_types::field::{CurrencyField, Decimal, MultipleIds, SingleId};
+use erp_types::model::MapOfFields;
+use std::error::Error;
+use test_utilities::backend::{Backend, TestApp};
+use test_utilities::backend_tests;
+use test_utilities::models::{SaleOrder, SaleOrderLine};
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+backend_tests!(test_decimal_digits, test_monetary_currency);
+
+fn decimal(value: &str) -> Decimal {
+    Decimal::from_str_exact(value).unwrap()
+}
+
+#[test]
+fn test_currency_field() {
+    // The decimal places of the currency are read from "decimal_places", unless another field is
+    // given
+    assert_eq!(
+        CurrencyField::try_from("currency").unwrap(),
+        CurrencyField {
+            field_name: "currency".to_string(),
+            decimal_places_field: "decimal_places".to_string(),
+        }
+    );
+    assert_eq!(
+        CurrencyField::try_from("currency.digits").unwrap(),
+        CurrencyField {
+            field_name: "currency".to_string(),
+            decimal_places_field: "digits".to_string(),
+        }
+    );
+    for currency_field in ["", ".digits", "currency.", "currency.digits.places"] {
+        assert!(CurrencyField::try_from(currency_field).is_err());
+    }
+}
+
+fn test_decimal_digits(backend: Backend) -> Result<()> {
+    let mut app = TestApp::with_sale_orders(backend)?;
+    let mut env = app.new_env()?;
+
+    let sale_order: SaleOrder<SingleId> = env.create_new_record_from_map(MapOfFields::default())?;
+    let mut ids = Vec::new();
+    for discount in ["1.005", "-1.005", "2.5"] {
+        let mut map = MapOfFields::default();
+        map.insert::<&u64>("order", &sale_order.get_id());
+        map.insert("discount", decimal(discount));
+        let line: SaleOrderLine<SingleId> = env.create_new_record_from_map(map)?;
+        ids.push(line.get_id());
+    }
+    let lines: SaleOrderLine<MultipleIds> = env.get_record(MultipleIds::from(ids.clone()));
+
+    // Values are rounded half away from zero, to the 2 digits of the field
+    let discounts: Vec<String> = lines
+        .get_discount(&mut env)?
+        .into_iter()
+        .map(|discount| discount.unwrap().to_string())
+        .collect();
+    assert_eq!(discounts, vec!["1.01", "-1.01", "2.50"]);
+
+    let line: SaleOrderLine<SingleId> = env.get_record(ids[0].into());
+    line.set_discount(Some(decimal("0.125")), &mut env)?;
+    assert_eq!(line.get_discount(&mut env)?, Some(&decimal("0.13")));
+
+    // Search and in-memory filtering compare decimals
+    let domain = make_domain!([("discount", ">", decimal("0.2"))]);
+    let sale_order_lines: SaleOrderLine<MultipleIds> = env.search(&domain)?;
+    assert_eq!(sale_order_lines.get_ids(), vec![ids[2]]);
+    let filtered = env.filtered_domain(&lines, &domain)?;
+    assert_eq!(filtered.get_ids(), vec![ids[2]]);
+    let sale_order_lines: SaleOrderLine<MultipleIds> =
+        env.search(&SaleOrderLine::fields().discount().le(decimal("0.13")))?;
+    assert_eq!(sale_order_lines.get_ids(), vec![ids[0], ids[1]]);
+
+    let groups = env.read_group::<SaleOrderLine<_>>(
+        &make_domain!([("id", "in", ids.clone())]),
+        &[],
+        &[Aggregate::sum("discount"), Aggregate::avg("discount")],
+    )?;
+    assert_eq!(
+        groups[0].aggregates,
+        vec![
+            Some(FieldType::Decimal(decimal("1.62"))),
+            Some(FieldType::Decimal(decimal("0.54"))),
+        ]
+    );
+
+    // Values should be decimals
+    let result: Result<SaleOrderLine<MultipleIds>> =
+        env.search(&make_domain!([("discount", ">", 0.2)]));
+    assert!(result.is_err_and(|error| error.is::<InvalidSearchValueError>()));
+
+    Ok(())
+}
+
+fn test_monetary_currency(backend: Backend) -> Result<()> {
+    let mut app = TestApp::new(backend)?;
+    app.register_plugin(Box::new(BasePlugin {}))?;
+    app.load_plugin("base")?;
+    let mut env = app.new_env()?;
+
+    let mut currencies = Vec::new();
+    for (name, decimal_places) in [("EUR", 2), ("JPY", 0), ("KWD", 3)] {
+        let mut map = MapOfFields::default();
+        map.insert("name", name);
+        map.insert("symbol", name);
+        map.insert("decimal_places", decimal_places);
+        let currency: Currency<SingleId> = env.create_new_record_from_map(map)?;
+        currencies.push(currency.get_id());
+    }
+
+    // Amounts are rounded to the currency given at creation
+    let mut ids = Vec::new();
+    for currency in [Some(currencies[0]), Some(currencies[1]), None] {
+        let mut map = MapOfFields::default();
+        map.insert("name", "0ddlyoko");
+        map.insert_option::<&u64>("currency", currency.as_ref());
+        map.insert("credit_limit", decimal("1234.5678"));
+        let contact: Contact<SingleId> = env.create_new_record_from_map(map)?;
+        ids.push(contact.get_id());
+    }
+    let contacts: Contact<MultipleIds> = env.get_record(MultipleIds::from(ids.clone()));
+    let credit_limits: Vec<String> = contacts
+        .get_credit_limit(&mut env)?
+        .into_iter()
+        .map(|credit_limit| credit_limit.to_string())
+        .collect();
+    // Without any currency, the amount is not rounded
+    assert_eq!(credit_limits, vec!["1234.57", "1235", "1234.5678"]);
+
+    // Only the currency M2O rounds Monetary fields again when it's written
+    let model_manager = env.model_manager;
+    assert_eq!(
+        model_manager.get_monetary_fields("contact", "currency"),
+        ["credit_limit"]
+    );
+    assert!(
+        model_manager
+            .get_monetary_fields("contact", "name")
+            .is_empty()
+    );
+
+    // Changing the currency rounds the amount to the new currency
+    let contact: Contact<SingleId> = env.get_record(ids[2].into());
+    contact.set_currency(Some(currencies[2].into()), &mut env)?;
+    assert_eq!(contact.get_credit_limit(&mut env)?.to_string(), "1234.568");
+
+    // Writing on multiple records rounds the amount of each record to its own currency
+    contacts.set_credit_limit(decimal("0.9999"), &mut env)?;
+    let credit_limits: Vec<String> = contacts
+        .get_credit_limit(&mut env)?
+        .into_iter()
+        .map(|credit_limit| credit_limit.to_string())
+        .collect();
+    assert_eq!(credit_limits, vec!["1.00", "1", "1.000"]);
+
+    // Decimal places of the currency are read when writing
+    let currency: Currency<SingleId> = env.get_record(currencies[0].into());
+    currency.set_decimal_places(1, &mut env)?;
+    let contact: Contact<SingleId> = env.get_record(ids[0].into());
+    contact.set_credit_limit(decimal("10.25"), &mut env)?;
+    assert_eq!(contact.get_credit_limit(&mut env)?.to_string(), "10.3");
+    env.close()?;
+
+    // Amounts read from the database keep the decimal places of their currency
+    let mut env = app.new_env()?;
+    let credit_limits: Vec<String> = contacts
+        .get_credit_limit(&mut env)?
+        .into_iter()
+        .map(|credit_limit| credit_limit.to_string())
+        .collect();
+    assert_eq!(credit_limits, vec!["10.3", "1", "1.000"]);
+
+    Ok(())
+}
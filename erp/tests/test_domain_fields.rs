@@ -0,0 +1,96 @@
+use base::models::Contact;
+use erp_search_code_gen::make_domain;
+use erp_types::field::{MultipleIds, SingleId};
+use erp_types::model::MapOfFields;
+use std::error::Error;
+use test_utilities::backend::{Backend, TestApp};
+use test_utilities::backend_tests;
+use test_utilities::models::{SaleOrder, SaleOrderLine, SaleOrderState};
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+backend_tests!(test_search_domain_fields);
+
+#[test]
+fn test_domain_fields() {
+    let fields = SaleOrder::fields();
+    assert_eq!(
+        fields.state().eq(SaleOrderState::Draft),
+        make_domain!([("state", "=", "draft")])
+    );
+    assert_eq!(
+        fields.name().ilike("0ddly") & fields.id().ne(42),
+        make_domain!([("name", "ilike", "0ddly"), ("id", "!=", 42u32)])
+    );
+    assert_eq!(
+        fields
+            .state()
+            .is_in([SaleOrderState::Draft, SaleOrderState::Sent])
+            | !fields.total_price().le(10),
+        make_domain!([
+            "|",
+            ("state", "in", vec!["draft", "sent"]),
+            "!",
+            ("total_price", "<=", 10)
+        ])
+    );
+    assert_eq!(fields.name().is_set(), make_domain!([("name", "!=", None)]));
+
+    // Through references
+    assert_eq!(
+        fields.lines().total_price().gt(10),
+        make_domain!([("lines.total_price", ">", 10)])
+    );
+    assert_eq!(
+        fields.lines().order().lines().price().eq(42),
+        make_domain!([("lines.order.lines.price", "=", 42)])
+    );
+    assert_eq!(
+        SaleOrderLine::fields().order().is_in([1, 2]),
+        make_domain!([("order", "in", vec![1u32, 2u32])])
+    );
+    assert_eq!(
+        fields.lines().any(SaleOrderLine::fields().price().gt(100)),
+        make_domain!([("lines", "any", [("price", ">", 100)])])
+    );
+    assert_eq!(
+        fields.lines().is_not_set(),
+        make_domain!([("lines", "=", None)])
+    );
+
+    let fields = Contact::fields();
+    assert_eq!(
+        fields.parent().child_of([1]) & fields.parent().parent().email().is_not_set(),
+        make_domain!([
+            ("parent", "child_of", vec![1u32]),
+            ("parent.parent.email", "=", None)
+        ])
+    );
+}
+
+fn test_search_domain_fields(backend: Backend) -> Result<()> {
+    let mut app = TestApp::with_sale_orders(backend)?;
+    let mut env = app.new_env()?;
+
+    let mut orders = Vec::new();
+    for (name, price) in [("A", 1), ("B", 2)] {
+        let mut map = MapOfFields::default();
+        map.insert("name", name);
+        let sale_order: SaleOrder<SingleId> = env.create_new_record_from_map(map)?;
+        let mut map = MapOfFields::default();
+        map.insert::<&u64>("order", &sale_order.get_id());
+        map.insert("price", price);
+        env.create_new_record_from_map::<SaleOrderLine<_>>(map)?;
+        orders.push(sale_order.get_id());
+    }
+
+    // Lines have an amount of 10
+    let fields = SaleOrder::fields();
+    let sale_orders: SaleOrder<MultipleIds> = env.search(&fields.lines().total_price().gt(15))?;
+    assert_eq!(sale_orders.get_ids(), vec![orders[1]]);
+    let sale_orders: SaleOrder<MultipleIds> =
+        env.search(&(fields.state().eq(SaleOrderState::Draft) & fields.name().eq("A")))?;
+    assert_eq!(sale_orders.get_ids(), vec![orders[0]]);
+
+    Ok(())
+}
@@ -1,5 +1,8 @@
-use erp::app::Application;
-use erp::database::Database;
+use erp::database::{
+    Aggregate, Database, FieldType as DbFieldType, InvalidPathError, InvalidSearchValueError,
+    UnknownFieldError, validate,
+};
+use erp_search::{OrderBy, SearchOptions};
 use erp_search_code_gen::make_domain;
 use erp_types::cache::{Dirty, Update};
 use erp_types::field::FieldType;
@@ -7,16 +10,32 @@ use erp_types::field::{IdMode, MultipleIds, SingleId};
 use erp_types::model::MapOfFields;
 use std::collections::HashMap;
 use std::error::Error;
+use test_utilities::backend::{Backend, TestApp};
+use test_utilities::backend_tests;
 use test_utilities::models::{SaleOrder, SaleOrderLine, SaleOrderState};
 
 type Result<T> = std::result::Result<T, Box<dyn Error>>;
 
-#[test]
-fn test_env_drop_rollback() -> Result<()> {
-    let mut app = Application::new_test();
-    app.model_manager.register_model::<SaleOrder<_>>();
-    app.model_manager.register_model::<SaleOrderLine<_>>();
-    app.model_manager.post_register();
+backend_tests!(
+    test_env_drop_rollback,
+    test_close_saves_dirty_fields,
+    test_save_model_computes_stored_fields,
+    test_fill_default_values_on_map,
+    test_get_fields_to_save,
+    test_get_record,
+    test_get_record_from_xxx,
+    test_compute_method,
+    test_save_fields_to_db,
+    test_search,
+    test_search_with_options,
+    test_search_count,
+    test_read_group,
+    test_validate_domain,
+    test_filtered_domain
+);
+
+fn test_env_drop_rollback(backend: Backend) -> Result<()> {
+    let mut app = TestApp::with_sale_orders(backend)?;
     let mut env = app.new_env()?;
     let mut map: MapOfFields = MapOfFields::new(HashMap::new());
     map.insert("amount", 42);
@@ -60,12 +79,96 @@ fn test_env_drop_rollback() -> Result<()> {
     Ok(())
 }
 
-#[test]
-fn test_fill_default_values_on_map() -> Result<()> {
-    let mut app = Application::new_test();
-    app.model_manager.register_model::<SaleOrder<_>>();
-    app.model_manager.register_model::<SaleOrderLine<_>>();
-    app.model_manager.post_register();
+fn test_close_saves_dirty_fields(backend: Backend) -> Result<()> {
+    let mut app = TestApp::with_sale_orders(backend)?;
+    let mut env = app.new_env()?;
+    let sale_order: SaleOrder<_> = env.create_new_record_from_map(MapOfFields::default())?;
+    let mut map = MapOfFields::default();
+    map.insert("order", sale_order.get_id());
+    let sale_order_line: SaleOrderLine<_> = env.create_new_record_from_map(map)?;
+
+    // Those values are only in cache, and stored computed fields are not computed yet
+    sale_order_line.set_price(50, &mut env)?;
+    sale_order.set_name("0ddlyoko's order".to_string(), &mut env)?;
+    env.close()?;
+
+    let mut env = app.new_env()?;
+    let sale_order_lines = env.database.search(
+        "sale_order_line",
+        &["price", "total_price"],
+        &make_domain!([("amount", "=", 10)]),
+        &SearchOptions::default(),
+        env.model_manager,
+    )?;
+    assert_eq!(sale_order_lines.len(), 1);
+    assert_eq!(
+        sale_order_lines[0].1.get("price"),
+        Some(&Some(erp::database::FieldType::Integer(50)))
+    );
+    assert_eq!(
+        sale_order_lines[0].1.get("total_price"),
+        Some(&Some(erp::database::FieldType::Integer(50 * 10)))
+    );
+    let sale_orders = env.database.search(
+        "sale_order",
+        &["name", "total_price"],
+        &make_domain!([("state", "=", "draft")]),
+        &SearchOptions::default(),
+        env.model_manager,
+    )?;
+    assert_eq!(sale_orders.len(), 1);
+    assert_eq!(
+        sale_orders[0].1.get("name"),
+        Some(&Some(erp::database::FieldType::String(
+            "0ddlyoko's order".to_string()
+        )))
+    );
+    assert_eq!(
+        sale_orders[0].1.get("total_price"),
+        Some(&Some(erp::database::FieldType::Integer(50 * 10)))
+    );
+
+    Ok(())
+}
+
+fn test_save_model_computes_stored_fields(backend: Backend) -> Result<()> {
+    let mut app = TestApp::with_sale_orders(backend)?;
+    let mut env = app.new_env()?;
+    let mut map = MapOfFields::default();
+    map.insert("price", 50);
+    let sale_order_line: SaleOrderLine<_> = env.create_new_record_from_map(map)?;
+    let id = sale_order_line.get_id();
+    assert!(
+        env.cache
+            .is_field_to_recompute("sale_order_line", "total_price", &id)
+    );
+
+    // Stored fields are computed until none of them needs to be computed anymore
+    env.save_model_to_db("sale_order_line")?;
+    assert!(
+        !env.cache
+            .is_field_to_recompute("sale_order_line", "total_price", &id)
+    );
+    let sale_order_lines = env.database.search(
+        "sale_order_line",
+        &["total_price"],
+        &make_domain!([("id", "=", id)]),
+        &SearchOptions::default(),
+        env.model_manager,
+    )?;
+    assert_eq!(
+        sale_order_lines[0].1.get("total_price"),
+        Some(&Some(erp::database::FieldType::Integer(50 * 10)))
+    );
+
+    // Nothing to compute
+    env.save_model_to_db("sale_order_line")?;
+
+    Ok(())
+}
+
+fn test_fill_default_values_on_map(backend: Backend) -> Result<()> {
+    let mut app = TestApp::with_sale_orders(backend)?;
     let env = app.new_env()?;
 
     let mut map: MapOfFields = MapOfFields::new(HashMap::new());
@@ -84,12 +187,8 @@ fn test_fill_default_values_on_map() -> Result<()> {
     Ok(())
 }
 
-#[test]
-fn test_get_fields_to_save() -> Result<()> {
-    let mut app = Application::new_test();
-    app.model_manager.register_model::<SaleOrder<_>>();
-    app.model_manager.register_model::<SaleOrderLine<_>>();
-    app.model_manager.post_register();
+fn test_get_fields_to_save(backend: Backend) -> Result<()> {
+    let mut app = TestApp::with_sale_orders(backend)?;
     let env = app.new_env()?;
 
     // Empty
@@ -188,12 +287,8 @@ fn test_get_fields_to_save() -> Result<()> {
     Ok(())
 }
 
-#[test]
-fn test_get_record() -> Result<()> {
-    let mut app = Application::new_test();
-    app.model_manager.register_model::<SaleOrder<_>>();
-    app.model_manager.register_model::<SaleOrderLine<_>>();
-    app.model_manager.post_register();
+fn test_get_record(backend: Backend) -> Result<()> {
+    let mut app = TestApp::with_sale_orders(backend)?;
     let mut env = app.new_env()?;
 
     // Create new record with default values
@@ -268,12 +363,8 @@ fn test_get_record() -> Result<()> {
     Ok(())
 }
 
-#[test]
-fn test_get_record_from_xxx() -> Result<()> {
-    let mut app = Application::new_test();
-    app.model_manager.register_model::<SaleOrder<_>>();
-    app.model_manager.register_model::<SaleOrderLine<_>>();
-    app.model_manager.post_register();
+fn test_get_record_from_xxx(backend: Backend) -> Result<()> {
+    let mut app = TestApp::with_sale_orders(backend)?;
     let mut env = app.new_env()?;
 
     // Insert random data inside
@@ -296,12 +387,8 @@ fn test_get_record_from_xxx() -> Result<()> {
     Ok(())
 }
 
-#[test]
-fn test_compute_method() -> Result<()> {
-    let mut app = Application::new_test();
-    app.model_manager.register_model::<SaleOrder<_>>();
-    app.model_manager.register_model::<SaleOrderLine<_>>();
-    app.model_manager.post_register();
+fn test_compute_method(backend: Backend) -> Result<()> {
+    let mut app = TestApp::with_sale_orders(backend)?;
     let mut env = app.new_env()?;
 
     // Insert random data inside
@@ -334,12 +421,8 @@ fn test_compute_method() -> Result<()> {
     Ok(())
 }
 
-#[test]
-fn test_save_fields_to_db() -> Result<()> {
-    let mut app = Application::new_test();
-    app.model_manager.register_model::<SaleOrder<_>>();
-    app.model_manager.register_model::<SaleOrderLine<_>>();
-    app.model_manager.post_register();
+fn test_save_fields_to_db(backend: Backend) -> Result<()> {
+    let mut app = TestApp::with_sale_orders(backend)?;
     let mut env = app.new_env()?;
 
     let map: MapOfFields = MapOfFields::default();
@@ -351,6 +434,7 @@ fn test_save_fields_to_db() -> Result<()> {
         "sale_order",
         &["name", "state", "total_price"],
         &make_domain!([("name", "=", "0ddlyoko")]),
+        &SearchOptions::default(),
         env.model_manager,
     )?;
     assert!(!sale_order_vec.is_empty());
@@ -435,6 +519,7 @@ fn test_save_fields_to_db() -> Result<()> {
         "sale_order",
         &["name", "state"],
         &make_domain!([("name", "=", "1ddlyoko")]),
+        &SearchOptions::default(),
         env.model_manager,
     )?;
     assert!(!sale_order_vec.is_empty());
@@ -457,12 +542,8 @@ fn test_save_fields_to_db() -> Result<()> {
     Ok(())
 }
 
-#[test]
-fn test_search() -> Result<()> {
-    let mut app = Application::new_test();
-    app.model_manager.register_model::<SaleOrder<_>>();
-    app.model_manager.register_model::<SaleOrderLine<_>>();
-    app.model_manager.post_register();
+fn test_search(backend: Backend) -> Result<()> {
+    let mut app = TestApp::with_sale_orders(backend)?;
     let mut env = app.new_env()?;
 
     // SO
@@ -655,3 +736,394 @@ fn test_search() -> Result<()> {
 
     Ok(())
 }
+
+fn test_search_with_options(backend: Backend) -> Result<()> {
+    let mut app = TestApp::with_sale_orders(backend)?;
+    let mut env = app.new_env()?;
+
+    let mut ids = vec![];
+    for name in ["b", "a", "c", "a"] {
+        let mut map = MapOfFields::default();
+        map.insert("name", name);
+        let sale_order: SaleOrder<SingleId> = env.create_new_record_from_map(map)?;
+        ids.push(sale_order.get_id());
+    }
+    for (order_idx, price) in [(0, 10), (1, 20), (1, 10), (2, 30)] {
+        let mut map = MapOfFields::default();
+        map.insert("order", ids[order_idx]);
+        map.insert("price", price);
+        env.create_new_record_from_map::<SaleOrderLine<_>>(map)?;
+    }
+    let all_orders = make_domain!([("state", "=", "draft")]);
+
+    // Default order of the model is "name asc, id desc"
+    let sale_orders: SaleOrder<MultipleIds> = env.search(&all_orders)?;
+    assert_eq!(
+        sale_orders.get_ids_ref(),
+        &vec![ids[3], ids[1], ids[0], ids[2]]
+    );
+
+    // Limit and offset
+    let options = SearchOptions {
+        limit: Some(2),
+        offset: 1,
+        ..Default::default()
+    };
+    let sale_orders: SaleOrder<MultipleIds> = env.search_with_options(&all_orders, &options)?;
+    assert_eq!(sale_orders.get_ids_ref(), &vec![ids[1], ids[0]]);
+
+    // Order through a M2O, records without any order are first in descending order
+    let options = SearchOptions {
+        order: OrderBy::parse_list("order.name desc, price")?,
+        ..Default::default()
+    };
+    let mut map = MapOfFields::default();
+    map.insert("price", 5);
+    let line_without_order: SaleOrderLine<SingleId> = env.create_new_record_from_map(map)?;
+    let lines: SaleOrderLine<MultipleIds> =
+        env.search_with_options(&make_domain!([("amount", "=", 10)]), &options)?;
+    let prices = lines
+        .get_ids_ref()
+        .iter()
+        .map(|id| {
+            let line: SaleOrderLine<SingleId> = env.get_record((*id).into());
+            line.get_price(&mut env).copied()
+        })
+        .collect::<Result<Vec<_>>>()?;
+    assert_eq!(lines.get_ids_ref()[0], line_without_order.get_id());
+    assert_eq!(prices, vec![5, 30, 10, 10, 20]);
+
+    // Values that are only in cache are saved before the search
+    let sale_order: SaleOrder<SingleId> = env.get_record(ids[2].into());
+    sale_order.set_name("0".to_string(), &mut env)?;
+    let sale_orders: SaleOrder<MultipleIds> = env.search(&all_orders)?;
+    assert_eq!(sale_orders.get_ids_ref()[0], ids[2]);
+
+    // O2M can't be used to sort records
+    let options = SearchOptions {
+        order: vec![OrderBy::asc("lines")],
+        ..Default::default()
+    };
+    assert!(
+        env.search_with_options::<SaleOrder<MultipleIds>>(&all_orders, &options)
+            .is_err()
+    );
+
+    Ok(())
+}
+
+fn test_search_count(backend: Backend) -> Result<()> {
+    let mut app = TestApp::with_sale_orders(backend)?;
+    let mut env = app.new_env()?;
+
+    let sale_order: SaleOrder<SingleId> = env.create_new_record_from_map(MapOfFields::default())?;
+    for price in [10, 20, 30] {
+        let mut map = MapOfFields::default();
+        map.insert("order", sale_order.get_id());
+        map.insert("price", price);
+        env.create_new_record_from_map::<SaleOrderLine<_>>(map)?;
+    }
+    let mut map = MapOfFields::default();
+    map.insert("price", 40);
+    let line_without_order: SaleOrderLine<SingleId> = env.create_new_record_from_map(map)?;
+
+    // Count should match the number of records returned by a search
+    for domain in [
+        make_domain!([("price", ">=", 20)]),
+        make_domain!([("order.name", "=", "0ddlyoko")]),
+        make_domain!(["|", ("price", "=", 10), ("order", "=", None)]),
+        make_domain!([("order.lines.price", "=", 40)]),
+    ] {
+        let lines: SaleOrderLine<MultipleIds> = env.search(&domain)?;
+        let count = env.search_count::<SaleOrderLine<_>>(&domain)?;
+        assert_eq!(count as usize, lines.get_ids_ref().len());
+        assert_eq!(
+            env.search_exists::<SaleOrderLine<_>>(&domain)?,
+            !lines.get_ids_ref().is_empty()
+        );
+    }
+    assert_eq!(
+        env.search_count::<SaleOrder<_>>(&make_domain!([("lines.price", ">", 15)]))?,
+        1
+    );
+    assert!(!env.search_exists::<SaleOrder<_>>(&make_domain!([("lines.price", ">", 30)]))?);
+
+    // Values that are only in cache are saved before counting
+    line_without_order.set_price(10, &mut env)?;
+    assert_eq!(
+        env.search_count::<SaleOrderLine<_>>(&make_domain!([("price", "=", 10)]))?,
+        2
+    );
+
+    Ok(())
+}
+
+fn test_read_group(backend: Backend) -> Result<()> {
+    let mut app = TestApp::with_sale_orders(backend)?;
+    let mut env = app.new_env()?;
+
+    let mut orders = Vec::new();
+    for name in ["B", "A"] {
+        let mut map = MapOfFields::default();
+        map.insert("name", name);
+        let sale_order: SaleOrder<SingleId> = env.create_new_record_from_map(map)?;
+        orders.push(sale_order.get_id());
+    }
+    for (order, price) in [(orders[1], 10), (orders[1], 20), (orders[0], 30)] {
+        let mut map = MapOfFields::default();
+        map.insert("order", order);
+        map.insert("price", price);
+        env.create_new_record_from_map::<SaleOrderLine<_>>(map)?;
+    }
+    let mut map = MapOfFields::default();
+    map.insert("price", 40);
+    env.create_new_record_from_map::<SaleOrderLine<_>>(map)?;
+
+    // Groups are sorted by key, empty keys being last
+    let aggregates = [
+        Aggregate::count(),
+        Aggregate::sum("price"),
+        Aggregate::avg("price"),
+        Aggregate::min("price"),
+        Aggregate::max("price"),
+    ];
+    let groups = env.read_group::<SaleOrderLine<_>>(
+        &make_domain!([("price", ">", 0)]),
+        &["order.name"],
+        &aggregates,
+    )?;
+    assert_eq!(groups.len(), 3);
+    assert_eq!(
+        groups[0].keys,
+        vec![Some(DbFieldType::String("A".to_string()))]
+    );
+    assert_eq!(
+        groups[0].aggregates,
+        vec![
+            Some(DbFieldType::Integer(2)),
+            Some(DbFieldType::BigInteger(30)),
+            Some(DbFieldType::Float(15.0)),
+            Some(DbFieldType::Integer(10)),
+            Some(DbFieldType::Integer(20)),
+        ]
+    );
+    assert_eq!(
+        groups[1].keys,
+        vec![Some(DbFieldType::String("B".to_string()))]
+    );
+    assert_eq!(groups[1].aggregates[0], Some(DbFieldType::Integer(1)));
+    assert_eq!(groups[1].aggregates[1], Some(DbFieldType::BigInteger(30)));
+    assert_eq!(groups[2].keys, vec![None]);
+    assert_eq!(groups[2].aggregates[1], Some(DbFieldType::BigInteger(40)));
+
+    // Group on multiple keys, with a domain
+    let groups = env.read_group::<SaleOrderLine<_>>(
+        &make_domain!([("price", "<", 40)]),
+        &["order", "price"],
+        &[Aggregate::count()],
+    )?;
+    assert_eq!(groups.len(), 3);
+    assert_eq!(groups[0].keys[1], Some(DbFieldType::Integer(30)));
+    assert_eq!(groups[1].keys[1], Some(DbFieldType::Integer(10)));
+    assert_eq!(groups[2].keys[1], Some(DbFieldType::Integer(20)));
+
+    // Without group_by, a single group is returned, even if no record matches
+    let groups = env.read_group::<SaleOrderLine<_>>(
+        &make_domain!([("price", ">", 100)]),
+        &[],
+        &[Aggregate::count(), Aggregate::sum("price")],
+    )?;
+    assert_eq!(groups.len(), 1);
+    assert!(groups[0].keys.is_empty());
+    assert_eq!(
+        groups[0].aggregates,
+        vec![Some(DbFieldType::Integer(0)), None]
+    );
+
+    // Aggregates are checked against the type of the field
+    assert!(
+        env.read_group::<SaleOrderLine<_>>(
+            &make_domain!([("price", ">", 0)]),
+            &[],
+            &[Aggregate::sum("order.name")]
+        )
+        .is_err()
+    );
+    assert!(
+        env.read_group::<SaleOrderLine<_>>(
+            &make_domain!([("price", ">", 0)]),
+            &["total_price"],
+            &[]
+        )
+        .is_ok()
+    );
+
+    // Sums of integers don't overflow when they exceed the range of an i32
+    for _ in 0..2 {
+        let mut map = MapOfFields::default();
+        map.insert("price", i32::MAX);
+        map.insert("amount", 1);
+        env.create_new_record_from_map::<SaleOrderLine<_>>(map)?;
+    }
+    let groups = env.read_group::<SaleOrderLine<_>>(
+        &make_domain!([("price", ">", 0)]),
+        &[],
+        &[Aggregate::sum("price")],
+    )?;
+    assert_eq!(
+        groups[0].aggregates,
+        vec![Some(DbFieldType::BigInteger(100 + 2 * i32::MAX as i64))]
+    );
+
+    Ok(())
+}
+
+fn test_validate_domain(backend: Backend) -> Result<()> {
+    let mut app = TestApp::with_sale_orders(backend)?;
+    let model_manager = &app.model_manager;
+
+    let domain = make_domain!([
+        ("order.lines.price", ">", 10),
+        "|",
+        ("order", "=", None),
+        ("order.name", "ilike", "0ddlyoko"),
+        ("id", "in", vec![1u32, 2u32])
+    ]);
+    validate(&domain, "sale_order_line", model_manager)?;
+    validate(
+        &make_domain!([("lines", "!=", None)]),
+        "sale_order",
+        model_manager,
+    )?;
+
+    // Unknown fields
+    let result = validate(
+        &make_domain!([("lins.price", ">", 1)]),
+        "sale_order",
+        model_manager,
+    );
+    assert!(result.is_err_and(|error| {
+        error
+            .downcast_ref::<UnknownFieldError>()
+            .is_some_and(|error| error.model_name == "sale_order" && error.field_name == "lins")
+    }));
+    let result = validate(
+        &make_domain!(["!", ("order.nam", "=", "0ddlyoko")]),
+        "sale_order_line",
+        model_manager,
+    );
+    assert!(result.is_err_and(|error| {
+        error
+            .downcast_ref::<UnknownFieldError>()
+            .is_some_and(|error| error.model_name == "sale_order" && error.field_name == "nam")
+    }));
+
+    // Intermediate fields should be M2O or O2M
+    let result = validate(
+        &make_domain!([("price.order", "=", 1u32)]),
+        "sale_order_line",
+        model_manager,
+    );
+    assert!(result.is_err_and(|error| error.is::<InvalidPathError>()));
+
+    // Values should have the same type as the field
+    for domain in [
+        make_domain!([("price", "=", "10")]),
+        make_domain!([("price", "in", vec![1.0, 2.0])]),
+        make_domain!([("order", "=", 1)]),
+        make_domain!([("order.name", "like", 1)]),
+        make_domain!([("amount", "like", 1)]),
+    ] {
+        let result = validate(&domain, "sale_order_line", model_manager);
+        assert!(result.is_err_and(|error| error.is::<InvalidSearchValueError>()));
+    }
+
+    // Search returns the error instead of panicking
+    let mut env = app.new_env()?;
+    let result: Result<SaleOrder<MultipleIds>> =
+        env.search(&make_domain!([("lins.price", ">", 1)]));
+    assert!(result.is_err_and(|error| error.is::<UnknownFieldError>()));
+    let result = env.search_count::<SaleOrder<_>>(&make_domain!([("name", "=", 42)]));
+    assert!(result.is_err_and(|error| error.is::<InvalidSearchValueError>()));
+
+    Ok(())
+}
+
+fn test_filtered_domain(backend: Backend) -> Result<()> {
+    let mut app = TestApp::with_sale_orders(backend)?;
+    let mut env = app.new_env()?;
+
+    let mut orders = Vec::new();
+    for name in ["0ddlyoko", "Other"] {
+        let mut map = MapOfFields::default();
+        map.insert("name", name);
+        let sale_order: SaleOrder<SingleId> = env.create_new_record_from_map(map)?;
+        orders.push(sale_order.get_id());
+    }
+    let mut lines = Vec::new();
+    for (order, price) in [
+        (Some(orders[0]), 10),
+        (Some(orders[0]), 20),
+        (Some(orders[1]), 30),
+        (None, 40),
+    ] {
+        let mut map = MapOfFields::default();
+        if let Some(order) = order {
+            map.insert::<&u64>("order", &order);
+        }
+        map.insert("price", price);
+        let line: SaleOrderLine<SingleId> = env.create_new_record_from_map(map)?;
+        lines.push(line.get_id());
+    }
+    let all_lines: SaleOrderLine<MultipleIds> = env.get_record(MultipleIds::from(lines.clone()));
+    let all_orders: SaleOrder<MultipleIds> = env.get_record(MultipleIds::from(orders.clone()));
+
+    // Values that are only in cache are used
+    let line: SaleOrderLine<SingleId> = env.get_record(lines[0].into());
+    line.set_price(50, &mut env)?;
+    let filtered = env.filtered_domain(&all_lines, &make_domain!([("price", ">=", 40)]))?;
+    assert_eq!(filtered.get_ids_ref(), &vec![lines[0], lines[3]]);
+
+    // Result is the same as a search on those records
+    let line_domains = [
+        make_domain!([("price", ">", 25)]),
+        make_domain!([("order.name", "=", "0ddlyoko")]),
+        make_domain!(["!", ("order.name", "=", "0ddlyoko")]),
+        make_domain!(["|", ("order", "=", None), ("order.lines.price", "=", 30)]),
+        make_domain!([("total_price", "in", vec![200, 500])]),
+        make_domain!([("id", "not in", vec![lines[1]])]),
+    ];
+    for domain in line_domains {
+        let filtered = env.filtered_domain(&all_lines, &domain)?;
+        let searched: SaleOrderLine<MultipleIds> = env.search(&domain)?;
+        let mut searched = searched.get_ids_ref().clone();
+        searched.sort();
+        assert_eq!(filtered.get_ids_ref(), &searched, "{domain}");
+    }
+    let order_domains = [
+        make_domain!([("lines", "=", None)]),
+        make_domain!([("lines", "!=", None)]),
+        make_domain!([("lines.price", "=", 30)]),
+        make_domain!([("name", "ilike", "ODD"), ("lines.price", ">", 10)]),
+        make_domain!([("total_price", ">", 300)]),
+    ];
+    for domain in order_domains {
+        let filtered = env.filtered_domain(&all_orders, &domain)?;
+        let searched: SaleOrder<MultipleIds> = env.search(&domain)?;
+        let mut searched = searched.get_ids_ref().clone();
+        searched.sort();
+        assert_eq!(filtered.get_ids_ref(), &searched, "{domain}");
+    }
+
+    // Only given records are filtered, in the same order
+    let some_lines: SaleOrderLine<MultipleIds> =
+        env.get_record(MultipleIds::from(vec![lines[3], lines[2], lines[1]]));
+    let filtered = env.filtered_domain(&some_lines, &make_domain!([("price", ">", 15)]))?;
+    assert_eq!(filtered.get_ids_ref(), &vec![lines[3], lines[2], lines[1]]);
+
+    // Invalid domains are rejected
+    let result = env.filtered_domain(&all_lines, &make_domain!([("ordr.name", "=", "0ddlyoko")]));
+    assert!(result.is_err_and(|error| error.is::<UnknownFieldError>()));
+
+    Ok(())
+}
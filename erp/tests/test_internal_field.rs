@@ -1,6 +1,6 @@
 use erp::app::Application;
 use erp_types::field::FieldType;
-use erp_types::field::{FieldCompute, FieldReferenceType};
+use erp_types::field::{DefaultKind, FieldCompute, FieldReferenceType};
 use std::any::TypeId;
 use std::error::Error;
 use erp_internal_types::{FinalInternalField, InternalField};
@@ -19,8 +19,11 @@ fn test_register_field() {
         &InternalField {
             name: "name".to_string(),
             default_value: Some(FieldType::String("0ddlyoko".to_string())),
+            default_kind: DefaultKind::Value,
             description: Some("This is the name".to_string()),
             required: false,
+            digits: None,
+            currency_field: None,
             compute: Some(FieldCompute {
                 type_id,
                 depends: vec!["age".to_string(), "test".to_string()],
@@ -34,8 +37,11 @@ fn test_register_field() {
         &InternalField {
             name: "age".to_string(),
             default_value: Some(FieldType::Integer(42)),
+            default_kind: DefaultKind::Value,
             description: Some("This is the age of the person".to_string()),
             required: false,
+            digits: None,
+            currency_field: None,
             compute: None,
             field_ref: None,
         },
@@ -70,8 +76,11 @@ fn test_register_field() {
         &InternalField {
             name: "name".to_string(),
             default_value: Some(FieldType::String("1ddlyoko".to_string())),
+            default_kind: DefaultKind::Value,
             description: None,
             required: true,
+            digits: None,
+            currency_field: None,
             compute: None,
             field_ref: None,
         },
@@ -97,8 +106,11 @@ fn test_register_field() {
         &InternalField {
             name: "name".to_string(),
             default_value: None,
+            default_kind: DefaultKind::Value,
             description: Some("This is another description".to_string()),
             required: true,
+            digits: None,
+            currency_field: None,
             compute: Some(FieldCompute {
                 type_id,
                 depends: vec!["age".to_string(), "test2".to_string()],
@@ -130,8 +142,11 @@ fn test_register_field() {
         &InternalField {
             name: "name".to_string(),
             default_value: None,
+            default_kind: DefaultKind::Value,
             description: Some("This is another description".to_string()),
             required: true,
+            digits: None,
+            currency_field: None,
             compute: Some(FieldCompute {
                 type_id,
                 depends: vec!["age".to_string()],
@@ -169,8 +184,11 @@ fn test_register_field_without_default_value_should_fail() {
         &InternalField {
             name: "name".to_string(),
             default_value: None,
+            default_kind: DefaultKind::Value,
             description: Some("This is the name".to_string()),
             required: true,
+            digits: None,
+            currency_field: None,
             compute: None,
             field_ref: None,
         },
@@ -188,8 +206,11 @@ fn test_register_field_with_another_default_type_should_fail() {
         &InternalField {
             name: "name".to_string(),
             default_value: Some(FieldType::String("0ddlyoko".to_string())),
+            default_kind: DefaultKind::Value,
             description: Some("This is the name".to_string()),
             required: true,
+            digits: None,
+            currency_field: None,
             compute: None,
             field_ref: None,
         },
@@ -200,8 +221,11 @@ fn test_register_field_with_another_default_type_should_fail() {
         &InternalField {
             name: "name".to_string(),
             default_value: Some(FieldType::Integer(42)),
+            default_kind: DefaultKind::Value,
             description: None,
             required: true,
+            digits: None,
+            currency_field: None,
             compute: None,
             field_ref: None,
         },
@@ -252,7 +276,7 @@ fn test_reference_inverse_link() -> Result<()> {
         matches!(so_field_inverse.inverse_field.clone(), FieldReferenceType::O2M { inverse_field } if inverse_field == "order")
     );
     assert!(
-        matches!(so_line_field_inverse.inverse_field.clone(), FieldReferenceType::M2O { inverse_fields } if inverse_fields.contains(&"lines".to_string()))
+        matches!(so_line_field_inverse.inverse_field.clone(), FieldReferenceType::M2O { inverse_fields, .. } if inverse_fields.contains(&"lines".to_string()))
     );
 
     Ok(())
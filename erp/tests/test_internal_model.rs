@@ -1,6 +1,9 @@
-use erp_types::field::FieldType;
+use erp_types::field::{DefaultKind, FieldType};
 use std::any::TypeId;
 use erp_internal_types::{FinalInternalModel, InternalField};
+use erp::model::ModelManager;
+use erp_search::{OrderBy, SearchOptions};
+use test_utilities::models::{SaleOrder, SaleOrderLine};
 
 #[test]
 fn test_get_fields_name() {
@@ -11,8 +14,11 @@ fn test_get_fields_name() {
         &InternalField {
             name: "name".to_string(),
             default_value: Some(FieldType::String("0ddlyoko".to_string())),
+            default_kind: DefaultKind::Value,
             description: Some("This is the name".to_string()),
             required: true,
+            digits: None,
+            currency_field: None,
             compute: None,
             field_ref: None,
         },
@@ -23,8 +29,11 @@ fn test_get_fields_name() {
         &InternalField {
             name: "age".to_string(),
             default_value: Some(FieldType::Integer(42)),
+            default_kind: DefaultKind::Value,
             description: Some("This is the age of the person".to_string()),
             required: false,
+            digits: None,
+            currency_field: None,
             compute: None,
             field_ref: None,
         },
@@ -41,3 +50,42 @@ fn test_get_fields_name() {
     );
     assert_eq!(internal_model.get_missing_fields(vec!["age"]), vec!["name"]);
 }
+
+#[test]
+fn test_get_order() {
+    let mut model_manager = ModelManager::default();
+    model_manager.register_model::<SaleOrder<_>>();
+    model_manager.register_model::<SaleOrderLine<_>>();
+    model_manager.post_register();
+
+    // Default order of the model, declared with #[erp(order = "...")]
+    let sale_order = model_manager.get_model("sale_order");
+    assert_eq!(sale_order.order, vec![OrderBy::asc("name"), OrderBy::desc("id")]);
+    assert_eq!(
+        sale_order.get_order(&SearchOptions::default()),
+        sale_order.order
+    );
+    // Given order replaces the default one, and records are always sorted by id at the end
+    let options = SearchOptions {
+        order: vec![OrderBy::desc("state")],
+        ..Default::default()
+    };
+    assert_eq!(
+        sale_order.get_order(&options),
+        vec![OrderBy::desc("state"), OrderBy::asc("id")]
+    );
+    // Unordered searches don't have any order
+    assert!(sale_order.get_order(&SearchOptions::unordered()).is_empty());
+
+    let sale_order_line = model_manager.get_model("sale_order_line");
+    assert_eq!(
+        sale_order_line.get_order(&SearchOptions::default()),
+        vec![OrderBy::asc("id")]
+    );
+
+    // Invalid orders
+    assert!(OrderBy::parse_list("name asc desc").is_err());
+    assert!(OrderBy::parse_list("name up").is_err());
+    assert!(OrderBy::parse_list("name, , id").is_err());
+    assert!(OrderBy::parse_list("order..name").is_err());
+}
@@ -0,0 +1,141 @@
+use erp::database::InvalidSearchValueError;
+use erp::environment::Environment;
+use erp_search::SearchType;
+use erp_search_code_gen::make_domain;
+use erp_types::field::{MultipleIds, SingleId};
+use erp_types::model::MapOfFields;
+use serde_json::json;
+use std::error::Error;
+use test_utilities::backend::{Backend, TestApp};
+use test_utilities::backend_tests;
+use test_utilities::models::{Delivery, SaleOrder};
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+backend_tests!(test_json_values, test_json_key_paths);
+
+fn search(env: &mut Environment, domain: &SearchType) -> Result<Vec<u64>> {
+    let sale_orders: SaleOrder<MultipleIds> = env.search(domain)?;
+    let mut ids = sale_orders.get_ids();
+    ids.sort();
+    Ok(ids)
+}
+
+fn create_sale_orders(env: &mut Environment) -> Result<Vec<u64>> {
+    let metadatas = [
+        json!({"source": "website", "priority": 3, "urgent": true, "address": {"city": "Paris"}}),
+        json!({"source": "phone", "priority": 1.5, "urgent": false, "tags": ["a", "b"]}),
+        json!({"source": 42, "priority": "high", "address": {"city": null}}),
+    ];
+    let mut ids = Vec::new();
+    for metadata in metadatas {
+        let mut map = MapOfFields::default();
+        map.insert("metadata", metadata);
+        let sale_order: SaleOrder<SingleId> = env.create_new_record_from_map(map)?;
+        ids.push(sale_order.get_id());
+    }
+    let sale_order: SaleOrder<SingleId> = env.create_new_record_from_map(MapOfFields::default())?;
+    ids.push(sale_order.get_id());
+    Ok(ids)
+}
+
+fn test_json_values(backend: Backend) -> Result<()> {
+    let mut app = TestApp::with_sale_orders(backend)?;
+    let mut env = app.new_env()?;
+
+    let ids = create_sale_orders(&mut env)?;
+    let sale_order: SaleOrder<SingleId> = env.get_record(ids[0].into());
+    assert_eq!(
+        sale_order.get_metadata(&mut env)?.unwrap()["address"]["city"],
+        json!("Paris")
+    );
+    // Optional JSON fields are empty by default
+    let sale_order: SaleOrder<SingleId> = env.get_record(ids[3].into());
+    assert_eq!(sale_order.get_metadata(&mut env)?, None);
+    sale_order.set_metadata(Some(json!([1, 2, 3])), &mut env)?;
+    assert_eq!(sale_order.get_metadata(&mut env)?, Some(&json!([1, 2, 3])));
+
+    // Structs are converted from and to JSON
+    assert_eq!(sale_order.get_delivery(&mut env)?, None);
+    let delivery = Delivery {
+        carrier: "bpost".to_string(),
+        weight: 2.5,
+        express: true,
+    };
+    sale_order.set_delivery(Some(delivery.clone()), &mut env)?;
+    assert_eq!(sale_order.get_delivery(&mut env)?, Some(delivery.clone()));
+    let sale_orders: SaleOrder<MultipleIds> = env.get_record(MultipleIds::from(ids.clone()));
+    assert_eq!(
+        sale_orders.get_delivery(&mut env)?,
+        vec![None, None, None, Some(delivery)]
+    );
+    Ok(())
+}
+
+fn test_json_key_paths(backend: Backend) -> Result<()> {
+    let mut app = TestApp::with_sale_orders(backend)?;
+    let mut env = app.new_env()?;
+
+    let ids = create_sale_orders(&mut env)?;
+    let sale_orders: SaleOrder<MultipleIds> = env.get_record(MultipleIds::from(ids.clone()));
+    let checks: Vec<(SearchType, Vec<u64>)> = vec![
+        (
+            make_domain!([("metadata.source", "=", "website")]),
+            vec![ids[0]],
+        ),
+        // Numbers are compared whatever their type is, and values of another type never match
+        (
+            make_domain!([("metadata.priority", ">", 1)]),
+            vec![ids[0], ids[1]],
+        ),
+        (
+            make_domain!([("metadata.priority", "<", 2.0)]),
+            vec![ids[1]],
+        ),
+        (make_domain!([("metadata.source", "=", 42)]), vec![ids[2]]),
+        (make_domain!([("metadata.urgent", "=", true)]), vec![ids[0]]),
+        (
+            make_domain!([("metadata.source", "in", vec!["phone", "website"])]),
+            vec![ids[0], ids[1]],
+        ),
+        (
+            make_domain!([("metadata.source", "like", "on")]),
+            vec![ids[1]],
+        ),
+        // Missing keys and null values are empty
+        (
+            make_domain!([("metadata.address.city", "=", None)]),
+            vec![ids[1], ids[2], ids[3]],
+        ),
+        (
+            make_domain!([("metadata.address.city", "!=", None)]),
+            vec![ids[0]],
+        ),
+        // Keys of arrays are their indexes
+        (make_domain!([("metadata.tags.1", "=", "b")]), vec![ids[1]]),
+        (
+            SaleOrder::fields()
+                .metadata()
+                .key("address")
+                .key("city")
+                .eq("Paris"),
+            vec![ids[0]],
+        ),
+        (
+            SaleOrder::fields().metadata().key("priority").ge(3),
+            vec![ids[0]],
+        ),
+    ];
+    for (domain, expected) in checks {
+        assert_eq!(search(&mut env, &domain)?, expected, "{domain:?}");
+        let mut filtered = env.filtered_domain(&sale_orders, &domain)?.get_ids();
+        filtered.sort();
+        assert_eq!(filtered, expected, "{domain:?}");
+    }
+
+    // "like" only works on strings
+    let result: Result<SaleOrder<MultipleIds>> =
+        env.search(&make_domain!([("metadata.source", "like", 42)]));
+    assert!(result.is_err_and(|error| error.is::<InvalidSearchValueError>()));
+    Ok(())
+}
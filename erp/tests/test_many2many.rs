@@ -0,0 +1,193 @@
+use base::BasePlugin;
+use base::models::{Contact, ContactTag};
+use erp::environment::Environment;
+use erp_search_code_gen::make_domain;
+use erp_types::field::{Command, MultipleIds, SingleId};
+use erp_types::model::MapOfFields;
+use std::error::Error;
+use test_utilities::backend::{Backend, TestApp};
+use test_utilities::backend_tests;
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+backend_tests!(
+    test_many2many,
+    test_many2many_commands,
+    test_many2many_unlink
+);
+
+fn create_contact(env: &mut Environment, name: &str) -> Result<Contact<SingleId>> {
+    let mut map = MapOfFields::default();
+    map.insert("name", name);
+    env.create_new_record_from_map::<Contact<_>>(map)
+}
+
+fn create_tag(env: &mut Environment, name: &str) -> Result<ContactTag<SingleId>> {
+    let mut map = MapOfFields::default();
+    map.insert("name", name);
+    env.create_new_record_from_map::<ContactTag<_>>(map)
+}
+
+fn test_many2many(backend: Backend) -> Result<()> {
+    let mut app = TestApp::new(backend)?;
+    app.register_plugin(Box::new(BasePlugin {}))?;
+    app.load_plugin("base")?;
+
+    let mut env = app.new_env()?;
+    let contact = create_contact(&mut env, "0ddlyoko")?;
+    let other_contact = create_contact(&mut env, "0ddlyoko's friend")?;
+    let vip = create_tag(&mut env, "VIP")?;
+    let customer = create_tag(&mut env, "Customer")?;
+
+    // Load the inverse side in cache before linking records
+    assert!(
+        vip.get_contacts::<Contact<_>>(&mut env)?
+            .get_ids_ref()
+            .is_empty()
+    );
+
+    contact.set_tags(vec![vip.get_id(), customer.get_id()].into(), &mut env)?;
+    other_contact.set_tags(vec![vip.get_id()].into(), &mut env)?;
+
+    // Both sides are linked in cache
+    let tags = contact.get_tags::<ContactTag<_>>(&mut env)?;
+    assert_eq!(tags.get_ids(), vec![vip.get_id(), customer.get_id()]);
+    let contacts = vip.get_contacts::<Contact<_>>(&mut env)?;
+    assert_eq!(
+        contacts.get_ids(),
+        vec![contact.get_id(), other_contact.get_id()]
+    );
+
+    // And in the database
+    env.close()?;
+
+    // Open a new environment, so data is read from the database
+    let mut env = app.new_env()?;
+    let contacts = customer.get_contacts::<Contact<_>>(&mut env)?;
+    assert_eq!(contacts.get_ids(), vec![contact.get_id()]);
+    let tags = other_contact.get_tags::<ContactTag<_>>(&mut env)?;
+    assert_eq!(tags.get_ids(), vec![vip.get_id()]);
+
+    // Removing a link updates the other side
+    contact.set_tags(vec![customer.get_id()].into(), &mut env)?;
+    let contacts = vip.get_contacts::<Contact<_>>(&mut env)?;
+    assert_eq!(contacts.get_ids(), vec![other_contact.get_id()]);
+
+    // Values not yet saved are used to search
+    let result: Contact<MultipleIds> = env.search(&make_domain!([("tags.name", "=", "VIP")]))?;
+    assert_eq!(result.get_ids(), vec![other_contact.get_id()]);
+    let result: ContactTag<MultipleIds> =
+        env.search(&make_domain!([("contacts.name", "=", "0ddlyoko")]))?;
+    assert_eq!(result.get_ids(), vec![customer.get_id()]);
+
+    Ok(())
+}
+
+fn test_many2many_commands(backend: Backend) -> Result<()> {
+    let mut app = TestApp::new(backend)?;
+    app.register_plugin(Box::new(BasePlugin {}))?;
+    app.load_plugin("base")?;
+
+    let mut env = app.new_env()?;
+    let contact = create_contact(&mut env, "0ddlyoko")?;
+    let vip = create_tag(&mut env, "VIP")?;
+    let customer = create_tag(&mut env, "Customer")?;
+    let supplier = create_tag(&mut env, "Supplier")?;
+
+    contact.update_tags(
+        &[
+            Command::Add(vip.get_id()),
+            Command::Add(customer.get_id()),
+            Command::Add(vip.get_id()),
+        ],
+        &mut env,
+    )?;
+    let tags = contact.get_tags::<ContactTag<_>>(&mut env)?;
+    assert_eq!(tags.get_ids(), vec![vip.get_id(), customer.get_id()]);
+
+    contact.update_tags(&[Command::Remove(vip.get_id())], &mut env)?;
+    let tags = contact.get_tags::<ContactTag<_>>(&mut env)?;
+    assert_eq!(tags.get_ids(), vec![customer.get_id()]);
+    assert!(
+        vip.get_contacts::<Contact<_>>(&mut env)?
+            .get_ids_ref()
+            .is_empty()
+    );
+
+    contact.update_tags(
+        &[Command::Replace(vec![supplier.get_id(), vip.get_id()])],
+        &mut env,
+    )?;
+    env.close()?;
+
+    // Open a new environment, so data is read from the database
+    let mut env = app.new_env()?;
+    let tags = contact.get_tags::<ContactTag<_>>(&mut env)?;
+    assert_eq!(tags.get_ids(), vec![vip.get_id(), supplier.get_id()]);
+    let contacts = supplier.get_contacts::<Contact<_>>(&mut env)?;
+    assert_eq!(contacts.get_ids(), vec![contact.get_id()]);
+
+    // Commands are applied on each record
+    let other_contact = create_contact(&mut env, "0ddlyoko's friend")?;
+    let ids = vec![contact.get_id(), other_contact.get_id()];
+    let contacts: Contact<MultipleIds> = env.search(&make_domain!([("id", "in", ids)]))?;
+    contacts.update_tags(&[Command::Add(customer.get_id())], &mut env)?;
+    let contacts = customer.get_contacts::<Contact<_>>(&mut env)?;
+    assert_eq!(
+        contacts.get_ids(),
+        vec![contact.get_id(), other_contact.get_id()]
+    );
+
+    contact.update_tags(&[Command::Clear], &mut env)?;
+    assert!(
+        contact
+            .get_tags::<ContactTag<_>>(&mut env)?
+            .get_ids_ref()
+            .is_empty()
+    );
+    let result: Contact<MultipleIds> = env.search(&make_domain!([("tags", "=", None)]))?;
+    assert_eq!(result.get_ids(), vec![contact.get_id()]);
+    let result: Contact<MultipleIds> = env.search(&make_domain!([(
+        "tags",
+        "any",
+        [("name", "=", "Customer")]
+    )]))?;
+    assert_eq!(result.get_ids(), vec![other_contact.get_id()]);
+
+    Ok(())
+}
+
+fn test_many2many_unlink(backend: Backend) -> Result<()> {
+    let mut app = TestApp::new(backend)?;
+    app.register_plugin(Box::new(BasePlugin {}))?;
+    app.load_plugin("base")?;
+
+    let mut env = app.new_env()?;
+    let contact = create_contact(&mut env, "0ddlyoko")?;
+    let vip = create_tag(&mut env, "VIP")?;
+    let customer = create_tag(&mut env, "Customer")?;
+    contact.update_tags(
+        &[Command::Add(vip.get_id()), Command::Add(customer.get_id())],
+        &mut env,
+    )?;
+    env.save_all_to_db()?;
+
+    // Deleted records are removed from loaded M2M, and from the relation table
+    vip.unlink(&mut env)?;
+    let tags = contact.get_tags::<ContactTag<_>>(&mut env)?;
+    assert_eq!(tags.get_ids(), vec![customer.get_id()]);
+    env.close()?;
+    let mut env = app.new_env()?;
+    let tags = contact.get_tags::<ContactTag<_>>(&mut env)?;
+    assert_eq!(tags.get_ids(), vec![customer.get_id()]);
+
+    contact.unlink(&mut env)?;
+    assert!(
+        customer
+            .get_contacts::<Contact<_>>(&mut env)?
+            .get_ids_ref()
+            .is_empty()
+    );
+
+    Ok(())
+}
@@ -1,18 +1,20 @@
 use base::models::{Contact, Lang};
 use base::BasePlugin;
-use erp::app::Application;
 use erp_types::field::{FieldDepend, Reference};
 use erp_types::field::{IdMode, SingleId};
 use erp_types::model::{CommonModel, MapOfFields};
 use std::error::Error;
-use test_utilities::models::{BaseSaleOrder, SaleOrder, SaleOrderLine, SaleOrderState};
 use test_utilities::TestLibPlugin;
+use test_utilities::backend::{Backend, TestApp};
+use test_utilities::backend_tests;
+use test_utilities::models::{BaseSaleOrder, SaleOrder, SaleOrderLine, SaleOrderState};
 
 type Result<T> = std::result::Result<T, Box<dyn Error>>;
 
-#[test]
-fn test_models() -> Result<()> {
-    let mut app = Application::new_test();
+backend_tests!(test_models, test_ref, test_many2one_one2many, test_depends);
+
+fn test_models(backend: Backend) -> Result<()> {
+    let mut app = TestApp::new(backend)?;
     app.register_plugin(Box::new(TestLibPlugin {}))?;
     app.load_plugin("test_lib_plugin")?;
 
@@ -67,9 +69,8 @@ fn test_models() -> Result<()> {
     Ok(())
 }
 
-#[test]
-fn test_ref() -> Result<()> {
-    let mut app = Application::new_test();
+fn test_ref(backend: Backend) -> Result<()> {
+    let mut app = TestApp::new(backend)?;
     app.register_plugin(Box::new(BasePlugin {}))?;
     app.register_plugin(Box::new(TestLibPlugin {}))?;
     app.load_plugin("base")?;
@@ -102,9 +103,8 @@ fn test_ref() -> Result<()> {
     Ok(())
 }
 
-#[test]
-fn test_many2one_one2many() -> Result<()> {
-    let mut app = Application::new_test();
+fn test_many2one_one2many(backend: Backend) -> Result<()> {
+    let mut app = TestApp::new(backend)?;
     app.register_plugin(Box::new(TestLibPlugin {}))?;
     app.load_plugin("test_lib_plugin")?;
 
@@ -133,9 +133,8 @@ fn test_many2one_one2many() -> Result<()> {
     Ok(())
 }
 
-#[test]
-fn test_depends() -> Result<()> {
-    let mut app = Application::new_test();
+fn test_depends(backend: Backend) -> Result<()> {
+    let mut app = TestApp::new(backend)?;
     app.register_plugin(Box::new(TestLibPlugin {}))?;
     app.load_plugin("test_lib_plugin")?;
 
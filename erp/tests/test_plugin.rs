@@ -2,8 +2,12 @@ extern crate test_plugin;
 extern crate test_utilities;
 
 use erp::app::Application;
+use erp::database::Database;
+use erp::model::ModelManager;
 use erp::plugin::errors::PluginAlreadyRegisteredError;
+use erp::plugin::{Migrations, Plugin};
 use std::error::Error;
+use std::sync::Mutex;
 use test_plugin::TestPlugin;
 use test_plugin::TestPlugin2;
 use test_plugin::TestPlugin3;
@@ -74,3 +78,107 @@ fn test_load_plugin_with_depend_not_register_should_fail() {
     app.load_plugin("test_plugin3")
         .expect("Plugin should not be loaded");
 }
+
+static MIGRATION_CALLS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+struct MigrationPlugin;
+
+impl Plugin for MigrationPlugin {
+    fn name(&self) -> String {
+        "migration_plugin".to_string()
+    }
+
+    fn version(&self) -> String {
+        "1.2.0".to_string()
+    }
+
+    fn init_models(&self, _model_manager: &mut ModelManager) {}
+
+    fn init_migrations(&self, migrations: &mut Migrations) {
+        // Registered out of order, to check that they are sorted
+        migrations.post_schema("1.2.0", |_env| {
+            MIGRATION_CALLS
+                .lock()
+                .unwrap()
+                .push("post 1.2.0".to_string());
+            Ok(())
+        });
+        migrations.pre_schema("1.1.0", |_env| {
+            MIGRATION_CALLS
+                .lock()
+                .unwrap()
+                .push("pre 1.1.0".to_string());
+            Ok(())
+        });
+        migrations.post_schema("1.1.0", |_env| {
+            MIGRATION_CALLS
+                .lock()
+                .unwrap()
+                .push("post 1.1.0".to_string());
+            Ok(())
+        });
+        migrations.pre_schema("1.2.0", |_env| {
+            MIGRATION_CALLS
+                .lock()
+                .unwrap()
+                .push("pre 1.2.0".to_string());
+            Ok(())
+        });
+        // Already installed
+        migrations.pre_schema("1.0.0", |_env| {
+            MIGRATION_CALLS
+                .lock()
+                .unwrap()
+                .push("pre 1.0.0".to_string());
+            Ok(())
+        });
+        // Not yet released
+        migrations.post_schema("1.3.0", |_env| {
+            MIGRATION_CALLS
+                .lock()
+                .unwrap()
+                .push("post 1.3.0".to_string());
+            Ok(())
+        });
+    }
+}
+
+#[test]
+fn test_plugin_migrations() -> Result<()> {
+    MIGRATION_CALLS.lock().unwrap().clear();
+
+    // A new installation doesn't execute any migration
+    let mut app = Application::new_test();
+    app.register_plugin(Box::new(MigrationPlugin {}))?;
+    app.load_plugin("migration_plugin")?;
+    assert!(MIGRATION_CALLS.lock().unwrap().is_empty());
+    let mut env = app.new_env()?;
+    assert_eq!(
+        env.database
+            .get_installed_plugin_version("migration_plugin")?,
+        Some("1.2.0".to_string())
+    );
+    drop(env);
+
+    // Upgrading from 1.0.0 executes steps of 1.1.0 and 1.2.0
+    let mut app = Application::new_test();
+    app.register_plugin(Box::new(MigrationPlugin {}))?;
+    let mut env = app.new_env()?;
+    env.database.initialize()?;
+    env.database
+        .set_installed_plugin_version("migration_plugin", "1.0.0")?;
+    env.close()?;
+    app.load_plugin("migration_plugin")?;
+    assert_eq!(
+        *MIGRATION_CALLS.lock().unwrap(),
+        vec!["pre 1.1.0", "pre 1.2.0", "post 1.1.0", "post 1.2.0"]
+    );
+    let mut env = app.new_env()?;
+    assert_eq!(
+        env.database
+            .get_installed_plugin_version("migration_plugin")?,
+        Some("1.2.0".to_string())
+    );
+
+    Ok(())
+}
@@ -1,13 +1,16 @@
-use erp::app::Application;
 use erp_types::cache::{Dirty, Update};
 use erp_types::field::SingleId;
 use erp_types::model::MapOfFields;
 use std::error::Error;
 use std::fmt;
-use test_utilities::models::{SaleOrder, SaleOrderLine};
+use test_utilities::backend::{Backend, TestApp};
+use test_utilities::backend_tests;
+use test_utilities::models::SaleOrder;
 
 type Result<T> = std::result::Result<T, Box<dyn Error>>;
 
+backend_tests!(test_savepoint_rollback);
+
 #[derive(Debug, Clone)]
 pub struct UselessError {}
 
@@ -19,12 +22,8 @@ impl fmt::Display for UselessError {
 
 impl Error for UselessError {}
 
-#[test]
-fn test_savepoint_rollback() -> Result<()> {
-    let mut app = Application::new_test();
-    app.model_manager.register_model::<SaleOrder<_>>();
-    app.model_manager.register_model::<SaleOrderLine<_>>();
-    app.model_manager.post_register();
+fn test_savepoint_rollback(backend: Backend) -> Result<()> {
+    let mut app = TestApp::with_sale_orders(backend)?;
     let mut env = app.new_env()?;
 
     // Insert random data inside
@@ -0,0 +1,115 @@
+use erp::database::cache::CacheDatabase;
+use erp::database::{Database, ForeignKeyViolationError, NotNullViolationError, UnknownFieldError};
+use erp::model::ModelManager;
+use erp_search::SearchOptions;
+use erp_search_code_gen::make_domain;
+use erp_types::model::MapOfFields;
+use std::collections::HashMap;
+use std::error::Error;
+use test_utilities::models::{SaleOrder, SaleOrderLine};
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+#[test]
+fn test_sync_schema_checks_invariants() -> Result<()> {
+    let mut model_manager = ModelManager::default();
+    model_manager.register_model::<SaleOrder<_>>();
+    model_manager.register_model::<SaleOrderLine<_>>();
+    model_manager.post_register();
+    let mut database = CacheDatabase::connect();
+    database.sync_schema(&["sale_order", "sale_order_line"], &model_manager)?;
+
+    // Required field without any value
+    let mut sale_order = MapOfFields::default();
+    sale_order.insert("name", "0ddlyoko");
+    let result = database.create("sale_order", &[&sale_order]);
+    assert!(result.unwrap_err().is::<NotNullViolationError>());
+
+    sale_order.insert("state", "draft");
+    let sale_order_ids = database.create("sale_order", &[&sale_order])?;
+    assert_eq!(sale_order_ids.len(), 1);
+
+    // Unknown field
+    let mut unknown = sale_order.clone();
+    unknown.insert("unknown_field", 42);
+    let result = database.create("sale_order", &[&unknown]);
+    assert!(result.unwrap_err().is::<UnknownFieldError>());
+
+    // M2O targeting a missing record
+    let mut line = MapOfFields::default();
+    line.insert("price", 42);
+    line.insert("amount", 10);
+    line.insert("order", 42u64);
+    let result = database.create("sale_order_line", &[&line]);
+    assert!(result.unwrap_err().is::<ForeignKeyViolationError>());
+
+    line.insert("order", sale_order_ids[0]);
+    let line_ids = database.create("sale_order_line", &[&line])?;
+
+    // Same checks are done on update
+    let mut update = MapOfFields::default();
+    update.insert_none("state");
+    let result = database.update("sale_order", &HashMap::from([(sale_order_ids[0], &update)]));
+    assert!(result.unwrap_err().is::<NotNullViolationError>());
+
+    let mut update = MapOfFields::default();
+    update.insert("order", 69u64);
+    let result = database.update("sale_order_line", &HashMap::from([(line_ids[0], &update)]));
+    assert!(result.unwrap_err().is::<ForeignKeyViolationError>());
+
+    Ok(())
+}
+
+#[test]
+fn test_sync_schema_existing_table() -> Result<()> {
+    let mut model_manager = ModelManager::default();
+    model_manager.register_model::<SaleOrder<_>>();
+    model_manager.register_model::<SaleOrderLine<_>>();
+    model_manager.post_register();
+    let mut database = CacheDatabase::connect();
+
+    // Records created before the sync don't have any value for new columns, so they can't be
+    // required
+    let mut sale_order = MapOfFields::default();
+    sale_order.insert("name", "0ddlyoko");
+    database.create("sale_order", &[&sale_order])?;
+    database.sync_schema(&["sale_order"], &model_manager)?;
+    database.create("sale_order", &[&sale_order])?;
+
+    Ok(())
+}
+
+#[test]
+fn test_delete_applies_ondelete() -> Result<()> {
+    let mut model_manager = ModelManager::default();
+    model_manager.register_model::<SaleOrder<_>>();
+    model_manager.register_model::<SaleOrderLine<_>>();
+    model_manager.post_register();
+    let mut database = CacheDatabase::connect();
+    database.sync_schema(&["sale_order", "sale_order_line"], &model_manager)?;
+
+    let mut sale_order = MapOfFields::default();
+    sale_order.insert("name", "0ddlyoko");
+    sale_order.insert("state", "draft");
+    let sale_order_ids = database.create("sale_order", &[&sale_order])?;
+    let mut line = MapOfFields::default();
+    line.insert("price", 42);
+    line.insert("amount", 10);
+    line.insert("order", sale_order_ids[0]);
+    let mut other_line = MapOfFields::default();
+    other_line.insert("price", 69);
+    other_line.insert("amount", 10);
+    database.create("sale_order_line", &[&line, &other_line])?;
+
+    // Lines of the order are deleted with it
+    assert_eq!(database.delete("sale_order", &sale_order_ids)?, 1);
+    let remaining = database.browse(
+        "sale_order_line",
+        &make_domain!([("amount", "=", 10)]),
+        &SearchOptions::default(),
+        &model_manager,
+    )?;
+    assert_eq!(remaining.len(), 1);
+
+    Ok(())
+}
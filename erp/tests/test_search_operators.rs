@@ -0,0 +1,448 @@
+use base::BasePlugin;
+use base::models::Contact;
+use chrono::{NaiveDate, TimeZone, Utc};
+use erp::database::{InvalidSearchValueError, NotHierarchicalFieldError, UnknownFieldError};
+use erp_search_code_gen::make_domain;
+use erp_types::field::{MultipleIds, SingleId};
+use erp_types::model::MapOfFields;
+use std::error::Error;
+use test_utilities::backend::{Backend, TestApp};
+use test_utilities::backend_tests;
+use test_utilities::models::{SaleOrder, SaleOrderLine};
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+backend_tests!(
+    test_in_and_not_in,
+    test_like_operators,
+    test_child_of_and_parent_of,
+    test_not,
+    test_any_and_not_any,
+    test_one2many_conditions,
+    test_date_operators
+);
+
+fn test_in_and_not_in(backend: Backend) -> Result<()> {
+    let mut app = TestApp::with_sale_orders(backend)?;
+    let mut env = app.new_env()?;
+
+    for price in [10, 20, 30] {
+        let mut map = MapOfFields::default();
+        map.insert("price", price);
+        env.create_new_record_from_map::<SaleOrderLine<_>>(map)?;
+    }
+
+    let lines: SaleOrderLine<MultipleIds> =
+        env.search(&make_domain!([("price", "in", vec![10, 30, 50])]))?;
+    assert_eq!(lines.get_ids_ref().len(), 2);
+    let lines: SaleOrderLine<MultipleIds> =
+        env.search(&make_domain!([("price", "not in", vec![10, 30])]))?;
+    assert_eq!(lines.get_ids_ref().len(), 1);
+    assert_eq!(lines.get_price(&mut env)?, vec![&20]);
+    // A single value is handled as a list of one value
+    let lines: SaleOrderLine<MultipleIds> = env.search(&make_domain!([("price", "in", 20)]))?;
+    assert_eq!(lines.get_ids_ref().len(), 1);
+    let lines: SaleOrderLine<MultipleIds> =
+        env.search(&make_domain!([("price", "in", Vec::<i32>::new())]))?;
+    assert!(lines.get_ids_ref().is_empty());
+    let lines: SaleOrderLine<MultipleIds> =
+        env.search(&make_domain!([("price", "not in", Vec::<i32>::new())]))?;
+    assert_eq!(lines.get_ids_ref().len(), 3);
+    // "=" compares values, so it never matches a list
+    let lines: SaleOrderLine<MultipleIds> =
+        env.search(&make_domain!([("price", "=", vec![10, 20])]))?;
+    assert!(lines.get_ids_ref().is_empty());
+
+    Ok(())
+}
+
+fn test_like_operators(backend: Backend) -> Result<()> {
+    let mut app = TestApp::with_sale_orders(backend)?;
+    let mut env = app.new_env()?;
+
+    for name in [
+        "0ddlyoko's order",
+        "Another ORDER",
+        "100% done",
+        "order_1",
+        "École",
+    ] {
+        let mut map = MapOfFields::default();
+        map.insert("name", name);
+        env.create_new_record_from_map::<SaleOrder<_>>(map)?;
+    }
+
+    let search = |env: &mut erp::environment::Environment, domain| -> Result<Vec<String>> {
+        let orders: SaleOrder<MultipleIds> = env.search(&domain)?;
+        let mut names: Vec<String> = orders.get_name(env)?.into_iter().cloned().collect();
+        names.sort();
+        Ok(names)
+    };
+    assert_eq!(
+        search(&mut env, make_domain!([("name", "like", "order")]))?,
+        vec!["0ddlyoko's order", "order_1"]
+    );
+    assert_eq!(
+        search(&mut env, make_domain!([("name", "ilike", "order")]))?,
+        vec!["0ddlyoko's order", "Another ORDER", "order_1"]
+    );
+    // Only ASCII letters are case-insensitive, as in SQLite
+    assert_eq!(
+        search(&mut env, make_domain!([("name", "ilike", "ÉCOLE")]))?,
+        vec!["École"]
+    );
+    assert!(search(&mut env, make_domain!([("name", "ilike", "école")]))?.is_empty());
+    // Wildcards are only used by "=like"
+    assert_eq!(
+        search(&mut env, make_domain!([("name", "like", "%")]))?,
+        vec!["100% done"]
+    );
+    assert!(search(&mut env, make_domain!([("name", "like", "r_1_")]))?.is_empty());
+    assert_eq!(
+        search(&mut env, make_domain!([("name", "=like", "%order")]))?,
+        vec!["0ddlyoko's order"]
+    );
+    assert_eq!(
+        search(&mut env, make_domain!([("name", "=like", "order__")]))?,
+        vec!["order_1"]
+    );
+    assert_eq!(
+        search(&mut env, make_domain!([("name", "=like", "order\\_%")]))?,
+        vec!["order_1"]
+    );
+    assert!(search(&mut env, make_domain!([("name", "=like", "ORDER%")]))?.is_empty());
+
+    Ok(())
+}
+
+fn test_child_of_and_parent_of(backend: Backend) -> Result<()> {
+    let mut app = TestApp::new(backend)?;
+    app.register_plugin(Box::new(BasePlugin {}))?;
+    app.load_plugin("base")?;
+    let mut env = app.new_env()?;
+
+    // root -> child -> grandchild, and another root without any child
+    let mut ids = Vec::new();
+    for (name, parent) in [
+        ("root", None),
+        ("child", Some(0)),
+        ("grandchild", Some(1)),
+        ("other", None),
+    ] {
+        let mut record = MapOfFields::default();
+        record.insert("name", name);
+        if let Some(parent) = parent {
+            record.insert::<&u64>("parent", &ids[parent]);
+        }
+        let contact: Contact<SingleId> = env.create_new_record_from_map(record)?;
+        ids.push(contact.get_id());
+    }
+
+    let search = |env: &mut erp::environment::Environment, domain| -> Result<Vec<String>> {
+        let contacts: Contact<MultipleIds> = env.search(&domain)?;
+        let mut names: Vec<String> = contacts.get_name(env)?.into_iter().cloned().collect();
+        names.sort();
+        Ok(names)
+    };
+    assert_eq!(
+        search(&mut env, make_domain!([("parent", "child_of", ids[0])]))?,
+        vec!["child", "grandchild", "root"]
+    );
+    assert_eq!(
+        search(
+            &mut env,
+            make_domain!([("parent", "child_of", vec![ids[1], ids[3]])])
+        )?,
+        vec!["child", "grandchild", "other"]
+    );
+    assert_eq!(
+        search(&mut env, make_domain!([("parent", "parent_of", ids[2])]))?,
+        vec!["child", "grandchild", "root"]
+    );
+    assert_eq!(
+        search(&mut env, make_domain!([("parent", "parent_of", ids[1])]))?,
+        vec!["child", "root"]
+    );
+    // Can be combined with other conditions
+    assert_eq!(
+        search(
+            &mut env,
+            make_domain!([("parent", "child_of", ids[0]), ("name", "!=", "root")])
+        )?,
+        vec!["child", "grandchild"]
+    );
+
+    // Same result when filtering records in memory
+    let contacts: Contact<MultipleIds> = env.get_record(MultipleIds::from(ids.clone()));
+    let filtered =
+        env.filtered_domain(&contacts, &make_domain!([("parent", "child_of", ids[1])]))?;
+    assert_eq!(filtered.get_ids_ref(), &vec![ids[1], ids[2]]);
+    let filtered =
+        env.filtered_domain(&contacts, &make_domain!([("parent", "parent_of", ids[2])]))?;
+    assert_eq!(filtered.get_ids_ref(), &vec![ids[0], ids[1], ids[2]]);
+
+    // Only M2O fields targeting their own model can be used
+    let result: Result<Contact<MultipleIds>> =
+        env.search(&make_domain!([("lang", "child_of", ids[0])]));
+    assert!(result.is_err_and(|error| error.is::<NotHierarchicalFieldError>()));
+
+    Ok(())
+}
+
+fn test_not(backend: Backend) -> Result<()> {
+    let mut app = TestApp::with_sale_orders(backend)?;
+    let mut env = app.new_env()?;
+
+    let mut map = MapOfFields::default();
+    map.insert("name", "0ddlyoko");
+    let sale_order: SaleOrder<SingleId> = env.create_new_record_from_map(map)?;
+    for (order, price) in [(Some(sale_order.get_id()), 10), (None, 20), (None, 30)] {
+        let mut map = MapOfFields::default();
+        if let Some(order) = order {
+            map.insert::<&u64>("order", &order);
+        }
+        map.insert("price", price);
+        env.create_new_record_from_map::<SaleOrderLine<_>>(map)?;
+    }
+
+    let lines: SaleOrderLine<MultipleIds> = env.search(&make_domain!([
+        "!",
+        "|",
+        ("price", "=", 10),
+        ("price", "=", 20)
+    ]))?;
+    assert_eq!(lines.get_price(&mut env)?, vec![&30]);
+    // Records without any order don't match the condition, so they match its negation
+    let lines: SaleOrderLine<MultipleIds> =
+        env.search(&make_domain!(["!", ("order.name", "=", "0ddlyoko")]))?;
+    assert_eq!(lines.get_ids_ref().len(), 2);
+    let count = env.search_count::<SaleOrderLine<_>>(&make_domain!([
+        "!",
+        ("order.name", "=", "0ddlyoko"),
+        ("price", ">", 20)
+    ]))?;
+    assert_eq!(count, 1);
+    // Double negation
+    let lines: SaleOrderLine<MultipleIds> =
+        env.search(&make_domain!(["!", "!", ("price", "=", 10)]))?;
+    assert_eq!(lines.get_price(&mut env)?, vec![&10]);
+
+    Ok(())
+}
+
+fn test_any_and_not_any(backend: Backend) -> Result<()> {
+    let mut app = TestApp::with_sale_orders(backend)?;
+    let mut env = app.new_env()?;
+
+    // "A" has lines 10 and 200, "B" has line 300, "C" doesn't have any line
+    let mut orders = Vec::new();
+    for name in ["A", "B", "C"] {
+        let mut map = MapOfFields::default();
+        map.insert("name", name);
+        let sale_order: SaleOrder<SingleId> = env.create_new_record_from_map(map)?;
+        orders.push(sale_order.get_id());
+    }
+    for (order, price) in [(orders[0], 10), (orders[0], 200), (orders[1], 300)] {
+        let mut map = MapOfFields::default();
+        map.insert::<&u64>("order", &order);
+        map.insert("price", price);
+        env.create_new_record_from_map::<SaleOrderLine<_>>(map)?;
+    }
+    let all_orders: SaleOrder<MultipleIds> = env.get_record(MultipleIds::from(orders.clone()));
+
+    let search = |env: &mut erp::environment::Environment, domain| -> Result<Vec<String>> {
+        let sale_orders: SaleOrder<MultipleIds> = env.search(&domain)?;
+        let mut names: Vec<String> = sale_orders.get_name(env)?.into_iter().cloned().collect();
+        names.sort();
+        // Filtering records in memory gives the same result
+        let filtered = env.filtered_domain(&all_orders, &domain)?;
+        let mut filtered_names: Vec<String> =
+            filtered.get_name(env)?.into_iter().cloned().collect();
+        filtered_names.sort();
+        assert_eq!(names, filtered_names, "{domain}");
+        Ok(names)
+    };
+    // At least one line
+    assert_eq!(
+        search(
+            &mut env,
+            make_domain!([("lines", "any", [("price", ">", 100)])])
+        )?,
+        vec!["A", "B"]
+    );
+    // All lines: no line that doesn't match
+    assert_eq!(
+        search(
+            &mut env,
+            make_domain!([
+                ("lines", "not any", ["!", ("price", ">", 100)]),
+                ("lines", "!=", None)
+            ])
+        )?,
+        vec!["B"]
+    );
+    // No line at all
+    assert_eq!(
+        search(&mut env, make_domain!([("lines", "not any", [])]))?,
+        vec!["C"]
+    );
+    // At least one line: an empty sub-domain matches all lines
+    assert_eq!(
+        search(&mut env, make_domain!([("lines", "any", [])]))?,
+        vec!["A", "B"]
+    );
+    // Conditions of the sub-domain are applied on the same line
+    assert_eq!(
+        search(
+            &mut env,
+            make_domain!([("lines", "any", [("price", ">", 100), ("price", "<", 250)])])
+        )?,
+        vec!["A"]
+    );
+    // Nested conditions, through a M2O
+    assert_eq!(
+        search(
+            &mut env,
+            make_domain!([("lines", "any", [("order", "any", [("name", "!=", "A")])])])
+        )?,
+        vec!["B"]
+    );
+    let lines: SaleOrderLine<MultipleIds> =
+        env.search(&make_domain!([("order", "not any", [("name", "=", "A")])]))?;
+    assert_eq!(lines.get_price(&mut env)?, vec![&300]);
+
+    // Sub-domains are validated on the target model
+    let result: Result<SaleOrder<MultipleIds>> =
+        env.search(&make_domain!([("lines", "any", [("nam", "=", "A")])]));
+    assert!(result.is_err_and(|error| error.is::<UnknownFieldError>()));
+    let result: Result<SaleOrder<MultipleIds>> =
+        env.search(&make_domain!([("name", "any", [("name", "=", "A")])]));
+    assert!(result.is_err_and(|error| error.is::<InvalidSearchValueError>()));
+
+    Ok(())
+}
+
+fn test_one2many_conditions(backend: Backend) -> Result<()> {
+    let mut app = TestApp::with_sale_orders(backend)?;
+    let mut env = app.new_env()?;
+
+    // "A" has two lines, "B" has one line, "C" doesn't have any line
+    let mut orders = Vec::new();
+    for name in ["A", "B", "C"] {
+        let mut map = MapOfFields::default();
+        map.insert("name", name);
+        let sale_order: SaleOrder<SingleId> = env.create_new_record_from_map(map)?;
+        orders.push(sale_order.get_id());
+    }
+    let mut lines = Vec::new();
+    for order in [orders[0], orders[0], orders[1]] {
+        let mut map = MapOfFields::default();
+        map.insert::<&u64>("order", &order);
+        let line: SaleOrderLine<SingleId> = env.create_new_record_from_map(map)?;
+        lines.push(line.get_id());
+    }
+
+    let search = |env: &mut erp::environment::Environment, domain| -> Result<Vec<String>> {
+        let sale_orders: SaleOrder<MultipleIds> = env.search(&domain)?;
+        let mut names: Vec<String> = sale_orders.get_name(env)?.into_iter().cloned().collect();
+        names.sort();
+        Ok(names)
+    };
+    // Empty O2M fields are the ones without any linked record
+    assert_eq!(
+        search(&mut env, make_domain!([("lines", "=", None)]))?,
+        vec!["C"]
+    );
+    assert_eq!(
+        search(&mut env, make_domain!([("lines", "!=", None)]))?,
+        vec!["A", "B"]
+    );
+    // Other conditions are applied on ids of linked records
+    assert_eq!(
+        search(&mut env, make_domain!([("lines", "=", lines[1])]))?,
+        vec!["A"]
+    );
+    assert_eq!(
+        search(
+            &mut env,
+            make_domain!([("lines", "in", vec![lines[0], lines[2]])])
+        )?,
+        vec!["A", "B"]
+    );
+    assert_eq!(
+        search(&mut env, make_domain!([("lines", "in", Vec::<u64>::new())]))?,
+        Vec::<String>::new()
+    );
+
+    Ok(())
+}
+
+fn test_date_operators(backend: Backend) -> Result<()> {
+    let mut app = TestApp::with_sale_orders(backend)?;
+    let mut env = app.new_env()?;
+
+    let mut ids = Vec::new();
+    for day in [1, 15, 28] {
+        let mut map = MapOfFields::default();
+        map.insert(
+            "validity_date",
+            NaiveDate::from_ymd_opt(2025, 2, day).unwrap(),
+        );
+        map.insert(
+            "date_order",
+            Utc.with_ymd_and_hms(2025, 1, day, 12, 0, 0).unwrap(),
+        );
+        let sale_order: SaleOrder<SingleId> = env.create_new_record_from_map(map)?;
+        ids.push(sale_order.get_id());
+    }
+    let sale_order: SaleOrder<SingleId> = env.create_new_record_from_map(MapOfFields::default())?;
+    ids.push(sale_order.get_id());
+    let all_orders: SaleOrder<MultipleIds> = env.get_record(MultipleIds::from(ids.clone()));
+
+    let search = |env: &mut erp::environment::Environment, domain| -> Result<Vec<u64>> {
+        let sale_orders: SaleOrder<MultipleIds> = env.search(&domain)?;
+        let mut ids = sale_orders.get_ids();
+        ids.sort();
+        // Filtering records in memory gives the same result
+        let mut filtered_ids = env.filtered_domain(&all_orders, &domain)?.get_ids();
+        filtered_ids.sort();
+        assert_eq!(ids, filtered_ids, "{domain}");
+        Ok(ids)
+    };
+    let date = NaiveDate::from_ymd_opt(2025, 2, 15).unwrap();
+    assert_eq!(
+        search(&mut env, make_domain!([("validity_date", ">", date)]))?,
+        vec![ids[2]]
+    );
+    assert_eq!(
+        search(&mut env, make_domain!([("validity_date", "<=", date)]))?,
+        vec![ids[0], ids[1]]
+    );
+    assert_eq!(
+        search(&mut env, make_domain!([("validity_date", "=", None)]))?,
+        vec![ids[3]]
+    );
+    let date_time = Utc.with_ymd_and_hms(2025, 1, 15, 12, 0, 0).unwrap();
+    assert_eq!(
+        search(&mut env, make_domain!([("date_order", "<", date_time)]))?,
+        vec![ids[0]]
+    );
+    assert_eq!(
+        search(
+            &mut env,
+            SaleOrder::fields().date_order().is_in([date_time])
+                | SaleOrder::fields().date_order().gt(Utc::now())
+        )?,
+        vec![ids[1]]
+    );
+
+    // Values should have the type of the field
+    let result: Result<SaleOrder<MultipleIds>> =
+        env.search(&make_domain!([("validity_date", ">", date_time)]));
+    assert!(result.is_err_and(|error| error.is::<InvalidSearchValueError>()));
+    let result: Result<SaleOrder<MultipleIds>> =
+        env.search(&make_domain!([("validity_date", ">", "2025-02-15")]));
+    assert!(result.is_err_and(|error| error.is::<InvalidSearchValueError>()));
+
+    Ok(())
+}
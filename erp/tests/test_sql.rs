@@ -0,0 +1,602 @@
+use erp::database::sql::{Dialect, QueryBuilder, delete_queries, insert_queries, update_queries};
+use erp::database::{Aggregate, FieldType};
+use erp::model::ModelManager;
+use erp_search::{OrderBy, SearchOptions};
+use erp_search_code_gen::make_domain;
+use erp_types::model::MapOfFields;
+use std::collections::HashMap;
+use std::error::Error;
+use test_utilities::models::{SaleOrder, SaleOrderLine};
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+fn model_manager() -> ModelManager {
+    let mut model_manager = ModelManager::default();
+    model_manager.register_model::<SaleOrder<_>>();
+    model_manager.register_model::<SaleOrderLine<_>>();
+    model_manager.post_register();
+    model_manager
+}
+
+#[test]
+fn test_select_simple_domain() -> Result<()> {
+    let model_manager = model_manager();
+
+    let query = QueryBuilder::new(&model_manager, Dialect::Postgres).select(
+        "sale_order_line",
+        &["id", "price"],
+        &make_domain!([("amount", "=", 42)]),
+        &SearchOptions::default(),
+    )?;
+    assert_eq!(
+        query.sql,
+        "SELECT \"sale_order_line\".\"id\", \"sale_order_line\".\"price\" FROM \"sale_order_line\" WHERE \"sale_order_line\".\"amount\" = $1 ORDER BY \"sale_order_line\".\"id\" ASC NULLS LAST"
+    );
+    assert_eq!(query.params, vec![FieldType::Integer(42)]);
+
+    let query = QueryBuilder::new(&model_manager, Dialect::Postgres).select(
+        "sale_order_line",
+        &["id"],
+        &make_domain!([
+            "|",
+            ("amount", "!=", 42),
+            "&",
+            ("price", ">", 10),
+            ("order", "=", None)
+        ]),
+        &SearchOptions::default(),
+    )?;
+    assert_eq!(
+        query.sql,
+        "SELECT \"sale_order_line\".\"id\" FROM \"sale_order_line\" WHERE ((\"sale_order_line\".\"amount\" IS NULL OR \"sale_order_line\".\"amount\" <> $1) OR (\"sale_order_line\".\"price\" > $2 AND \"sale_order_line\".\"order\" IS NULL)) ORDER BY \"sale_order_line\".\"id\" ASC NULLS LAST"
+    );
+    assert_eq!(
+        query.params,
+        vec![FieldType::Integer(42), FieldType::Integer(10)]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_select_array() -> Result<()> {
+    let model_manager = model_manager();
+
+    let query = QueryBuilder::new(&model_manager, Dialect::Postgres).select(
+        "sale_order",
+        &["id"],
+        &make_domain!([
+            ("id", "in", vec![1u32, 2u32]),
+            ("id", "!=", Vec::<u64>::new())
+        ]),
+        &SearchOptions::default(),
+    )?;
+    assert_eq!(
+        query.sql,
+        "SELECT \"sale_order\".\"id\" FROM \"sale_order\" WHERE (\"sale_order\".\"id\" IN ($1, $2) AND TRUE) ORDER BY \"sale_order\".\"name\" ASC NULLS LAST, \"sale_order\".\"id\" DESC NULLS FIRST"
+    );
+    assert_eq!(
+        query.params,
+        vec![FieldType::UInteger(1), FieldType::UInteger(2)]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_select_path() -> Result<()> {
+    let model_manager = model_manager();
+
+    // M2O
+    let query = QueryBuilder::new(&model_manager, Dialect::Postgres).select(
+        "sale_order_line",
+        &["id"],
+        &make_domain!([
+            ("order.name", "=", "0ddlyoko"),
+            ("order.total_price", ">", 0)
+        ]),
+        &SearchOptions::default(),
+    )?;
+    assert_eq!(
+        query.sql,
+        "SELECT \"sale_order_line\".\"id\" FROM \"sale_order_line\" LEFT JOIN \"sale_order\" AS \"sale_order_line__order\" ON \"sale_order_line__order\".\"id\" = \"sale_order_line\".\"order\" WHERE ((\"sale_order_line__order\".\"id\" IS NOT NULL AND \"sale_order_line__order\".\"name\" = $1) AND (\"sale_order_line__order\".\"id\" IS NOT NULL AND \"sale_order_line__order\".\"total_price\" > $2)) ORDER BY \"sale_order_line\".\"id\" ASC NULLS LAST"
+    );
+    assert_eq!(
+        query.params,
+        vec![
+            FieldType::String("0ddlyoko".to_string()),
+            FieldType::Integer(0)
+        ]
+    );
+
+    // O2M
+    let query = QueryBuilder::new(&model_manager, Dialect::Postgres).select(
+        "sale_order",
+        &["id"],
+        &make_domain!([("lines.order.name", "=", "0ddlyoko")]),
+        &SearchOptions::default(),
+    )?;
+    assert_eq!(
+        query.sql,
+        "SELECT \"sale_order\".\"id\" FROM \"sale_order\" WHERE \"sale_order\".\"id\" IN (SELECT \"sale_order__lines\".\"order\" FROM \"sale_order_line\" AS \"sale_order__lines\" LEFT JOIN \"sale_order\" AS \"sale_order__lines__order\" ON \"sale_order__lines__order\".\"id\" = \"sale_order__lines\".\"order\" WHERE (\"sale_order__lines__order\".\"id\" IS NOT NULL AND \"sale_order__lines__order\".\"name\" = $1)) ORDER BY \"sale_order\".\"name\" ASC NULLS LAST, \"sale_order\".\"id\" DESC NULLS FIRST"
+    );
+
+    // O2M without any line
+    let query = QueryBuilder::new(&model_manager, Dialect::Postgres).select(
+        "sale_order",
+        &["id"],
+        &make_domain!([("lines", "=", None)]),
+        &SearchOptions::default(),
+    )?;
+    assert_eq!(
+        query.sql,
+        "SELECT \"sale_order\".\"id\" FROM \"sale_order\" WHERE \"sale_order\".\"id\" NOT IN (SELECT \"sale_order__lines\".\"order\" FROM \"sale_order_line\" AS \"sale_order__lines\" WHERE \"sale_order__lines\".\"order\" IS NOT NULL) ORDER BY \"sale_order\".\"name\" ASC NULLS LAST, \"sale_order\".\"id\" DESC NULLS FIRST"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_select_invalid_path() {
+    let model_manager = model_manager();
+
+    let query = QueryBuilder::new(&model_manager, Dialect::Postgres).select(
+        "sale_order_line",
+        &["id"],
+        &make_domain!([("unknown_field", "=", 42)]),
+        &SearchOptions::default(),
+    );
+    assert!(query.is_err());
+
+    // "price" is not a relational field
+    let query = QueryBuilder::new(&model_manager, Dialect::Postgres).select(
+        "sale_order_line",
+        &["id"],
+        &make_domain!([("price.name", "=", 42)]),
+        &SearchOptions::default(),
+    );
+    assert!(query.is_err());
+}
+
+#[test]
+fn test_select_order() -> Result<()> {
+    let model_manager = model_manager();
+
+    // Order through a M2O, with limit and offset
+    let options = SearchOptions {
+        order: OrderBy::parse_list("order.name desc, price")?,
+        limit: Some(10),
+        offset: 20,
+        ..Default::default()
+    };
+    let query = QueryBuilder::new(&model_manager, Dialect::Postgres).select(
+        "sale_order_line",
+        &["id"],
+        &make_domain!([("amount", "=", 42)]),
+        &options,
+    )?;
+    assert_eq!(
+        query.sql,
+        "SELECT \"sale_order_line\".\"id\" FROM \"sale_order_line\" LEFT JOIN \"sale_order\" AS \"sale_order_line__order\" ON \"sale_order_line__order\".\"id\" = \"sale_order_line\".\"order\" WHERE \"sale_order_line\".\"amount\" = $1 ORDER BY \"sale_order_line__order\".\"name\" DESC NULLS FIRST, \"sale_order_line\".\"price\" ASC NULLS LAST, \"sale_order_line\".\"id\" ASC NULLS LAST LIMIT 10 OFFSET 20"
+    );
+
+    // SQLite needs a limit to use an offset
+    let options = SearchOptions {
+        order: vec![OrderBy::desc("id")],
+        limit: None,
+        offset: 5,
+        ..Default::default()
+    };
+    let query = QueryBuilder::new(&model_manager, Dialect::Sqlite).select(
+        "sale_order_line",
+        &["id"],
+        &make_domain!([("amount", "=", 42)]),
+        &options,
+    )?;
+    assert_eq!(
+        query.sql,
+        "SELECT \"sale_order_line\".\"id\" FROM \"sale_order_line\" WHERE \"sale_order_line\".\"amount\" = ?1 ORDER BY \"sale_order_line\".\"id\" DESC NULLS FIRST LIMIT -1 OFFSET 5"
+    );
+
+    // Unordered searches don't sort records, not even on the default order of the model
+    let query = QueryBuilder::new(&model_manager, Dialect::Postgres).select(
+        "sale_order",
+        &["id"],
+        &make_domain!([("name", "=", "0ddlyoko")]),
+        &SearchOptions::unordered(),
+    )?;
+    assert_eq!(
+        query.sql,
+        "SELECT \"sale_order\".\"id\" FROM \"sale_order\" WHERE \"sale_order\".\"name\" = $1"
+    );
+
+    // O2M and unknown fields can't be used to sort records
+    for order in ["lines", "lines.price", "unknown_field", "price.name"] {
+        let options = SearchOptions {
+            order: vec![OrderBy::asc(order)],
+            ..Default::default()
+        };
+        let query = QueryBuilder::new(&model_manager, Dialect::Postgres).select(
+            "sale_order",
+            &["id"],
+            &make_domain!([("name", "=", "0ddlyoko")]),
+            &options,
+        );
+        assert!(query.is_err(), "Order {order} should be invalid");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_select_like() -> Result<()> {
+    let model_manager = model_manager();
+    let domain = make_domain!([
+        ("name", "like", "50%_off"),
+        ("name", "ilike", "order"),
+        ("name", "=like", "a*b_%")
+    ]);
+
+    let query = QueryBuilder::new(&model_manager, Dialect::Postgres).select(
+        "sale_order",
+        &["id"],
+        &domain,
+        &SearchOptions::default(),
+    )?;
+    assert_eq!(
+        query.sql,
+        "SELECT \"sale_order\".\"id\" FROM \"sale_order\" WHERE ((\"sale_order\".\"name\" LIKE $1 ESCAPE '\\' AND \"sale_order\".\"name\" ILIKE $2 ESCAPE '\\') AND \"sale_order\".\"name\" LIKE $3 ESCAPE '\\') ORDER BY \"sale_order\".\"name\" ASC NULLS LAST, \"sale_order\".\"id\" DESC NULLS FIRST"
+    );
+    assert_eq!(
+        query.params,
+        vec![
+            FieldType::String("%50\\%\\_off%".to_string()),
+            FieldType::String("%order%".to_string()),
+            FieldType::String("a*b_%".to_string()),
+        ]
+    );
+
+    // SQLite's LIKE is case-insensitive, so GLOB is used for case-sensitive operators
+    let query = QueryBuilder::new(&model_manager, Dialect::Sqlite).select(
+        "sale_order",
+        &["id"],
+        &domain,
+        &SearchOptions::default(),
+    )?;
+    assert_eq!(
+        query.sql,
+        "SELECT \"sale_order\".\"id\" FROM \"sale_order\" WHERE ((\"sale_order\".\"name\" GLOB ?1 AND \"sale_order\".\"name\" LIKE ?2 ESCAPE '\\') AND \"sale_order\".\"name\" GLOB ?3) ORDER BY \"sale_order\".\"name\" ASC NULLS LAST, \"sale_order\".\"id\" DESC NULLS FIRST"
+    );
+    assert_eq!(
+        query.params,
+        vec![
+            FieldType::String("*50%_off*".to_string()),
+            FieldType::String("%order%".to_string()),
+            FieldType::String("a[*]b?*".to_string()),
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_select_json() -> Result<()> {
+    let model_manager = model_manager();
+
+    // Values of another JSON type are NULL, and numbers are compared as NUMERIC
+    let query = QueryBuilder::new(&model_manager, Dialect::Postgres).select(
+        "sale_order",
+        &["id"],
+        &make_domain!([
+            ("metadata.address.city", "=", "Paris"),
+            ("metadata.priority", ">", 1)
+        ]),
+        &SearchOptions::default(),
+    )?;
+    assert_eq!(
+        query.sql,
+        "SELECT \"sale_order\".\"id\" FROM \"sale_order\" WHERE ((CASE WHEN jsonb_typeof(\"sale_order\".\"metadata\" #> ARRAY['address', 'city']::TEXT[]) = 'string' THEN (\"sale_order\".\"metadata\" #>> ARRAY['address', 'city']::TEXT[]) END) = $1 AND (CASE WHEN jsonb_typeof(\"sale_order\".\"metadata\" #> ARRAY['priority']::TEXT[]) = 'number' THEN (\"sale_order\".\"metadata\" #>> ARRAY['priority']::TEXT[])::NUMERIC END) > $2) ORDER BY \"sale_order\".\"name\" ASC NULLS LAST, \"sale_order\".\"id\" DESC NULLS FIRST"
+    );
+    assert_eq!(
+        query.params,
+        vec![
+            FieldType::String("Paris".to_string()),
+            FieldType::Decimal(1.into())
+        ]
+    );
+
+    let query = QueryBuilder::new(&model_manager, Dialect::Sqlite).select(
+        "sale_order",
+        &["id"],
+        &make_domain!([
+            ("metadata.tags.0", "=", None),
+            ("metadata.priority", "<", 2.5)
+        ]),
+        &SearchOptions::default(),
+    )?;
+    assert_eq!(
+        query.sql,
+        "SELECT \"sale_order\".\"id\" FROM \"sale_order\" WHERE (json_extract(\"sale_order\".\"metadata\", '$.\"tags\"[0]') IS NULL AND (CASE WHEN json_type(\"sale_order\".\"metadata\", '$.\"priority\"') IN ('integer', 'real') THEN json_extract(\"sale_order\".\"metadata\", '$.\"priority\"') END) < ?1) ORDER BY \"sale_order\".\"name\" ASC NULLS LAST, \"sale_order\".\"id\" DESC NULLS FIRST"
+    );
+    // Decimals are sent to SQLite as texts, so numbers are compared as doubles
+    assert_eq!(query.params, vec![FieldType::Double(2.5)]);
+
+    Ok(())
+}
+
+#[test]
+fn test_select_not() -> Result<()> {
+    let model_manager = model_manager();
+
+    // Domains are normalized, so the negation is pushed down to the tuples
+    let query = QueryBuilder::new(&model_manager, Dialect::Postgres).select(
+        "sale_order_line",
+        &["id"],
+        &make_domain!([
+            "!",
+            "|",
+            ("price", "=", 10),
+            ("order.name", "=", "0ddlyoko")
+        ]),
+        &SearchOptions::default(),
+    )?;
+    assert_eq!(
+        query.sql,
+        "SELECT \"sale_order_line\".\"id\" FROM \"sale_order_line\" LEFT JOIN \"sale_order\" AS \"sale_order_line__order\" ON \"sale_order_line__order\".\"id\" = \"sale_order_line\".\"order\" WHERE (NOT COALESCE(\"sale_order_line\".\"price\" = $1, FALSE) AND NOT COALESCE((\"sale_order_line__order\".\"id\" IS NOT NULL AND \"sale_order_line__order\".\"name\" = $2), FALSE)) ORDER BY \"sale_order_line\".\"id\" ASC NULLS LAST"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_select_any() -> Result<()> {
+    let model_manager = model_manager();
+
+    let query = QueryBuilder::new(&model_manager, Dialect::Postgres).select(
+        "sale_order",
+        &["id"],
+        &make_domain!([
+            (
+                "lines",
+                "any",
+                [("price", ">", 10), ("order.name", "=", "0ddlyoko")]
+            ),
+            ("lines", "not any", [("amount", "=", 0)])
+        ]),
+        &SearchOptions::default(),
+    )?;
+    assert_eq!(
+        query.sql,
+        "SELECT \"sale_order\".\"id\" FROM \"sale_order\" WHERE (EXISTS (SELECT 1 FROM \"sale_order_line\" AS \"sale_order__lines__any\" LEFT JOIN \"sale_order\" AS \"sale_order__lines__any__order\" ON \"sale_order__lines__any__order\".\"id\" = \"sale_order__lines__any\".\"order\" WHERE \"sale_order__lines__any\".\"order\" = \"sale_order\".\"id\" AND (\"sale_order__lines__any\".\"price\" > $1 AND (\"sale_order__lines__any__order\".\"id\" IS NOT NULL AND \"sale_order__lines__any__order\".\"name\" = $2))) AND NOT EXISTS (SELECT 1 FROM \"sale_order_line\" AS \"sale_order__lines__any\" WHERE \"sale_order__lines__any\".\"order\" = \"sale_order\".\"id\" AND \"sale_order__lines__any\".\"amount\" = $3)) ORDER BY \"sale_order\".\"name\" ASC NULLS LAST, \"sale_order\".\"id\" DESC NULLS FIRST"
+    );
+    assert_eq!(
+        query.params,
+        vec![
+            FieldType::Integer(10),
+            FieldType::String("0ddlyoko".to_string()),
+            FieldType::Integer(0)
+        ]
+    );
+
+    let query = QueryBuilder::new(&model_manager, Dialect::Postgres).count(
+        "sale_order_line",
+        &make_domain!([("order", "any", [("name", "=", "0ddlyoko")])]),
+    )?;
+    assert_eq!(
+        query.sql,
+        "SELECT COUNT(*) FROM \"sale_order_line\" WHERE EXISTS (SELECT 1 FROM \"sale_order\" AS \"sale_order_line__order__any\" WHERE \"sale_order_line__order__any\".\"id\" = \"sale_order_line\".\"order\" AND \"sale_order_line__order__any\".\"name\" = $1)"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_count_and_exists() -> Result<()> {
+    let model_manager = model_manager();
+
+    let query = QueryBuilder::new(&model_manager, Dialect::Postgres).count(
+        "sale_order_line",
+        &make_domain!([("order.name", "=", "0ddlyoko")]),
+    )?;
+    assert_eq!(
+        query.sql,
+        "SELECT COUNT(*) FROM \"sale_order_line\" LEFT JOIN \"sale_order\" AS \"sale_order_line__order\" ON \"sale_order_line__order\".\"id\" = \"sale_order_line\".\"order\" WHERE (\"sale_order_line__order\".\"id\" IS NOT NULL AND \"sale_order_line__order\".\"name\" = $1)"
+    );
+    assert_eq!(
+        query.params,
+        vec![FieldType::String("0ddlyoko".to_string())]
+    );
+
+    let query = QueryBuilder::new(&model_manager, Dialect::Sqlite)
+        .exists("sale_order_line", &make_domain!([("amount", "=", 42)]))?;
+    assert_eq!(
+        query.sql,
+        "SELECT EXISTS (SELECT 1 FROM \"sale_order_line\" WHERE \"sale_order_line\".\"amount\" = ?1)"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_read_group() -> Result<()> {
+    let model_manager = model_manager();
+
+    let query = QueryBuilder::new(&model_manager, Dialect::Postgres).read_group(
+        "sale_order_line",
+        &make_domain!([("price", ">", 5)]),
+        &["order.name"],
+        &[
+            Aggregate::count(),
+            Aggregate::sum("price"),
+            Aggregate::avg("price"),
+        ],
+    )?;
+    assert_eq!(
+        query.sql,
+        "SELECT \"sale_order_line__order\".\"name\", CAST(COUNT(\"sale_order_line\".\"id\") AS INTEGER), CAST(SUM(\"sale_order_line\".\"price\") AS BIGINT), CAST(AVG(\"sale_order_line\".\"price\") AS REAL) FROM \"sale_order_line\" LEFT JOIN \"sale_order\" AS \"sale_order_line__order\" ON \"sale_order_line__order\".\"id\" = \"sale_order_line\".\"order\" WHERE \"sale_order_line\".\"price\" > $1 GROUP BY \"sale_order_line__order\".\"name\" ORDER BY \"sale_order_line__order\".\"name\" ASC NULLS LAST"
+    );
+    assert_eq!(query.params, vec![FieldType::Integer(5)]);
+
+    let query = QueryBuilder::new(&model_manager, Dialect::Sqlite).read_group(
+        "sale_order_line",
+        &make_domain!([("amount", "=", 42)]),
+        &[],
+        &[Aggregate::max("price")],
+    )?;
+    assert_eq!(
+        query.sql,
+        "SELECT MAX(\"sale_order_line\".\"price\") FROM \"sale_order_line\" WHERE \"sale_order_line\".\"amount\" = ?1"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_insert_queries() {
+    let mut first = MapOfFields::default();
+    first.insert("price", 42);
+    first.insert("amount", 10);
+    let mut second = MapOfFields::default();
+    second.insert("price", 69);
+    let mut third = MapOfFields::default();
+    third.insert("price", 1);
+    third.insert_none("amount");
+    let empty = MapOfFields::default();
+
+    let queries = insert_queries(
+        Dialect::Postgres,
+        "sale_order_line",
+        &[&first, &second, &third, &empty],
+        None,
+    );
+    assert_eq!(queries.len(), 3);
+
+    // Records without any field are inserted one by one
+    let (indexes, query) = &queries[0];
+    assert_eq!(indexes, &vec![3]);
+    assert_eq!(
+        query.sql,
+        "INSERT INTO \"sale_order_line\" DEFAULT VALUES RETURNING \"id\""
+    );
+
+    // Records with the same fields are inserted together
+    let (indexes, query) = &queries[1];
+    assert_eq!(indexes, &vec![0, 2]);
+    assert_eq!(
+        query.sql,
+        "INSERT INTO \"sale_order_line\" (\"amount\", \"price\") VALUES ($1, $2), (NULL, $3) RETURNING \"id\""
+    );
+    assert_eq!(
+        query.params,
+        vec![
+            FieldType::Integer(10),
+            FieldType::Integer(42),
+            FieldType::Integer(1)
+        ]
+    );
+
+    let (indexes, query) = &queries[2];
+    assert_eq!(indexes, &vec![1]);
+    assert_eq!(
+        query.sql,
+        "INSERT INTO \"sale_order_line\" (\"price\") VALUES ($1) RETURNING \"id\""
+    );
+
+    // SQLite uses numbered "?" placeholders
+    let queries = insert_queries(Dialect::Sqlite, "sale_order_line", &[&first], None);
+    assert_eq!(
+        queries[0].1.sql,
+        "INSERT INTO \"sale_order_line\" (\"amount\", \"price\") VALUES (?1, ?2) RETURNING \"id\""
+    );
+
+    // Given ids are inserted with their record, even without any other field
+    let queries = insert_queries(
+        Dialect::Postgres,
+        "sale_order_line",
+        &[&first, &empty, &third],
+        Some(&[7, 8, 9]),
+    );
+    assert_eq!(queries.len(), 2);
+    let (indexes, query) = &queries[0];
+    assert_eq!(indexes, &vec![1]);
+    assert_eq!(
+        query.sql,
+        "INSERT INTO \"sale_order_line\" (\"id\") VALUES ($1) RETURNING \"id\""
+    );
+    assert_eq!(query.params, vec![FieldType::UInteger(8)]);
+    let (indexes, query) = &queries[1];
+    assert_eq!(indexes, &vec![0, 2]);
+    assert_eq!(
+        query.sql,
+        "INSERT INTO \"sale_order_line\" (\"id\", \"amount\", \"price\") VALUES ($1, $2, $3), ($4, NULL, $5) RETURNING \"id\""
+    );
+    assert_eq!(
+        query.params,
+        vec![
+            FieldType::UInteger(7),
+            FieldType::Integer(10),
+            FieldType::Integer(42),
+            FieldType::UInteger(9),
+            FieldType::Integer(1)
+        ]
+    );
+}
+
+#[test]
+fn test_update_queries() {
+    let mut first = MapOfFields::default();
+    first.insert("price", 42);
+    first.insert_none("order");
+    let mut second = MapOfFields::default();
+    second.insert("price", 69);
+    second.insert_none("order");
+    let mut third = MapOfFields::default();
+    third.insert("name", "0ddlyoko");
+
+    let data = HashMap::from([(1, &first), (2, &second), (3, &third)]);
+    let queries = update_queries(Dialect::Postgres, "sale_order_line", &data);
+    // One request per distinct set of fields
+    assert_eq!(queries.len(), 2);
+
+    assert_eq!(
+        queries[0].sql,
+        "UPDATE \"sale_order_line\" SET \"name\" = \"values\".\"column2\" FROM (VALUES (CAST($1 AS BIGINT), CAST($2 AS TEXT))) AS \"values\" WHERE \"sale_order_line\".\"id\" = \"values\".\"column1\""
+    );
+    assert_eq!(
+        queries[0].params,
+        vec![
+            FieldType::UInteger(3),
+            FieldType::String("0ddlyoko".to_string())
+        ]
+    );
+
+    assert_eq!(
+        queries[1].sql,
+        "UPDATE \"sale_order_line\" SET \"order\" = NULL, \"price\" = \"values\".\"column2\" FROM (VALUES (CAST($1 AS BIGINT), CAST($2 AS INTEGER)), (CAST($3 AS BIGINT), CAST($4 AS INTEGER))) AS \"values\" WHERE \"sale_order_line\".\"id\" = \"values\".\"column1\""
+    );
+    assert_eq!(
+        queries[1].params,
+        vec![
+            FieldType::UInteger(1),
+            FieldType::Integer(42),
+            FieldType::UInteger(2),
+            FieldType::Integer(69)
+        ]
+    );
+}
+
+#[test]
+fn test_delete_queries() {
+    let queries = delete_queries(Dialect::Postgres, "sale_order_line", &[1, 2]);
+    assert_eq!(queries.len(), 1);
+    assert_eq!(
+        queries[0].sql,
+        "DELETE FROM \"sale_order_line\" WHERE \"id\" IN ($1, $2)"
+    );
+    assert_eq!(
+        queries[0].params,
+        vec![FieldType::UInteger(1), FieldType::UInteger(2)]
+    );
+
+    assert!(delete_queries(Dialect::Sqlite, "sale_order_line", &[]).is_empty());
+}
@@ -0,0 +1,933 @@
+use base::BasePlugin;
+use base::models::{Attachment, Contact, ContactTag};
+use chrono::{DateTime, NaiveDate, SubsecRound, TimeZone, Utc};
+use erp::app::Application;
+use erp::config::Config;
+use erp::database::{Aggregate, Database, DatabaseConfig, FieldType, IdOutOfRangeError};
+use erp::plugin::Plugin;
+use erp_search::{OrderBy, SearchOptions};
+use erp_search_code_gen::make_domain;
+use erp_types::field::{Command, Decimal, IdMode, MultipleIds, SingleId, content_hash};
+use erp_types::model::MapOfFields;
+use std::collections::HashMap;
+use std::error::Error;
+use std::path::PathBuf;
+use std::time::Duration;
+use test_utilities::TestLibPlugin;
+use test_utilities::models::{Delivery, SaleOrder, SaleOrderLine};
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+/// Database file and data directory removed once the test is over
+struct TempDatabase {
+    path: PathBuf,
+    data_dir: PathBuf,
+}
+
+impl TempDatabase {
+    fn new() -> Self {
+        let name = format!("erp_test_{}", uuid::Uuid::new_v4());
+        let path = std::env::temp_dir().join(format!("{name}.sqlite"));
+        let data_dir = std::env::temp_dir().join(name);
+        Self { path, data_dir }
+    }
+
+    fn config(&self) -> Config {
+        Config {
+            database: DatabaseConfig::sqlite(self.path.to_str().unwrap()),
+            plugin_path: String::new(),
+            data_dir: self.data_dir.to_str().unwrap().to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn app(&self) -> Result<Application> {
+        let mut app = Application::new(self.config());
+        let mut database = app.create_new_database()?;
+        if !database.is_installed()? {
+            database.initialize()?;
+        }
+        drop(database);
+        app.register_plugin(Box::new(TestLibPlugin))?;
+        app.load_plugin("test_lib_plugin")?;
+        Ok(app)
+    }
+}
+
+impl Drop for TempDatabase {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+        let _ = std::fs::remove_dir_all(&self.data_dir);
+    }
+}
+
+#[test]
+fn test_sqlite_create_and_search() -> Result<()> {
+    let temp_database = TempDatabase::new();
+    let mut app = temp_database.app()?;
+
+    let mut env = app.new_env()?;
+    let mut map = MapOfFields::new(HashMap::new());
+    map.insert("name", "0ddlyoko");
+    map.insert("state", "draft");
+    let sale_order = env.create_new_record_from_map::<SaleOrder<_>>(map)?;
+    let mut map = MapOfFields::new(HashMap::new());
+    map.insert("order", sale_order.id.get_id());
+    map.insert("price", 10);
+    env.create_new_record_from_map::<SaleOrderLine<_>>(map)?;
+    let mut map = MapOfFields::new(HashMap::new());
+    map.insert("price", 20);
+    env.create_new_record_from_map::<SaleOrderLine<_>>(map)?;
+    env.close()?;
+
+    // Open a new environment, so data is read from the database
+    let mut env = app.new_env()?;
+    let lines: SaleOrderLine<MultipleIds> = env.search(&make_domain!([("price", ">=", 10)]))?;
+    assert_eq!(lines.id.get_ids_ref().len(), 2);
+    let count = env.search_count::<SaleOrderLine<_>>(&make_domain!([("price", ">=", 10)]))?;
+    assert_eq!(count, 2);
+    let count = env.search_count::<SaleOrder<_>>(&make_domain!([("lines.price", "=", 20)]))?;
+    assert_eq!(count, 0);
+    assert!(env.search_exists::<SaleOrder<_>>(&make_domain!([("lines.price", "=", 10)]))?);
+    assert!(!env.search_exists::<SaleOrder<_>>(&make_domain!([("lines.price", "=", 20)]))?);
+    let groups = env.read_group::<SaleOrderLine<_>>(
+        &make_domain!([("price", ">=", 10)]),
+        &["order.name"],
+        &[
+            Aggregate::count(),
+            Aggregate::sum("price"),
+            Aggregate::avg("price"),
+        ],
+    )?;
+    assert_eq!(groups.len(), 2);
+    assert_eq!(
+        groups[0].keys,
+        vec![Some(FieldType::String("0ddlyoko".to_string()))]
+    );
+    assert_eq!(
+        groups[0].aggregates,
+        vec![
+            Some(FieldType::Integer(1)),
+            Some(FieldType::BigInteger(10)),
+            Some(FieldType::Float(10.0)),
+        ]
+    );
+    assert_eq!(groups[1].keys, vec![None]);
+    let groups = env.read_group::<SaleOrderLine<_>>(
+        &make_domain!([("price", ">", 100)]),
+        &[],
+        &[Aggregate::count(), Aggregate::max("price")],
+    )?;
+    assert_eq!(groups.len(), 1);
+    assert_eq!(
+        groups[0].aggregates,
+        vec![Some(FieldType::Integer(0)), None]
+    );
+    let lines: SaleOrderLine<MultipleIds> =
+        env.search(&make_domain!([("order.name", "=", "0ddlyoko")]))?;
+    assert_eq!(lines.id.get_ids_ref().len(), 1);
+    let price = **lines.get_price(&mut env)?.first().unwrap();
+    assert_eq!(price, 10);
+    let sale_orders: SaleOrder<MultipleIds> =
+        env.search(&make_domain!([("lines.price", "=", 10)]))?;
+    assert_eq!(sale_orders.id.get_ids_ref().len(), 1);
+
+    // Updates are saved too
+    lines.set_price(30, &mut env)?;
+    env.save_fields_to_db("sale_order_line", &["price"])?;
+    env.close()?;
+    let mut env = app.new_env()?;
+    let lines: SaleOrderLine<MultipleIds> = env.search(&make_domain!([("price", "=", 30)]))?;
+    assert_eq!(lines.id.get_ids_ref().len(), 1);
+
+    // And deletions
+    lines.unlink(&mut env)?;
+    let lines: SaleOrderLine<MultipleIds> = env.search(&make_domain!([("price", ">=", 10)]))?;
+    assert_eq!(lines.id.get_ids_ref().len(), 1);
+
+    // Sums of integers don't overflow when they exceed the range of an i32
+    for _ in 0..2 {
+        let mut map = MapOfFields::new(HashMap::new());
+        map.insert("price", i32::MAX);
+        map.insert("amount", 1);
+        env.create_new_record_from_map::<SaleOrderLine<_>>(map)?;
+    }
+    let groups = env.read_group::<SaleOrderLine<_>>(
+        &make_domain!([("price", ">=", 10)]),
+        &[],
+        &[Aggregate::sum("price")],
+    )?;
+    assert_eq!(
+        groups[0].aggregates,
+        vec![Some(FieldType::BigInteger(20 + 2 * i32::MAX as i64))]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_sqlite_rollback() -> Result<()> {
+    let temp_database = TempDatabase::new();
+    let mut app = temp_database.app()?;
+
+    let mut env = app.new_env()?;
+    let mut map = MapOfFields::new(HashMap::new());
+    map.insert("amount", 42);
+    env.create_new_record_from_map::<SaleOrderLine<_>>(map)?;
+    let result: SaleOrderLine<MultipleIds> = env.search(&make_domain!([("amount", "=", 42)]))?;
+    assert_eq!(result.id.get_ids_ref().len(), 1);
+
+    // Dropping the environment should roll back its state
+    drop(env);
+    let mut env = app.new_env()?;
+    let result: SaleOrderLine<MultipleIds> = env.search(&make_domain!([("amount", "=", 42)]))?;
+    assert!(result.id.is_empty());
+
+    // Loading the plugin again doesn't fail, as the schema is already synced
+    drop(env);
+    drop(app);
+    temp_database.app()?;
+
+    Ok(())
+}
+
+#[test]
+fn test_sqlite_ondelete() -> Result<()> {
+    let temp_database = TempDatabase::new();
+    let mut app = temp_database.app()?;
+
+    let mut env = app.new_env()?;
+    let mut map = MapOfFields::new(HashMap::new());
+    map.insert("name", "0ddlyoko");
+    map.insert("state", "draft");
+    let sale_order = env.create_new_record_from_map::<SaleOrder<_>>(map)?;
+    let mut map = MapOfFields::new(HashMap::new());
+    map.insert("order", sale_order.id.get_id());
+    map.insert("price", 10);
+    env.create_new_record_from_map::<SaleOrderLine<_>>(map)?;
+    env.close()?;
+
+    // The foreign key deletes lines along with their order, even without going through the
+    // environment
+    let mut env = app.new_env()?;
+    env.database
+        .delete("sale_order", &[*sale_order.id.get_id_ref()])?;
+    let lines: SaleOrderLine<MultipleIds> = env.search(&make_domain!([("price", "=", 10)]))?;
+    assert!(lines.id.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_sqlite_search_with_options() -> Result<()> {
+    let temp_database = TempDatabase::new();
+    let mut app = temp_database.app()?;
+
+    let mut env = app.new_env()?;
+    let mut ids = vec![];
+    for name in ["b", "a", "c", "a"] {
+        let mut map = MapOfFields::new(HashMap::new());
+        map.insert("name", name);
+        map.insert("state", "draft");
+        let sale_order = env.create_new_record_from_map::<SaleOrder<_>>(map)?;
+        ids.push(sale_order.id.get_id());
+    }
+    let mut map = MapOfFields::new(HashMap::new());
+    map.insert("order", ids[2]);
+    map.insert("price", 10);
+    env.create_new_record_from_map::<SaleOrderLine<_>>(map)?;
+    let mut map = MapOfFields::new(HashMap::new());
+    map.insert("price", 20);
+    env.create_new_record_from_map::<SaleOrderLine<_>>(map)?;
+
+    // Default order of the model is "name asc, id desc"
+    let all_orders = make_domain!([("state", "=", "draft")]);
+    let sale_orders: SaleOrder<MultipleIds> = env.search(&all_orders)?;
+    assert_eq!(
+        sale_orders.id.get_ids_ref(),
+        &vec![ids[3], ids[1], ids[0], ids[2]]
+    );
+
+    let options = SearchOptions {
+        offset: 3,
+        ..Default::default()
+    };
+    let sale_orders: SaleOrder<MultipleIds> = env.search_with_options(&all_orders, &options)?;
+    assert_eq!(sale_orders.id.get_ids_ref(), &vec![ids[2]]);
+
+    // Lines without any order are last in ascending order
+    let options = SearchOptions {
+        order: vec![OrderBy::asc("order.name")],
+        limit: Some(1),
+        offset: 1,
+        ..Default::default()
+    };
+    let lines: SaleOrderLine<MultipleIds> =
+        env.search_with_options(&make_domain!([("price", ">", 0)]), &options)?;
+    assert_eq!(lines.id.get_ids_ref().len(), 1);
+    assert_eq!(**lines.get_price(&mut env)?.first().unwrap(), 20);
+
+    Ok(())
+}
+
+#[test]
+fn test_sqlite_dates() -> Result<()> {
+    let temp_database = TempDatabase::new();
+    let mut app = temp_database.app()?;
+
+    let before = Utc::now().trunc_subsecs(6);
+    let mut env = app.new_env()?;
+    let mut ids = vec![];
+    for day in [30, 31] {
+        let mut map = MapOfFields::new(HashMap::new());
+        map.insert("state", "draft");
+        map.insert(
+            "validity_date",
+            NaiveDate::from_ymd_opt(2025, 1, day).unwrap(),
+        );
+        let sale_order = env.create_new_record_from_map::<SaleOrder<_>>(map)?;
+        ids.push(sale_order.get_id());
+    }
+    let mut map = MapOfFields::new(HashMap::new());
+    map.insert("state", "draft");
+    let date_order = Utc.with_ymd_and_hms(2025, 1, 31, 10, 0, 0).unwrap();
+    map.insert("date_order", date_order);
+    let sale_order = env.create_new_record_from_map::<SaleOrder<_>>(map)?;
+    ids.push(sale_order.get_id());
+    env.close()?;
+    let after = Utc::now();
+
+    // Open a new environment, so data is read from the database
+    let mut env = app.new_env()?;
+    let sale_orders: SaleOrder<MultipleIds> = env.get_record(MultipleIds::from(ids.clone()));
+    let validity_dates = sale_orders.get_validity_date(&mut env)?;
+    assert_eq!(
+        validity_dates,
+        vec![
+            Some(&NaiveDate::from_ymd_opt(2025, 1, 30).unwrap()),
+            Some(&NaiveDate::from_ymd_opt(2025, 1, 31).unwrap()),
+            None,
+        ]
+    );
+    // "date_order" is set to the creation date by default
+    let date_orders: Vec<DateTime<Utc>> = sale_orders
+        .get_date_order(&mut env)?
+        .into_iter()
+        .map(|date| *date.unwrap())
+        .collect();
+    assert!(
+        date_orders[..2]
+            .iter()
+            .all(|date| before <= *date && *date <= after)
+    );
+    assert_eq!(date_orders[2], date_order);
+
+    let search = |env: &mut erp::environment::Environment, domain| -> Result<Vec<u64>> {
+        let sale_orders: SaleOrder<MultipleIds> = env.search(&domain)?;
+        let mut ids = sale_orders.get_ids();
+        ids.sort();
+        Ok(ids)
+    };
+    let date = NaiveDate::from_ymd_opt(2025, 1, 31).unwrap();
+    assert_eq!(
+        search(&mut env, make_domain!([("validity_date", "<", date)]))?,
+        vec![ids[0]]
+    );
+    assert_eq!(
+        search(&mut env, make_domain!([("validity_date", ">=", date)]))?,
+        vec![ids[1]]
+    );
+    assert_eq!(
+        search(&mut env, make_domain!([("date_order", "<", before)]))?,
+        vec![ids[2]]
+    );
+    assert_eq!(
+        search(&mut env, make_domain!([("date_order", "=", date_order)]))?,
+        vec![ids[2]]
+    );
+    let groups = env.read_group::<SaleOrder<_>>(
+        &make_domain!([("id", "in", ids.clone())]),
+        &[],
+        &[
+            Aggregate::min("validity_date"),
+            Aggregate::max("date_order"),
+        ],
+    )?;
+    assert_eq!(
+        groups[0].aggregates,
+        vec![
+            Some(FieldType::Date(
+                NaiveDate::from_ymd_opt(2025, 1, 30).unwrap()
+            )),
+            Some(FieldType::DateTime(date_orders[1].max(date_orders[0]))),
+        ]
+    );
+
+    // Updates are saved too
+    sale_orders.set_validity_date(Some(date), &mut env)?;
+    env.save_fields_to_db("sale_order", &["validity_date"])?;
+    env.close()?;
+    let mut env = app.new_env()?;
+    assert_eq!(
+        search(&mut env, make_domain!([("validity_date", "=", date)]))?,
+        ids
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_sqlite_decimals() -> Result<()> {
+    let temp_database = TempDatabase::new();
+    let mut app = temp_database.app()?;
+
+    let mut env = app.new_env()?;
+    let sale_order = env.create_new_record_from_map::<SaleOrder<_>>(MapOfFields::default())?;
+    let mut ids = vec![];
+    for discount in ["0.1", "0.2", "12345678.125"] {
+        let mut map = MapOfFields::default();
+        map.insert::<&u64>("order", &sale_order.get_id());
+        map.insert("discount", Decimal::from_str_exact(discount)?);
+        let line = env.create_new_record_from_map::<SaleOrderLine<_>>(map)?;
+        ids.push(line.get_id());
+    }
+    env.close()?;
+
+    // Open a new environment, so data is read from the database
+    let mut env = app.new_env()?;
+    let lines: SaleOrderLine<MultipleIds> = env.get_record(MultipleIds::from(ids.clone()));
+    // Values are rounded to the 2 digits of the field
+    let discounts: Vec<Decimal> = lines
+        .get_discount(&mut env)?
+        .into_iter()
+        .map(|discount| *discount.unwrap())
+        .collect();
+    assert_eq!(
+        discounts,
+        vec![
+            Decimal::new(1, 1),
+            Decimal::new(2, 1),
+            Decimal::new(1234567813, 2)
+        ]
+    );
+
+    let sale_order_lines: SaleOrderLine<MultipleIds> =
+        env.search(&make_domain!([("discount", ">", Decimal::new(15, 2))]))?;
+    assert_eq!(sale_order_lines.get_ids(), vec![ids[1], ids[2]]);
+    let sale_order_lines: SaleOrderLine<MultipleIds> =
+        env.search(&make_domain!([("discount", "=", Decimal::new(10, 2))]))?;
+    assert_eq!(sale_order_lines.get_ids(), vec![ids[0]]);
+
+    // Sums don't have any rounding error
+    let groups = env.read_group::<SaleOrderLine<_>>(
+        &make_domain!([("id", "in", vec![ids[0], ids[1]])]),
+        &[],
+        &[Aggregate::sum("discount"), Aggregate::max("discount")],
+    )?;
+    assert_eq!(
+        groups[0].aggregates,
+        vec![
+            Some(FieldType::Decimal(Decimal::new(3, 1))),
+            Some(FieldType::Decimal(Decimal::new(2, 1))),
+        ]
+    );
+
+    // Updates are rounded and saved too
+    lines.set_discount(Some(Decimal::from_str_exact("1.005")?), &mut env)?;
+    env.save_fields_to_db("sale_order_line", &["discount"])?;
+    env.close()?;
+    let mut env = app.new_env()?;
+    let sale_order_lines: SaleOrderLine<MultipleIds> =
+        env.search(&make_domain!([("discount", "=", Decimal::new(101, 2))]))?;
+    assert_eq!(sale_order_lines.get_ids(), ids);
+
+    Ok(())
+}
+
+#[test]
+fn test_sqlite_big_numbers() -> Result<()> {
+    let temp_database = TempDatabase::new();
+    let mut app = temp_database.app()?;
+
+    // Ids are 64-bit, so tables can have more than 4 billion rows
+    let connection = rusqlite::Connection::open(&temp_database.path)?;
+    for table in ["sale_order", "sale_order_line"] {
+        connection.execute(
+            "INSERT INTO sqlite_sequence (name, seq) VALUES (?1, 5000000000)",
+            [table],
+        )?;
+    }
+    drop(connection);
+
+    let mut env = app.new_env()?;
+    let sale_order = env.create_new_record_from_map::<SaleOrder<_>>(MapOfFields::default())?;
+    assert_eq!(sale_order.get_id(), 5_000_000_001);
+    let mut map = MapOfFields::default();
+    map.insert::<&u64>("order", &sale_order.get_id());
+    map.insert("quantity", -3_000_000_000i64);
+    map.insert("weight", 0.1f64);
+    let line = env.create_new_record_from_map::<SaleOrderLine<_>>(map)?;
+    assert_eq!(line.get_id(), 5_000_000_001);
+    env.close()?;
+
+    // Open a new environment, so data is read from the database
+    let mut env = app.new_env()?;
+    let sale_order_lines: SaleOrderLine<MultipleIds> = env.search(&make_domain!([
+        ("order", "=", 5_000_000_001u64),
+        ("quantity", "<", -2_000_000_000i64)
+    ]))?;
+    assert_eq!(sale_order_lines.get_ids(), vec![5_000_000_001]);
+    assert_eq!(sale_order_lines.get_weight(&mut env)?, vec![Some(&0.1)]);
+    let sale_orders: SaleOrder<MultipleIds> =
+        env.search(&SaleOrder::fields().lines().quantity().lt(0))?;
+    assert_eq!(sale_orders.get_ids(), vec![5_000_000_001]);
+
+    let groups = env.read_group::<SaleOrderLine<_>>(
+        &make_domain!([("id", "=", 5_000_000_001u64)]),
+        &[],
+        &[Aggregate::sum("quantity"), Aggregate::sum("weight")],
+    )?;
+    assert_eq!(
+        groups[0].aggregates,
+        vec![
+            Some(FieldType::BigInteger(-3_000_000_000)),
+            Some(FieldType::Double(0.1)),
+        ]
+    );
+
+    // Ids that can't be stored are refused, instead of wrapping around to negative ids
+    let result = env.search::<SaleOrder<MultipleIds>>(&make_domain!([("id", "=", u64::MAX)]));
+    assert!(result.is_err_and(|error| error.is::<IdOutOfRangeError>()));
+
+    Ok(())
+}
+
+#[test]
+fn test_sqlite_ilike_non_ascii() -> Result<()> {
+    let temp_database = TempDatabase::new();
+    let mut app = temp_database.app()?;
+    app.register_plugin(Box::new(BasePlugin {}))?;
+    app.load_plugin("base")?;
+
+    let mut env = app.new_env()?;
+    let mut map = MapOfFields::new(HashMap::new());
+    map.insert("name", "École");
+    env.create_new_record_from_map::<Contact<_>>(map)?;
+    env.close()?;
+
+    // Only ASCII letters are case-insensitive, as in the cache
+    let mut env = app.new_env()?;
+    let count = |env: &mut erp::environment::Environment, name: &str| -> Result<u64> {
+        env.search_count::<Contact<_>>(&make_domain!([("name", "ilike", name)]))
+    };
+    assert_eq!(count(&mut env, "ÉCOLE")?, 1);
+    assert_eq!(count(&mut env, "école")?, 0);
+
+    Ok(())
+}
+
+#[test]
+fn test_sqlite_search_operators() -> Result<()> {
+    let temp_database = TempDatabase::new();
+    let mut app = temp_database.app()?;
+    app.register_plugin(Box::new(BasePlugin {}))?;
+    app.load_plugin("base")?;
+
+    let mut env = app.new_env()?;
+    let mut parent = None;
+    for name in ["0ddlyoko", "0ddlyoko's child", "0DDLYOKO_2"] {
+        let mut map = MapOfFields::new(HashMap::new());
+        map.insert("name", name);
+        if let Some(parent) = parent {
+            map.insert::<&u64>("parent", &parent);
+        }
+        let contact = env.create_new_record_from_map::<Contact<_>>(map)?;
+        parent = Some(contact.get_id());
+    }
+    env.close()?;
+
+    // Open a new environment, so data is read from the database
+    let mut env = app.new_env()?;
+    let count = |env: &mut erp::environment::Environment, domain| -> Result<u64> {
+        env.search_count::<Contact<_>>(&domain)
+    };
+    assert_eq!(
+        count(&mut env, make_domain!([("name", "like", "0ddlyoko")]))?,
+        2
+    );
+    assert_eq!(
+        count(&mut env, make_domain!([("name", "ilike", "0ddlyoko")]))?,
+        3
+    );
+    assert_eq!(count(&mut env, make_domain!([("name", "like", "_")]))?, 1);
+    assert_eq!(
+        count(&mut env, make_domain!([("name", "=like", "0dd%")]))?,
+        2
+    );
+    assert_eq!(
+        count(&mut env, make_domain!([("name", "=like", "0DDLYOKO\\_?")]))?,
+        0
+    );
+    assert_eq!(
+        count(&mut env, make_domain!([("name", "=like", "0DDLYOKO\\__")]))?,
+        1
+    );
+    assert_eq!(
+        count(
+            &mut env,
+            make_domain!([("name", "in", vec!["0ddlyoko", "0DDLYOKO_2"])])
+        )?,
+        2
+    );
+    assert_eq!(
+        count(
+            &mut env,
+            make_domain!([("name", "not in", vec!["0ddlyoko"])])
+        )?,
+        2
+    );
+
+    let root: Contact<MultipleIds> = env.search(&make_domain!([("name", "=", "0ddlyoko")]))?;
+    let root_id = root.get_ids_ref()[0];
+    let last: Contact<MultipleIds> = env.search(&make_domain!([("name", "=", "0DDLYOKO_2")]))?;
+    let last_id = last.get_ids_ref()[0];
+    assert_eq!(
+        count(&mut env, make_domain!([("parent", "child_of", root_id)]))?,
+        3
+    );
+    assert_eq!(
+        count(&mut env, make_domain!([("parent", "child_of", last_id)]))?,
+        1
+    );
+    assert_eq!(
+        count(&mut env, make_domain!([("parent", "parent_of", last_id)]))?,
+        3
+    );
+    assert_eq!(
+        count(&mut env, make_domain!([("parent", "parent_of", root_id)]))?,
+        1
+    );
+
+    // Contacts without any parent match the negation of a condition on their parent
+    assert_eq!(
+        count(
+            &mut env,
+            make_domain!(["!", ("parent.name", "=", "0ddlyoko")])
+        )?,
+        2
+    );
+    assert_eq!(
+        count(
+            &mut env,
+            make_domain!(["!", "|", ("name", "like", "child"), ("parent", "=", None)])
+        )?,
+        1
+    );
+
+    // Quantified conditions on M2O and O2M fields
+    assert_eq!(
+        count(
+            &mut env,
+            make_domain!([("childrens", "any", [("name", "ilike", "_2")])])
+        )?,
+        1
+    );
+    assert_eq!(
+        count(
+            &mut env,
+            make_domain!([("childrens", "not any", [("name", "ilike", "_2")])])
+        )?,
+        2
+    );
+    assert_eq!(
+        count(
+            &mut env,
+            make_domain!([(
+                "parent",
+                "any",
+                [("childrens", "any", [("name", "=", "0DDLYOKO_2")])]
+            )])
+        )?,
+        1
+    );
+    assert_eq!(
+        count(
+            &mut env,
+            make_domain!([("parent", "not any", [("name", "=", "0ddlyoko")])])
+        )?,
+        2
+    );
+    // An empty sub-domain matches all target records
+    assert_eq!(
+        count(&mut env, make_domain!([("childrens", "any", [])]))?,
+        count(&mut env, make_domain!([("childrens", "!=", None)]))?
+    );
+    assert_eq!(
+        count(&mut env, make_domain!([("parent", "not any", [])]))?,
+        count(&mut env, make_domain!([("parent", "=", None)]))?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_sqlite_many2many() -> Result<()> {
+    let temp_database = TempDatabase::new();
+    let mut app = temp_database.app()?;
+    app.register_plugin(Box::new(BasePlugin {}))?;
+    app.load_plugin("base")?;
+
+    let mut env = app.new_env()?;
+    let mut map = MapOfFields::new(HashMap::new());
+    map.insert("name", "0ddlyoko");
+    let contact = env.create_new_record_from_map::<Contact<_>>(map)?;
+    let mut tag_ids = Vec::new();
+    for name in ["VIP", "Customer"] {
+        let mut map = MapOfFields::new(HashMap::new());
+        map.insert("name", name);
+        tag_ids.push(
+            env.create_new_record_from_map::<ContactTag<_>>(map)?
+                .get_id(),
+        );
+    }
+    contact.set_tags(tag_ids.clone().into(), &mut env)?;
+    env.close()?;
+
+    // Open a new environment, so data is read from the database
+    let mut env = app.new_env()?;
+    let tags = contact.get_tags::<ContactTag<_>>(&mut env)?;
+    assert_eq!(tags.get_ids(), tag_ids);
+    let vip: ContactTag<MultipleIds> = env.search(&make_domain!([("name", "=", "VIP")]))?;
+    let contacts = vip.get_contacts::<Contact<_>>(&mut env)?;
+    assert_eq!(contacts.get_ids(), vec![contact.get_id()]);
+    assert_eq!(
+        env.search_count::<Contact<_>>(&make_domain!([("tags.name", "=", "Customer")]))?,
+        1
+    );
+    assert_eq!(
+        env.search_count::<ContactTag<_>>(&make_domain!([(
+            "contacts",
+            "any",
+            [("name", "=", "0ddlyoko")]
+        )]))?,
+        2
+    );
+
+    contact.update_tags(&[Command::Remove(tag_ids[0])], &mut env)?;
+    assert!(!env.search_exists::<Contact<_>>(&make_domain!([("tags.name", "=", "VIP")]))?);
+    env.close()?;
+
+    // Links are removed along with deleted records
+    let mut env = app.new_env()?;
+    contact.unlink(&mut env)?;
+    assert_eq!(
+        env.search_count::<ContactTag<_>>(&make_domain!([("contacts", "=", None)]))?,
+        2
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_sqlite_json() -> Result<()> {
+    let temp_database = TempDatabase::new();
+    let mut app = temp_database.app()?;
+
+    let mut env = app.new_env()?;
+    let mut ids = vec![];
+    for metadata in [
+        serde_json::json!({"source": "website", "priority": 3, "address": {"city": "Paris"}}),
+        serde_json::json!({"source": "phone", "priority": 1.5, "urgent": true, "say \"hi\"\\": 1}),
+    ] {
+        let mut map = MapOfFields::new(HashMap::new());
+        map.insert("state", "draft");
+        map.insert("metadata", metadata);
+        let sale_order = env.create_new_record_from_map::<SaleOrder<_>>(map)?;
+        ids.push(sale_order.get_id());
+    }
+    let delivery = Delivery {
+        carrier: "bpost".to_string(),
+        weight: 2.5,
+        express: false,
+    };
+    let sale_order: SaleOrder<SingleId> = env.get_record(ids[1].into());
+    sale_order.set_delivery(Some(delivery.clone()), &mut env)?;
+    env.close()?;
+
+    // Open a new environment, so data is read from the database
+    let mut env = app.new_env()?;
+    let sale_orders: SaleOrder<MultipleIds> = env.get_record(MultipleIds::from(ids.clone()));
+    assert_eq!(
+        sale_orders.get_metadata(&mut env)?[0].unwrap()["address"]["city"],
+        serde_json::json!("Paris")
+    );
+    assert_eq!(
+        sale_orders.get_delivery(&mut env)?,
+        vec![None, Some(delivery)]
+    );
+
+    let search = |env: &mut erp::environment::Environment, domain| -> Result<Vec<u64>> {
+        let sale_orders: SaleOrder<MultipleIds> = env.search(&domain)?;
+        let mut ids = sale_orders.get_ids();
+        ids.sort();
+        Ok(ids)
+    };
+    assert_eq!(
+        search(
+            &mut env,
+            make_domain!([("metadata.address.city", "=", "Paris")])
+        )?,
+        vec![ids[0]]
+    );
+    assert_eq!(
+        search(&mut env, make_domain!([("metadata.priority", ">", 1)]))?,
+        ids
+    );
+    assert_eq!(
+        search(&mut env, make_domain!([("metadata.priority", "<", 3)]))?,
+        vec![ids[1]]
+    );
+    assert_eq!(
+        search(&mut env, make_domain!([("metadata.urgent", "=", true)]))?,
+        vec![ids[1]]
+    );
+    assert_eq!(
+        search(&mut env, make_domain!([("metadata.urgent", "=", None)]))?,
+        vec![ids[0]]
+    );
+    // Quotes and backslashes of keys are escaped
+    assert_eq!(
+        search(&mut env, make_domain!([("metadata.say \"hi\"\\", "=", 1)]))?,
+        vec![ids[1]]
+    );
+    assert_eq!(
+        search(
+            &mut env,
+            make_domain!([("delivery.carrier", "ilike", "BPOST")])
+        )?,
+        vec![ids[1]]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_sqlite_binary() -> Result<()> {
+    let temp_database = TempDatabase::new();
+    let mut app = temp_database.app()?;
+    app.register_plugin(Box::new(BasePlugin {}))?;
+    app.load_plugin("base")?;
+
+    let mut env = app.new_env()?;
+    let mut map = MapOfFields::new(HashMap::new());
+    map.insert("name", "0ddlyoko");
+    let contact = env.create_new_record_from_map::<Contact<_>>(map)?;
+    let mut attachments = vec![];
+    for (res_id, content) in [(contact.get_id(), b"avatar"), (0, b"avatar")] {
+        let mut map = MapOfFields::new(HashMap::new());
+        map.insert("name", "avatar.png");
+        map.insert("res_model", "contact");
+        map.insert("res_id", res_id);
+        map.insert("content", content.to_vec());
+        attachments.push(env.create_new_record_from_map::<Attachment<_>>(map)?);
+    }
+    env.close()?;
+
+    // Contents are saved once in the filestore, and only their key is stored in the database
+    let key = content_hash(b"avatar");
+    let blob_path = temp_database
+        .data_dir
+        .join("filestore")
+        .join(&key[..2])
+        .join(&key);
+    assert_eq!(std::fs::read(&blob_path)?, b"avatar".to_vec());
+
+    // Open a new environment, so data is read from the database and the filestore
+    let mut env = app.new_env()?;
+    let model_manager = env.model_manager;
+    let rows = env.database.search(
+        "attachment",
+        &["content"],
+        &make_domain!([("name", "=", "avatar.png")]),
+        &SearchOptions::default(),
+        model_manager,
+    )?;
+    assert!(
+        rows.iter()
+            .all(|(_, row)| row.get("content") == Some(&Some(FieldType::String(key.clone()))))
+    );
+    assert_eq!(
+        attachments[1].get_content(&mut env)?,
+        Some(&b"avatar".to_vec())
+    );
+    assert_eq!(
+        env.search_count::<Attachment<_>>(&make_domain!([("content", "=", key.clone())]))?,
+        2
+    );
+
+    // Contents are deleted once no record references them anymore
+    contact.unlink(&mut env)?;
+    env.close()?;
+    assert_eq!(app.collect_filestore_garbage(Duration::ZERO)?, 0);
+    assert!(blob_path.exists());
+    let mut env = app.new_env()?;
+    assert!(attachments[1].get_content(&mut env)?.is_some());
+    attachments[1].set_content(None, &mut env)?;
+    env.close()?;
+    // Recently written contents are kept
+    assert_eq!(app.collect_filestore_garbage(Duration::from_secs(3600))?, 0);
+    assert!(blob_path.exists());
+    assert_eq!(app.collect_filestore_garbage(Duration::ZERO)?, 1);
+    assert!(!blob_path.exists());
+
+    Ok(())
+}
+
+#[test]
+fn test_sqlite_upgrade_plugin_table() -> Result<()> {
+    let temp_database = TempDatabase::new();
+    // Plugin table created before versions were saved, with plugins already installed
+    let connection = rusqlite::Connection::open(&temp_database.path)?;
+    connection.execute_batch(
+        "
+        CREATE TABLE plugin (
+            id              INTEGER PRIMARY KEY AUTOINCREMENT,
+            name            VARCHAR NOT NULL,
+            description     TEXT,
+            website         TEXT,
+            url             TEXT,
+            state           VARCHAR NOT NULL
+        );
+        INSERT INTO plugin (name, state) VALUES ('base', 'installed');
+        INSERT INTO plugin (name, state) VALUES ('test_lib_plugin', 'installed');
+        INSERT INTO plugin (name, state) VALUES ('old_plugin', 'installed');
+        ",
+    )?;
+    drop(connection);
+
+    let mut app = Application::new(temp_database.config());
+    assert!(app.create_new_database()?.is_installed()?);
+    app.register_plugin(Box::new(BasePlugin {}))?;
+    app.register_plugin(Box::new(TestLibPlugin))?;
+    // Loaded first, so that the plugin table is not synced by the "plugin" model of "base"
+    app.load_plugin("test_lib_plugin")?;
+    app.load_plugin("base")?;
+
+    let mut database = app.create_new_database()?;
+    assert_eq!(
+        database.get_installed_plugin_version("base")?,
+        Some(BasePlugin {}.version())
+    );
+    assert_eq!(
+        database.get_installed_plugin_version("test_lib_plugin")?,
+        Some(TestLibPlugin.version())
+    );
+    // Plugins installed before versions were saved are upgraded from version "0"
+    assert_eq!(
+        database.get_installed_plugin_version("old_plugin")?,
+        Some("0".to_string())
+    );
+    // Upgrading an up to date database does nothing
+    database.upgrade()?;
+    Ok(())
+}
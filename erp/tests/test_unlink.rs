@@ -0,0 +1,188 @@
+use base::BasePlugin;
+use base::models::{Company, Contact, Lang};
+use erp::database::{Database, RestrictViolationError};
+use erp_search_code_gen::make_domain;
+use erp_types::field::{IdMode, MultipleIds, Reference, SingleId};
+use erp_types::model::MapOfFields;
+use std::error::Error;
+use test_utilities::TestLibPlugin;
+use test_utilities::backend::{Backend, TestApp};
+use test_utilities::backend_tests;
+use test_utilities::models::{BaseSaleOrder, SaleOrder, SaleOrderLine};
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+backend_tests!(
+    test_unlink,
+    test_unlink_cascade,
+    test_unlink_set_null_and_restrict,
+    test_failed_delete_changes_nothing
+);
+
+fn test_unlink(backend: Backend) -> Result<()> {
+    let mut app = TestApp::new(backend)?;
+    app.register_plugin(Box::new(TestLibPlugin {}))?;
+    app.load_plugin("test_lib_plugin")?;
+
+    let mut env = app.new_env()?;
+
+    let mut sale_order_map = MapOfFields::default();
+    sale_order_map.insert("name", "0ddlyoko's SO");
+    let sale_order = env.create_new_record_from_map::<SaleOrder<_>>(sale_order_map)?;
+    let order: Reference<BaseSaleOrder, SingleId> = sale_order.id.clone().into();
+
+    let mut sale_order_line_map = MapOfFields::default();
+    sale_order_line_map.insert::<&i32>("price", &100);
+    sale_order_line_map.insert::<&i32>("amount", &2);
+    sale_order_line_map.insert("order", &order);
+    let first_line = env.create_new_record_from_map::<SaleOrderLine<_>>(sale_order_line_map)?;
+    let mut sale_order_line_map = MapOfFields::default();
+    sale_order_line_map.insert::<&i32>("price", &10);
+    sale_order_line_map.insert::<&i32>("amount", &1);
+    sale_order_line_map.insert("order", &order);
+    let second_line = env.create_new_record_from_map::<SaleOrderLine<_>>(sale_order_line_map)?;
+
+    // Load the O2M and the computed field in cache
+    let lines = sale_order.get_lines::<SaleOrderLine<_>>(&mut env)?;
+    assert_eq!(lines.get_ids_ref().len(), 2);
+    assert_eq!(*sale_order.get_total_price(&mut env)?, 100 * 2 + 10);
+
+    first_line.unlink(&mut env)?;
+
+    // Removed from the cache and from the database
+    assert!(
+        !env.cache
+            .is_record_present("sale_order_line", first_line.get_id_ref())
+    );
+    let result: SaleOrderLine<MultipleIds> = env.search(&make_domain!([("price", "=", 100)]))?;
+    assert!(result.id.is_empty());
+
+    // Loaded O2M and fields depending on the removed line are updated
+    let lines = sale_order.get_lines::<SaleOrderLine<_>>(&mut env)?;
+    assert_eq!(lines.get_ids(), vec![second_line.get_id()]);
+    assert_eq!(*sale_order.get_total_price(&mut env)?, 10);
+
+    // Unlink a whole recordset
+    lines.unlink(&mut env)?;
+    assert!(
+        sale_order
+            .get_lines::<SaleOrderLine<_>>(&mut env)?
+            .get_ids_ref()
+            .is_empty()
+    );
+    assert_eq!(*sale_order.get_total_price(&mut env)?, 0);
+    sale_order.unlink(&mut env)?;
+    let result: SaleOrder<MultipleIds> =
+        env.search(&make_domain!([("name", "=", "0ddlyoko's SO")]))?;
+    assert!(result.id.is_empty());
+
+    Ok(())
+}
+
+fn test_unlink_cascade(backend: Backend) -> Result<()> {
+    let mut app = TestApp::new(backend)?;
+    app.register_plugin(Box::new(TestLibPlugin {}))?;
+    app.load_plugin("test_lib_plugin")?;
+
+    let mut env = app.new_env()?;
+
+    let sale_order = env.create_new_record_from_map::<SaleOrder<_>>(MapOfFields::default())?;
+    let mut sale_order_line_map = MapOfFields::default();
+    sale_order_line_map.insert::<&i32>("price", &100);
+    sale_order_line_map.insert("order", sale_order.get_id());
+    let sale_order_line =
+        env.create_new_record_from_map::<SaleOrderLine<_>>(sale_order_line_map)?;
+
+    // Lines are deleted along with their order
+    sale_order.unlink(&mut env)?;
+    assert!(
+        !env.cache
+            .is_record_present("sale_order_line", sale_order_line.get_id_ref())
+    );
+    let result: SaleOrderLine<MultipleIds> = env.search(&make_domain!([("price", "=", 100)]))?;
+    assert!(result.id.is_empty());
+
+    Ok(())
+}
+
+fn test_unlink_set_null_and_restrict(backend: Backend) -> Result<()> {
+    let mut app = TestApp::new(backend)?;
+    app.register_plugin(Box::new(BasePlugin {}))?;
+    app.load_plugin("base")?;
+
+    let mut env = app.new_env()?;
+
+    let mut record = MapOfFields::default();
+    record.insert("name", "French");
+    record.insert("code", "fr_FR");
+    let lang = env.create_new_record_from_map::<Lang<_>>(record)?;
+    let mut record = MapOfFields::default();
+    record.insert("name", "0ddlyoko");
+    record.insert("lang", lang.get_id());
+    let contact = env.create_new_record_from_map::<Contact<_>>(record)?;
+    let mut record = MapOfFields::default();
+    record.insert("name", "0ddlyoko's child");
+    record.insert("parent", contact.get_id());
+    let child = env.create_new_record_from_map::<Contact<_>>(record)?;
+    let mut record = MapOfFields::default();
+    record.insert("name", "0ddlyoko's company");
+    record.insert("contact", contact.get_id());
+    let company = env.create_new_record_from_map::<Company<_>>(record)?;
+
+    // Lang is emptied on contacts
+    lang.unlink(&mut env)?;
+    assert!(contact.get_lang::<Lang<_>>(&mut env)?.is_none());
+
+    // Contact is still used by a company, so it can't be deleted, and nothing changes
+    let result = contact.unlink(&mut env);
+    let e = result.unwrap_err();
+    assert!(e.is::<RestrictViolationError>());
+    assert_eq!(contact.get_name(&mut env)?, "0ddlyoko");
+    assert_eq!(child.get_name(&mut env)?, "0ddlyoko's child");
+
+    // Once the company is deleted, the contact and its children can be deleted
+    company.unlink(&mut env)?;
+    contact.unlink(&mut env)?;
+    let result: Contact<MultipleIds> = env.search(&make_domain!([("name", "!=", "")]))?;
+    assert!(result.get_ids_ref().is_empty());
+
+    Ok(())
+}
+
+fn test_failed_delete_changes_nothing(backend: Backend) -> Result<()> {
+    let mut app = TestApp::new(backend)?;
+    app.register_plugin(Box::new(BasePlugin {}))?;
+    app.load_plugin("base")?;
+
+    let mut env = app.new_env()?;
+    let mut record = MapOfFields::default();
+    record.insert("name", "0ddlyoko");
+    let contact = env.create_new_record_from_map::<Contact<_>>(record)?;
+    let mut record = MapOfFields::default();
+    record.insert("name", "0ddlyoko's child");
+    record.insert("parent", contact.get_id());
+    let child = env.create_new_record_from_map::<Contact<_>>(record)?;
+    let mut record = MapOfFields::default();
+    record.insert("name", "0ddlyoko's company");
+    record.insert("contact", child.get_id());
+    env.create_new_record_from_map::<Company<_>>(record)?;
+    env.save_all_to_db()?;
+
+    // The child is deleted with its parent, but is still used by a company
+    let error = env
+        .database
+        .delete("contact", &[contact.get_id()])
+        .unwrap_err();
+    // SQL databases return the error of their engine
+    if backend == Backend::Cache {
+        assert!(error.is::<RestrictViolationError>());
+    }
+    let count = env.database.count(
+        "contact",
+        &make_domain!([("id", "in", vec![contact.get_id(), child.get_id()])]),
+        env.model_manager,
+    )?;
+    assert_eq!(count, 2);
+
+    Ok(())
+}
@@ -4,15 +4,24 @@ use std::{error, fmt};
 #[derive(Debug, Clone)]
 pub struct InvalidDomainError {
     pub search_key: Vec<SearchKey>,
+    /// Position (in characters) of the error, if the domain has been parsed from a text
+    pub position: Option<usize>,
 }
 
 impl fmt::Display for InvalidDomainError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(
-            f,
-            "Given domain \"{:?}\" is invalid, please check it",
-            self.search_key
-        )
+        match self.position {
+            Some(position) => write!(
+                f,
+                "Given domain \"{:?}\" is invalid at position {position}, please check it",
+                self.search_key
+            ),
+            None => write!(
+                f,
+                "Given domain \"{:?}\" is invalid, please check it",
+                self.search_key
+            ),
+        }
     }
 }
 
@@ -27,7 +36,7 @@ impl fmt::Display for UnknownSearchKeyError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
-            "Unknown search key \"{:?}\", it should be \"&\" or \"|\"",
+            "Unknown search key \"{:?}\", it should be \"&\", \"|\" or \"!\"",
             self.search_key
         )
     }
@@ -38,16 +47,55 @@ impl error::Error for UnknownSearchKeyError {}
 #[derive(Debug, Clone)]
 pub struct UnknownSearchOperatorError {
     pub search_operator: String,
+    /// Position (in characters) of the operator, if the domain has been parsed from a text
+    pub position: Option<usize>,
 }
 
 impl fmt::Display for UnknownSearchOperatorError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Unknown search operator \"{:?}\"", self.search_operator)?;
+        if let Some(position) = self.position {
+            write!(f, " at position {position}")?;
+        }
         write!(
             f,
-            "Unknown search operator \"{:?}\", it should be \"=\", \"!=\", \">\", \">=\", \"<\" or \"<=\"",
-            self.search_operator
+            ", it should be \"=\", \"!=\", \">\", \">=\", \"<\", \"<=\", \"in\", \"not in\", \"like\", \"ilike\", \"=like\", \"child_of\", \"parent_of\", \"any\" or \"not any\""
         )
     }
 }
 
 impl error::Error for UnknownSearchOperatorError {}
+
+#[derive(Debug, Clone)]
+pub struct InvalidOrderError {
+    pub order: String,
+}
+
+impl fmt::Display for InvalidOrderError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Invalid order \"{}\", it should look like \"name asc, id desc\"",
+            self.order
+        )
+    }
+}
+
+impl error::Error for InvalidOrderError {}
+
+#[derive(Debug, Clone)]
+pub struct NonFiniteNumberError {
+    pub path: String,
+}
+
+impl fmt::Display for NonFiniteNumberError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Value of \"{}\" is NaN or infinite, it can't be written in JSON",
+            self.path
+        )
+    }
+}
+
+impl error::Error for NonFiniteNumberError {}
@@ -0,0 +1,194 @@
+use crate::parser::{DomainBuilder, DomainElement, parse_string_value};
+use crate::serializer::datetime_string;
+use crate::{
+    ErrorType, LeftTuple, NonFiniteNumberError, RightTuple, SearchKey, SearchOperator, SearchTuple,
+    SearchType, UnknownSearchOperatorError,
+};
+use serde_json::{Number, Value, json};
+
+impl SearchType {
+    /// JSON representation of this domain, that can be parsed back with `from_json`.
+    ///
+    /// JSON doesn't support NaN nor infinity, so domains containing them can't be written
+    pub fn to_json(&self) -> Result<String, ErrorType> {
+        let mut elements = Vec::new();
+        if *self != SearchType::Nothing {
+            self.json_elements(&mut elements)?;
+        }
+        Ok(Value::Array(elements).to_string())
+    }
+
+    /// Parse a domain written with the JSON representation returned by `to_json`.
+    ///
+    /// A domain is a list of "&", "|", "!", tuples written as lists of 3 elements, and empty lists
+    /// for `Nothing`. Values can be strings, numbers, booleans, null, lists, `{"u64": 42}` for
+    /// unsigned integers, `{"i64": 42}` and `{"f64": 4.2}` for 64-bit numbers, `{"decimal": "4.20"}`
+    /// for decimals, `{"date": "2025-01-31"}` and `{"datetime": "2025-01-31T10:00:00Z"}` for
+    /// temporal values, or domains for "any" and "not any"
+    pub fn from_json(json: &str) -> Result<SearchType, ErrorType> {
+        let mut builder = DomainBuilder::default();
+        let value = serde_json::from_str(json).map_err(|_| builder.invalid(None))?;
+        parse_domain(&mut builder, &value)
+    }
+
+    /// Add elements of this domain to given list, following the polish notation
+    fn json_elements(&self, elements: &mut Vec<Value>) -> Result<(), ErrorType> {
+        match self {
+            SearchType::And(left, right) => {
+                elements.push("&".into());
+                left.json_elements(elements)?;
+                right.json_elements(elements)?;
+            }
+            SearchType::Or(left, right) => {
+                elements.push("|".into());
+                left.json_elements(elements)?;
+                right.json_elements(elements)?;
+            }
+            SearchType::Not(search_type) => {
+                elements.push("!".into());
+                search_type.json_elements(elements)?;
+            }
+            SearchType::Tuple(tuple) => {
+                let path = tuple.left.path.join(".");
+                let value = json_value(&tuple.right, &path)?;
+                elements.push(json!([path, tuple.operator.to_string(), value]));
+            }
+            // Nothing can't be written in the polish notation, so an empty domain is used
+            SearchType::Nothing => elements.push(json!([])),
+        }
+        Ok(())
+    }
+}
+
+/// JSON value of given right part of a tuple, on given path
+fn json_value(value: &RightTuple, path: &str) -> Result<Value, ErrorType> {
+    let non_finite = || {
+        ErrorType::NonFiniteNumber(NonFiniteNumberError {
+            path: path.to_string(),
+        })
+    };
+    Ok(match value {
+        RightTuple::String(value) => json!(value),
+        RightTuple::Integer(value) => json!(value),
+        RightTuple::UInteger(value) => json!({"u64": value}),
+        RightTuple::BigInteger(value) => json!({"i64": value}),
+        // Going through the shortest representation of the f32 avoids writing 0.1 as
+        // 0.10000000149011612
+        RightTuple::Float(value) => {
+            let value = value.to_string().parse().map_err(|_| non_finite())?;
+            Value::Number(Number::from_f64(value).ok_or_else(non_finite)?)
+        }
+        RightTuple::Double(value) => {
+            json!({"f64": Number::from_f64(*value).ok_or_else(non_finite)?})
+        }
+        RightTuple::Boolean(value) => json!(value),
+        RightTuple::Decimal(value) => json!({"decimal": value.to_string()}),
+        RightTuple::Date(value) => json!({"date": value.to_string()}),
+        RightTuple::DateTime(value) => json!({"datetime": datetime_string(value)}),
+        RightTuple::Array(values) => Value::Array(
+            values
+                .iter()
+                .map(|value| json_value(value, path))
+                .collect::<Result<_, _>>()?,
+        ),
+        RightTuple::Domain(domain) => {
+            let mut elements = Vec::new();
+            if **domain != SearchType::Nothing {
+                domain.json_elements(&mut elements)?;
+            }
+            Value::Array(elements)
+        }
+        RightTuple::None => Value::Null,
+    })
+}
+
+/// Parse a list of elements, and build the domain they represent
+fn parse_domain(builder: &mut DomainBuilder, value: &Value) -> Result<SearchType, ErrorType> {
+    let Value::Array(values) = value else {
+        return Err(builder.invalid(None));
+    };
+    let start = builder.len();
+    for value in values {
+        let element = match value {
+            Value::String(key) => match key.as_str() {
+                "&" => DomainElement::Key(SearchKey::And),
+                "|" => DomainElement::Key(SearchKey::Or),
+                "!" => DomainElement::Key(SearchKey::Not),
+                _ => return Err(builder.invalid(None)),
+            },
+            Value::Array(tuple) if tuple.is_empty() => DomainElement::Nothing,
+            Value::Array(tuple) => {
+                DomainElement::Key(SearchKey::Tuple(parse_tuple(builder, tuple)?))
+            }
+            _ => return Err(builder.invalid(None)),
+        };
+        builder.push(None, element);
+    }
+    builder.build_domain(start, None)
+}
+
+fn parse_tuple(builder: &mut DomainBuilder, tuple: &[Value]) -> Result<SearchTuple, ErrorType> {
+    let [Value::String(left), Value::String(operator), right] = tuple else {
+        return Err(builder.invalid(None));
+    };
+    let left: LeftTuple = left.as_str().into();
+    let operator = SearchOperator::try_from(operator.as_str()).map_err(|_| {
+        ErrorType::UnknownSearchOperator(UnknownSearchOperatorError {
+            search_operator: operator.clone(),
+            position: None,
+        })
+    })?;
+    let right = if matches!(operator, SearchOperator::Any | SearchOperator::NotAny) {
+        RightTuple::Domain(Box::new(parse_domain(builder, right)?))
+    } else {
+        parse_value(right).ok_or_else(|| builder.invalid(None))?
+    };
+    Ok(SearchTuple {
+        left,
+        operator,
+        right,
+    })
+}
+
+/// Value of a tuple, or None if it's not valid
+fn parse_value(value: &Value) -> Option<RightTuple> {
+    Some(match value {
+        Value::Null => RightTuple::None,
+        Value::Bool(value) => RightTuple::Boolean(*value),
+        Value::String(value) => RightTuple::String(value.clone()),
+        Value::Number(number) if number.is_f64() => {
+            RightTuple::Float(finite(number.as_f64()? as f32)?)
+        }
+        // Integers that don't fit in an i32 are unsigned
+        Value::Number(number) => {
+            match number.as_i64().and_then(|value| i32::try_from(value).ok()) {
+                Some(value) => RightTuple::Integer(value),
+                None => RightTuple::UInteger(number.as_u64()?),
+            }
+        }
+        Value::Array(values) => {
+            RightTuple::Array(values.iter().map(parse_value).collect::<Option<_>>()?)
+        }
+        // Typed value: {"u64": 42}, {"i64": 42}, {"f64": 4.2}, {"decimal": "4.20"},
+        // {"date": "2025-01-31"} or {"datetime": "..."}
+        Value::Object(object) if object.len() == 1 => {
+            let (value_type, value) = object.iter().next()?;
+            match (value_type.as_str(), value) {
+                ("u32", Value::Number(number)) => {
+                    RightTuple::from(u32::try_from(number.as_u64()?).ok()?)
+                }
+                ("u64", Value::Number(number)) => RightTuple::UInteger(number.as_u64()?),
+                ("i64", Value::Number(number)) => RightTuple::BigInteger(number.as_i64()?),
+                ("f64", Value::Number(number)) => RightTuple::Double(finite(number.as_f64()?)?),
+                (value_type, Value::String(value)) => parse_string_value(value_type, value)?,
+                _ => return None,
+            }
+        }
+        Value::Object(_) => return None,
+    })
+}
+
+/// Given number, if it's neither NaN nor infinite (as an f64 too large for an f32)
+fn finite<F: Into<f64> + Copy>(value: F) -> Option<F> {
+    value.into().is_finite().then_some(value)
+}
@@ -1,11 +1,17 @@
 mod errors;
+mod json;
+mod normalize;
+mod parser;
 mod search_key;
 mod search_operator;
+mod search_order;
 mod search_tuple;
 mod search_type;
+mod serializer;
 
 pub use errors::*;
 pub use search_key::*;
 pub use search_operator::*;
+pub use search_order::*;
 pub use search_tuple::*;
 pub use search_type::*;
@@ -0,0 +1,161 @@
+use crate::{RightTuple, SearchOperator, SearchTuple, SearchType};
+
+impl SearchType {
+    /// Return an equivalent domain in a canonical form, matching exactly the same records.
+    ///
+    /// - And / Or chains are flattened, and duplicated elements are removed
+    /// - `Nothing` is removed from Or chains, and an And chain containing it becomes `Nothing`
+    /// - Equalities on the same field in an Or chain are merged into a single "in"
+    /// - Negations are pushed down to the tuples
+    /// - Sub-domains of "any" and "not any" are normalized as well
+    ///
+    /// Negated tuples are not replaced by the opposite operator, as a path going through a M2O or
+    /// a O2M field doesn't match the same records.
+    ///
+    /// As `Nothing` doesn't match any record, `Not(Nothing)` is used to match all records
+    pub fn normalize(&self) -> SearchType {
+        match self {
+            SearchType::And(left, right) => make_and(vec![left.normalize(), right.normalize()]),
+            SearchType::Or(left, right) => make_or(vec![left.normalize(), right.normalize()]),
+            SearchType::Not(search_type) => negate(search_type),
+            SearchType::Tuple(tuple) => SearchType::Tuple(normalize_tuple(tuple)),
+            SearchType::Nothing => SearchType::Nothing,
+        }
+    }
+}
+
+fn normalize_tuple(tuple: &SearchTuple) -> SearchTuple {
+    match &tuple.right {
+        RightTuple::Domain(domain) => SearchTuple {
+            left: tuple.left.clone(),
+            operator: tuple.operator.clone(),
+            right: RightTuple::Domain(Box::new(domain.normalize())),
+        },
+        _ => tuple.clone(),
+    }
+}
+
+/// Normalized negation of given domain
+fn negate(search_type: &SearchType) -> SearchType {
+    match search_type {
+        SearchType::And(left, right) => make_or(vec![negate(left), negate(right)]),
+        SearchType::Or(left, right) => make_and(vec![negate(left), negate(right)]),
+        SearchType::Not(search_type) => search_type.normalize(),
+        SearchType::Tuple(tuple) => {
+            SearchType::Not(Box::new(SearchType::Tuple(normalize_tuple(tuple))))
+        }
+        SearchType::Nothing => SearchType::Not(Box::new(SearchType::Nothing)),
+    }
+}
+
+fn is_everything(search_type: &SearchType) -> bool {
+    matches!(search_type, SearchType::Not(search_type) if **search_type == SearchType::Nothing)
+}
+
+/// Add given normalized domain to given list, splitting it if it's an And (or an Or) chain
+fn split(search_type: SearchType, is_and: bool, result: &mut Vec<SearchType>) {
+    match search_type {
+        SearchType::And(left, right) if is_and => {
+            split(*left, is_and, result);
+            split(*right, is_and, result);
+        }
+        SearchType::Or(left, right) if !is_and => {
+            split(*left, is_and, result);
+            split(*right, is_and, result);
+        }
+        search_type => {
+            if !result.contains(&search_type) {
+                result.push(search_type);
+            }
+        }
+    }
+}
+
+/// Chain given elements, from left to right, as the parser does
+fn chain(
+    search_types: Vec<SearchType>,
+    make: fn(Box<SearchType>, Box<SearchType>) -> SearchType,
+) -> Option<SearchType> {
+    search_types
+        .into_iter()
+        .reduce(|left, right| make(Box::new(left), Box::new(right)))
+}
+
+fn make_and(search_types: Vec<SearchType>) -> SearchType {
+    let mut result = Vec::new();
+    for search_type in search_types {
+        split(search_type, true, &mut result);
+    }
+    if result.contains(&SearchType::Nothing) {
+        return SearchType::Nothing;
+    }
+    result.retain(|search_type| !is_everything(search_type));
+    chain(result, SearchType::And).unwrap_or(SearchType::Not(Box::new(SearchType::Nothing)))
+}
+
+fn make_or(search_types: Vec<SearchType>) -> SearchType {
+    let mut split_search_types = Vec::new();
+    for search_type in search_types {
+        split(search_type, false, &mut split_search_types);
+    }
+    if split_search_types.iter().any(is_everything) {
+        return SearchType::Not(Box::new(SearchType::Nothing));
+    }
+
+    let mut result: Vec<SearchType> = Vec::new();
+    for search_type in split_search_types {
+        if search_type == SearchType::Nothing {
+            continue;
+        }
+        if let (SearchType::Tuple(tuple), Some(values)) =
+            (&search_type, get_in_values(&search_type))
+            && let Some(SearchType::Tuple(existing)) = result.iter_mut().find(|existing| {
+                matches!(existing, SearchType::Tuple(existing) if existing.left == tuple.left)
+                    && get_in_values(existing).is_some()
+            })
+        {
+            let mut merged_values = existing.right.values().to_vec();
+            for value in values {
+                if !merged_values.contains(value) {
+                    merged_values.push(value.clone());
+                }
+            }
+            *existing = SearchTuple {
+                left: tuple.left.clone(),
+                operator: SearchOperator::In,
+                right: RightTuple::Array(merged_values),
+            };
+            continue;
+        }
+        if !result.contains(&search_type) {
+            result.push(search_type);
+        }
+    }
+    chain(result, SearchType::Or).unwrap_or(SearchType::Nothing)
+}
+
+/// Values matched by given tuple if it's an equality or an "in", so that it can be merged with
+/// other ones on the same field.
+///
+/// "= None" matches empty values, while "in" never matches them, so it can't be merged
+fn get_in_values(search_type: &SearchType) -> Option<&[RightTuple]> {
+    match search_type {
+        SearchType::Tuple(SearchTuple {
+            operator: SearchOperator::In,
+            right,
+            ..
+        }) => Some(right.values()),
+        SearchType::Tuple(SearchTuple {
+            operator: SearchOperator::Equal,
+            right,
+            ..
+        }) if !matches!(
+            right,
+            RightTuple::None | RightTuple::Array(_) | RightTuple::Domain(_)
+        ) =>
+        {
+            Some(right.values())
+        }
+        _ => None,
+    }
+}
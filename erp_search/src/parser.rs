@@ -0,0 +1,430 @@
+use crate::{
+    ErrorType, InvalidDomainError, LeftTuple, RightTuple, SearchKey, SearchOperator, SearchTuple,
+    SearchType, UnknownSearchOperatorError,
+};
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use std::str::FromStr;
+
+/// Parse a domain with the same syntax as `make_domain!`, as returned by `to_string`.
+///
+/// Decimals are written `decimal("4.20")`, and temporal values are written `date("2025-01-31")`
+/// and `datetime("2025-01-31T10:00:00Z")`
+impl FromStr for SearchType {
+    type Err = ErrorType;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        Parser::new(text).parse()
+    }
+}
+
+pub(crate) enum DomainElement {
+    Key(SearchKey),
+    Nothing,
+}
+
+/// Elements of the domains being parsed, with their position if they have been parsed from a text
+#[derive(Default)]
+pub(crate) struct DomainBuilder {
+    elements: Vec<(Option<usize>, DomainElement)>,
+}
+
+impl DomainBuilder {
+    pub(crate) fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    pub(crate) fn push(&mut self, position: Option<usize>, element: DomainElement) {
+        self.elements.push((position, element));
+    }
+
+    /// Build the domain represented by the elements added since given start.
+    ///
+    /// Elements that are not in an explicit "&" or "|" are joined with a "&". `end` is the
+    /// position of the end of this domain
+    pub(crate) fn build_domain(
+        &mut self,
+        start: usize,
+        end: Option<usize>,
+    ) -> Result<SearchType, ErrorType> {
+        if self.elements.len() == start {
+            return Ok(SearchType::Nothing);
+        }
+        let mut index = start;
+        let mut result = self.build(&mut index, end)?;
+        while index < self.elements.len() {
+            let search_type = self.build(&mut index, end)?;
+            result = SearchType::And(Box::new(result), Box::new(search_type));
+        }
+        // Elements of a sub-domain are not part of the parent domain
+        self.elements.truncate(start);
+        Ok(result)
+    }
+
+    /// Build the SearchType starting at given index, following the polish notation.
+    ///
+    /// `parent` is the position of the "&", "|" or "!" waiting for this element
+    fn build(&self, index: &mut usize, parent: Option<usize>) -> Result<SearchType, ErrorType> {
+        let Some((position, element)) = self.elements.get(*index) else {
+            return Err(self.invalid(parent));
+        };
+        *index += 1;
+        Ok(match element {
+            DomainElement::Key(SearchKey::And) => {
+                let left = self.build(index, *position)?;
+                let right = self.build(index, *position)?;
+                SearchType::And(Box::new(left), Box::new(right))
+            }
+            DomainElement::Key(SearchKey::Or) => {
+                let left = self.build(index, *position)?;
+                let right = self.build(index, *position)?;
+                SearchType::Or(Box::new(left), Box::new(right))
+            }
+            DomainElement::Key(SearchKey::Not) => {
+                SearchType::Not(Box::new(self.build(index, *position)?))
+            }
+            DomainElement::Key(SearchKey::Tuple(tuple)) => SearchType::Tuple(tuple.clone()),
+            DomainElement::Nothing => SearchType::Nothing,
+        })
+    }
+
+    pub(crate) fn invalid(&self, position: Option<usize>) -> ErrorType {
+        ErrorType::InvalidDomain(InvalidDomainError {
+            search_key: self
+                .elements
+                .iter()
+                .filter_map(|(_, element)| match element {
+                    DomainElement::Key(search_key) => Some(search_key.clone()),
+                    DomainElement::Nothing => None,
+                })
+                .collect(),
+            position,
+        })
+    }
+}
+
+/// Parser of domains written as a text
+struct Parser {
+    chars: Vec<char>,
+    position: usize,
+    builder: DomainBuilder,
+}
+
+impl Parser {
+    fn new(text: &str) -> Self {
+        Self {
+            chars: text.chars().collect(),
+            position: 0,
+            builder: DomainBuilder::default(),
+        }
+    }
+
+    fn parse(mut self) -> Result<SearchType, ErrorType> {
+        let domain = self.parse_domain()?;
+        self.skip_whitespaces();
+        if self.position < self.chars.len() {
+            return Err(self.invalid(self.position));
+        }
+        Ok(domain)
+    }
+
+    /// Parse a list of elements, and build the domain they represent
+    fn parse_domain(&mut self) -> Result<SearchType, ErrorType> {
+        let start = self.builder.len();
+        self.expect('[')?;
+        if !self.eat(']') {
+            loop {
+                self.parse_element()?;
+                if self.eat(',') {
+                    // Trailing comma
+                    if self.eat(']') {
+                        break;
+                    }
+                    continue;
+                }
+                self.expect(']')?;
+                break;
+            }
+        }
+        self.builder.build_domain(start, Some(self.position))
+    }
+
+    fn invalid(&self, position: usize) -> ErrorType {
+        self.builder.invalid(Some(position))
+    }
+
+    fn skip_whitespaces(&mut self) {
+        while self
+            .chars
+            .get(self.position)
+            .is_some_and(|char| char.is_whitespace())
+        {
+            self.position += 1;
+        }
+    }
+
+    /// Next character that is not a whitespace
+    fn peek(&mut self) -> Option<char> {
+        self.skip_whitespaces();
+        self.chars.get(self.position).copied()
+    }
+
+    /// Consume given character if it's the next one
+    fn eat(&mut self, char: char) -> bool {
+        if self.peek() == Some(char) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, char: char) -> Result<(), ErrorType> {
+        if self.eat(char) {
+            Ok(())
+        } else {
+            Err(self.invalid(self.position))
+        }
+    }
+
+    fn parse_element(&mut self) -> Result<(), ErrorType> {
+        self.skip_whitespaces();
+        let position = self.position;
+        let element = match self.peek() {
+            Some('"') => {
+                let key = self.parse_string()?;
+                match key.as_str() {
+                    "&" => DomainElement::Key(SearchKey::And),
+                    "|" => DomainElement::Key(SearchKey::Or),
+                    "!" => DomainElement::Key(SearchKey::Not),
+                    _ => return Err(self.invalid(position)),
+                }
+            }
+            Some('(') => DomainElement::Key(SearchKey::Tuple(self.parse_tuple()?)),
+            Some('[') => {
+                self.position += 1;
+                if self.eat(']') {
+                    DomainElement::Nothing
+                } else {
+                    return Err(self.invalid(self.position));
+                }
+            }
+            _ => return Err(self.invalid(self.position)),
+        };
+        self.builder.push(Some(position), element);
+        Ok(())
+    }
+
+    /// Parse a tuple, starting at its opening parenthesis
+    fn parse_tuple(&mut self) -> Result<SearchTuple, ErrorType> {
+        self.position += 1;
+        if self.peek() != Some('"') {
+            return Err(self.invalid(self.position));
+        }
+        let left: LeftTuple = self.parse_string()?.into();
+        self.expect(',')?;
+        self.skip_whitespaces();
+        let operator_position = self.position;
+        if self.peek() != Some('"') {
+            return Err(self.invalid(self.position));
+        }
+        let operator = self.parse_string()?;
+        let operator = SearchOperator::try_from(operator.as_str()).map_err(|_| {
+            ErrorType::UnknownSearchOperator(UnknownSearchOperatorError {
+                search_operator: operator,
+                position: Some(operator_position),
+            })
+        })?;
+        self.expect(',')?;
+        let right = if matches!(operator, SearchOperator::Any | SearchOperator::NotAny) {
+            self.skip_whitespaces();
+            RightTuple::Domain(Box::new(self.parse_domain()?))
+        } else {
+            self.parse_value()?
+        };
+        self.eat(',');
+        self.expect(')')?;
+        Ok(SearchTuple {
+            left,
+            operator,
+            right,
+        })
+    }
+
+    fn parse_value(&mut self) -> Result<RightTuple, ErrorType> {
+        self.skip_whitespaces();
+        let position = self.position;
+        match self.peek() {
+            Some('"') => Ok(RightTuple::String(self.parse_string()?)),
+            Some('[') => self.parse_array(),
+            Some(char) if char == '-' || char.is_ascii_digit() => self.parse_number(),
+            Some(char) if char.is_ascii_alphabetic() => {
+                let identifier = self.parse_identifier();
+                match identifier.as_str() {
+                    "true" => Ok(RightTuple::Boolean(true)),
+                    "false" => Ok(RightTuple::Boolean(false)),
+                    "None" => Ok(RightTuple::None),
+                    "vec" if self.eat('!') && self.peek() == Some('[') => self.parse_array(),
+                    "f32::NAN" => Ok(RightTuple::Float(f32::NAN)),
+                    "f32::INFINITY" => Ok(RightTuple::Float(f32::INFINITY)),
+                    "f32::NEG_INFINITY" => Ok(RightTuple::Float(f32::NEG_INFINITY)),
+                    "f64::NAN" => Ok(RightTuple::Double(f64::NAN)),
+                    "f64::INFINITY" => Ok(RightTuple::Double(f64::INFINITY)),
+                    "f64::NEG_INFINITY" => Ok(RightTuple::Double(f64::NEG_INFINITY)),
+                    "decimal" | "date" | "datetime" if self.eat('(') => {
+                        self.skip_whitespaces();
+                        let value = parse_string_value(&identifier, &self.parse_string()?)
+                            .ok_or_else(|| self.invalid(position))?;
+                        self.expect(')')?;
+                        Ok(value)
+                    }
+                    _ => Err(self.invalid(position)),
+                }
+            }
+            _ => Err(self.invalid(self.position)),
+        }
+    }
+
+    fn parse_array(&mut self) -> Result<RightTuple, ErrorType> {
+        self.expect('[')?;
+        let mut values = Vec::new();
+        if self.eat(']') {
+            return Ok(RightTuple::Array(values));
+        }
+        loop {
+            values.push(self.parse_value()?);
+            if self.eat(',') {
+                // Trailing comma
+                if self.eat(']') {
+                    break;
+                }
+                continue;
+            }
+            self.expect(']')?;
+            break;
+        }
+        Ok(RightTuple::Array(values))
+    }
+
+    fn parse_identifier(&mut self) -> String {
+        let start = self.position;
+        while self
+            .chars
+            .get(self.position)
+            .is_some_and(|char| char.is_ascii_alphanumeric() || *char == '_' || *char == ':')
+        {
+            self.position += 1;
+        }
+        self.chars[start..self.position].iter().collect()
+    }
+
+    /// Parse a number.
+    ///
+    /// Numbers with a decimal part or an exponent are floats, other ones are integers. They can be
+    /// followed by a "u32", "u64", "i32", "i64", "f32" or "f64" suffix, as in Rust
+    fn parse_number(&mut self) -> Result<RightTuple, ErrorType> {
+        self.skip_whitespaces();
+        let start = self.position;
+        let (number, is_float) = self.read_number();
+        let suffix = self.parse_identifier();
+        number_value(&number, &suffix, is_float).ok_or_else(|| self.invalid(start))
+    }
+
+    /// Read the characters of a number, and whether it has a decimal part or an exponent
+    fn read_number(&mut self) -> (String, bool) {
+        let start = self.position;
+        let is_number_char = |char: char| char.is_ascii_digit() || matches!(char, '-' | '+' | '.');
+        let mut is_float = false;
+        while let Some(char) = self.chars.get(self.position).copied() {
+            if char == '.' || char == 'e' || char == 'E' {
+                is_float = true;
+            } else if !is_number_char(char) {
+                break;
+            }
+            self.position += 1;
+        }
+        (self.chars[start..self.position].iter().collect(), is_float)
+    }
+
+    /// Parse a string, with Rust escape sequences
+    fn parse_string(&mut self) -> Result<String, ErrorType> {
+        let start = self.position;
+        self.expect('"')?;
+        let mut result = String::new();
+        loop {
+            let Some(char) = self.chars.get(self.position).copied() else {
+                return Err(self.invalid(start));
+            };
+            self.position += 1;
+            match char {
+                '"' => return Ok(result),
+                '\\' => {
+                    let escape_position = self.position - 1;
+                    let escaped = self.chars.get(self.position).copied();
+                    self.position += 1;
+                    result.push(match escaped {
+                        Some('"') => '"',
+                        Some('\\') => '\\',
+                        Some('\'') => '\'',
+                        Some('n') => '\n',
+                        Some('r') => '\r',
+                        Some('t') => '\t',
+                        Some('0') => '\0',
+                        Some('u') => self
+                            .parse_unicode_escape()
+                            .ok_or_else(|| self.invalid(escape_position))?,
+                        _ => return Err(self.invalid(escape_position)),
+                    });
+                }
+                char => result.push(char),
+            }
+        }
+    }
+
+    /// Parse the character of a "\u{1F600}" escape sequence
+    fn parse_unicode_escape(&mut self) -> Option<char> {
+        if self.chars.get(self.position) != Some(&'{') {
+            return None;
+        }
+        let end = self.chars[self.position..]
+            .iter()
+            .position(|char| *char == '}')?;
+        let hex: String = self.chars[self.position + 1..self.position + end]
+            .iter()
+            .collect();
+        self.position += end + 1;
+        char::from_u32(u32::from_str_radix(&hex, 16).ok()?)
+    }
+}
+
+/// Value of given number, typed by given Rust suffix
+fn number_value(number: &str, suffix: &str, is_float: bool) -> Option<RightTuple> {
+    match suffix {
+        "u32" => number.parse::<u32>().ok().map(RightTuple::from),
+        "u64" => number.parse().ok().map(RightTuple::UInteger),
+        "i32" => number.parse().ok().map(RightTuple::Integer),
+        "i64" => number.parse().ok().map(RightTuple::BigInteger),
+        "f32" => number.parse().ok().map(RightTuple::Float),
+        "f64" => number.parse().ok().map(RightTuple::Double),
+        "" if is_float => number.parse().ok().map(RightTuple::Float),
+        "" => number
+            .parse()
+            .ok()
+            .map(RightTuple::Integer)
+            .or_else(|| number.parse().ok().map(RightTuple::UInteger)),
+        _ => None,
+    }
+}
+
+/// Parse the value of a "decimal" (as "4.20"), a "date" (as "2025-01-31") or a "datetime" (as
+/// RFC 3339, converted to UTC)
+pub(crate) fn parse_string_value(value_type: &str, value: &str) -> Option<RightTuple> {
+    match value_type {
+        "decimal" => Decimal::from_str_exact(value).ok().map(RightTuple::Decimal),
+        "date" => NaiveDate::from_str(value).ok().map(RightTuple::Date),
+        "datetime" => DateTime::parse_from_rfc3339(value)
+            .ok()
+            .map(|value| RightTuple::DateTime(value.with_timezone(&Utc))),
+        _ => None,
+    }
+}
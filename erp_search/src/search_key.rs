@@ -4,6 +4,7 @@ use crate::{SearchTuple, UnknownSearchKeyError, UnknownSearchOperatorError};
 pub enum SearchKey {
     And,
     Or,
+    Not,
     Tuple(SearchTuple),
 }
 
@@ -16,6 +17,7 @@ impl TryFrom<&str> for SearchKey {
             // "and" => SearchKey::And,
             "|" => Ok(SearchKey::Or),
             // "or" => SearchKey::Or,
+            "!" => Ok(SearchKey::Not),
             _ => Err(UnknownSearchKeyError {
                 search_key: value.to_string(),
             }),
@@ -1,4 +1,5 @@
 use crate::UnknownSearchOperatorError;
+use std::fmt::{Display, Formatter};
 
 #[derive(Clone, PartialEq, Debug)]
 pub enum SearchOperator {
@@ -8,6 +9,23 @@ pub enum SearchOperator {
     GreaterEqual,
     Lower,
     LowerEqual,
+    In,
+    NotIn,
+    /// Case-sensitive search of given value inside the field
+    Like,
+    /// Case-insensitive search of given value inside the field
+    ILike,
+    /// Case-sensitive match of given pattern, where "%" matches any sequence of characters and
+    /// "_" matches any single character
+    EqualLike,
+    /// Given records, and all records having them as ancestor through the given M2O field
+    ChildOf,
+    /// Given records, and all their ancestors through the given M2O field
+    ParentOf,
+    /// At least one record targeted by the given M2O or O2M field matches given domain
+    Any,
+    /// No record targeted by the given M2O or O2M field matches given domain
+    NotAny,
 }
 
 impl TryFrom<&str> for SearchOperator {
@@ -21,15 +39,51 @@ impl TryFrom<&str> for SearchOperator {
             ">=" => SearchOperator::GreaterEqual,
             "<" => SearchOperator::Lower,
             "<=" => SearchOperator::LowerEqual,
+            "in" => SearchOperator::In,
+            "not in" => SearchOperator::NotIn,
+            "like" => SearchOperator::Like,
+            "ilike" => SearchOperator::ILike,
+            "=like" => SearchOperator::EqualLike,
+            "child_of" => SearchOperator::ChildOf,
+            "parent_of" => SearchOperator::ParentOf,
+            "any" => SearchOperator::Any,
+            "not any" => SearchOperator::NotAny,
             _ => {
                 return Err(UnknownSearchOperatorError {
                     search_operator: str.to_string(),
+                    position: None,
                 });
             }
         })
     }
 }
 
+impl Display for SearchOperator {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                SearchOperator::Equal => "=",
+                SearchOperator::NotEqual => "!=",
+                SearchOperator::Greater => ">",
+                SearchOperator::GreaterEqual => ">=",
+                SearchOperator::Lower => "<",
+                SearchOperator::LowerEqual => "<=",
+                SearchOperator::In => "in",
+                SearchOperator::NotIn => "not in",
+                SearchOperator::Like => "like",
+                SearchOperator::ILike => "ilike",
+                SearchOperator::EqualLike => "=like",
+                SearchOperator::ChildOf => "child_of",
+                SearchOperator::ParentOf => "parent_of",
+                SearchOperator::Any => "any",
+                SearchOperator::NotAny => "not any",
+            }
+        )
+    }
+}
+
 impl TryFrom<String> for SearchOperator {
     type Error = UnknownSearchOperatorError;
 
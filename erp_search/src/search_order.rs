@@ -0,0 +1,116 @@
+use crate::InvalidOrderError;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum OrderDirection {
+    #[default]
+    Asc,
+    Desc,
+}
+
+impl TryFrom<&str> for OrderDirection {
+    type Error = InvalidOrderError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value.to_lowercase().as_str() {
+            "asc" => Ok(OrderDirection::Asc),
+            "desc" => Ok(OrderDirection::Desc),
+            _ => Err(InvalidOrderError {
+                order: value.to_string(),
+            }),
+        }
+    }
+}
+
+/// Single element of an order, sorting records on given path.
+///
+/// Path can go through M2O fields ("order.name")
+#[derive(Clone, PartialEq, Debug)]
+pub struct OrderBy {
+    pub path: Vec<String>,
+    pub direction: OrderDirection,
+}
+
+impl OrderBy {
+    pub fn asc(path: &str) -> Self {
+        Self {
+            path: split_path(path),
+            direction: OrderDirection::Asc,
+        }
+    }
+
+    pub fn desc(path: &str) -> Self {
+        Self {
+            path: split_path(path),
+            direction: OrderDirection::Desc,
+        }
+    }
+
+    /// Parse a comma-separated list of orders, like "name asc, id desc".
+    ///
+    /// Direction is optional, and is ascending by default
+    pub fn parse_list(value: &str) -> Result<Vec<Self>, InvalidOrderError> {
+        if value.trim().is_empty() {
+            return Ok(vec![]);
+        }
+        value.split(',').map(OrderBy::try_from).collect()
+    }
+
+    /// Return true if this order is only on the id of the record
+    pub fn is_id(&self) -> bool {
+        self.path.len() == 1 && self.path[0] == "id"
+    }
+}
+
+fn split_path(path: &str) -> Vec<String> {
+    path.split('.').map(|str| str.to_string()).collect()
+}
+
+impl TryFrom<&str> for OrderBy {
+    type Error = InvalidOrderError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let error = || InvalidOrderError {
+            order: value.to_string(),
+        };
+        let mut parts = value.split_whitespace();
+        let path = parts.next().ok_or_else(error)?;
+        let direction = match parts.next() {
+            Some(direction) => direction.try_into().map_err(|_| error())?,
+            None => OrderDirection::Asc,
+        };
+        if parts.next().is_some() || path.split('.').any(|field| field.is_empty()) {
+            return Err(error());
+        }
+        Ok(Self {
+            path: split_path(path),
+            direction,
+        })
+    }
+}
+
+/// Options of a search request
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct SearchOptions {
+    /// Order of returned records.
+    ///
+    /// If empty, the default order of the model is used
+    pub order: Vec<OrderBy>,
+    /// Maximum number of returned records
+    pub limit: Option<usize>,
+    /// Number of records to skip
+    pub offset: usize,
+    /// Don't sort returned records, not even on the default order of the model.
+    ///
+    /// Used by internal fetches, where the order of records doesn't matter
+    pub unordered: bool,
+}
+
+impl SearchOptions {
+    /// Options of a search whose records don't need to be sorted
+    pub fn unordered() -> Self {
+        Self {
+            unordered: true,
+            ..Default::default()
+        }
+    }
+}
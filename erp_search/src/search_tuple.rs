@@ -1,4 +1,6 @@
-use crate::{SearchOperator, UnknownSearchOperatorError};
+use crate::{SearchOperator, SearchType, UnknownSearchOperatorError};
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
 
 #[derive(Clone, PartialEq, Debug)]
 pub struct SearchTuple {
@@ -55,13 +57,30 @@ impl From<String> for LeftTuple {
 pub enum RightTuple {
     String(String),
     Integer(i32),
-    UInteger(u32),
+    UInteger(u64),
+    BigInteger(i64),
     Float(f32),
+    Double(f64),
     Boolean(bool),
+    Decimal(Decimal),
+    Date(NaiveDate),
+    DateTime(DateTime<Utc>),
     Array(Vec<RightTuple>),
+    /// Domain on the model targeted by the field, used by "any" and "not any"
+    Domain(Box<SearchType>),
     None,
 }
 
+impl RightTuple {
+    /// Values of this array, or this value alone if it's not an array
+    pub fn values(&self) -> &[RightTuple] {
+        match self {
+            RightTuple::Array(values) => values,
+            value => std::slice::from_ref(value),
+        }
+    }
+}
+
 impl From<&str> for RightTuple {
     fn from(s: &str) -> Self {
         Self::String(s.to_string())
@@ -82,22 +101,58 @@ impl From<i32> for RightTuple {
 
 impl From<u32> for RightTuple {
     fn from(f: u32) -> Self {
+        Self::UInteger(f.into())
+    }
+}
+
+impl From<u64> for RightTuple {
+    fn from(f: u64) -> Self {
         Self::UInteger(f)
     }
 }
 
+impl From<i64> for RightTuple {
+    fn from(i: i64) -> Self {
+        Self::BigInteger(i)
+    }
+}
+
 impl From<f32> for RightTuple {
     fn from(f: f32) -> Self {
         Self::Float(f)
     }
 }
 
+impl From<f64> for RightTuple {
+    fn from(f: f64) -> Self {
+        Self::Double(f)
+    }
+}
+
 impl From<bool> for RightTuple {
     fn from(b: bool) -> Self {
         Self::Boolean(b)
     }
 }
 
+impl From<Decimal> for RightTuple {
+    fn from(d: Decimal) -> Self {
+        Self::Decimal(d)
+    }
+}
+
+impl From<NaiveDate> for RightTuple {
+    fn from(d: NaiveDate) -> Self {
+        Self::Date(d)
+    }
+}
+
+impl From<DateTime<Utc>> for RightTuple {
+    fn from(d: DateTime<Utc>) -> Self {
+        Self::DateTime(d)
+    }
+}
+
 impl<E> From<Vec<E>> for RightTuple
 where
     E: Into<RightTuple>,
@@ -107,6 +162,12 @@ where
     }
 }
 
+impl From<SearchType> for RightTuple {
+    fn from(domain: SearchType) -> Self {
+        Self::Domain(Box::new(domain))
+    }
+}
+
 impl From<Option<RightTuple>> for RightTuple {
     fn from(v: Option<RightTuple>) -> Self {
         v.unwrap_or(Self::None)
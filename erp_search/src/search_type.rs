@@ -1,14 +1,17 @@
 use crate::{
-    InvalidDomainError, LeftTuple, RightTuple, SearchKey, SearchOperator, SearchTuple,
-    UnknownSearchOperatorError,
+    InvalidDomainError, LeftTuple, NonFiniteNumberError, RightTuple, SearchKey, SearchOperator,
+    SearchTuple, UnknownSearchOperatorError,
 };
 use std::error;
 use std::fmt::Display;
+use std::ops::{BitAnd, BitOr, Not};
 
 #[derive(Clone, PartialEq, Debug)]
 pub enum SearchType {
     And(Box<SearchType>, Box<SearchType>),
     Or(Box<SearchType>, Box<SearchType>),
+    /// Records that don't match given domain
+    Not(Box<SearchType>),
     Tuple(SearchTuple),
     Nothing,
 }
@@ -28,6 +31,7 @@ impl SearchType {
                 SearchType::handle_search_type(left, result);
                 SearchType::handle_search_type(right, result);
             }
+            SearchType::Not(search_type) => SearchType::handle_search_type(search_type, result),
             SearchType::Tuple(tuple) => {
                 result.push(&tuple.left);
             }
@@ -42,6 +46,33 @@ impl From<SearchTuple> for SearchType {
     }
 }
 
+/// `a & b` matches records that match both domains
+impl BitAnd for SearchType {
+    type Output = SearchType;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        SearchType::And(Box::new(self), Box::new(rhs))
+    }
+}
+
+/// `a | b` matches records that match at least one of the domains
+impl BitOr for SearchType {
+    type Output = SearchType;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        SearchType::Or(Box::new(self), Box::new(rhs))
+    }
+}
+
+/// `!a` matches records that don't match the domain
+impl Not for SearchType {
+    type Output = SearchType;
+
+    fn not(self) -> Self::Output {
+        SearchType::Not(Box::new(self))
+    }
+}
+
 impl<L, OP, R> TryFrom<(L, OP, R)> for SearchType
 where
     L: Into<LeftTuple>,
@@ -59,6 +90,7 @@ where
 pub enum ErrorType {
     InvalidDomain(InvalidDomainError),
     UnknownSearchOperator(UnknownSearchOperatorError),
+    NonFiniteNumber(NonFiniteNumberError),
 }
 
 impl Display for ErrorType {
@@ -66,6 +98,7 @@ impl Display for ErrorType {
         match self {
             ErrorType::InvalidDomain(e) => e.fmt(f),
             ErrorType::UnknownSearchOperator(e) => e.fmt(f),
+            ErrorType::NonFiniteNumber(e) => e.fmt(f),
         }
     }
 }
@@ -118,6 +151,13 @@ impl TryFrom<Vec<SearchKey>> for SearchType {
                     }
                     None
                 }
+                SearchKey::Not => {
+                    let search_type = parse_value(value)?;
+                    if search_type == SearchType::Nothing {
+                        return None;
+                    }
+                    Some(SearchType::Not(Box::new(search_type)))
+                }
                 SearchKey::Tuple(tuple) => Some(SearchType::Tuple(tuple)),
             }
         }
@@ -127,6 +167,7 @@ impl TryFrom<Vec<SearchKey>> for SearchType {
         if result.is_none() {
             return Err(ErrorType::InvalidDomain(InvalidDomainError {
                 search_key: original_value,
+                position: None,
             }));
         }
         let mut result = result.unwrap();
@@ -138,6 +179,7 @@ impl TryFrom<Vec<SearchKey>> for SearchType {
             if new_result.is_none() {
                 return Err(ErrorType::InvalidDomain(InvalidDomainError {
                     search_key: original_value,
+                    position: None,
                 }));
             }
             let new_result = new_result.unwrap();
@@ -0,0 +1,86 @@
+use crate::{RightTuple, SearchTuple, SearchType};
+use chrono::{DateTime, SecondsFormat, Utc};
+use std::fmt::{Display, Formatter};
+
+impl SearchType {
+    /// Add elements of this domain to given list, following the polish notation
+    fn write_elements(
+        &self,
+        elements: &mut Vec<String>,
+        write_tuple: &dyn Fn(&SearchTuple) -> String,
+    ) {
+        match self {
+            SearchType::And(left, right) => {
+                elements.push("\"&\"".to_string());
+                left.write_elements(elements, write_tuple);
+                right.write_elements(elements, write_tuple);
+            }
+            SearchType::Or(left, right) => {
+                elements.push("\"|\"".to_string());
+                left.write_elements(elements, write_tuple);
+                right.write_elements(elements, write_tuple);
+            }
+            SearchType::Not(search_type) => {
+                elements.push("\"!\"".to_string());
+                search_type.write_elements(elements, write_tuple);
+            }
+            SearchType::Tuple(tuple) => elements.push(write_tuple(tuple)),
+            // Nothing can't be written in the polish notation, so an empty domain is used
+            SearchType::Nothing => elements.push("[]".to_string()),
+        }
+    }
+}
+
+/// Write this domain with the same syntax as `make_domain!`, so that it can be parsed back
+impl Display for SearchType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut elements = Vec::new();
+        if *self != SearchType::Nothing {
+            self.write_elements(&mut elements, &|tuple| {
+                format!(
+                    "({:?}, {:?}, {})",
+                    tuple.left.path.join("."),
+                    tuple.operator.to_string(),
+                    text_value(&tuple.right),
+                )
+            });
+        }
+        write!(f, "[{}]", elements.join(", "))
+    }
+}
+
+fn text_value(value: &RightTuple) -> String {
+    match value {
+        RightTuple::String(value) => format!("{value:?}"),
+        RightTuple::Integer(value) => value.to_string(),
+        RightTuple::UInteger(value) => format!("{value}u64"),
+        RightTuple::BigInteger(value) => format!("{value}i64"),
+        RightTuple::Float(value) if value.is_nan() => "f32::NAN".to_string(),
+        RightTuple::Float(value) if value.is_infinite() && *value > 0.0 => {
+            "f32::INFINITY".to_string()
+        }
+        RightTuple::Float(value) if value.is_infinite() => "f32::NEG_INFINITY".to_string(),
+        RightTuple::Float(value) => format!("{value:?}f32"),
+        RightTuple::Double(value) if value.is_nan() => "f64::NAN".to_string(),
+        RightTuple::Double(value) if value.is_infinite() && *value > 0.0 => {
+            "f64::INFINITY".to_string()
+        }
+        RightTuple::Double(value) if value.is_infinite() => "f64::NEG_INFINITY".to_string(),
+        RightTuple::Double(value) => format!("{value:?}f64"),
+        RightTuple::Boolean(value) => value.to_string(),
+        RightTuple::Decimal(value) => format!("decimal(\"{value}\")"),
+        RightTuple::Date(value) => format!("date(\"{value}\")"),
+        RightTuple::DateTime(value) => format!("datetime(\"{}\")", datetime_string(value)),
+        RightTuple::Array(values) => format!(
+            "vec![{}]",
+            values.iter().map(text_value).collect::<Vec<_>>().join(", ")
+        ),
+        RightTuple::Domain(domain) => domain.to_string(),
+        RightTuple::None => "None".to_string(),
+    }
+}
+
+/// RFC 3339 representation of given date and time, in UTC
+pub(crate) fn datetime_string(value: &DateTime<Utc>) -> String {
+    value.to_rfc3339_opts(SecondsFormat::AutoSi, true)
+}
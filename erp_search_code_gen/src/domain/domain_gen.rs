@@ -2,15 +2,59 @@ use crate::domain::search_key::{SearchKey, SearchOperator, SearchTuple, SearchTy
 use crate::domain::util::{
     gen_and_or_or_without_enough_tuple, gen_invalid_or_unknown_attribute,
     gen_invalid_search_string, gen_invalid_tuple_len, gen_invalid_tuple_operator,
+    gen_not_without_tuple,
 };
 use proc_macro2::TokenStream;
 use quote::quote;
 use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
 use syn::token::Comma;
-use syn::Expr::{Lit, Tuple};
-use syn::Lit::Str;
-use syn::{Expr, ExprLit, Result};
+use syn::Expr::{Array, Lit, Tuple};
+use syn::Lit::{Float, Str};
+use syn::parse::Parser;
+use syn::{parse_quote, Expr, ExprArray, ExprLit, ExprMacro, ExprUnary, LitFloat, Result};
+
+/// Add a "f32" suffix to float literals that don't have any suffix, in given value and in the
+/// values of given array or `vec!`.
+///
+/// Values are converted with `.into()`, so that unsuffixed float literals would otherwise be
+/// f64. Integer literals are already i32
+fn with_f32_literals(expr: &Expr) -> Expr {
+    match expr {
+        Lit(ExprLit {
+            lit: Float(float),
+            attrs,
+        }) if float.suffix().is_empty() => Lit(ExprLit {
+            lit: Float(LitFloat::new(
+                &format!("{}f32", float.base10_digits()),
+                float.span(),
+            )),
+            attrs: attrs.clone(),
+        }),
+        Expr::Unary(unary) => Expr::Unary(ExprUnary {
+            expr: Box::new(with_f32_literals(&unary.expr)),
+            ..unary.clone()
+        }),
+        Array(array) => Array(ExprArray {
+            elems: array.elems.iter().map(with_f32_literals).collect(),
+            ..array.clone()
+        }),
+        Expr::Macro(ExprMacro { mac, attrs }) if mac.path.is_ident("vec") => {
+            let Ok(elems) = Punctuated::<Expr, Comma>::parse_terminated.parse2(mac.tokens.clone())
+            else {
+                return expr.clone();
+            };
+            let elems: Punctuated<Expr, Comma> = elems.iter().map(with_f32_literals).collect();
+            let mut mac = mac.clone();
+            mac.tokens = quote! { #elems };
+            Expr::Macro(ExprMacro {
+                mac,
+                attrs: attrs.clone(),
+            })
+        }
+        _ => expr.clone(),
+    }
+}
 
 pub fn derive(items: &Punctuated<Expr, Comma>) -> Result<TokenStream> {
     if items.is_empty() {
@@ -29,10 +73,11 @@ pub fn derive(items: &Punctuated<Expr, Comma>) -> Result<TokenStream> {
                 match str_value.as_str() {
                     "&" => Ok(SearchKey::And),
                     "|" => Ok(SearchKey::Or),
+                    "!" => Ok(SearchKey::Not),
                     _ => Err(gen_invalid_search_string(
                         expr.span(),
                         str_value.as_str(),
-                        &["&", "|"],
+                        &["&", "|", "!"],
                     )),
                 }
             }
@@ -61,10 +106,23 @@ pub fn derive(items: &Punctuated<Expr, Comma>) -> Result<TokenStream> {
                     }
                     expr => SearchOperator::Expr(expr.clone()),
                 };
+                // Sub-domain of "any" and "not any", written with the same syntax
+                let right = match (&operator, &elems[2]) {
+                    (
+                        SearchOperator::Operator(
+                            erp_search::SearchOperator::Any | erp_search::SearchOperator::NotAny,
+                        ),
+                        Array(array),
+                    ) => {
+                        let domain = derive(&array.elems)?;
+                        parse_quote! { #domain }
+                    }
+                    (_, right) => with_f32_literals(right),
+                };
                 Ok(SearchKey::Tuple(SearchTuple {
                     left: elems[0].clone(),
                     operator,
-                    right: elems[2].clone(),
+                    right,
                 }))
             }
             _ => Err(gen_invalid_or_unknown_attribute(expr.span())),
@@ -95,6 +153,13 @@ pub fn derive(items: &Punctuated<Expr, Comma>) -> Result<TokenStream> {
                     SearchType::Or(Box::new(left_value), Box::new(right_value))
                 })
             }
+            SearchKey::Not => {
+                let value = parse_value(value)?;
+                if matches!(value, SearchType::Nothing) {
+                    return Err(gen_not_without_tuple(search_key.0.span()));
+                }
+                Ok(SearchType::Not(Box::new(value)))
+            }
             SearchKey::Tuple(tuple) => Ok(SearchType::Tuple(tuple)),
         }
     }
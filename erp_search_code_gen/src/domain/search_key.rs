@@ -3,16 +3,20 @@ use syn::Expr;
 use syn::__private::quote::quote;
 use syn::__private::ToTokens;
 
+#[allow(clippy::large_enum_variant)]
 pub enum SearchType {
     And(Box<SearchType>, Box<SearchType>),
     Or(Box<SearchType>, Box<SearchType>),
+    Not(Box<SearchType>),
     Tuple(SearchTuple),
     Nothing,
 }
 
+#[allow(clippy::large_enum_variant)]
 pub enum SearchKey {
     And,
     Or,
+    Not,
     Tuple(SearchTuple),
 }
 
@@ -46,6 +50,13 @@ impl ToTokens for SearchType {
                     )
                 }
             }
+            SearchType::Not(search_type) => {
+                quote! {
+                    erp_search::SearchType::Not(
+                        Box::new(#search_type),
+                    )
+                }
+            }
             SearchType::Tuple(tuple) => {
                 quote! {
                     erp_search::SearchType::Tuple(#tuple)
@@ -72,7 +83,7 @@ impl ToTokens for SearchTuple {
             erp_search::SearchTuple {
                 left: #left.into(),
                 operator: #operator,
-                right: #right.into(),
+                right: (#right).into(),
             }
         };
 
@@ -98,6 +109,23 @@ impl ToTokens for SearchOperator {
                 erp_search::SearchOperator::LowerEqual => {
                     quote! { erp_search::SearchOperator::LowerEqual }
                 }
+                erp_search::SearchOperator::In => quote! { erp_search::SearchOperator::In },
+                erp_search::SearchOperator::NotIn => quote! { erp_search::SearchOperator::NotIn },
+                erp_search::SearchOperator::Like => quote! { erp_search::SearchOperator::Like },
+                erp_search::SearchOperator::ILike => quote! { erp_search::SearchOperator::ILike },
+                erp_search::SearchOperator::EqualLike => {
+                    quote! { erp_search::SearchOperator::EqualLike }
+                }
+                erp_search::SearchOperator::ChildOf => {
+                    quote! { erp_search::SearchOperator::ChildOf }
+                }
+                erp_search::SearchOperator::ParentOf => {
+                    quote! { erp_search::SearchOperator::ParentOf }
+                }
+                erp_search::SearchOperator::Any => quote! { erp_search::SearchOperator::Any },
+                erp_search::SearchOperator::NotAny => {
+                    quote! { erp_search::SearchOperator::NotAny }
+                }
             },
             SearchOperator::Expr(expr) => quote! {#expr.try_into()?},
         };
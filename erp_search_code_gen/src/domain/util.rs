@@ -20,6 +20,7 @@ pub fn gen_invalid_tuple_operator(span: Span, operator: String) -> Error {
         span,
         UnknownSearchOperatorError {
             search_operator: operator,
+            position: None,
         },
     )
 }
@@ -27,3 +28,7 @@ pub fn gen_invalid_tuple_operator(span: Span, operator: String) -> Error {
 pub fn gen_and_or_or_without_enough_tuple(span: Span) -> Error {
     Error::new(span, "Given \"&\" or \"|\" doesn't have enough tuple")
 }
+
+pub fn gen_not_without_tuple(span: Span) -> Error {
+    Error::new(span, "Given \"!\" doesn't have any tuple")
+}
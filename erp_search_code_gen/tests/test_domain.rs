@@ -1,4 +1,4 @@
-use erp_search::{RightTuple, SearchOperator, SearchTuple, SearchType};
+use erp_search::{RightTuple, SearchKey, SearchOperator, SearchTuple, SearchType};
 use erp_search_code_gen::make_domain;
 
 #[test]
@@ -123,6 +123,115 @@ fn test_domain_macro_copy() -> Result<(), Box<dyn std::error::Error>> {
         })
     );
 
+    // Other operators
+    let domain = make_domain!([("test", "not in", vec![1, 2]), ("parent", "child_of", 1)]);
+    assert_eq!(
+        domain,
+        SearchType::And(
+            Box::new(SearchType::Tuple(SearchTuple {
+                left: "test".into(),
+                operator: SearchOperator::NotIn,
+                right: RightTuple::Array(vec![RightTuple::Integer(1), RightTuple::Integer(2)]),
+            })),
+            Box::new(SearchType::Tuple(SearchTuple {
+                left: "parent".into(),
+                operator: SearchOperator::ChildOf,
+                right: RightTuple::Integer(1),
+            })),
+        )
+    );
+    for (operator, expected) in [
+        ("in", SearchOperator::In),
+        ("like", SearchOperator::Like),
+        ("ilike", SearchOperator::ILike),
+        ("=like", SearchOperator::EqualLike),
+        ("parent_of", SearchOperator::ParentOf),
+    ] {
+        let domain = make_domain!([("test", operator, "lol")]);
+        assert_eq!(
+            domain,
+            SearchType::Tuple(SearchTuple {
+                left: "test".into(),
+                operator: expected,
+                right: RightTuple::String("lol".to_string()),
+            })
+        );
+    }
+
+    // Sub-domains of "any" and "not any"
+    let domain = make_domain!([(
+        "lines",
+        "any",
+        [("price", ">", 10), ("state", "!=", "done")]
+    )]);
+    assert_eq!(
+        domain,
+        SearchType::Tuple(SearchTuple {
+            left: "lines".into(),
+            operator: SearchOperator::Any,
+            right: RightTuple::Domain(Box::new(make_domain!([
+                ("price", ">", 10),
+                ("state", "!=", "done")
+            ]))),
+        })
+    );
+    let sub_domain = make_domain!([("name", "=", "0ddlyoko")]);
+    let domain = make_domain!([("order", "not any", sub_domain.clone())]);
+    assert_eq!(
+        domain,
+        SearchType::Tuple(SearchTuple {
+            left: "order".into(),
+            operator: SearchOperator::NotAny,
+            right: sub_domain.into(),
+        })
+    );
+
+    // Not
+    let domain = make_domain!([
+        "!",
+        "|",
+        ("state", "=", "draft"),
+        ("state", "=", "sent"),
+        ("test", "=", 1)
+    ]);
+    let expected = SearchType::And(
+        Box::new(SearchType::Not(Box::new(SearchType::Or(
+            Box::new(SearchType::Tuple(SearchTuple {
+                left: "state".into(),
+                operator: SearchOperator::Equal,
+                right: RightTuple::String("draft".to_string()),
+            })),
+            Box::new(SearchType::Tuple(SearchTuple {
+                left: "state".into(),
+                operator: SearchOperator::Equal,
+                right: RightTuple::String("sent".to_string()),
+            })),
+        )))),
+        Box::new(SearchType::Tuple(SearchTuple {
+            left: "test".into(),
+            operator: SearchOperator::Equal,
+            right: RightTuple::Integer(1),
+        })),
+    );
+    assert_eq!(domain, expected);
+    // Same result with the parser
+    let domain: SearchType = vec![
+        SearchKey::Not,
+        SearchKey::Or,
+        ("state", "=", "draft").try_into()?,
+        ("state", "=", "sent").try_into()?,
+        SearchKey::Tuple(SearchTuple {
+            left: "test".into(),
+            operator: SearchOperator::Equal,
+            right: RightTuple::Integer(1),
+        }),
+    ]
+    .try_into()?;
+    assert_eq!(domain, expected);
+    assert_eq!(SearchKey::try_from("!")?, SearchKey::Not);
+    // "!" needs a domain to negate
+    assert!(SearchType::try_from(vec![SearchKey::Not]).is_err());
+
     // TODO Add more tests
 
     Ok(())
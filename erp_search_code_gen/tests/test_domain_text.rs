@@ -0,0 +1,265 @@
+use chrono::{NaiveDate, TimeZone, Utc};
+use erp_search::{ErrorType, RightTuple, SearchOperator, SearchTuple, SearchType};
+use erp_search_code_gen::make_domain;
+use rust_decimal::Decimal;
+
+type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+fn domains() -> Vec<SearchType> {
+    let negative = SearchType::Tuple(SearchTuple {
+        left: "a".into(),
+        operator: SearchOperator::Equal,
+        right: RightTuple::Integer(-1),
+    });
+    let date = NaiveDate::from_ymd_opt(2025, 1, 31).unwrap();
+    let date_time = Utc.with_ymd_and_hms(2025, 1, 31, 23, 59, 1).unwrap();
+    let price = Decimal::new(-420, 2);
+    vec![
+        make_domain!([("name", "=", "0ddlyoko")]),
+        make_domain!([("price", ">=", price), ("price", "not in", vec![price])]),
+        make_domain!([("lines.price", ">", 10), ("state", "!=", None)]),
+        make_domain!([
+            "|",
+            ("state", "in", vec!["draft", "sent"]),
+            "!",
+            ("total", "<=", 4.2)
+        ]),
+        make_domain!([
+            "!",
+            "&",
+            ("name", "ilike", "say \"hello\"\n\t\\ é"),
+            ("active", "=", true)
+        ]),
+        make_domain!([
+            ("parent", "child_of", vec![1, 2, 3]),
+            ("ids", "not in", Vec::<i32>::new())
+        ]),
+        make_domain!([("id", "=", 42u32), ("value", "=like", "a_%")]),
+        make_domain!([
+            ("quantity", ">", -5_000_000_000i64),
+            ("ratio", "<", 0.1f64),
+            ("id", "in", vec![5_000_000_000u64])
+        ]),
+        make_domain!([
+            (
+                "lines",
+                "any",
+                [
+                    "|",
+                    ("price", ">", 10),
+                    ("order", "not any", [("a", "=", 1)])
+                ]
+            ),
+            ("name", "=", "any")
+        ]),
+        make_domain!([
+            ("validity_date", ">=", date),
+            ("date_order", "<", date_time),
+            ("date_order", "in", vec![date_time])
+        ]),
+        SearchType::Nothing,
+        SearchType::Not(Box::new(SearchType::Nothing)),
+        SearchType::Or(Box::new(SearchType::Nothing), Box::new(negative)),
+    ]
+}
+
+#[test]
+fn test_text_round_trip() -> Result<()> {
+    for domain in domains() {
+        let text = domain.to_string();
+        assert_eq!(text.parse::<SearchType>()?, domain, "{text}");
+    }
+
+    assert_eq!(
+        make_domain!(["|", ("a", "=", 1u64), ("b", "=", 1.5)]).to_string(),
+        r#"["|", ("a", "=", 1u64), ("b", "=", 1.5f32)]"#
+    );
+    assert_eq!(
+        make_domain!([("a", "=", 1i64), ("b", "in", vec![1.5f64, f64::INFINITY])]).to_string(),
+        r#"["&", ("a", "=", 1i64), ("b", "in", vec![1.5f64, f64::INFINITY])]"#
+    );
+    assert_eq!(SearchType::Nothing.to_string(), "[]");
+    let price = Decimal::new(420, 2);
+    assert_eq!(
+        make_domain!([("a", "=", price)]).to_string(),
+        r#"[("a", "=", decimal("4.20"))]"#
+    );
+    let date_time = Utc.with_ymd_and_hms(2025, 1, 31, 12, 0, 0).unwrap();
+    assert_eq!(
+        make_domain!([("a", "<", date_time)]).to_string(),
+        r#"[("a", "<", datetime("2025-01-31T12:00:00Z"))]"#
+    );
+    assert_eq!(
+        make_domain!([("lines", "not any", [("price", ">", 10)])]).to_string(),
+        r#"[("lines", "not any", [("price", ">", 10)])]"#
+    );
+    assert_eq!(
+        make_domain!([("lines", "any", [("price", ">", 10)])]).to_json()?,
+        r#"[["lines","any",[["price",">",10]]]]"#
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_text_parser() -> Result<()> {
+    // Same syntax as make_domain!, with implicit "&"
+    let domain: SearchType = r#"[
+        ("lines.price", ">", 10),
+        "|",
+        ("state", "=", "draft"),
+        ("partner.name", "like", "0ddlyoko"),
+    ]"#
+    .parse()?;
+    assert_eq!(
+        domain,
+        make_domain!([
+            ("lines.price", ">", 10),
+            "|",
+            ("state", "=", "draft"),
+            ("partner.name", "like", "0ddlyoko"),
+        ])
+    );
+
+    let domain: SearchType =
+        r#"[("a", "in", [1, 2]), ("b", "=", None), ("c", "=", 3i32), ("d", "=", 1e3)]"#.parse()?;
+    assert_eq!(
+        domain,
+        make_domain!([
+            ("a", "in", vec![1, 2]),
+            ("b", "=", None),
+            ("c", "=", 3),
+            ("d", "=", 1000.0)
+        ])
+    );
+    let domain: SearchType = r#"[("a", "=", f32::NAN)]"#.parse()?;
+    assert!(matches!(
+        domain,
+        SearchType::Tuple(SearchTuple {
+            right: RightTuple::Float(value),
+            ..
+        }) if value.is_nan()
+    ));
+    assert_eq!("[]".parse::<SearchType>()?, SearchType::Nothing);
+
+    Ok(())
+}
+
+#[test]
+fn test_json() -> Result<()> {
+    for domain in domains() {
+        let json = domain.to_json()?;
+        assert_eq!(SearchType::from_json(&json)?, domain, "{json}");
+    }
+
+    let domain = make_domain!([
+        "!",
+        ("a", "in", vec![1, 2]),
+        ("b", "=", None),
+        ("c", "=", 3u64)
+    ]);
+    assert_eq!(
+        domain.to_json()?,
+        r#"["&","&","!",["a","in",[1,2]],["b","=",null],["c","=",{"u64":3}]]"#
+    );
+    assert_eq!(
+        SearchType::from_json(r#"[["a", "=", {"u32": 3}], ["b", "=", {"i64": -5000000000}]]"#)?,
+        make_domain!([("a", "=", 3u64), ("b", "=", -5_000_000_000i64)])
+    );
+    assert_eq!(
+        make_domain!([("a", "=", 0.1f64), ("b", "=", 0.1f32)]).to_json()?,
+        r#"["&",["a","=",{"f64":0.1}],["b","=",0.1]]"#
+    );
+    assert_eq!(
+        SearchType::from_json(r#"[["a", "=", "é😀"], ["b", "<", 0.5]]"#)?,
+        make_domain!([("a", "=", "é😀"), ("b", "<", 0.5)])
+    );
+    // Integers that don't fit in an i32 are unsigned
+    assert_eq!(
+        SearchType::from_json(r#"[["id", "=", 4000000000]]"#)?,
+        SearchType::Tuple(SearchTuple {
+            left: "id".into(),
+            operator: SearchOperator::Equal,
+            right: RightTuple::UInteger(4000000000),
+        })
+    );
+    let price = Decimal::new(420, 2);
+    assert_eq!(
+        make_domain!([("a", "=", price)]).to_json()?,
+        r#"[["a","=",{"decimal":"4.20"}]]"#
+    );
+    let date = NaiveDate::from_ymd_opt(2025, 1, 31).unwrap();
+    assert_eq!(
+        make_domain!([("a", "=", date)]).to_json()?,
+        r#"[["a","=",{"date":"2025-01-31"}]]"#
+    );
+    let date_time =
+        Utc.with_ymd_and_hms(2025, 1, 31, 12, 0, 0).unwrap() + chrono::Duration::milliseconds(500);
+    assert_eq!(
+        SearchType::from_json(r#"[["a", ">", {"datetime": "2025-01-31T12:00:00.5Z"}]]"#)?,
+        make_domain!([("a", ">", date_time)])
+    );
+    // Text syntax is not valid JSON
+    assert!(SearchType::from_json(r#"[("a", "=", 1)]"#).is_err());
+    assert!(SearchType::from_json(r#"[["a", "=", None]]"#).is_err());
+
+    // JSON doesn't support NaN nor infinity, they are not silently replaced by null
+    for domain in [
+        make_domain!([("a", "=", f32::NAN)]),
+        make_domain!([("a", "in", vec![1.0, f32::INFINITY])]),
+        make_domain!([("lines", "any", [("a", "<", f64::NEG_INFINITY)])]),
+    ] {
+        let error = domain.to_json().unwrap_err();
+        assert!(
+            matches!(&error, ErrorType::NonFiniteNumber(error) if error.path == "a"),
+            "{error}"
+        );
+    }
+    assert!(SearchType::from_json(r#"[["a", "=", 1e39]]"#).is_err());
+    assert!(SearchType::from_json(r#"[["a", "=", {"f64": 1e999}]]"#).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_parse_errors() -> Result<()> {
+    let error = r#"[("a", "=", 1), ("b", "~", 2)]"#.parse::<SearchType>().unwrap_err();
+    let ErrorType::UnknownSearchOperator(error) = error else {
+        panic!("Unexpected error {error}");
+    };
+    assert_eq!(error.search_operator, "~");
+    assert_eq!(error.position, Some(22));
+
+    let error = SearchType::from_json(r#"[["a", "nope", 1]]"#).unwrap_err();
+    assert!(matches!(
+        error,
+        ErrorType::UnknownSearchOperator(error) if error.search_operator == "nope" && error.position.is_none()
+    ));
+
+    // Missing operand of "|"
+    let error = r#"[("a", "=", 1), "|", ("b", "=", 2)]"#.parse::<SearchType>().unwrap_err();
+    let ErrorType::InvalidDomain(error) = error else {
+        panic!("Unexpected error {error}");
+    };
+    assert_eq!(error.position, Some(16));
+    assert_eq!(error.search_key.len(), 3);
+
+    for (text, position) in [
+        (r#"[("a", "=", 1)"#, 14),
+        (r#"[("a", "=")]"#, 10),
+        (r#"[("a", "=", oops)]"#, 12),
+        (r#"["&&"]"#, 1),
+        (r#"[("a", "=", "unterminated)]"#, 12),
+        (r#"[] trailing"#, 3),
+        (r#"[("a", "=", date("2025-02-30"))]"#, 12),
+        (r#"[("a", "=", decimal("4,20"))]"#, 12),
+    ] {
+        let error = text.parse::<SearchType>().unwrap_err();
+        assert!(
+            matches!(&error, ErrorType::InvalidDomain(error) if error.position == Some(position)),
+            "{text}: {error}"
+        );
+    }
+
+    Ok(())
+}
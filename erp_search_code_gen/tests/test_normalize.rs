@@ -0,0 +1,139 @@
+use erp_search::{RightTuple, SearchOperator, SearchTuple, SearchType};
+use erp_search_code_gen::make_domain;
+
+type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+fn everything() -> SearchType {
+    SearchType::Not(Box::new(SearchType::Nothing))
+}
+
+#[test]
+fn test_normalize_flatten() -> Result<()> {
+    // Chains are flattened from left to right, as the parser does
+    let domain = make_domain!(["&", ("a", "=", 1), "&", ("b", "=", 2), ("c", "=", 3)]);
+    assert_eq!(
+        domain.normalize(),
+        make_domain!([("a", "=", 1), ("b", "=", 2), ("c", "=", 3)])
+    );
+    let domain = make_domain!(["|", ("a", "=", 1), "|", ("b", "=", 2), ("c", "=", 3)]);
+    assert_eq!(
+        domain.normalize(),
+        make_domain!(["|", "|", ("a", "=", 1), ("b", "=", 2), ("c", "=", 3)])
+    );
+
+    // Duplicates are removed
+    let domain = make_domain!([("a", "=", 1), ("b", "=", 2), ("a", "=", 1)]);
+    assert_eq!(
+        domain.normalize(),
+        make_domain!([("a", "=", 1), ("b", "=", 2)])
+    );
+    let domain = make_domain!(["|", ("a", ">", 1), ("a", ">", 1)]);
+    assert_eq!(domain.normalize(), make_domain!([("a", ">", 1)]));
+
+    // Already normalized domains are not modified
+    let domain = make_domain!(["|", ("a", "=", 1), ("b", "=", 2), ("c", "like", "d")]);
+    assert_eq!(domain.normalize(), domain);
+    assert_eq!(domain.normalize().normalize(), domain.normalize());
+
+    Ok(())
+}
+
+#[test]
+fn test_normalize_nothing() -> Result<()> {
+    let tuple = make_domain!([("a", "=", 1)]);
+
+    // Nothing doesn't match any record
+    let domain = SearchType::And(Box::new(tuple.clone()), Box::new(SearchType::Nothing));
+    assert_eq!(domain.normalize(), SearchType::Nothing);
+    let domain = SearchType::Or(Box::new(SearchType::Nothing), Box::new(tuple.clone()));
+    assert_eq!(domain.normalize(), tuple);
+    let domain = SearchType::Or(Box::new(SearchType::Nothing), Box::new(SearchType::Nothing));
+    assert_eq!(domain.normalize(), SearchType::Nothing);
+
+    // And its negation matches all records
+    let domain = SearchType::And(Box::new(tuple.clone()), Box::new(everything()));
+    assert_eq!(domain.normalize(), tuple);
+    let domain = SearchType::Or(Box::new(tuple.clone()), Box::new(everything()));
+    assert_eq!(domain.normalize(), everything());
+    let domain = SearchType::Not(Box::new(SearchType::Or(
+        Box::new(SearchType::Nothing),
+        Box::new(SearchType::Nothing),
+    )));
+    assert_eq!(domain.normalize(), everything());
+
+    Ok(())
+}
+
+#[test]
+fn test_normalize_in() -> Result<()> {
+    let domain = make_domain!([
+        "|",
+        "|",
+        "|",
+        ("state", "=", "draft"),
+        ("name", "=", "0ddlyoko"),
+        ("state", "in", vec!["sent", "draft"]),
+        ("state", "=", "done")
+    ]);
+    assert_eq!(
+        domain.normalize(),
+        make_domain!([
+            "|",
+            ("state", "in", vec!["draft", "sent", "done"]),
+            ("name", "=", "0ddlyoko")
+        ])
+    );
+
+    // "= None" matches empty values, so it's kept as it is
+    let domain = make_domain!(["|", ("state", "=", "draft"), ("state", "=", None)]);
+    assert_eq!(domain.normalize(), domain);
+
+    // Equalities are only merged in Or chains
+    let domain = make_domain!([("state", "=", "draft"), ("state", "=", "sent")]);
+    assert_eq!(domain.normalize(), domain);
+
+    // Paths are merged as well
+    let domain = make_domain!([
+        "|",
+        ("order.state", "=", "draft"),
+        ("order.state", "=", "sent")
+    ]);
+    assert_eq!(
+        domain.normalize(),
+        SearchType::Tuple(SearchTuple {
+            left: "order.state".into(),
+            operator: SearchOperator::In,
+            right: RightTuple::Array(vec!["draft".into(), "sent".into()]),
+        })
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_normalize_not() -> Result<()> {
+    // De Morgan's laws
+    let domain = make_domain!(["!", "|", ("a", "=", 1), ("b", ">", 2)]);
+    assert_eq!(
+        domain.normalize(),
+        SearchType::And(
+            Box::new(SearchType::Not(Box::new(make_domain!([("a", "=", 1)])))),
+            Box::new(SearchType::Not(Box::new(make_domain!([("b", ">", 2)])))),
+        )
+    );
+    let domain = make_domain!(["!", "&", ("a", "=", 1), "!", ("b", ">", 2)]);
+    assert_eq!(
+        domain.normalize(),
+        make_domain!(["|", "!", ("a", "=", 1), ("b", ">", 2)])
+    );
+
+    // Double negation
+    let domain = make_domain!(["!", "!", ("a", "=", 1)]);
+    assert_eq!(domain.normalize(), make_domain!([("a", "=", 1)]));
+
+    // Negated tuples are kept, as their opposite operator doesn't match the same records
+    let domain = make_domain!(["!", ("order.name", "=", "0ddlyoko")]);
+    assert_eq!(domain.normalize(), domain);
+
+    Ok(())
+}
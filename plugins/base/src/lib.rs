@@ -13,9 +13,12 @@ impl Plugin for BasePlugin {
     }
 
     fn init_models(&self, model_manager: &mut ModelManager) {
+        model_manager.register_model::<models::Attachment<_>>();
         model_manager.register_model::<models::Company<_>>();
         model_manager.register_model::<models::Contact<_>>();
+        model_manager.register_model::<models::ContactTag<_>>();
         model_manager.register_model::<models::Country<_>>();
+        model_manager.register_model::<models::Currency<_>>();
         model_manager.register_model::<models::Lang<_>>();
         model_manager.register_model::<models::Plugin<_>>();
     }
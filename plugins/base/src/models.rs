@@ -1,11 +1,17 @@
+mod attachment;
 mod company;
 mod contact;
+mod contact_tag;
 mod country;
+mod currency;
 mod lang;
 mod plugin;
 
+pub use attachment::Attachment;
 pub use company::Company;
 pub use contact::Contact;
+pub use contact_tag::ContactTag;
 pub use country::Country;
+pub use currency::Currency;
 pub use lang::Lang;
 pub use plugin::{Plugin, PluginState};
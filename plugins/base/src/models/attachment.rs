@@ -0,0 +1,17 @@
+use code_gen::Model;
+use erp::types::field::{Binary, IdMode};
+
+#[derive(Model)]
+#[erp(table_name = "attachment")]
+#[allow(dead_code)]
+pub struct Attachment<Mode: IdMode> {
+    id: Mode,
+    name: String,
+    mimetype: Option<String>,
+    #[erp(description = "Model of the record this attachment is linked to")]
+    res_model: Option<String>,
+    #[erp(description = "Id of the record this attachment is linked to")]
+    res_id: Option<u64>,
+    #[erp(description = "Content of the file, saved in the filestore")]
+    content: Option<Binary>,
+}
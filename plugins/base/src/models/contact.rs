@@ -1,7 +1,9 @@
+use crate::models::contact_tag::BaseContactTag;
 use crate::models::country::BaseCountry;
+use crate::models::currency::BaseCurrency;
 use crate::models::lang::BaseLang;
 use code_gen::Model;
-use erp::types::field::{IdMode, MultipleIds, Reference, SingleId};
+use erp::types::field::{IdMode, Monetary, MultipleIds, Reference, SingleId};
 
 #[derive(Model)]
 #[erp(table_name = "contact")]
@@ -14,8 +16,14 @@ pub struct Contact<Mode: IdMode> {
     website: Option<String>,
     lang: Reference<BaseLang, SingleId>,
     country: Reference<BaseCountry, SingleId>,
+    currency: Reference<BaseCurrency, SingleId>,
+    #[erp(currency_field = "currency")]
+    credit_limit: Monetary,
+    #[erp(ondelete = "cascade")]
     parent: Reference<BaseContact, SingleId>,
     // TODO MultipleIds should not exist without SingleId ref
     #[erp(inverse = "parent")]
     childrens: Reference<BaseContact, MultipleIds>,
+    #[erp(many2many, relation = "contact_tag_rel")]
+    tags: Reference<BaseContactTag, MultipleIds>,
 }
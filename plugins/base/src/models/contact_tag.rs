@@ -0,0 +1,13 @@
+use crate::models::contact::BaseContact;
+use code_gen::Model;
+use erp::types::field::{IdMode, MultipleIds, Reference};
+
+#[derive(Model)]
+#[erp(table_name = "contact_tag")]
+#[allow(dead_code)]
+pub struct ContactTag<Mode: IdMode> {
+    id: Mode,
+    name: String,
+    #[erp(many2many, relation = "contact_tag_rel")]
+    contacts: Reference<BaseContact, MultipleIds>,
+}
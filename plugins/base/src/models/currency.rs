@@ -0,0 +1,16 @@
+use code_gen::Model;
+use erp::types::field::IdMode;
+
+#[derive(Model)]
+#[erp(table_name = "currency")]
+#[allow(dead_code)]
+pub struct Currency<Mode: IdMode> {
+    id: Mode,
+    name: String,
+    symbol: String,
+    #[erp(
+        description = "Number of decimal places of amounts in this currency",
+        default = 2
+    )]
+    decimal_places: i32,
+}